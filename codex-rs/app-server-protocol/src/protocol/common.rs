@@ -117,6 +117,22 @@ client_request_definitions! {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
     },
+    ThreadUndoList => "thread/undo/list" {
+        params: v2::ThreadUndoListParams,
+        response: v2::ThreadUndoListResponse,
+    },
+    ThreadUndoDiff => "thread/undo/diff" {
+        params: v2::ThreadUndoDiffParams,
+        response: v2::ThreadUndoDiffResponse,
+    },
+    ThreadUndoRestore => "thread/undo/restore" {
+        params: v2::ThreadUndoRestoreParams,
+        response: v2::ThreadUndoRestoreResponse,
+    },
+    ThreadUndoRedo => "thread/undo/redo" {
+        params: v2::ThreadUndoRedoParams,
+        response: v2::ThreadUndoRedoResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
@@ -153,6 +169,11 @@ client_request_definitions! {
         response: v2::ListMcpServerStatusResponse,
     },
 
+    McpServerPromptRun => "mcpServer/prompt/run" {
+        params: v2::McpServerPromptRunParams,
+        response: v2::McpServerPromptRunResponse,
+    },
+
     LoginAccount => "account/login/start" {
         params: v2::LoginAccountParams,
         response: v2::LoginAccountResponse,
@@ -27,9 +27,12 @@ use codex_protocol::protocol::SkillMetadata as CoreSkillMetadata;
 use codex_protocol::protocol::SkillScope as CoreSkillScope;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
+use codex_protocol::protocol::UndoSnapshot as CoreUndoSnapshot;
+use codex_protocol::protocol::UndoSnapshotFile as CoreUndoSnapshotFile;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::ContentBlock as McpContentBlock;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
 use mcp_types::Tool as McpTool;
@@ -90,6 +93,7 @@ pub enum CodexErrorInfo {
     Unauthorized,
     BadRequest,
     ThreadRollbackFailed,
+    UndoFailed,
     SandboxError,
     /// The response SSE stream disconnected in the middle of a turn before completion.
     ResponseStreamDisconnected {
@@ -121,6 +125,7 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::Unauthorized => CodexErrorInfo::Unauthorized,
             CoreCodexErrorInfo::BadRequest => CodexErrorInfo::BadRequest,
             CoreCodexErrorInfo::ThreadRollbackFailed => CodexErrorInfo::ThreadRollbackFailed,
+            CoreCodexErrorInfo::UndoFailed => CodexErrorInfo::UndoFailed,
             CoreCodexErrorInfo::SandboxError => CodexErrorInfo::SandboxError,
             CoreCodexErrorInfo::ResponseStreamDisconnected { http_status_code } => {
                 CodexErrorInfo::ResponseStreamDisconnected { http_status_code }
@@ -306,6 +311,10 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    #[serde(default)]
+    pub read_only_subpaths: Vec<PathBuf>,
+    #[serde(default)]
+    pub deny_read_paths: Vec<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -566,6 +575,10 @@ pub enum SandboxPolicy {
         exclude_tmpdir_env_var: bool,
         #[serde(default)]
         exclude_slash_tmp: bool,
+        #[serde(default)]
+        read_only_subpaths: Vec<AbsolutePathBuf>,
+        #[serde(default)]
+        deny_read_paths: Vec<AbsolutePathBuf>,
     },
 }
 
@@ -589,11 +602,15 @@ impl SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                read_only_subpaths,
+                deny_read_paths,
             } => codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: writable_roots.clone(),
                 network_access: *network_access,
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                read_only_subpaths: read_only_subpaths.clone(),
+                deny_read_paths: deny_read_paths.clone(),
             },
         }
     }
@@ -619,11 +636,15 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                read_only_subpaths,
+                deny_read_paths,
             } => SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                read_only_subpaths,
+                deny_read_paths,
             },
         }
     }
@@ -927,6 +948,8 @@ pub struct McpServerStatus {
     pub tools: std::collections::HashMap<String, McpTool>,
     pub resources: Vec<McpResource>,
     pub resource_templates: Vec<McpResourceTemplate>,
+    /// Prompts the server publishes; run them with `mcpServer/prompt/run`.
+    pub prompts: Vec<McpPrompt>,
     pub auth_status: McpAuthStatus,
 }
 
@@ -940,6 +963,27 @@ pub struct ListMcpServerStatusResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptRunParams {
+    pub thread_id: String,
+    /// Name of the MCP server that publishes the prompt.
+    pub server: String,
+    /// Prompt name as listed in `McpServerStatus.prompts`.
+    pub prompt: String,
+    /// Values for the prompt's declared arguments.
+    #[serde(default)]
+    pub arguments: std::collections::HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptRunResponse {
+    pub turn: Turn,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1116,6 +1160,115 @@ pub struct ThreadRollbackResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoListResponse {
+    /// Snapshots taken at the start of each turn, oldest first.
+    pub snapshots: Vec<UndoSnapshot>,
+    /// Whether `thread/undo/redo` can bring back the state before the last restore.
+    pub redo_available: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct UndoSnapshot {
+    pub commit_id: String,
+    /// First line of the user message that started the turn.
+    pub summary: Option<String>,
+    /// Files the turn changed.
+    pub files: Vec<UndoSnapshotFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct UndoSnapshotFile {
+    pub path: PathBuf,
+    #[ts(type = "number")]
+    pub insertions: u64,
+    #[ts(type = "number")]
+    pub deletions: u64,
+}
+
+impl From<CoreUndoSnapshot> for UndoSnapshot {
+    fn from(value: CoreUndoSnapshot) -> Self {
+        Self {
+            commit_id: value.commit_id,
+            summary: value.summary,
+            files: value.files.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CoreUndoSnapshotFile> for UndoSnapshotFile {
+    fn from(value: CoreUndoSnapshotFile) -> Self {
+        Self {
+            path: value.path,
+            insertions: value.insertions,
+            deletions: value.deletions,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoDiffParams {
+    pub thread_id: String,
+    pub commit_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoDiffResponse {
+    /// Unified diff from the snapshot to the current working tree.
+    pub diff: String,
+    pub files: Vec<UndoSnapshotFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoRestoreParams {
+    pub thread_id: String,
+    pub commit_id: String,
+    /// Restore only these files. When empty, the whole snapshot is restored and
+    /// later snapshots are dropped until a redo.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoRestoreResponse {
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoRedoParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoRedoResponse {
+    pub message: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
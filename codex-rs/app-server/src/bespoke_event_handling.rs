@@ -1,8 +1,10 @@
 use crate::codex_message_processor::ApiVersion;
 use crate::codex_message_processor::PendingInterrupts;
 use crate::codex_message_processor::PendingRollbacks;
+use crate::codex_message_processor::PendingUndoRequests;
 use crate::codex_message_processor::TurnSummary;
 use crate::codex_message_processor::TurnSummaryStore;
+use crate::codex_message_processor::UndoRequest;
 use crate::codex_message_processor::read_event_msgs_from_rollout;
 use crate::codex_message_processor::read_summary_from_rollout;
 use crate::codex_message_processor::summary_to_thread;
@@ -44,6 +46,7 @@ use codex_app_server_protocol::RawResponseItemCompletedNotification;
 use codex_app_server_protocol::ReasoningSummaryPartAddedNotification;
 use codex_app_server_protocol::ReasoningSummaryTextDeltaNotification;
 use codex_app_server_protocol::ReasoningTextDeltaNotification;
+use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
 use codex_app_server_protocol::TerminalInteractionNotification;
@@ -51,6 +54,10 @@ use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadRollbackResponse;
 use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
+use codex_app_server_protocol::ThreadUndoDiffResponse;
+use codex_app_server_protocol::ThreadUndoListResponse;
+use codex_app_server_protocol::ThreadUndoRedoResponse;
+use codex_app_server_protocol::ThreadUndoRestoreResponse;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnCompletedNotification;
 use codex_app_server_protocol::TurnDiffUpdatedNotification;
@@ -97,6 +104,7 @@ pub(crate) async fn apply_bespoke_event_handling(
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
     pending_rollbacks: PendingRollbacks,
+    pending_undo_requests: PendingUndoRequests,
     turn_summary_store: TurnSummaryStore,
     api_version: ApiVersion,
     fallback_model_provider: String,
@@ -370,6 +378,16 @@ pub(crate) async fn apply_bespoke_event_handling(
                 )
                 .await;
             };
+            // Likewise for a `thread/undo/*` request that core could not serve.
+            if matches!(codex_error_info, Some(CoreCodexErrorInfo::UndoFailed)) {
+                return handle_undo_failed(
+                    conversation_id,
+                    message,
+                    &pending_undo_requests,
+                    &outgoing,
+                )
+                .await;
+            }
 
             let turn_error = TurnError {
                 message: ev.message,
@@ -776,6 +794,61 @@ pub(crate) async fn apply_bespoke_event_handling(
                 outgoing.send_response(request_id, response).await;
             }
         }
+        EventMsg::UndoTimeline(event) => {
+            if let Some((request_id, _)) =
+                take_pending_undo_request(conversation_id, &pending_undo_requests, |request| {
+                    request == UndoRequest::List
+                })
+                .await
+            {
+                let response = ThreadUndoListResponse {
+                    snapshots: event.snapshots.into_iter().map(Into::into).collect(),
+                    redo_available: event.redo_available,
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::UndoSnapshotDiff(event) => {
+            if let Some((request_id, _)) =
+                take_pending_undo_request(conversation_id, &pending_undo_requests, |request| {
+                    request == UndoRequest::Diff
+                })
+                .await
+            {
+                let response = ThreadUndoDiffResponse {
+                    diff: event.diff,
+                    files: event.files.into_iter().map(Into::into).collect(),
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::UndoCompleted(event) => {
+            let pending =
+                take_pending_undo_request(conversation_id, &pending_undo_requests, |request| {
+                    matches!(request, UndoRequest::Restore | UndoRequest::Redo)
+                })
+                .await;
+            if let Some((request_id, request)) = pending {
+                if !event.success {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: event.message.unwrap_or_else(|| "undo failed".to_string()),
+                        data: None,
+                    };
+                    outgoing.send_error(request_id, error).await;
+                } else if request == UndoRequest::Redo {
+                    let response = ThreadUndoRedoResponse {
+                        message: event.message,
+                    };
+                    outgoing.send_response(request_id, response).await;
+                } else {
+                    let response = ThreadUndoRestoreResponse {
+                        message: event.message,
+                    };
+                    outgoing.send_response(request_id, response).await;
+                }
+            }
+        }
         EventMsg::TurnDiff(turn_diff_event) => {
             handle_turn_diff(
                 conversation_id,
@@ -1017,6 +1090,44 @@ async fn handle_thread_rollback_failed(
     }
 }
 
+/// Removes the thread's pending undo request if `accepts` says this answer is for it.
+async fn take_pending_undo_request(
+    conversation_id: ThreadId,
+    pending_undo_requests: &PendingUndoRequests,
+    accepts: impl Fn(UndoRequest) -> bool,
+) -> Option<(RequestId, UndoRequest)> {
+    let mut map = pending_undo_requests.lock().await;
+    match map.get(&conversation_id) {
+        Some((_, request)) if accepts(*request) => map.remove(&conversation_id),
+        _ => None,
+    }
+}
+
+async fn handle_undo_failed(
+    conversation_id: ThreadId,
+    message: String,
+    pending_undo_requests: &PendingUndoRequests,
+    outgoing: &OutgoingMessageSender,
+) {
+    let pending = take_pending_undo_request(conversation_id, pending_undo_requests, |request| {
+        matches!(request, UndoRequest::List | UndoRequest::Diff)
+    })
+    .await;
+
+    if let Some((request_id, _)) = pending {
+        outgoing
+            .send_error(
+                request_id,
+                JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message,
+                    data: None,
+                },
+            )
+            .await;
+    }
+}
+
 async fn handle_token_count_event(
     conversation_id: ThreadId,
     turn_id: String,
@@ -1174,6 +1285,12 @@ fn map_patch_change_kind(change: &CoreFileChange) -> V2PatchChangeKind {
         CoreFileChange::Update { move_path, .. } => V2PatchChangeKind::Update {
             move_path: move_path.clone(),
         },
+        CoreFileChange::Copy { .. } | CoreFileChange::Symlink { .. } => V2PatchChangeKind::Add,
+        CoreFileChange::Mode { .. } => V2PatchChangeKind::Update { move_path: None },
+        CoreFileChange::Binary { previous_size, .. } => match previous_size {
+            Some(_) => V2PatchChangeKind::Update { move_path: None },
+            None => V2PatchChangeKind::Add,
+        },
     }
 }
 
@@ -1191,6 +1308,22 @@ fn format_file_change_diff(change: &CoreFileChange) -> String {
                 unified_diff.clone()
             }
         }
+        CoreFileChange::Copy {
+            source,
+            unified_diff,
+        } => {
+            if unified_diff.is_empty() {
+                format!("Copied from: {}", source.display())
+            } else {
+                format!("{unified_diff}\n\nCopied from: {}", source.display())
+            }
+        }
+        CoreFileChange::Mode { old_mode, new_mode } => match old_mode {
+            Some(old_mode) => format!("Mode changed from {old_mode:o} to {new_mode:o}"),
+            None => format!("Mode set to {new_mode:o}"),
+        },
+        CoreFileChange::Symlink { target } => format!("Symlink to: {}", target.display()),
+        CoreFileChange::Binary { size, .. } => format!("Binary file ({size} bytes)"),
     }
 }
 
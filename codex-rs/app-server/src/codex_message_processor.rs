@@ -58,6 +58,8 @@ use codex_app_server_protocol::LogoutChatGptResponse;
 use codex_app_server_protocol::McpServerOauthLoginCompletedNotification;
 use codex_app_server_protocol::McpServerOauthLoginParams;
 use codex_app_server_protocol::McpServerOauthLoginResponse;
+use codex_app_server_protocol::McpServerPromptRunParams;
+use codex_app_server_protocol::McpServerPromptRunResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
@@ -95,6 +97,10 @@ use codex_app_server_protocol::ThreadRollbackParams;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
+use codex_app_server_protocol::ThreadUndoDiffParams;
+use codex_app_server_protocol::ThreadUndoListParams;
+use codex_app_server_protocol::ThreadUndoRedoParams;
+use codex_app_server_protocol::ThreadUndoRestoreParams;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptParams;
@@ -181,6 +187,17 @@ pub(crate) type PendingInterrupts = Arc<Mutex<HashMap<ThreadId, PendingInterrupt
 
 pub(crate) type PendingRollbacks = Arc<Mutex<HashMap<ThreadId, RequestId>>>;
 
+/// The `thread/undo/*` request waiting for its answer from core.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UndoRequest {
+    List,
+    Diff,
+    Restore,
+    Redo,
+}
+
+pub(crate) type PendingUndoRequests = Arc<Mutex<HashMap<ThreadId, (RequestId, UndoRequest)>>>;
+
 /// Per-conversation accumulation of the latest states e.g. error message while a turn runs.
 #[derive(Default, Clone)]
 pub(crate) struct TurnSummary {
@@ -225,6 +242,8 @@ pub(crate) struct CodexMessageProcessor {
     pending_interrupts: PendingInterrupts,
     // Queue of pending rollback requests per conversation. We reply when ThreadRollback arrives.
     pending_rollbacks: PendingRollbacks,
+    // Pending `thread/undo/*` requests per conversation. We reply when the matching undo event arrives.
+    pending_undo_requests: PendingUndoRequests,
     turn_summary_store: TurnSummaryStore,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     feedback: CodexFeedback,
@@ -280,6 +299,7 @@ impl CodexMessageProcessor {
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             pending_rollbacks: Arc::new(Mutex::new(HashMap::new())),
+            pending_undo_requests: Arc::new(Mutex::new(HashMap::new())),
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             feedback,
@@ -373,6 +393,48 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadRollback { request_id, params } => {
                 self.thread_rollback(request_id, params).await;
             }
+            ClientRequest::ThreadUndoList { request_id, params } => {
+                let ThreadUndoListParams { thread_id } = params;
+                self.thread_undo(
+                    request_id,
+                    thread_id,
+                    UndoRequest::List,
+                    Op::ListUndoSnapshots,
+                )
+                .await;
+            }
+            ClientRequest::ThreadUndoDiff { request_id, params } => {
+                let ThreadUndoDiffParams {
+                    thread_id,
+                    commit_id,
+                } = params;
+                self.thread_undo(
+                    request_id,
+                    thread_id,
+                    UndoRequest::Diff,
+                    Op::GetUndoSnapshotDiff { commit_id },
+                )
+                .await;
+            }
+            ClientRequest::ThreadUndoRestore { request_id, params } => {
+                let ThreadUndoRestoreParams {
+                    thread_id,
+                    commit_id,
+                    paths,
+                } = params;
+                self.thread_undo(
+                    request_id,
+                    thread_id,
+                    UndoRequest::Restore,
+                    Op::RestoreUndoSnapshot { commit_id, paths },
+                )
+                .await;
+            }
+            ClientRequest::ThreadUndoRedo { request_id, params } => {
+                let ThreadUndoRedoParams { thread_id } = params;
+                self.thread_undo(request_id, thread_id, UndoRequest::Redo, Op::Redo)
+                    .await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(request_id, params).await;
             }
@@ -415,6 +477,9 @@ impl CodexMessageProcessor {
             ClientRequest::McpServerStatusList { request_id, params } => {
                 self.list_mcp_server_status(request_id, params).await;
             }
+            ClientRequest::McpServerPromptRun { request_id, params } => {
+                self.mcp_server_prompt_run(request_id, params).await;
+            }
             ClientRequest::LoginAccount { request_id, params } => {
                 self.login_v2(request_id, params).await;
             }
@@ -1564,6 +1629,45 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_undo(
+        &mut self,
+        request_id: RequestId,
+        thread_id: String,
+        request: UndoRequest,
+        op: Op,
+    ) {
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        {
+            let mut map = self.pending_undo_requests.lock().await;
+            if map.contains_key(&thread_id) {
+                self.send_invalid_request_error(
+                    request_id,
+                    "undo request already in progress for this thread".to_string(),
+                )
+                .await;
+                return;
+            }
+
+            map.insert(thread_id, (request_id.clone(), request));
+        }
+
+        if let Err(err) = thread.submit(op).await {
+            // No undo event will arrive if an error occurs.
+            let mut map = self.pending_undo_requests.lock().await;
+            map.remove(&thread_id);
+
+            self.send_internal_error(request_id, format!("failed to submit undo request: {err}"))
+                .await;
+        }
+    }
+
     async fn thread_list(&self, request_id: RequestId, params: ThreadListParams) {
         let ThreadListParams {
             cursor,
@@ -2167,6 +2271,7 @@ impl CodexMessageProcessor {
             .chain(snapshot.auth_statuses.keys().cloned())
             .chain(snapshot.resources.keys().cloned())
             .chain(snapshot.resource_templates.keys().cloned())
+            .chain(snapshot.prompts.keys().cloned())
             .collect();
         server_names.sort();
         server_names.dedup();
@@ -2213,6 +2318,7 @@ impl CodexMessageProcessor {
                     .get(name)
                     .cloned()
                     .unwrap_or_default(),
+                prompts: snapshot.prompts.get(name).cloned().unwrap_or_default(),
                 auth_status: snapshot
                     .auth_statuses
                     .get(name)
@@ -2582,6 +2688,13 @@ impl CodexMessageProcessor {
                 .join(codex_core::ARCHIVED_SESSIONS_SUBDIR);
             tokio::fs::create_dir_all(&archive_folder).await?;
             tokio::fs::rename(&canonical_rollout_path, &archive_folder.join(&file_name)).await?;
+            // Archived threads drop out of session search.
+            if let Err(err) =
+                codex_core::remove_session_index(&self.config.codex_home, &canonical_rollout_path)
+                    .await
+            {
+                warn!("failed to remove search index for thread {thread_id}: {err}");
+            }
             Ok(())
         }
         .await;
@@ -2811,6 +2924,55 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn mcp_server_prompt_run(&self, request_id: RequestId, params: McpServerPromptRunParams) {
+        let (_, thread) = match self.load_thread(&params.thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // The prompt is fetched and expanded by the thread; its messages become the turn input.
+        let turn_id = thread
+            .submit(Op::RunMcpPrompt {
+                server: params.server,
+                prompt: params.prompt,
+                arguments: params.arguments,
+            })
+            .await;
+
+        match turn_id {
+            Ok(turn_id) => {
+                let turn = Turn {
+                    id: turn_id.clone(),
+                    items: vec![],
+                    error: None,
+                    status: TurnStatus::InProgress,
+                };
+
+                let response = McpServerPromptRunResponse { turn: turn.clone() };
+                self.outgoing.send_response(request_id, response).await;
+
+                let notif = TurnStartedNotification {
+                    thread_id: params.thread_id,
+                    turn,
+                };
+                self.outgoing
+                    .send_server_notification(ServerNotification::TurnStarted(notif))
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to run MCP prompt: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     fn build_review_turn(turn_id: String, display_text: &str) -> Turn {
         let items = if display_text.is_empty() {
             Vec::new()
@@ -3120,6 +3282,7 @@ impl CodexMessageProcessor {
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
         let pending_rollbacks = self.pending_rollbacks.clone();
+        let pending_undo_requests = self.pending_undo_requests.clone();
         let turn_summary_store = self.turn_summary_store.clone();
         let api_version_for_task = api_version;
         let fallback_model_provider = self.config.model_provider_id.clone();
@@ -3179,6 +3342,7 @@ impl CodexMessageProcessor {
                             outgoing_for_task.clone(),
                             pending_interrupts.clone(),
                             pending_rollbacks.clone(),
+                            pending_undo_requests.clone(),
                             turn_summary_store.clone(),
                             api_version_for_task,
                             fallback_model_provider.clone(),
@@ -413,6 +413,8 @@ async fn test_send_user_turn_updates_sandbox_and_cwd_between_turns() -> Result<(
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                read_only_subpaths: vec![],
+                deny_read_paths: vec![],
             },
             model: model.clone(),
             effort: Some(ReasoningEffort::Medium),
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use tree_sitter::Parser;
@@ -161,7 +162,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                 })
                 .unwrap_or_else(|| cwd.to_path_buf());
             let mut changes = HashMap::new();
-            for hunk in hunks {
+            for (index, hunk) in hunks.into_iter().enumerate() {
                 match hunk {
                     Hunk::AddFile { path, contents } => {
                         let path = match crate::resolve_patch_path_in_dir(&effective_cwd, &path) {
@@ -170,7 +171,10 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                                 return MaybeApplyPatchVerified::CorrectnessError(err);
                             }
                         };
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+                        let change = ApplyPatchFileChange::Add { content: contents };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
                     }
                     Hunk::DeleteFile { path } => {
                         let path = match crate::resolve_patch_path_in_dir(&effective_cwd, &path) {
@@ -190,7 +194,10 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                                 );
                             }
                         };
-                        changes.insert(path, ApplyPatchFileChange::Delete { content });
+                        let change = ApplyPatchFileChange::Delete { content };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
                     }
                     Hunk::UpdateFile {
                         path,
@@ -209,7 +216,9 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         } = match unified_diff_from_chunks(&path, &chunks) {
                             Ok(diff) => diff,
                             Err(e) => {
-                                return MaybeApplyPatchVerified::CorrectnessError(e);
+                                return MaybeApplyPatchVerified::CorrectnessError(
+                                    crate::with_hunk_number(e, index + 1),
+                                );
                             }
                         };
                         let move_path = match move_path {
@@ -223,14 +232,112 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                             }
                             None => None,
                         };
-                        changes.insert(
-                            path,
-                            ApplyPatchFileChange::Update {
+                        let change = ApplyPatchFileChange::Update {
+                            unified_diff,
+                            move_path,
+                            new_content: contents,
+                        };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
+                    }
+                    Hunk::CopyFile {
+                        path,
+                        copy_path,
+                        chunks,
+                    } => {
+                        let (source, copy_path) = match (
+                            crate::resolve_patch_path_in_dir(&effective_cwd, &path),
+                            crate::resolve_patch_path_in_dir(&effective_cwd, &copy_path),
+                        ) {
+                            (Ok(source), Ok(copy_path)) => (source, copy_path),
+                            (Err(err), _) | (_, Err(err)) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(err);
+                            }
+                        };
+                        let unified_diff = if chunks.is_empty() {
+                            String::new()
+                        } else {
+                            match unified_diff_from_chunks(&source, &chunks) {
+                                Ok(diff) => diff.unified_diff,
+                                Err(e) => {
+                                    return MaybeApplyPatchVerified::CorrectnessError(
+                                        crate::with_hunk_number(e, index + 1),
+                                    );
+                                }
+                            }
+                        };
+                        if let Err(err) = insert_change(
+                            &mut changes,
+                            copy_path,
+                            ApplyPatchFileChange::Copy {
+                                source,
                                 unified_diff,
-                                move_path,
-                                new_content: contents,
                             },
-                        );
+                        ) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
+                    }
+                    Hunk::SetMode { path, mode } => {
+                        let path = match crate::resolve_patch_path_in_dir(&effective_cwd, &path) {
+                            Ok(path) => path,
+                            Err(err) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(err);
+                            }
+                        };
+                        let change = ApplyPatchFileChange::Mode {
+                            old_mode: crate::file_mode(&path),
+                            new_mode: mode,
+                        };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
+                    }
+                    Hunk::AddSymlink { path, target } => {
+                        let path = match crate::resolve_patch_path_in_dir(&effective_cwd, &path) {
+                            Ok(path) => path,
+                            Err(err) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(err);
+                            }
+                        };
+                        let change = ApplyPatchFileChange::Symlink { target };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
+                    }
+                    Hunk::BinaryFile {
+                        path,
+                        contents,
+                        replace,
+                    } => {
+                        let path = match crate::resolve_patch_path_in_dir(&effective_cwd, &path) {
+                            Ok(path) => path,
+                            Err(err) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(err);
+                            }
+                        };
+                        let previous_size = if replace {
+                            match std::fs::metadata(&path) {
+                                Ok(metadata) => Some(metadata.len()),
+                                Err(e) => {
+                                    return MaybeApplyPatchVerified::CorrectnessError(
+                                        ApplyPatchError::IoError(IoError {
+                                            context: format!("Failed to read {}", path.display()),
+                                            source: e,
+                                        }),
+                                    );
+                                }
+                            }
+                        } else {
+                            None
+                        };
+                        let change = ApplyPatchFileChange::Binary {
+                            size: contents.len() as u64,
+                            previous_size,
+                        };
+                        if let Err(err) = insert_change(&mut changes, path, change) {
+                            return MaybeApplyPatchVerified::CorrectnessError(err);
+                        }
                     }
                 }
             }
@@ -246,6 +353,24 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
     }
 }
 
+/// Records the change a hunk makes to `path`. The changes are keyed by path, so a path touched
+/// by more than one hunk would show only the last of them for approval; such patches are
+/// rejected rather than approved on incomplete information.
+fn insert_change(
+    changes: &mut HashMap<PathBuf, ApplyPatchFileChange>,
+    path: PathBuf,
+    change: ApplyPatchFileChange,
+) -> Result<(), ApplyPatchError> {
+    if changes.contains_key(&path) {
+        return Err(ApplyPatchError::ComputeReplacements(format!(
+            "{} is changed by more than one hunk; split the patch so each change can be reviewed",
+            path.display()
+        )));
+    }
+    changes.insert(path, change);
+    Ok(())
+}
+
 /// Extract the heredoc body (and optional `cd` workdir) from a `bash -lc` script
 /// that invokes the apply_patch tool using a heredoc.
 ///
@@ -840,4 +965,99 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_apply_patch_describes_copy_and_binary_changes() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("template.txt"), "name\n").unwrap();
+        fs::write(session_dir.path().join("logo.bin"), [1, 2, 3]).unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(
+                r#"*** Copy File: template.txt
+*** Copy to: app.txt
+@@
+-name
++app
+*** Replace Binary File: logo.bin
++AAEC/w=="#,
+            ),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+        let action = match result {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected verified body, got {other:?}"),
+        };
+        assert_eq!(
+            action.changes(),
+            &HashMap::from([
+                (
+                    session_dir.path().join("app.txt"),
+                    ApplyPatchFileChange::Copy {
+                        source: session_dir.path().join("template.txt"),
+                        unified_diff: "@@ -1 +1 @@\n-name\n+app\n".to_string(),
+                    },
+                ),
+                (
+                    session_dir.path().join("logo.bin"),
+                    ApplyPatchFileChange::Binary {
+                        size: 4,
+                        previous_size: Some(3),
+                    },
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_rejects_second_hunk_for_same_path() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("run.sh"), "echo hi\n").unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(
+                r#"*** Update File: run.sh
+@@
+-echo hi
++echo bye
+*** Set Mode: run.sh
+*** Mode: 755"#,
+            ),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+        assert_matches!(
+            result,
+            MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(_))
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_rejects_two_updates_for_same_path() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch(
+                r#"*** Update File: a.txt
+@@
+-one
++uno
+*** Update File: a.txt
+@@
+-two
++dos"#,
+            ),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+        assert_matches!(
+            result,
+            MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(_))
+        );
+    }
 }
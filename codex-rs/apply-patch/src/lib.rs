@@ -97,6 +97,25 @@ pub enum ApplyPatchFileChange {
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
     },
+    /// The file is a copy of `source`, with `unified_diff` applied to the copy.
+    Copy {
+        source: PathBuf,
+        unified_diff: String,
+    },
+    /// The permission bits of the file change, e.g. from `0o644` to `0o755`.
+    Mode {
+        old_mode: Option<u32>,
+        new_mode: u32,
+    },
+    /// The file becomes a symbolic link to `target`.
+    Symlink {
+        target: PathBuf,
+    },
+    /// The file is written with `size` bytes of binary contents.
+    Binary {
+        size: u64,
+        previous_size: Option<u64>,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -290,6 +309,9 @@ pub fn apply_hunks(
                 }
                 None => Some(path.as_path()),
             },
+            Hunk::CopyFile { path, .. } | Hunk::SetMode { path, .. } => Some(path.as_path()),
+            Hunk::AddSymlink { .. } => None,
+            Hunk::BinaryFile { path, replace, .. } => replace.then_some(path.as_path()),
         })
         .collect::<Vec<&Path>>();
 
@@ -337,6 +359,34 @@ fn resolve_hunks_in_dir(workdir: &Path, hunks: &[Hunk]) -> Result<Vec<Hunk>, App
                     .transpose()?,
                 chunks: chunks.clone(),
             },
+            Hunk::CopyFile {
+                path,
+                copy_path,
+                chunks,
+            } => Hunk::CopyFile {
+                path: resolve_patch_path_in_dir(workdir, path)?,
+                copy_path: resolve_patch_path_in_dir(workdir, copy_path)?,
+                chunks: chunks.clone(),
+            },
+            Hunk::SetMode { path, mode } => Hunk::SetMode {
+                path: resolve_patch_path_in_dir(workdir, path)?,
+                mode: *mode,
+            },
+            // The link target is stored as written; a relative target is
+            // relative to the directory containing the link.
+            Hunk::AddSymlink { path, target } => Hunk::AddSymlink {
+                path: resolve_patch_path_in_dir(workdir, path)?,
+                target: target.clone(),
+            },
+            Hunk::BinaryFile {
+                path,
+                contents,
+                replace,
+            } => Hunk::BinaryFile {
+                path: resolve_patch_path_in_dir(workdir, path)?,
+                contents: contents.clone(),
+                replace: *replace,
+            },
         };
         resolved.push(resolved_hunk);
     }
@@ -401,31 +451,27 @@ pub struct AffectedPaths {
 
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
 /// Returns an error if the patch could not be applied.
+///
+/// The patch is applied all-or-nothing: every hunk is verified and the new file contents are
+/// computed in memory before anything is written, and if writing one of the files fails, the
+/// files already written are restored.
 fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
 
+    let mut plan = PatchPlan::default();
     let mut added: Vec<PathBuf> = Vec::new();
     let mut modified: Vec<PathBuf> = Vec::new();
     let mut deleted: Vec<PathBuf> = Vec::new();
-    for hunk in hunks {
+    for (index, hunk) in hunks.iter().enumerate() {
         match hunk {
             Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
-                    && !parent.as_os_str().is_empty()
-                {
-                    std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
-                    })?;
-                }
-                std::fs::write(path, contents)
-                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                plan.write(path, contents.clone())?;
                 added.push(path.clone());
             }
             Hunk::DeleteFile { path } => {
-                std::fs::remove_file(path)
-                    .with_context(|| format!("Failed to delete file {}", path.display()))?;
+                plan.delete(path)?;
                 deleted.push(path.clone());
             }
             Hunk::UpdateFile {
@@ -433,29 +479,72 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                 move_path,
                 chunks,
             } => {
+                let original_contents = plan.read_for_update(path)?;
                 let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
+                    apply_chunks(original_contents, path, chunks)
+                        .map_err(|err| with_hunk_number(err, index + 1))?;
                 if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
-                        })?;
-                    }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
+                    plan.write(dest, new_contents)?;
+                    plan.delete(path)
                         .with_context(|| format!("Failed to remove original {}", path.display()))?;
                     modified.push(dest.clone());
                 } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
+                    plan.write(path, new_contents)?;
                     modified.push(path.clone());
                 }
             }
+            Hunk::CopyFile {
+                path,
+                copy_path,
+                chunks,
+            } => {
+                let (contents, permissions) = plan.read_for_copy(path)?;
+                let contents = if chunks.is_empty() {
+                    contents
+                } else {
+                    let original_contents = String::from_utf8(contents).map_err(|_| {
+                        anyhow::anyhow!(
+                            "Failed to update copy of {}: the file is not valid UTF-8",
+                            path.display()
+                        )
+                    })?;
+                    apply_chunks(original_contents, path, chunks)
+                        .map_err(|err| with_hunk_number(err, index + 1))?
+                        .new_contents
+                        .into_bytes()
+                };
+                plan.write_bytes(copy_path, contents)?;
+                plan.set_permissions(copy_path, permissions)?;
+                added.push(copy_path.clone());
+            }
+            Hunk::SetMode { path, mode } => {
+                plan.set_mode(path, *mode)?;
+                modified.push(path.clone());
+            }
+            Hunk::AddSymlink { path, target } => {
+                plan.symlink(path, target)?;
+                added.push(path.clone());
+            }
+            Hunk::BinaryFile {
+                path,
+                contents,
+                replace,
+            } => {
+                if *replace {
+                    plan.read_for_copy(path).with_context(|| {
+                        format!("Failed to replace binary file {}", path.display())
+                    })?;
+                    plan.write_bytes(path, contents.clone())?;
+                    modified.push(path.clone());
+                } else {
+                    plan.write_bytes(path, contents.clone())?;
+                    added.push(path.clone());
+                }
+            }
         }
     }
+
+    plan.commit()?;
     Ok(AffectedPaths {
         added,
         modified,
@@ -463,6 +552,402 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     })
 }
 
+/// Prefixes a failure to match an update hunk against its file with the hunk's (1-based)
+/// position in the patch.
+pub(crate) fn with_hunk_number(err: ApplyPatchError, hunk_number: usize) -> ApplyPatchError {
+    match err {
+        ApplyPatchError::ComputeReplacements(message) => {
+            ApplyPatchError::ComputeReplacements(format!("Hunk {hunk_number}: {message}"))
+        }
+        other => other,
+    }
+}
+
+/// The end state of every file a patch touches, worked out before anything is written.
+#[derive(Default)]
+struct PatchPlan {
+    /// Touched files in the order the patch first touches them.
+    files: Vec<PlannedFile>,
+}
+
+struct PlannedFile {
+    path: PathBuf,
+    /// What was at `path` before the patch, if it was a file or a symlink.
+    original: Option<OriginalFile>,
+    state: PlannedState,
+    /// Permissions the patch gives the file, if it sets them.
+    permissions: Option<std::fs::Permissions>,
+}
+
+enum OriginalFile {
+    File {
+        contents: Vec<u8>,
+        permissions: std::fs::Permissions,
+    },
+    Symlink(PathBuf),
+}
+
+enum PlannedState {
+    Unchanged,
+    Written(Vec<u8>),
+    Symlink(PathBuf),
+    Deleted,
+}
+
+impl PlannedFile {
+    fn exists(&self) -> bool {
+        match self.state {
+            PlannedState::Unchanged => self.original.is_some(),
+            PlannedState::Written(_) | PlannedState::Symlink(_) => true,
+            PlannedState::Deleted => false,
+        }
+    }
+
+    /// Contents of the file as of the hunks planned so far, if it is a regular file.
+    fn contents(&self) -> Option<&[u8]> {
+        match (&self.state, &self.original) {
+            (PlannedState::Written(contents), _) => Some(contents),
+            (PlannedState::Unchanged, Some(OriginalFile::File { contents, .. })) => Some(contents),
+            _ => None,
+        }
+    }
+
+    fn current_permissions(&self) -> Option<std::fs::Permissions> {
+        match (&self.permissions, &self.original) {
+            (Some(permissions), _) => Some(permissions.clone()),
+            (None, Some(OriginalFile::File { permissions, .. })) => Some(permissions.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_touched(&self) -> bool {
+        !matches!(self.state, PlannedState::Unchanged) || self.permissions.is_some()
+    }
+}
+
+impl PatchPlan {
+    /// The planned file for `path`, which is resolved if it is a symlink and `follow_symlink`
+    /// is set.
+    fn entry(&mut self, path: &Path, follow_symlink: bool) -> std::io::Result<&mut PlannedFile> {
+        let metadata = std::fs::symlink_metadata(path).ok();
+        let path = match &metadata {
+            Some(metadata) if follow_symlink && metadata.file_type().is_symlink() => {
+                std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+            }
+            _ => path.to_path_buf(),
+        };
+        if let Some(index) = self.files.iter().position(|file| file.path == path) {
+            return Ok(&mut self.files[index]);
+        }
+
+        let original = match std::fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                Some(OriginalFile::Symlink(std::fs::read_link(&path)?))
+            }
+            Ok(metadata) if metadata.is_file() => Some(OriginalFile::File {
+                contents: std::fs::read(&path)?,
+                permissions: metadata.permissions(),
+            }),
+            _ => None,
+        };
+        self.files.push(PlannedFile {
+            path,
+            original,
+            state: PlannedState::Unchanged,
+            permissions: None,
+        });
+        let index = self.files.len() - 1;
+        Ok(&mut self.files[index])
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> anyhow::Result<()> {
+        self.write_bytes(path, contents.into_bytes())
+    }
+
+    fn write_bytes(&mut self, path: &Path, contents: Vec<u8>) -> anyhow::Result<()> {
+        let file = self
+            .entry(path, true)
+            .with_context(|| format!("Failed to write file {}", path.display()))?;
+        if let Some(OriginalFile::File { .. }) = file.original {
+            // Renaming over the file would succeed even where writing to it fails, so check
+            // that it is writable rather than quietly replacing a read-only file.
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&file.path)
+                .with_context(|| format!("Failed to write file {}", path.display()))?;
+        }
+        file.state = PlannedState::Written(contents);
+        Ok(())
+    }
+
+    fn symlink(&mut self, path: &Path, target: &Path) -> anyhow::Result<()> {
+        let file = self
+            .entry(path, false)
+            .with_context(|| format!("Failed to create symlink {}", path.display()))?;
+        file.state = PlannedState::Symlink(target.to_path_buf());
+        file.permissions = None;
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &Path) -> anyhow::Result<()> {
+        let file = self
+            .entry(path, false)
+            .with_context(|| format!("Failed to delete file {}", path.display()))?;
+        if !file.exists() {
+            anyhow::bail!("Failed to delete file {}", path.display());
+        }
+        file.state = PlannedState::Deleted;
+        file.permissions = None;
+        Ok(())
+    }
+
+    fn set_mode(&mut self, path: &Path, mode: u32) -> anyhow::Result<()> {
+        let file = self
+            .entry(path, true)
+            .with_context(|| format!("Failed to set mode of {}", path.display()))?;
+        if file.contents().is_none() {
+            anyhow::bail!("Failed to set mode of {}: no such file", path.display());
+        }
+        file.permissions = mode_permissions(mode);
+        Ok(())
+    }
+
+    fn set_permissions(
+        &mut self,
+        path: &Path,
+        permissions: Option<std::fs::Permissions>,
+    ) -> anyhow::Result<()> {
+        let file = self
+            .entry(path, true)
+            .with_context(|| format!("Failed to write file {}", path.display()))?;
+        file.permissions = permissions;
+        Ok(())
+    }
+
+    /// Current contents of a file to update, including changes made by earlier hunks.
+    fn read_for_update(&mut self, path: &Path) -> Result<String, ApplyPatchError> {
+        let read_error = |source| {
+            ApplyPatchError::IoError(IoError {
+                context: format!("Failed to read file to update {}", path.display()),
+                source,
+            })
+        };
+        let file = self.entry(path, true).map_err(read_error)?;
+        match (file.contents(), &file.state) {
+            (Some(contents), _) => String::from_utf8(contents.to_vec()).map_err(|_| {
+                read_error(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ))
+            }),
+            (None, PlannedState::Unchanged) => {
+                std::fs::read_to_string(&file.path).map_err(read_error)
+            }
+            (None, _) => Err(read_error(std::io::Error::from(
+                std::io::ErrorKind::NotFound,
+            ))),
+        }
+    }
+
+    /// Current contents and permissions of a file to copy, including changes made by earlier
+    /// hunks.
+    fn read_for_copy(
+        &mut self,
+        path: &Path,
+    ) -> anyhow::Result<(Vec<u8>, Option<std::fs::Permissions>)> {
+        let context = || format!("Failed to read file to copy {}", path.display());
+        let file = self.entry(path, true).with_context(context)?;
+        let contents = match (file.contents(), &file.state) {
+            (Some(contents), _) => contents.to_vec(),
+            (None, PlannedState::Unchanged) => std::fs::read(&file.path).with_context(context)?,
+            (None, _) => {
+                return Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+                    .with_context(context);
+            }
+        };
+        Ok((contents, file.current_permissions()))
+    }
+
+    /// Writes every planned file through a temporary file that is then renamed into place,
+    /// restoring the original files if any step fails.
+    fn commit(self) -> anyhow::Result<()> {
+        let mut created_dirs: Vec<PathBuf> = Vec::new();
+        let mut staged: Vec<(&Path, PathBuf)> = Vec::new();
+        let result = self
+            .stage(&mut created_dirs, &mut staged)
+            .and_then(|()| self.replace_files(&staged));
+        if result.is_err() {
+            for (_, temp) in &staged {
+                let _ = std::fs::remove_file(temp);
+            }
+            for dir in created_dirs.iter().rev() {
+                let _ = std::fs::remove_dir(dir);
+            }
+        }
+        result
+    }
+
+    /// Writes the new files and links next to the paths they replace, leaving those untouched.
+    fn stage<'a>(
+        &'a self,
+        created_dirs: &mut Vec<PathBuf>,
+        staged: &mut Vec<(&'a Path, PathBuf)>,
+    ) -> anyhow::Result<()> {
+        for file in &self.files {
+            if !matches!(
+                file.state,
+                PlannedState::Written(_) | PlannedState::Symlink(_)
+            ) {
+                continue;
+            }
+            if let Some(parent) = file.path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                let missing: Vec<PathBuf> = parent
+                    .ancestors()
+                    .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+                    .map(Path::to_path_buf)
+                    .collect();
+                std::fs::create_dir_all(parent).with_context(|| {
+                    format!(
+                        "Failed to create parent directories for {}",
+                        file.path.display()
+                    )
+                })?;
+                created_dirs.extend(missing.into_iter().rev());
+            }
+            let temp = temp_path_for(&file.path);
+            let written = match &file.state {
+                PlannedState::Symlink(target) => create_symlink(target, &temp),
+                PlannedState::Written(contents) => std::fs::write(&temp, contents),
+                PlannedState::Unchanged | PlannedState::Deleted => Ok(()),
+            };
+            staged.push((&file.path, temp.clone()));
+            written.with_context(|| format!("Failed to write file {}", file.path.display()))?;
+            if let PlannedState::Written(_) = file.state
+                && let Some(permissions) = file.current_permissions()
+            {
+                std::fs::set_permissions(&temp, permissions)
+                    .with_context(|| format!("Failed to write file {}", file.path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the staged files into place and removes deleted ones, in patch order.
+    fn replace_files(&self, staged: &[(&Path, PathBuf)]) -> anyhow::Result<()> {
+        for (done, file) in self.files.iter().enumerate() {
+            let replaced = match &file.state {
+                PlannedState::Unchanged => match &file.permissions {
+                    Some(permissions) => std::fs::set_permissions(&file.path, permissions.clone())
+                        .with_context(|| format!("Failed to set mode of {}", file.path.display())),
+                    None => Ok(()),
+                },
+                PlannedState::Written(_) | PlannedState::Symlink(_) => {
+                    match staged.iter().find(|(path, _)| *path == file.path) {
+                        Some((_, temp)) => std::fs::rename(temp, &file.path).with_context(|| {
+                            format!("Failed to write file {}", file.path.display())
+                        }),
+                        None => Ok(()),
+                    }
+                }
+                PlannedState::Deleted if file.original.is_none() => Ok(()),
+                PlannedState::Deleted => std::fs::remove_file(&file.path)
+                    .with_context(|| format!("Failed to delete file {}", file.path.display())),
+            };
+            if let Err(err) = replaced {
+                return Err(restore(&self.files[..done], err));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Puts back the files that were already replaced when `err` stopped the patch.
+fn restore(replaced: &[PlannedFile], err: anyhow::Error) -> anyhow::Error {
+    let mut not_restored = Vec::new();
+    for file in replaced.iter().rev() {
+        if !file.is_touched() {
+            continue;
+        }
+        let restored = match &file.original {
+            Some(OriginalFile::File {
+                contents,
+                permissions,
+            }) => {
+                if std::fs::symlink_metadata(&file.path).is_ok_and(|m| m.file_type().is_symlink()) {
+                    let _ = std::fs::remove_file(&file.path);
+                }
+                std::fs::write(&file.path, contents)
+                    .and_then(|()| std::fs::set_permissions(&file.path, permissions.clone()))
+            }
+            Some(OriginalFile::Symlink(target)) => {
+                let _ = std::fs::remove_file(&file.path);
+                create_symlink(target, &file.path)
+            }
+            None if matches!(file.state, PlannedState::Deleted) => Ok(()),
+            None => std::fs::remove_file(&file.path),
+        };
+        if restored.is_err() {
+            not_restored.push(file.path.display().to_string());
+        }
+    }
+    if not_restored.is_empty() {
+        err
+    } else {
+        let not_restored = not_restored.join(", ");
+        err.context(format!(
+            "Failed to apply patch and could not restore {not_restored}"
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+#[cfg(unix)]
+fn mode_permissions(mode: u32) -> Option<std::fs::Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn mode_permissions(_mode: u32) -> Option<std::fs::Permissions> {
+    // There are no permission bits to set.
+    None
+}
+
+/// The permission bits of the file at `path`, where the platform has them.
+#[cfg(unix)]
+pub(crate) fn file_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn file_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// A path next to `path` for staging its new contents, so that the final rename stays on the
+/// same filesystem.
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let pid = std::process::id();
+    path.with_file_name(format!(".{name}.apply_patch.{pid}.tmp"))
+}
+
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -483,7 +968,15 @@ fn derive_new_contents_from_chunks(
             }));
         }
     };
+    apply_chunks(original_contents, path, chunks)
+}
 
+/// Applies the chunks to `original_contents`, the contents of the file at `path`.
+fn apply_chunks(
+    original_contents: String,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
 
     // Drop the trailing empty element that results from the final newline so
@@ -516,7 +1009,8 @@ fn compute_replacements(
     let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
     let mut line_index: usize = 0;
 
-    for chunk in chunks {
+    for (chunk_index, chunk) in chunks.iter().enumerate() {
+        let chunk_number = chunk_index + 1;
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
         // adjust our `line_index` to continue from there.
         if let Some(ctx_line) = &chunk.change_context {
@@ -529,7 +1023,7 @@ fn compute_replacements(
                 line_index = idx + 1;
             } else {
                 return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
+                    "Failed to find context '{}' in {} (chunk {chunk_number})",
                     ctx_line,
                     path.display()
                 )));
@@ -586,9 +1080,10 @@ fn compute_replacements(
             line_index = start_idx + pattern.len();
         } else {
             return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
+                "Failed to find expected lines in {} (chunk {chunk_number}):\n{}\n{}",
                 path.display(),
                 chunk.old_lines.join("\n"),
+                describe_mismatch(original_lines, pattern, line_index),
             )));
         }
     }
@@ -598,6 +1093,37 @@ fn compute_replacements(
     Ok(replacements)
 }
 
+/// Explains why `pattern` could not be found in `lines` at or after `start`: the first line of
+/// the pattern that does not match, after the longest prefix of it that does.
+fn describe_mismatch(lines: &[String], pattern: &[String], start: usize) -> String {
+    let longest_match = (1..pattern.len()).rev().find_map(|len| {
+        seek_sequence::seek_sequence(lines, &pattern[..len], start, false).map(|idx| (len, idx))
+    });
+    match longest_match {
+        Some((len, idx)) => {
+            let matched = if len == 1 {
+                "Line 1 of the chunk matches".to_string()
+            } else {
+                format!("Lines 1-{len} of the chunk match")
+            };
+            format!(
+                "{matched} at line {}, but line {} does not: '{}'",
+                idx + 1,
+                len + 1,
+                pattern[len]
+            )
+        }
+        None => {
+            let line = pattern.first().map(String::as_str).unwrap_or_default();
+            if start == 0 {
+                format!("Line 1 of the chunk does not match any line: '{line}'")
+            } else {
+                format!("Line 1 of the chunk does not match any line after line {start}: '{line}'")
+            }
+        }
+    }
+}
+
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
 /// returning the modified file contents as a vector of lines.
 fn apply_replacements(
@@ -1177,6 +1703,178 @@ g
         );
     }
 
+    #[test]
+    fn test_failed_hunk_leaves_earlier_files_untouched() {
+        let dir = tempdir().unwrap();
+        let modify = dir.path().join("modify.txt");
+        let delete = dir.path().join("delete.txt");
+        fs::write(&modify, "line1\nline2\n").unwrap();
+        fs::write(&delete, "obsolete\n").unwrap();
+        let patch = wrap_patch(
+            r#"*** Add File: nested/created.txt
++hello
+*** Update File: modify.txt
+@@
+-line2
++changed
+*** Delete File: delete.txt
+*** Update File: modify.txt
+@@
+ line1
+-line9
++other"#,
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch_in_dir(dir.path(), &patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!(
+                "Hunk 4: Failed to find expected lines in {} (chunk 1):\nline1\nline9\nLine 1 of the chunk matches at line 1, but line 2 does not: 'line9'\n",
+                modify.display()
+            )
+        );
+        assert!(!dir.path().join("nested").exists());
+        assert_eq!(fs::read_to_string(&modify).unwrap(), "line1\nline2\n");
+        assert_eq!(fs::read_to_string(&delete).unwrap(), "obsolete\n");
+    }
+
+    #[test]
+    fn test_failed_write_restores_replaced_files() {
+        let dir = tempdir().unwrap();
+        let modify = dir.path().join("modify.txt");
+        let blocker = dir.path().join("blocker");
+        fs::write(&modify, "before\n").unwrap();
+        fs::create_dir(&blocker).unwrap();
+        fs::write(blocker.join("inner.txt"), "x").unwrap();
+        // Renaming the new file over a non-empty directory only fails once `modify.txt` has
+        // already been replaced.
+        let patch = wrap_patch(
+            r#"*** Update File: modify.txt
+@@
+-before
++after
+*** Add File: blocker
++contents"#,
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch_in_dir(dir.path(), &patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&modify).unwrap(), "before\n");
+        assert!(blocker.join("inner.txt").exists());
+        let mut entries: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec!["blocker", "modify.txt"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_mode_and_copy_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "echo hi\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        let patch = wrap_patch(
+            r#"*** Set Mode: run.sh
+*** Mode: 755
+*** Copy File: run.sh
+*** Copy to: scripts/other.sh
+@@
+-echo hi
++echo bye"#,
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch_in_dir(dir.path(), &patch, &mut stdout, &mut stderr).unwrap();
+
+        let copy = dir.path().join("scripts/other.sh");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&script), 0o755);
+        assert_eq!(mode(&copy), 0o755);
+        assert_eq!(fs::read_to_string(&script).unwrap(), "echo hi\n");
+        assert_eq!(fs::read_to_string(&copy).unwrap(), "echo bye\n");
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nA {}\nM {}\n",
+                copy.display(),
+                script.display()
+            )
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_add_symlink_and_binary_file() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("image.png"), [1, 2, 3]).unwrap();
+        let patch = wrap_patch(
+            r#"*** Add Symlink: current
+*** Link to: image.png
+*** Replace Binary File: image.png
++AAEC/w==
+*** Add Binary File: blank.bin"#,
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch_in_dir(dir.path(), &patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            fs::read_link(dir.path().join("current")).unwrap(),
+            PathBuf::from("image.png")
+        );
+        assert_eq!(
+            fs::read(dir.path().join("current")).unwrap(),
+            [0, 1, 2, 255]
+        );
+        assert_eq!(
+            fs::read(dir.path().join("blank.bin")).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failed_patch_restores_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "echo hi\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::create_dir_all(dir.path().join("blocker/inner")).unwrap();
+        let patch = wrap_patch(
+            r#"*** Set Mode: run.sh
+*** Mode: 755
+*** Add Symlink: blocker
+*** Link to: run.sh"#,
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch_in_dir(dir.path(), &patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o644
+        );
+        assert!(dir.path().join("blocker/inner").is_dir());
+    }
+
     #[test]
     fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
@@ -7,14 +7,21 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
+//! hunk: add_hunk | delete_hunk | update_hunk | copy_hunk | mode_hunk | symlink_hunk | binary_hunk
 //! add_hunk: "*** Add File: " filename LF add_line+
 //! delete_hunk: "*** Delete File: " filename LF
 //! update_hunk: "*** Update File: " filename LF change_move? change?
+//! copy_hunk: "*** Copy File: " filename LF change_copy change?
+//! mode_hunk: "*** Set Mode: " filename LF "*** Mode: " mode LF
+//! symlink_hunk: "*** Add Symlink: " filename LF "*** Link to: " filename LF
+//! binary_hunk: ("*** Add Binary File: " | "*** Replace Binary File: ") filename LF base64_line*
 //! filename: /(.+)/
+//! mode: /0?[0-7]{3}/
 //! add_line: "+" /(.+)/ LF -> line
+//! base64_line: "+" /[A-Za-z0-9+\/=]*/ LF
 //!
 //! change_move: "*** Move to: " filename LF
+//! change_copy: "*** Copy to: " filename LF
 //! change: (change_context | change_line)+ eof_line?
 //! change_context: ("@@" | "@@ " /(.+)/) LF
 //! change_line: ("+" | "-" | " ") /(.+)/ LF
@@ -23,6 +30,7 @@
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
+use base64::Engine;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -34,6 +42,14 @@ const ADD_FILE_MARKER: &str = "*** Add File: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
+const COPY_FILE_MARKER: &str = "*** Copy File: ";
+const COPY_TO_MARKER: &str = "*** Copy to: ";
+const SET_MODE_MARKER: &str = "*** Set Mode: ";
+const MODE_MARKER: &str = "*** Mode: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
+const LINK_TO_MARKER: &str = "*** Link to: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const REPLACE_BINARY_FILE_MARKER: &str = "*** Replace Binary File: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
 const EMPTY_CHANGE_CONTEXT_MARKER: &str = "@@";
@@ -73,6 +89,30 @@ pub enum Hunk {
         /// should occur later in the file than the previous chunk.
         chunks: Vec<UpdateFileChunk>,
     },
+    /// Copies the file at `path` to `copy_path`, then applies `chunks` (if
+    /// any) to the copy.
+    CopyFile {
+        path: PathBuf,
+        copy_path: PathBuf,
+        chunks: Vec<UpdateFileChunk>,
+    },
+    /// Sets the permission bits of an existing file, e.g. `0o755` to make a
+    /// script executable.
+    SetMode {
+        path: PathBuf,
+        mode: u32,
+    },
+    AddSymlink {
+        path: PathBuf,
+        target: PathBuf,
+    },
+    /// Writes `contents`, decoded from base64, to `path`. `replace` requires
+    /// the file to exist already.
+    BinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+        replace: bool,
+    },
 }
 
 impl Hunk {
@@ -81,6 +121,10 @@ impl Hunk {
             Hunk::AddFile { path, .. } => cwd.join(path),
             Hunk::DeleteFile { path } => cwd.join(path),
             Hunk::UpdateFile { path, .. } => cwd.join(path),
+            Hunk::CopyFile { copy_path, .. } => cwd.join(copy_path),
+            Hunk::SetMode { path, .. } => cwd.join(path),
+            Hunk::AddSymlink { path, .. } => cwd.join(path),
+            Hunk::BinaryFile { path, .. } => cwd.join(path),
         }
     }
 }
@@ -291,29 +335,9 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             parsed_lines += 1;
         }
 
-        let mut chunks = Vec::new();
-        // NOTE: we need to know to stop once we reach the next special marker header.
-        while !remaining_lines.is_empty() {
-            // Skip over any completely blank lines that may separate chunks.
-            if remaining_lines[0].trim().is_empty() {
-                parsed_lines += 1;
-                remaining_lines = &remaining_lines[1..];
-                continue;
-            }
-
-            if remaining_lines[0].starts_with("***") {
-                break;
-            }
-
-            let (chunk, chunk_lines) = parse_update_file_chunk(
-                remaining_lines,
-                line_number + parsed_lines,
-                chunks.is_empty(),
-            )?;
-            chunks.push(chunk);
-            parsed_lines += chunk_lines;
-            remaining_lines = &remaining_lines[chunk_lines..]
-        }
+        let (chunks, chunk_lines) =
+            parse_update_file_chunks(remaining_lines, line_number + parsed_lines)?;
+        parsed_lines += chunk_lines;
 
         if chunks.is_empty() {
             return Err(InvalidHunkError {
@@ -330,16 +354,144 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             },
             parsed_lines,
         ));
+    } else if let Some(path) = first_line.strip_prefix(COPY_FILE_MARKER) {
+        // Copy File
+        let copy_path = required_marker_line(lines, line_number, COPY_TO_MARKER, "Copy File")?;
+        let (chunks, chunk_lines) = parse_update_file_chunks(&lines[2..], line_number + 2)?;
+        return Ok((
+            CopyFile {
+                path: PathBuf::from(path),
+                copy_path: PathBuf::from(copy_path),
+                chunks,
+            },
+            2 + chunk_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(SET_MODE_MARKER) {
+        // Set Mode
+        let mode = required_marker_line(lines, line_number, MODE_MARKER, "Set Mode")?;
+        let mode = parse_mode(mode).ok_or_else(|| InvalidHunkError {
+            message: format!(
+                "Invalid mode '{mode}' for path '{path}': expected octal permission bits such as 755 or 644"
+            ),
+            line_number: line_number + 1,
+        })?;
+        return Ok((
+            SetMode {
+                path: PathBuf::from(path),
+                mode,
+            },
+            2,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        // Add Symlink
+        let target = required_marker_line(lines, line_number, LINK_TO_MARKER, "Add Symlink")?;
+        return Ok((
+            AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            },
+            2,
+        ));
+    } else if let Some((path, replace)) = first_line
+        .strip_prefix(ADD_BINARY_FILE_MARKER)
+        .map(|path| (path, false))
+        .or_else(|| {
+            first_line
+                .strip_prefix(REPLACE_BINARY_FILE_MARKER)
+                .map(|path| (path, true))
+        })
+    {
+        // Add/Replace Binary File
+        let encoded: Vec<&str> = lines[1..]
+            .iter()
+            .map_while(|line| line.strip_prefix('+'))
+            .collect();
+        let contents = base64::engine::general_purpose::STANDARD
+            .decode(encoded.iter().map(|line| line.trim()).collect::<String>())
+            .map_err(|err| InvalidHunkError {
+                message: format!("Invalid base64 contents for binary file '{path}': {err}"),
+                line_number,
+            })?;
+        return Ok((
+            BinaryFile {
+                path: PathBuf::from(path),
+                contents,
+                replace,
+            },
+            1 + encoded.len(),
+        ));
     }
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}', '*** Copy File: {{path}}', '*** Set Mode: {{path}}', '*** Add Symlink: {{path}}', '*** Add Binary File: {{path}}', '*** Replace Binary File: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Returns the value of the `marker` line that must follow a `hunk` header.
+fn required_marker_line<'a>(
+    lines: &[&'a str],
+    line_number: usize,
+    marker: &str,
+    hunk: &str,
+) -> Result<&'a str, ParseError> {
+    lines
+        .get(1)
+        .and_then(|line| line.trim().strip_prefix(marker))
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| InvalidHunkError {
+            message: format!("{hunk} hunk must be followed by '{marker}{{value}}'"),
+            line_number: line_number + 1,
+        })
+}
+
+/// Parses octal permission bits such as `755` or `0644`. The setuid, setgid
+/// and sticky bits are rejected: a patch has no business setting them.
+fn parse_mode(mode: &str) -> Option<u32> {
+    let mode = mode.trim();
+    if !(3..=4).contains(&mode.len()) || !mode.chars().all(|c| c.is_digit(8)) {
+        return None;
+    }
+    u32::from_str_radix(mode, 8)
+        .ok()
+        .filter(|mode| *mode <= 0o777)
+}
+
+/// Parses the change chunks of an update or copy hunk, stopping at the next
+/// `***` marker. Returns the chunks and the number of lines parsed.
+fn parse_update_file_chunks(
+    mut remaining_lines: &[&str],
+    line_number: usize,
+) -> Result<(Vec<UpdateFileChunk>, usize), ParseError> {
+    let mut chunks = Vec::new();
+    let mut parsed_lines = 0;
+    // NOTE: we need to know to stop once we reach the next special marker header.
+    while !remaining_lines.is_empty() {
+        // Skip over any completely blank lines that may separate chunks.
+        if remaining_lines[0].trim().is_empty() {
+            parsed_lines += 1;
+            remaining_lines = &remaining_lines[1..];
+            continue;
+        }
+
+        if remaining_lines[0].starts_with("***") {
+            break;
+        }
+
+        let (chunk, chunk_lines) = parse_update_file_chunk(
+            remaining_lines,
+            line_number + parsed_lines,
+            chunks.is_empty(),
+        )?;
+        chunks.push(chunk);
+        parsed_lines += chunk_lines;
+        remaining_lines = &remaining_lines[chunk_lines..]
+    }
+    Ok((chunks, parsed_lines))
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -675,13 +827,123 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], 234),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', \
+            '*** Copy File: {path}', '*** Set Mode: {path}', '*** Add Symlink: {path}', \
+            '*** Add Binary File: {path}', '*** Replace Binary File: {path}'".to_string(),
             line_number: 234
         })
     );
     // Other edge cases are already covered by tests above/below.
 }
 
+#[test]
+fn test_parse_copy_mode_symlink_and_binary_hunks() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Copy File: src.txt\n\
+             *** Copy to: dst.txt\n\
+             @@\n\
+             -old\n\
+             +new\n\
+             *** Set Mode: run.sh\n\
+             *** Mode: 755\n\
+             *** Add Symlink: link\n\
+             *** Link to: ../target\n\
+             *** Add Binary File: data.bin\n\
+             +AAEC\n\
+             +/w==\n\
+             *** Replace Binary File: empty.bin\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            CopyFile {
+                path: PathBuf::from("src.txt"),
+                copy_path: PathBuf::from("dst.txt"),
+                chunks: vec![UpdateFileChunk {
+                    change_context: None,
+                    old_lines: vec!["old".to_string()],
+                    new_lines: vec!["new".to_string()],
+                    is_end_of_file: false
+                }]
+            },
+            SetMode {
+                path: PathBuf::from("run.sh"),
+                mode: 0o755
+            },
+            AddSymlink {
+                path: PathBuf::from("link"),
+                target: PathBuf::from("../target")
+            },
+            BinaryFile {
+                path: PathBuf::from("data.bin"),
+                contents: vec![0, 1, 2, 255],
+                replace: false
+            },
+            BinaryFile {
+                path: PathBuf::from("empty.bin"),
+                contents: Vec::new(),
+                replace: true
+            },
+        ]
+    );
+
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Set Mode: run.sh\n*** Mode: 789\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message:
+                "Invalid mode '789' for path 'run.sh': expected octal permission bits such as 755 or 644"
+                    .to_string(),
+            line_number: 3
+        })
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Add Symlink: link\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "Add Symlink hunk must be followed by '*** Link to: {value}'".to_string(),
+            line_number: 3
+        })
+    );
+}
+
+#[test]
+fn test_parse_set_mode_rejects_special_bits() {
+    let parse = |mode: &str| {
+        parse_patch_text(
+            &format!("*** Begin Patch\n*** Set Mode: run.sh\n*** Mode: {mode}\n*** End Patch"),
+            ParseMode::Strict,
+        )
+        .map(|args| args.hunks)
+    };
+    assert_eq!(
+        parse("0755"),
+        Ok(vec![SetMode {
+            path: PathBuf::from("run.sh"),
+            mode: 0o755,
+        }])
+    );
+    for mode in ["4755", "2755", "1777"] {
+        assert_eq!(
+            parse(mode),
+            Err(InvalidHunkError {
+                message: format!(
+                    "Invalid mode '{mode}' for path 'run.sh': expected octal permission bits such as 755 or 644"
+                ),
+                line_number: 3
+            })
+        );
+    }
+}
+
 #[test]
 fn test_update_file_chunk() {
     assert_eq!(
@@ -2,8 +2,14 @@ use anyhow::Result;
 use clap::Args;
 use clap::Subcommand;
 use codex_council::CouncilConfig;
+use codex_council::SweepOptions;
+use codex_council::cleanup::set_pinned;
+use codex_council::gc;
+use codex_council::hook;
+use codex_council::hook::HookKind;
 use codex_council::run_fix;
 use codex_council::run_review;
+use codex_council::run_sweep;
 use std::path::PathBuf;
 
 #[derive(Debug, Args)]
@@ -25,6 +31,30 @@ pub struct FixArgs {
     pub full_tests: bool,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum HookCommand {
+    /// Write pre-commit and pre-push hooks that run a diff-scoped council review.
+    Install {
+        /// Only install the pre-commit hook.
+        #[arg(long, conflicts_with = "pre_push_only")]
+        pre_commit_only: bool,
+        /// Only install the pre-push hook.
+        #[arg(long)]
+        pre_push_only: bool,
+        /// Replace existing hooks that were not installed by codex.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Review the staged (pre-commit) or outgoing (pre-push) diff. Called by the hooks.
+    Run {
+        /// `pre-commit` or `pre-push`.
+        kind: String,
+        /// Arguments git passes to the hook (remote name and URL for pre-push).
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum CouncilCommand {
     /// Review a file or path.
@@ -37,6 +67,38 @@ pub enum CouncilCommand {
     },
     /// Fix a file or path.
     Fix(FixArgs),
+    /// Rank files by churn, size and past failures, then review the top N.
+    Sweep {
+        /// Limit the sweep to these paths. Defaults to the whole repository.
+        paths: Vec<PathBuf>,
+        /// Number of top-ranked files to review.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Token budget shared by all runs in the sweep.
+        #[arg(long)]
+        max_tokens: Option<u64>,
+        /// Cost budget (USD) shared by all runs in the sweep.
+        #[arg(long)]
+        max_cost: Option<f64>,
+    },
+    /// Manage git hooks that review changes before commit or push.
+    Hook {
+        #[clap(subcommand)]
+        command: HookCommand,
+    },
+    /// Delete runs outside the retention policy and orphaned worktrees.
+    Gc {
+        /// Only list what would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Protect a run from garbage collection.
+    Pin {
+        run_id: String,
+        /// Remove the pin instead.
+        #[arg(long)]
+        remove: bool,
+    },
     /// Apply a fix from a run.
     Apply {
         run_id: String,
@@ -100,6 +162,7 @@ pub async fn run_review_for_path(path: PathBuf) -> Result<()> {
         critic_gpt_model: core_config.council_critic_gpt_model,
         critic_gemini_model: core_config.council_critic_gemini_model,
         implementer_model: core_config.council_implementer_model,
+        settings: core_config.council,
     };
     run_review(config, abs_path).await
 }
@@ -121,6 +184,7 @@ pub async fn run_fix_args(args: FixArgs) -> Result<()> {
         critic_gpt_model: core_config.council_critic_gpt_model,
         critic_gemini_model: core_config.council_critic_gemini_model,
         implementer_model: core_config.council_implementer_model,
+        settings: core_config.council,
     };
     run_fix(config, abs_path).await
 }
@@ -140,6 +204,7 @@ pub async fn run(cli: CouncilCli) -> Result<()> {
         critic_gpt_model: core_config.council_critic_gpt_model,
         critic_gemini_model: core_config.council_critic_gemini_model,
         implementer_model: core_config.council_implementer_model,
+        settings: core_config.council,
     };
 
     match cli.command {
@@ -159,9 +224,113 @@ pub async fn run(cli: CouncilCli) -> Result<()> {
             };
             run_fix(config, abs_path).await?;
         }
+        CouncilCommand::Sweep {
+            paths,
+            top,
+            max_tokens,
+            max_cost,
+        } => {
+            let cwd = std::env::current_dir()?;
+            let paths = paths
+                .into_iter()
+                .map(|p| if p.is_absolute() { p } else { cwd.join(p) })
+                .collect();
+            let report = run_sweep(
+                config,
+                SweepOptions {
+                    paths,
+                    top,
+                    max_tokens,
+                    max_cost,
+                },
+            )
+            .await?;
+            let reviewed = report.entries.iter().filter(|e| e.run_id.is_some()).count();
+            println!(
+                "Sweep {} reviewed {reviewed} of {} files ({} tokens, ${:.4}).",
+                report.sweep_id,
+                report.entries.len(),
+                report.total_tokens,
+                report.total_cost_usd
+            );
+            println!("Report: .council/sweeps/{}/report.md", report.sweep_id);
+        }
+        CouncilCommand::Hook { command } => run_hook_command(config, command).await?,
+        CouncilCommand::Gc { dry_run } => {
+            let report = gc(&config.repo_root, &config.settings.retention, dry_run).await?;
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            for run_id in &report.removed_runs {
+                println!("{verb} run {run_id}");
+            }
+            for path in &report.orphaned_worktrees {
+                println!("{verb} orphaned worktree {}", path.display());
+            }
+            println!(
+                "{verb} {} runs and {} worktrees; kept {} runs.",
+                report.removed_runs.len(),
+                report.orphaned_worktrees.len(),
+                report.kept_runs
+            );
+        }
+        CouncilCommand::Pin { run_id, remove } => {
+            set_pinned(&config.repo_root, &run_id, !remove).await?;
+            if remove {
+                println!("Unpinned {run_id}.");
+            } else {
+                println!("Pinned {run_id}; it will not be garbage-collected.");
+            }
+        }
         _ => {
             println!("Command not implemented yet.");
         }
     }
     Ok(())
 }
+
+async fn run_hook_command(config: CouncilConfig, command: HookCommand) -> Result<()> {
+    match command {
+        HookCommand::Install {
+            pre_commit_only,
+            pre_push_only,
+            force,
+        } => {
+            let kinds: &[HookKind] = if pre_commit_only {
+                &[HookKind::PreCommit]
+            } else if pre_push_only {
+                &[HookKind::PrePush]
+            } else {
+                &HookKind::ALL
+            };
+            let codex_bin = std::env::current_exe()?;
+            for path in hook::install_hooks(&config.repo_root, &codex_bin, kinds, force).await? {
+                println!("Installed {}", path.display());
+            }
+            println!(
+                "Set {}=1 to skip the review for a single commit or push.",
+                hook::BYPASS_ENV_VAR
+            );
+        }
+        HookCommand::Run { kind, .. } => {
+            if hook::bypassed() {
+                return Ok(());
+            }
+            let Some(kind) = HookKind::parse(&kind) else {
+                anyhow::bail!("unknown hook kind '{kind}'; expected pre-commit or pre-push");
+            };
+            // git feeds pre-push the refs being pushed on stdin.
+            let push_refs = match kind {
+                HookKind::PrePush => std::io::read_to_string(std::io::stdin())?,
+                HookKind::PreCommit => String::new(),
+            };
+            let threshold = config.settings.hook_block_severity;
+            let report = hook::run_hook(&config, kind, &push_refs).await?;
+            for line in report.stderr_lines(threshold) {
+                eprintln!("{line}");
+            }
+            if report.blocking > 0 {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
@@ -21,6 +21,9 @@ use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicyMigrateCommand;
+use codex_execpolicy::ExecPolicySuggestCommand;
+use codex_execpolicy::ExecPolicyTestCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui2::AppExitInfo;
 use codex_tui2::Cli as TuiCli;
@@ -32,13 +35,16 @@ use supports_color::Stream;
 
 mod council_cmd;
 mod mcp_cmd;
+mod sessions_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
+use codex_core::config::find_codex_home;
 use codex_core::features::is_known_feature_key;
 
 /// Codex CLI
@@ -118,6 +124,9 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Search recorded sessions.
+    Sessions(SessionsCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -191,6 +200,18 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Run a corpus of commands against execpolicy files and report coverage.
+    #[clap(name = "test")]
+    Test(ExecPolicyTestCommand),
+
+    /// Suggest prefix rules from commands you approved in the past.
+    #[clap(name = "suggest")]
+    Suggest(ExecPolicySuggestCommand),
+
+    /// Translate a legacy execpolicy into rules and report where the two engines disagree.
+    #[clap(name = "migrate")]
+    Migrate(ExecPolicyMigrateCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -616,8 +637,14 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 .await?;
             }
         },
+        Some(Subcommand::Sessions(sessions_cli)) => {
+            sessions_cli.run().await?;
+        }
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
+            ExecpolicySubcommand::Test(cmd) => cmd.run()?,
+            ExecpolicySubcommand::Suggest(cmd) => cmd.run(&find_codex_home()?)?,
+            ExecpolicySubcommand::Migrate(cmd) => cmd.run()?,
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
@@ -245,6 +245,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        sampling_token_budget: None,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -691,6 +692,7 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             "transport": transport,
             "enabled_tools": server.enabled_tools.clone(),
             "disabled_tools": server.disabled_tools.clone(),
+            "sampling_token_budget": server.sampling_token_budget,
             "startup_timeout_sec": server
                 .startup_timeout_sec
                 .map(|timeout| timeout.as_secs_f64()),
@@ -724,6 +726,9 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
         let disabled_tools_display = format_tool_list(&server.disabled_tools);
         println!("  disabled_tools: {disabled_tools_display}");
     }
+    if let Some(budget) = server.sampling_token_budget {
+        println!("  sampling_token_budget: {budget}");
+    }
     match &server.transport {
         McpServerTransportConfig::Stdio {
             command,
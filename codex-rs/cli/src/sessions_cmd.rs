@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::NaiveDate;
+use codex_core::SearchEntryKind;
+use codex_core::SessionSearchHit;
+use codex_core::SessionSearchQuery;
+use codex_core::config::find_codex_home;
+use codex_core::search_sessions;
+
+/// Subcommands:
+/// - `search` — full-text search over recorded sessions
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Find sessions by what was said, run or edited in them.
+    Search(SearchArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SearchArgs {
+    /// Words to look for. A session matches when it has a word starting with each of them.
+    #[arg(required = true, value_name = "QUERY")]
+    pub query: Vec<String>,
+
+    /// Only sessions started in this directory or below it.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Only sessions started on or after this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<NaiveDate>,
+
+    /// Only sessions started on or before this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<NaiveDate>,
+
+    /// Only sessions that used a model whose name contains this text.
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Only sessions that edited a file whose path contains this text.
+    #[arg(long, value_name = "PATH")]
+    pub file: Option<String>,
+
+    /// Maximum number of sessions to show. 0 shows all of them.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Output the matching sessions as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            SessionsSubcommand::Search(args) => run_search(args).await,
+        }
+    }
+}
+
+async fn run_search(args: SearchArgs) -> Result<()> {
+    let SearchArgs {
+        query,
+        cwd,
+        since,
+        until,
+        model,
+        file,
+        limit,
+        json,
+    } = args;
+
+    let cwd = match cwd {
+        Some(cwd) if cwd.is_relative() => Some(std::env::current_dir()?.join(cwd)),
+        cwd => cwd,
+    };
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let hits = search_sessions(
+        &codex_home,
+        SessionSearchQuery {
+            text: query.join(" "),
+            cwd,
+            since,
+            until,
+            model,
+            file,
+            limit,
+            ..Default::default()
+        },
+    )
+    .await
+    .context("failed to search sessions")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No sessions match.");
+        return Ok(());
+    }
+    for (idx, hit) in hits.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        print_hit(hit);
+    }
+    println!("\nResume a session with `codex resume <id>`.");
+    Ok(())
+}
+
+fn print_hit(hit: &SessionSearchHit) {
+    let date = hit.created_at.get(..10).unwrap_or(&hit.created_at);
+    println!("{date}  {}", hit.thread_id);
+    let mut location = hit.cwd.display().to_string();
+    if let Some(branch) = &hit.git_branch {
+        location.push_str(&format!(" ({branch})"));
+    }
+    if !hit.models.is_empty() {
+        location.push_str(&format!("  [{}]", hit.models.join(", ")));
+    }
+    println!("  {location}");
+    for found in &hit.matches {
+        println!("  {:<6} {}", kind_label(found.kind), found.snippet);
+    }
+}
+
+fn kind_label(kind: SearchEntryKind) -> &'static str {
+    match kind {
+        SearchEntryKind::UserMessage => "user",
+        SearchEntryKind::AgentMessage => "agent",
+        SearchEntryKind::Command => "$",
+        SearchEntryKind::File => "file",
+    }
+}
@@ -117,3 +117,87 @@ prefix_rule(
 
     Ok(())
 }
+
+#[test]
+fn execpolicy_test_fails_on_decision_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let policy_path = dir.path().join("git.rules");
+    fs::write(
+        &policy_path,
+        r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "forbidden",
+)
+"#,
+    )?;
+    let cases_path = dir.path().join("cases.jsonl");
+    fs::write(
+        &cases_path,
+        r#"{"command": "git push origin main", "decision": "forbidden"}
+{"command": ["git", "status"], "decision": "allow"}
+"#,
+    )?;
+
+    let output = Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+        .env("CODEX_HOME", dir.path())
+        .args([
+            "execpolicy",
+            "test",
+            "--json",
+            "--cases",
+            cases_path
+                .to_str()
+                .expect("cases path should be valid UTF-8"),
+            "-r",
+            policy_path
+                .to_str()
+                .expect("policy path should be valid UTF-8"),
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        report["mismatches"],
+        json!([
+            {
+                "line": 2,
+                "command": "git status",
+                "expected": "allow",
+                "actual": "heuristics"
+            }
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn execpolicy_migrate_translates_default_legacy_policy() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let output_path = dir.path().join("migrated.rules");
+
+    let output = Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+        .env("CODEX_HOME", dir.path())
+        .args([
+            "execpolicy",
+            "migrate",
+            "--json",
+            "--output",
+            output_path
+                .to_str()
+                .expect("output path should be valid UTF-8"),
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["stubs"], json!(2));
+    let rules = fs::read_to_string(&output_path)?;
+    // `ls` only allows certain options in the legacy policy, so it must not be auto-allowed.
+    assert!(rules.contains("prefix_rule(\n    pattern = [\"ls\"],\n    decision = \"prompt\",\n)"));
+    assert!(rules.contains("# TODO: not migrated: sed commands"));
+
+    Ok(())
+}
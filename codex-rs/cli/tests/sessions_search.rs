@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+const THREAD_ID: &str = "0194b6a0-0000-7000-8000-000000000001";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let timestamp = "2025-01-02T10:00:00.000Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": THREAD_ID,
+                "timestamp": timestamp,
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "user_message", "message": "Fix the OAuth refresh bug"},
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "agent_message", "message": "The refresh token was never saved."},
+        }),
+    ];
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(
+        dir.join(format!("rollout-2025-01-02T10-00-00-{THREAD_ID}.jsonl")),
+        contents,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn search_finds_session_by_message() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["sessions", "search", "oauth", "refresh", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let hits: JsonValue = serde_json::from_slice(&output.stdout)?;
+    let hits = hits.as_array().expect("hits array");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["thread_id"], json!(THREAD_ID));
+    assert_eq!(hits[0]["cwd"], json!("/repo"));
+    assert_eq!(hits[0]["matches"][0]["kind"], json!("user_message"));
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["sessions", "search", "oauth"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains(THREAD_ID));
+    assert!(stdout.contains("Fix the OAuth refresh bug"));
+
+    Ok(())
+}
+
+#[test]
+fn search_reports_when_nothing_matches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["sessions", "search", "oauth", "--cwd", "/elsewhere"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("No sessions match."));
+
+    Ok(())
+}
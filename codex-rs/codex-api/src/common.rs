@@ -27,6 +27,8 @@ pub struct Prompt {
     pub parallel_tool_calls: bool,
     /// Optional output schema used to build the `text.format` controls.
     pub output_schema: Option<Value>,
+    /// Optional cap on the tokens the model may generate for this request.
+    pub max_output_tokens: Option<u64>,
 }
 
 /// Canonical input payload for the compaction endpoint.
@@ -134,6 +136,8 @@ pub struct ResponsesApiRequest<'a> {
     pub prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
 }
 
 pub fn create_text_param_for_request(
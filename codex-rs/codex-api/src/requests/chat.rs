@@ -24,6 +24,7 @@ pub struct ChatRequestBuilder<'a> {
     instructions: &'a str,
     input: &'a [ResponseItem],
     tools: &'a [Value],
+    max_tokens: Option<u64>,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
 }
@@ -40,11 +41,17 @@ impl<'a> ChatRequestBuilder<'a> {
             instructions,
             input,
             tools,
+            max_tokens: None,
             conversation_id: None,
             session_source: None,
         }
     }
 
+    pub fn max_tokens(mut self, max_tokens: Option<u64>) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
     pub fn conversation_id(mut self, id: Option<String>) -> Self {
         self.conversation_id = id;
         self
@@ -296,13 +303,16 @@ impl<'a> ChatRequestBuilder<'a> {
             temperature = Some(1.0);
         }
 
-        let payload = json!({
+        let mut payload = json!({
             "model": self.model,
             "messages": messages,
             "stream": true,
             "tools": self.tools,
             "temperature": temperature,
         });
+        if let (Some(max_tokens), Some(payload)) = (self.max_tokens, payload.as_object_mut()) {
+            payload.insert("max_tokens".to_string(), json!(max_tokens));
+        }
 
         let mut headers = build_conversation_headers(self.conversation_id);
         if let Some(subagent) = subagent_header(&self.session_source) {
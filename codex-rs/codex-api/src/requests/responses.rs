@@ -36,6 +36,7 @@ pub struct ResponsesRequestBuilder<'a> {
     include: Vec<String>,
     prompt_cache_key: Option<String>,
     text: Option<TextControls>,
+    max_output_tokens: Option<u64>,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
     store_override: Option<bool>,
@@ -83,6 +84,11 @@ impl<'a> ResponsesRequestBuilder<'a> {
         self
     }
 
+    pub fn max_output_tokens(mut self, max_output_tokens: Option<u64>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
     pub fn conversation(mut self, conversation_id: Option<String>) -> Self {
         self.conversation_id = conversation_id;
         self
@@ -137,6 +143,7 @@ impl<'a> ResponsesRequestBuilder<'a> {
             include: self.include,
             prompt_cache_key: self.prompt_cache_key,
             text: self.text,
+            max_output_tokens: self.max_output_tokens,
         };
 
         let mut body = serde_json::to_value(&req)
@@ -262,4 +269,24 @@ mod tests {
             Some(&HeaderValue::from_static("review"))
         );
     }
+
+    #[test]
+    fn max_output_tokens_is_sent_only_when_set() {
+        let provider = provider("openai", "https://api.openai.com/v1");
+        let input = Vec::new();
+
+        let capped = ResponsesRequestBuilder::new("gpt-test", "inst", &input)
+            .max_output_tokens(Some(256))
+            .build(&provider)
+            .expect("request");
+        let uncapped = ResponsesRequestBuilder::new("gpt-test", "inst", &input)
+            .build(&provider)
+            .expect("request");
+
+        assert_eq!(
+            capped.body.get("max_output_tokens"),
+            Some(&Value::from(256))
+        );
+        assert_eq!(uncapped.body.get("max_output_tokens"), None);
+    }
 }
@@ -1,5 +1,6 @@
 use codex_core::protocol::NetworkAccess;
 use codex_core::protocol::SandboxPolicy;
+use std::path::Path;
 
 pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
     match sandbox_policy {
@@ -17,6 +18,8 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            read_only_subpaths,
+            deny_read_paths,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -38,11 +41,30 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             if *network_access {
                 summary.push_str(" (network access enabled)");
             }
+
+            let mut carve_outs = Vec::<String>::new();
+            if !read_only_subpaths.is_empty() {
+                carve_outs.push(format!("read-only: {}", join_paths(read_only_subpaths)));
+            }
+            if !deny_read_paths.is_empty() {
+                carve_outs.push(format!("no access: {}", join_paths(deny_read_paths)));
+            }
+            if !carve_outs.is_empty() {
+                summary.push_str(&format!(" ({})", carve_outs.join("; ")));
+            }
             summary
         }
     }
 }
 
+fn join_paths(paths: &[impl AsRef<Path>]) -> String {
+    paths
+        .iter()
+        .map(|p| p.as_ref().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +96,8 @@ mod tests {
             network_access: true,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         });
         assert_eq!(
             summary,
@@ -83,4 +107,25 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn workspace_write_summary_lists_carve_outs() {
+        let (hooks, ssh) = if cfg!(windows) {
+            ("C:\\repo\\.git\\hooks", "C:\\home\\.ssh")
+        } else {
+            ("/repo/.git/hooks", "/home/.ssh")
+        };
+        let summary = summarize_sandbox_policy(&SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            read_only_subpaths: vec![AbsolutePathBuf::try_from(hooks).unwrap()],
+            deny_read_paths: vec![AbsolutePathBuf::try_from(ssh).unwrap()],
+        });
+        assert_eq!(
+            summary,
+            format!("workspace-write [workdir] (read-only: {hooks}; no access: {ssh})")
+        );
+    }
 }
@@ -94,6 +94,27 @@ pub(crate) fn convert_apply_patch_to_protocol(
                 unified_diff: unified_diff.clone(),
                 move_path: move_path.clone(),
             },
+            ApplyPatchFileChange::Copy {
+                source,
+                unified_diff,
+            } => FileChange::Copy {
+                source: source.clone(),
+                unified_diff: unified_diff.clone(),
+            },
+            ApplyPatchFileChange::Mode { old_mode, new_mode } => FileChange::Mode {
+                old_mode: *old_mode,
+                new_mode: *new_mode,
+            },
+            ApplyPatchFileChange::Symlink { target } => FileChange::Symlink {
+                target: target.clone(),
+            },
+            ApplyPatchFileChange::Binary {
+                size,
+                previous_size,
+            } => FileChange::Binary {
+                size: *size,
+                previous_size: *previous_size,
+            },
         };
         result.insert(path.clone(), protocol_change);
     }
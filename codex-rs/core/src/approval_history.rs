@@ -0,0 +1,128 @@
+//! Append-only log of command approval decisions, stored at `~/.codex/approvals.jsonl`.
+//!
+//! Each approval prompt produces one record per plain command (scripts passed to
+//! `bash -lc` are split into their parts), tagged with the command's shape. The log is
+//! mined by `codex execpolicy suggest` to propose prefix rules; see
+//! [`codex_execpolicy::suggest::ApprovalRecord`] for the schema.
+
+use std::path::Path;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_execpolicy::suggest::APPROVAL_LOG_FILENAME;
+use codex_execpolicy::suggest::ApprovalDecision;
+use codex_execpolicy::suggest::ApprovalRecord;
+use codex_execpolicy::suggest::command_shape;
+use codex_protocol::protocol::ReviewDecision;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::bash::parse_shell_lc_plain_commands;
+
+fn approval_decision(decision: &ReviewDecision) -> ApprovalDecision {
+    match decision {
+        ReviewDecision::Approved => ApprovalDecision::Approved,
+        ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
+            ApprovalDecision::ApprovedWithAmendment
+        }
+        ReviewDecision::ApprovedForSession => ApprovalDecision::ApprovedForSession,
+        ReviewDecision::Denied => ApprovalDecision::Denied,
+        ReviewDecision::Abort => ApprovalDecision::Abort,
+    }
+}
+
+/// Records the user's answer to an approval prompt. Failures are only logged: the history
+/// feeds suggestions and must never block a turn.
+pub(crate) async fn record_approval(
+    codex_home: &Path,
+    command: &[String],
+    cwd: &Path,
+    decision: &ReviewDecision,
+) {
+    if let Err(err) = append_records(codex_home, command, cwd, decision).await {
+        warn!("failed to record approval decision: {err}");
+    }
+}
+
+async fn append_records(
+    codex_home: &Path,
+    command: &[String],
+    cwd: &Path,
+    decision: &ReviewDecision,
+) -> std::io::Result<()> {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let decision = approval_decision(decision);
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+
+    // Build every line up front so the records land in a single append.
+    let mut lines = String::new();
+    for command in commands {
+        let record = ApprovalRecord {
+            ts,
+            shape: command_shape(&command),
+            command,
+            cwd: cwd.to_path_buf(),
+            decision,
+        };
+        lines.push_str(&serde_json::to_string(&record).map_err(std::io::Error::other)?);
+        lines.push('\n');
+    }
+
+    tokio::fs::create_dir_all(codex_home).await?;
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(codex_home.join(APPROVAL_LOG_FILENAME)).await?;
+    file.write_all(lines.as_bytes()).await?;
+    file.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_execpolicy::suggest::read_approval_log;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn records_one_entry_per_plain_command() {
+        let codex_home = tempdir().expect("create temp dir");
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "git add -A && git commit -m wip".to_string(),
+        ];
+
+        record_approval(
+            codex_home.path(),
+            &command,
+            Path::new("/repo"),
+            &ReviewDecision::ApprovedForSession,
+        )
+        .await;
+
+        let records = read_approval_log(&codex_home.path().join(APPROVAL_LOG_FILENAME))
+            .expect("read approval log");
+        let shapes: Vec<(Vec<String>, ApprovalDecision)> = records
+            .into_iter()
+            .map(|record| (record.shape, record.decision))
+            .collect();
+        assert_eq!(
+            shapes,
+            vec![
+                (
+                    vec!["git".to_string(), "add".to_string()],
+                    ApprovalDecision::ApprovedForSession
+                ),
+                (
+                    vec!["git".to_string(), "commit".to_string()],
+                    ApprovalDecision::ApprovedForSession
+                ),
+            ]
+        );
+    }
+}
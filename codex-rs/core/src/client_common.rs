@@ -39,6 +39,9 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Optional cap on the tokens the model may generate.
+    pub max_output_tokens: Option<u64>,
 }
 
 impl Prompt {
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -51,6 +52,7 @@ use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
 use mcp_types::CallToolResult;
+use mcp_types::GetPromptResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -83,6 +85,8 @@ use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::types::HistoryPersistence;
+use crate::config::types::ResourceLimits;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
@@ -92,9 +96,14 @@ use crate::error::Result as CodexResult;
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
+use crate::lsp::LspManager;
 use crate::mcp::auth::compute_auth_statuses;
+use crate::mcp::sampling::McpSampler;
+use crate::mcp::server_changes::resource_changes_message;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::network_proxy::DeniedConnection;
+use crate::network_proxy::NetworkProxy;
 use crate::project_doc::get_user_instructions;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
@@ -133,6 +142,7 @@ use crate::skills::SkillMetadata;
 use crate::skills::SkillsManager;
 use crate::skills::build_skill_injections;
 use crate::state::ActiveTurn;
+use crate::state::RedoEntry;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::tasks::GhostSnapshotTask;
@@ -147,6 +157,7 @@ use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::unified_exec::remove_stale_session_logs;
 use crate::user_instructions::DeveloperInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
@@ -212,6 +223,62 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Starts the filtering proxy when `[network_proxy]` is enabled. Denied connections are
+/// reported as warnings, once per destination.
+fn maybe_start_network_proxy(
+    config: &Config,
+    tx_event: &Sender<Event>,
+    post_session_configured_events: &mut Vec<Event>,
+) -> Option<NetworkProxy> {
+    if !config.network_proxy.enabled {
+        return None;
+    }
+    let mut push_warning = |message: String| {
+        post_session_configured_events.push(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    };
+    if !cfg!(target_os = "linux") {
+        push_warning(
+            "network_proxy is only supported on Linux; sandboxed commands keep the sandbox's network policy."
+                .to_string(),
+        );
+        return None;
+    }
+
+    let tx_event = tx_event.clone();
+    let reported = std::sync::Mutex::new(HashSet::new());
+    let on_denied = move |denied: &DeniedConnection| {
+        let destination = format!("{}:{}", denied.host, denied.port);
+        let first_time = reported
+            .lock()
+            .map(|mut reported| reported.insert(destination.clone()))
+            .unwrap_or(false);
+        if !first_time {
+            return;
+        }
+        let message = format!(
+            "Network proxy blocked a connection to {destination}. Add it to network_proxy.allowed_hosts to allow it."
+        );
+        let _ = tx_event.try_send(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    };
+
+    match NetworkProxy::start(config.network_proxy.allowlist.clone(), on_denied) {
+        Ok(proxy) => Some(proxy),
+        Err(err) => {
+            error!("failed to start network proxy: {err}");
+            push_warning(format!(
+                "Failed to start the network proxy ({err}); sandboxed commands have no network access."
+            ));
+            None
+        }
+    }
+}
+
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub(crate) async fn spawn(
@@ -385,6 +452,7 @@ pub(crate) struct TurnContext {
     pub(crate) ghost_snapshot: GhostSnapshotConfig,
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
+    pub(crate) resource_limits: ResourceLimits,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
 }
@@ -543,6 +611,7 @@ impl Session {
             ghost_snapshot: per_turn_config.ghost_snapshot.clone(),
             final_output_json_schema: None,
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
+            resource_limits: per_turn_config.resource_limits,
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
         }
@@ -632,6 +701,8 @@ impl Session {
             });
         }
         maybe_push_chat_wire_api_deprecation(&config, &mut post_session_configured_events);
+        let network_proxy =
+            maybe_start_network_proxy(&config, &tx_event, &mut post_session_configured_events);
 
         let otel_manager = OtelManager::new(
             conversation_id,
@@ -667,10 +738,21 @@ impl Session {
         }
         let state = SessionState::new(session_configuration.clone());
 
+        let exec_logs_dir = config.codex_home.join("exec_logs");
+        let session_log_dir = exec_logs_dir.join(conversation_id.to_string());
+        tokio::task::spawn_blocking({
+            let session_log_dir = session_log_dir.clone();
+            move || remove_stale_session_logs(&exec_logs_dir, &session_log_dir)
+        });
+        // Command output is history too: without persistence, only the
+        // truncated transcript keeps it.
+        let output_log_dir =
+            (config.history.persistence == HistoryPersistence::SaveAll).then_some(session_log_dir);
+
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
-            unified_exec_manager: UnifiedExecProcessManager::default(),
+            unified_exec_manager: UnifiedExecProcessManager::new(output_log_dir),
             notifier: UserNotifier::new(config.notify.clone()),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: Arc::new(default_shell),
@@ -682,6 +764,8 @@ impl Session {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy,
+            lsp: LspManager::new(config.lsp_servers.clone()),
         };
 
         let sess = Arc::new(Session {
@@ -737,6 +821,10 @@ impl Session {
                 tx_event.clone(),
                 sess.services.mcp_startup_cancellation_token.clone(),
                 sandbox_state,
+                Some(Arc::new(McpSampler::new(
+                    Arc::downgrade(&sess),
+                    &config.mcp_servers,
+                ))),
             )
             .await;
 
@@ -872,14 +960,15 @@ impl Session {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed) = {
+        let (session_configuration, sandbox_state_changed) = {
             let mut state = self.state.lock().await;
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    let sandbox_state_changed = state.session_configuration.sandbox_policy
+                        != next.sandbox_policy
+                        || state.session_configuration.cwd != next.cwd;
                     state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed)
+                    (next, sandbox_state_changed)
                 }
                 Err(err) => {
                     drop(state);
@@ -901,7 +990,7 @@ impl Session {
                 sub_id,
                 session_configuration,
                 updates.final_output_json_schema,
-                sandbox_policy_changed,
+                sandbox_state_changed,
             )
             .await)
     }
@@ -911,11 +1000,11 @@ impl Session {
         sub_id: String,
         session_configuration: SessionConfiguration,
         final_output_json_schema: Option<Option<Value>>,
-        sandbox_policy_changed: bool,
+        sandbox_state_changed: bool,
     ) -> Arc<TurnContext> {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
 
-        if sandbox_policy_changed {
+        if sandbox_state_changed {
             let sandbox_state = SandboxState {
                 sandbox_policy: per_turn_config.sandbox_policy.get().clone(),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
@@ -1134,14 +1223,26 @@ impl Session {
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
-            command,
-            cwd,
+            command: command.clone(),
+            cwd: cwd.clone(),
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
-        rx_approve.await.unwrap_or_default()
+        let Ok(decision) = rx_approve.await else {
+            return ReviewDecision::default();
+        };
+
+        let (codex_home, history_persistence) = {
+            let state = self.state.lock().await;
+            let config = &state.session_configuration.original_config_do_not_use;
+            (config.codex_home.clone(), config.history.persistence)
+        };
+        if history_persistence == HistoryPersistence::SaveAll {
+            crate::approval_history::record_approval(&codex_home, &command, &cwd, &decision).await;
+        }
+        decision
     }
 
     pub async fn request_patch_approval(
@@ -1293,6 +1394,33 @@ impl Session {
         state.replace_history(items);
     }
 
+    pub(crate) async fn replace_history_keeping_redo(&self, items: Vec<ResponseItem>) {
+        let mut state = self.state.lock().await;
+        state.replace_history_keeping_redo(items);
+    }
+
+    pub(crate) async fn push_redo(&self, entry: RedoEntry) {
+        let mut state = self.state.lock().await;
+        state.redo_stack.push(entry);
+    }
+
+    pub(crate) async fn pop_redo(&self) -> Option<RedoEntry> {
+        let mut state = self.state.lock().await;
+        state.redo_stack.pop()
+    }
+
+    /// Drops the redo stack once a new snapshot is taken, since the states it
+    /// holds no longer follow from the current one.
+    pub(crate) async fn clear_redo(&self) {
+        let mut state = self.state.lock().await;
+        state.redo_stack.clear();
+    }
+
+    pub(crate) async fn redo_available(&self) -> bool {
+        let state = self.state.lock().await;
+        !state.redo_stack.is_empty()
+    }
+
     async fn persist_rollout_response_items(&self, items: &[ResponseItem]) {
         let rollout_items: Vec<RolloutItem> = items
             .iter()
@@ -1576,6 +1704,29 @@ impl Session {
             .await
     }
 
+    pub async fn subscribe_resource(&self, server: &str, uri: String) -> anyhow::Result<()> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .subscribe_resource(server, uri)
+            .await
+    }
+
+    pub async fn get_mcp_prompt(
+        &self,
+        server: &str,
+        prompt: &str,
+        arguments: HashMap<String, String>,
+    ) -> anyhow::Result<GetPromptResult> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(server, prompt, arguments)
+            .await
+    }
+
     pub async fn call_tool(
         &self,
         server: &str,
@@ -1664,6 +1815,21 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
             }
+            Op::RunMcpPrompt {
+                server,
+                prompt,
+                arguments,
+            } => {
+                handlers::run_mcp_prompt(
+                    &sess,
+                    sub.id.clone(),
+                    server,
+                    prompt,
+                    arguments,
+                    &mut previous_context,
+                )
+                .await;
+            }
             Op::ExecApproval { id, decision } => {
                 handlers::exec_approval(&sess, id, decision).await;
             }
@@ -1683,12 +1849,30 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::ListMcpPrompts => {
+                handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+            }
+            Op::ListProcesses => {
+                handlers::list_processes(&sess, sub.id.clone()).await;
+            }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::ListUndoSnapshots => {
+                handlers::list_undo_snapshots(&sess, sub.id.clone()).await;
+            }
+            Op::GetUndoSnapshotDiff { commit_id } => {
+                handlers::get_undo_snapshot_diff(&sess, sub.id.clone(), commit_id).await;
+            }
+            Op::RestoreUndoSnapshot { commit_id, paths } => {
+                handlers::restore_undo_snapshot(&sess, sub.id.clone(), commit_id, paths).await;
+            }
+            Op::Redo => {
+                handlers::redo(&sess, sub.id.clone()).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -1736,17 +1920,24 @@ mod handlers {
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
+    use crate::mcp::prompts::McpPromptInput;
+    use crate::mcp::prompts::mcp_prompt_input;
     use crate::review_prompts::resolve_review_request;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
+    use crate::tasks::UndoTarget;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::tasks::undo_snapshot_diff;
+    use crate::tasks::undo_timeline;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListMcpPromptsResponseEvent;
+    use codex_protocol::protocol::ListProcessesResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::ReviewDecision;
@@ -1754,12 +1945,14 @@ mod handlers {
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::ThreadRolledBackEvent;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::UndoTimelineEvent;
     use codex_protocol::protocol::WarningEvent;
 
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
     use mcp_types::RequestId;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
@@ -1851,6 +2044,83 @@ mod handlers {
         }
     }
 
+    /// Fetches an MCP prompt and starts a turn with its messages. Earlier
+    /// messages of multi-message prompts are recorded as conversation history,
+    /// which replaces any running turn; plain prompts are injected like user input.
+    pub async fn run_mcp_prompt(
+        sess: &Arc<Session>,
+        sub_id: String,
+        server: String,
+        prompt: String,
+        arguments: HashMap<String, String>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let input = match sess.get_mcp_prompt(&server, &prompt, arguments).await {
+            Ok(result) => mcp_prompt_input(result.messages),
+            Err(err) => Err(format!("{err:#}")),
+        };
+        // A prompt with assistant messages rewrites the end of the conversation, which cannot
+        // happen under a running turn; the running turn is not aborted to make room for it.
+        let input = match input {
+            Ok(input) if !input.history.is_empty() && sess.active_turn.lock().await.is_some() => {
+                Err(
+                    "it includes assistant messages, so it can only run between turns; \
+                     run it again once the current turn ends"
+                        .to_string(),
+                )
+            }
+            input => input,
+        };
+        let McpPromptInput { history, items } = match input {
+            Ok(input) => input,
+            Err(err) => {
+                sess.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Error(ErrorEvent {
+                        message: format!("Failed to run MCP prompt `{server}/{prompt}`: {err}"),
+                        codex_error_info: Some(CodexErrorInfo::Other),
+                    }),
+                })
+                .await;
+                return;
+            }
+        };
+
+        let Ok(current_context) = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await
+        else {
+            return;
+        };
+        current_context
+            .client
+            .get_otel_manager()
+            .user_prompt(&items);
+
+        let items = if history.is_empty() {
+            match sess.inject_input(items).await {
+                Ok(()) => return,
+                Err(items) => items,
+            }
+        } else {
+            items
+        };
+        if let Some(env_item) =
+            sess.build_environment_update_item(previous_context.as_ref(), &current_context)
+        {
+            sess.record_conversation_items(&current_context, std::slice::from_ref(&env_item))
+                .await;
+        }
+        if !history.is_empty() {
+            sess.record_conversation_items(&current_context, &history)
+                .await;
+        }
+
+        sess.spawn_task(Arc::clone(&current_context), items, RegularTask)
+            .await;
+        *previous_context = Some(current_context);
+    }
+
     pub async fn run_user_shell_command(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1994,6 +2264,21 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let prompts = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_prompts()
+            .await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListMcpPromptsResponse(ListMcpPromptsResponseEvent { prompts }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -2011,6 +2296,15 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_processes(sess: &Session, sub_id: String) {
+        let processes = sess.services.unified_exec_manager.list_processes().await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListProcessesResponse(ListProcessesResponseEvent { processes }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_skills(
         sess: &Session,
         sub_id: String,
@@ -2050,6 +2344,66 @@ mod handlers {
             .await;
     }
 
+    pub async fn list_undo_snapshots(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let history = sess.clone_history().await.get_history();
+        let redo_available = sess.redo_available().await;
+        let sess = Arc::clone(sess);
+        // Diffing every snapshot runs git; keep the submission loop free meanwhile.
+        tokio::spawn(async move {
+            let snapshots = undo_timeline(&turn_context, &history).await;
+            sess.send_event_raw(Event {
+                id: turn_context.sub_id.clone(),
+                msg: EventMsg::UndoTimeline(UndoTimelineEvent {
+                    snapshots,
+                    redo_available,
+                }),
+            })
+            .await;
+        });
+    }
+
+    pub async fn get_undo_snapshot_diff(sess: &Arc<Session>, sub_id: String, commit_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let history = sess.clone_history().await.get_history();
+        let sess = Arc::clone(sess);
+        tokio::spawn(async move {
+            let msg = match undo_snapshot_diff(&turn_context, &history, commit_id).await {
+                Ok(event) => EventMsg::UndoSnapshotDiff(event),
+                Err(message) => EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: Some(CodexErrorInfo::UndoFailed),
+                }),
+            };
+            sess.send_event_raw(Event {
+                id: turn_context.sub_id.clone(),
+                msg,
+            })
+            .await;
+        });
+    }
+
+    pub async fn restore_undo_snapshot(
+        sess: &Arc<Session>,
+        sub_id: String,
+        commit_id: String,
+        paths: Vec<PathBuf>,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let task = UndoTask::with_target(UndoTarget::Snapshot { commit_id, paths });
+        sess.spawn_task(turn_context, Vec::new(), task).await;
+    }
+
+    pub async fn redo(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            UndoTask::with_target(UndoTarget::Redo),
+        )
+        .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -2109,6 +2463,7 @@ mod handlers {
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        sess.services.lsp.shutdown().await;
         info!("Shutting down Codex instance");
 
         // Gracefully flush and shutdown rollout recorder on session end so tests
@@ -2240,6 +2595,7 @@ async fn spawn_review_thread(
         cwd: parent_turn_context.cwd.clone(),
         final_output_json_schema: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
+        resource_limits: parent_turn_context.resource_limits,
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         truncation_policy: model_info.truncation_policy.into(),
     };
@@ -2353,6 +2709,16 @@ pub(crate) async fn run_task(
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
 
     loop {
+        // Refresh changed MCP tool lists before `run_turn` builds the tool
+        // registry, and tell the model about changed resources.
+        let resource_changes = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .apply_server_changes()
+            .await;
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2361,6 +2727,7 @@ pub(crate) async fn run_task(
             .await
             .into_iter()
             .map(ResponseItem::from)
+            .chain(resource_changes_message(&resource_changes))
             .collect::<Vec<ResponseItem>>();
 
         // Construct the input that we will send to the model.
@@ -2491,6 +2858,7 @@ async fn run_turn(
         parallel_tool_calls: model_supports_parallel,
         base_instructions_override: turn_context.base_instructions.clone(),
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     };
 
     let mut retries = 0;
@@ -2911,6 +3279,29 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn replacing_history_drops_redo_entries() {
+        let (session, _turn_context) = make_session_and_context().await;
+        session
+            .push_redo(RedoEntry {
+                ghost_commit: codex_git::GhostCommit::new(
+                    "ghost-1".to_string(),
+                    None,
+                    Vec::new(),
+                    Vec::new(),
+                ),
+                removed_items: Vec::new(),
+            })
+            .await;
+
+        session.replace_history_keeping_redo(Vec::new()).await;
+        assert!(session.redo_available().await);
+
+        // Compaction and rollback replace the history the entries point into.
+        session.replace_history(Vec::new()).await;
+        assert!(!session.redo_available().await);
+    }
+
     #[tokio::test]
     async fn record_initial_history_seeds_token_info_from_rollout() {
         let (session, turn_context) = make_session_and_context().await;
@@ -3493,6 +3884,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
+            lsp: LspManager::new(config.lsp_servers.clone()),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3587,6 +3980,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
+            lsp: LspManager::new(config.lsp_servers.clone()),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
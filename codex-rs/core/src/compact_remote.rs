@@ -47,6 +47,7 @@ async fn run_remote_compact_task_inner_impl(
         parallel_tool_calls: false,
         base_instructions_override: turn_context.base_instructions.clone(),
         output_schema: None,
+        max_output_tokens: None,
     };
 
     let mut new_history = turn_context
@@ -160,6 +160,9 @@ mod document_helpers {
         {
             entry["disabled_tools"] = array_from_iter(disabled_tools.iter().cloned());
         }
+        if let Some(budget) = config.sampling_token_budget {
+            entry["sampling_token_budget"] = value(i64::try_from(budget).unwrap_or(i64::MAX));
+        }
 
         entry
     }
@@ -1127,6 +1130,7 @@ gpt-5 = "gpt-5.1"
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1148,6 +1152,7 @@ gpt-5 = "gpt-5.1"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
+                sampling_token_budget: None,
             },
         );
 
@@ -1212,6 +1217,7 @@ foo = { command = "cmd" }
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1255,6 +1261,7 @@ foo = { command = "cmd" } # keep me
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1297,6 +1304,7 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1340,6 +1348,7 @@ foo = { command = "cmd" }
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
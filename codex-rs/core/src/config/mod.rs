@@ -1,12 +1,16 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::CouncilSettings;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::LspServerConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
+use crate::config::types::NetworkProxySettings;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ResourceLimits;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ScrollInputMode;
 use crate::config::types::ShellEnvironmentPolicy;
@@ -41,6 +45,7 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+pub use codex_utils_home_dir::find_codex_home;
 use dirs::home_dir;
 use serde::Deserialize;
 use serde::Serialize;
@@ -359,13 +364,27 @@ pub struct Config {
     /// OTEL configuration (exporter type, endpoint, headers, etc.).
     pub otel: crate::config::types::OtelConfig,
 
-    /// Prompt version tag (e.g. "v1", "v2").
+    /// Council prompt pack name: a built-in pack ("v1", "v2") or a directory under
+    /// `.council/prompts/` or `~/.codex/council/prompts/`.
     pub prompt_version: String,
 
     pub council_chair_model: String,
     pub council_critic_gpt_model: String,
     pub council_critic_gemini_model: String,
     pub council_implementer_model: String,
+
+    /// Budgets and price table for council runs.
+    pub council: CouncilSettings,
+
+    /// Filtering proxy that gives sandboxed commands access to allowlisted hosts.
+    pub network_proxy: NetworkProxySettings,
+
+    /// Per-command resource limits for sandboxed commands (Linux only).
+    pub resource_limits: ResourceLimits,
+
+    /// Language servers the LSP tools may start, keyed by name: the built-in
+    /// servers with any `[lsp_servers]` entries applied on top.
+    pub lsp_servers: BTreeMap<String, LspServerConfig>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -846,7 +865,8 @@ pub struct ConfigToml {
     /// Preferred OSS provider for local models, e.g. "lmstudio" or "ollama".
     pub oss_provider: Option<String>,
 
-    /// Version tag for prompt assets (e.g. "v1", "v2").
+    /// Council prompt pack name: a built-in pack ("v1", "v2") or a directory under
+    /// `.council/prompts/` or `~/.codex/council/prompts/`.
     #[serde(default)]
     pub prompt_version: Option<String>,
 
@@ -854,6 +874,19 @@ pub struct ConfigToml {
     pub council_critic_gpt_model: Option<String>,
     pub council_critic_gemini_model: Option<String>,
     pub council_implementer_model: Option<String>,
+
+    /// Budgets and price table for council runs.
+    pub council: Option<crate::config::types::CouncilConfigToml>,
+
+    /// Filtering proxy for network access from sandboxed commands (Linux only).
+    pub network_proxy: Option<crate::config::types::NetworkProxyToml>,
+
+    /// Per-command resource limits for sandboxed commands (Linux only).
+    pub resource_limits: Option<ResourceLimits>,
+
+    /// Language servers for the LSP tools, in addition to or replacing the built-in ones.
+    #[serde(default)]
+    pub lsp_servers: HashMap<String, LspServerConfig>,
 }
 
 impl From<ConfigToml> for UserSavedConfig {
@@ -934,6 +967,21 @@ pub struct SandboxPolicyResolution {
     pub forced_auto_mode_downgraded_on_windows: bool,
 }
 
+/// Resolves `read_only_subpaths` / `deny_read_paths` entries: `~/` is the home
+/// directory and relative paths are taken from the session cwd.
+fn resolve_sandbox_carve_outs(paths: &[PathBuf], resolved_cwd: &Path) -> Vec<AbsolutePathBuf> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let expanded = match path.strip_prefix("~") {
+                Ok(rest) => home_dir()?.join(rest),
+                Err(_) => path.clone(),
+            };
+            AbsolutePathBuf::resolve_path_against_base(expanded, resolved_cwd).ok()
+        })
+        .collect()
+}
+
 impl ConfigToml {
     /// Derive the effective sandbox policy from the configuration.
     fn derive_sandbox_policy(
@@ -964,11 +1012,15 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    read_only_subpaths,
+                    deny_read_paths,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    read_only_subpaths: resolve_sandbox_carve_outs(read_only_subpaths, resolved_cwd),
+                    deny_read_paths: resolve_sandbox_carve_outs(deny_read_paths, resolved_cwd),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -1315,6 +1367,16 @@ impl Config {
         )?;
         let compact_prompt = compact_prompt.or(file_compact_prompt);
 
+        let mut lsp_servers = LspServerConfig::builtins();
+        lsp_servers.extend(cfg.lsp_servers);
+
+        let network_proxy = cfg
+            .network_proxy
+            .map(NetworkProxySettings::try_from)
+            .transpose()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+            .unwrap_or_default();
+
         // Default review model when not set in config; allow CLI override to take precedence.
         let review_model = override_review_model
             .or(cfg.review_model)
@@ -1476,6 +1538,10 @@ impl Config {
             council_implementer_model: cfg
                 .council_implementer_model
                 .unwrap_or_else(|| "gemini-3-flash-preview".to_string()),
+            council: cfg.council.map(CouncilSettings::from).unwrap_or_default(),
+            network_proxy,
+            resource_limits: cfg.resource_limits.unwrap_or_default(),
+            lsp_servers,
         };
         Ok(config)
     }
@@ -1539,33 +1605,6 @@ fn default_review_model() -> String {
     OPENAI_DEFAULT_REVIEW_MODEL.to_string()
 }
 
-/// Returns the path to the Codex configuration directory, which can be
-/// specified by the `CODEX_HOME` environment variable. If not set, defaults to
-/// `~/.codex`.
-///
-/// - If `CODEX_HOME` is set, the value will be canonicalized and this
-///   function will Err if the path does not exist.
-/// - If `CODEX_HOME` is not set, this function does not verify that the
-///   directory exists.
-pub fn find_codex_home() -> std::io::Result<PathBuf> {
-    // Honor the `CODEX_HOME` environment variable when it is set to allow users
-    // (and tests) to override the default location.
-    if let Ok(val) = std::env::var("CODEX_HOME")
-        && !val.is_empty()
-    {
-        return PathBuf::from(val).canonicalize();
-    }
-
-    let mut p = home_dir().ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "Could not find home directory",
-        )
-    })?;
-    p.push(".codex");
-    Ok(p)
-}
-
 /// Returns the path to the folder where Codex logs are stored. Does not verify
 /// that the directory exists.
 pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
@@ -1623,6 +1662,148 @@ persistence = "none"
         );
     }
 
+    #[test]
+    fn council_budget_and_prices_parse() {
+        let cfg = r#"
+[council]
+max_tokens_per_run = 400000
+max_cost_per_run = 2.5
+hook_block_severity = "P0"
+worktree_pool_size = 0
+shared_build_cache = false
+
+[council.retention]
+keep_runs = 5
+keep_successful = true
+pinned = ["run-1700000000"]
+
+[council.prices."gpt-5.1-codex"]
+input_per_mtok = 1.25
+cached_input_per_mtok = 0.125
+output_per_mtok = 10.0
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("council config should parse");
+        let council = CouncilSettings::from(parsed.council.expect("council section"));
+
+        assert_eq!(council.max_tokens_per_run, Some(400_000));
+        assert_eq!(council.max_cost_per_run, Some(2.5));
+        assert_eq!(
+            council.hook_block_severity,
+            crate::config::types::CouncilHookSeverity::P0
+        );
+        assert_eq!(council.worktree_pool_size, 0);
+        assert!(!council.shared_build_cache);
+        assert_eq!(
+            council.retention,
+            crate::config::types::CouncilRetention {
+                keep_runs: 5,
+                keep_successful: true,
+                pinned: vec!["run-1700000000".to_string()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            council.prices.get("gpt-5.1-codex"),
+            Some(&crate::config::types::ModelPrice {
+                input_per_mtok: 1.25,
+                cached_input_per_mtok: Some(0.125),
+                output_per_mtok: 10.0,
+            })
+        );
+    }
+
+    #[test]
+    fn network_proxy_allowlist_parses() {
+        let cfg = r#"
+[network_proxy]
+enabled = true
+allowed_hosts = ["crates.io", "*.github.com", "localhost:8080"]
+"#;
+
+        let parsed =
+            toml::from_str::<ConfigToml>(cfg).expect("network proxy config should parse");
+        let settings =
+            NetworkProxySettings::try_from(parsed.network_proxy.expect("network_proxy section"))
+                .expect("allowlist should be valid");
+
+        assert!(settings.enabled);
+        assert!(settings.allowlist.allows("api.github.com", 443));
+        assert!(!settings.allowlist.allows("github.com", 443));
+        assert!(settings.allowlist.allows("localhost", 8080));
+        assert!(!settings.allowlist.allows("localhost", 443));
+
+        let invalid = crate::config::types::NetworkProxyToml {
+            enabled: Some(true),
+            allowed_hosts: vec!["https://crates.io".to_string()],
+        };
+        assert!(NetworkProxySettings::try_from(invalid).is_err());
+    }
+
+    #[test]
+    fn lsp_servers_override_builtins() -> std::io::Result<()> {
+        let cfg = r#"
+[lsp_servers.rust-analyzer]
+command = "/opt/ra/bin/rust-analyzer"
+extensions = ["rs"]
+
+[lsp_servers.pyright]
+command = "pyright-langserver"
+extensions = ["py"]
+enabled = false
+
+[lsp_servers.gopls]
+command = "gopls"
+extensions = ["go"]
+"#;
+        let cfg = toml::from_str::<ConfigToml>(cfg).expect("lsp servers should parse");
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.lsp_servers.keys().collect::<Vec<_>>(),
+            vec!["gopls", "pyright", "rust-analyzer", "typescript"]
+        );
+        assert_eq!(
+            config.lsp_servers["rust-analyzer"].command,
+            "/opt/ra/bin/rust-analyzer"
+        );
+        assert!(!config.lsp_servers["pyright"].enabled);
+        assert_eq!(
+            config.lsp_servers["typescript"].args,
+            vec!["--stdio".to_string()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resource_limits_parse() {
+        let cfg = r#"
+[resource_limits]
+max_memory_mb = 2048
+max_cpu_seconds = 300
+max_open_files = 1024
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("resource limits should parse");
+
+        assert_eq!(
+            parsed.resource_limits,
+            Some(ResourceLimits {
+                max_memory_mb: Some(2048),
+                max_cpu_seconds: Some(300),
+                max_processes: None,
+                max_open_files: Some(1024),
+                max_file_size_mb: None,
+            })
+        );
+        assert!(ResourceLimits::default().is_empty());
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -1739,6 +1920,8 @@ exclude_slash_tmp = true
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        read_only_subpaths: vec![],
+                        deny_read_paths: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1787,6 +1970,8 @@ trust_level = "trusted"
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        read_only_subpaths: vec![],
+                        deny_read_paths: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1794,6 +1979,38 @@ trust_level = "trusted"
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn sandbox_carve_outs_resolve_against_cwd_and_home() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+read_only_subpaths = [".git/hooks", "/etc/codex"]
+deny_read_paths = ["~/.ssh", "secrets"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let resolution = cfg.derive_sandbox_policy(None, None, &PathBuf::from("/repo"));
+        let home = home_dir().expect("home dir");
+
+        assert_eq!(
+            vec![
+                test_absolute_path("/repo/.git/hooks"),
+                test_absolute_path("/etc/codex"),
+            ],
+            resolution.policy.get_read_only_subpaths()
+        );
+        assert_eq!(
+            vec![
+                AbsolutePathBuf::try_from(home.join(".ssh")).expect("absolute home"),
+                test_absolute_path("/repo/secrets"),
+            ],
+            resolution.policy.get_deny_read_paths()
+        );
+    }
+
     #[test]
     fn add_dir_override_extends_workspace_writable_roots() -> std::io::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -2155,6 +2372,7 @@ trust_level = "trusted"
                 tool_timeout_sec: Some(Duration::from_secs(5)),
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -2308,6 +2526,7 @@ bearer_token = "secret"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2376,6 +2595,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2424,6 +2644,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2470,6 +2691,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2532,6 +2754,7 @@ startup_timeout_sec = 2.0
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
         apply_blocking(
@@ -2606,6 +2829,7 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2633,6 +2857,7 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         );
         apply_blocking(
@@ -2698,6 +2923,7 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    sampling_token_budget: None,
                 },
             ),
             (
@@ -2715,6 +2941,7 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    sampling_token_budget: None,
                 },
             ),
         ]);
@@ -2795,6 +3022,7 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -2837,6 +3065,7 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
+                sampling_token_budget: None,
             },
         )]);
 
@@ -3269,6 +3498,10 @@ model_verbosity = "high"
                 council_critic_gpt_model: "gpt-5.1-codex".to_string(),
                 council_critic_gemini_model: "gemini-3-pro-preview".to_string(),
                 council_implementer_model: "gemini-3-flash-preview".to_string(),
+                council: CouncilSettings::default(),
+                network_proxy: NetworkProxySettings::default(),
+                resource_limits: ResourceLimits::default(),
+                lsp_servers: LspServerConfig::builtins(),
             },
             o3_profile_config
         );
@@ -3358,6 +3591,10 @@ model_verbosity = "high"
             council_critic_gpt_model: "gpt-5.1-codex".to_string(),
             council_critic_gemini_model: "gemini-3-pro-preview".to_string(),
             council_implementer_model: "gemini-3-flash-preview".to_string(),
+            council: CouncilSettings::default(),
+            network_proxy: NetworkProxySettings::default(),
+            resource_limits: ResourceLimits::default(),
+            lsp_servers: LspServerConfig::builtins(),
         };
 
         assert_eq!(expected_gpt3_profile_config, gpt3_profile_config);
@@ -3462,6 +3699,10 @@ model_verbosity = "high"
             council_critic_gpt_model: "gpt-5.1-codex".to_string(),
             council_critic_gemini_model: "gemini-3-pro-preview".to_string(),
             council_implementer_model: "gemini-3-flash-preview".to_string(),
+            council: CouncilSettings::default(),
+            network_proxy: NetworkProxySettings::default(),
+            resource_limits: ResourceLimits::default(),
+            lsp_servers: LspServerConfig::builtins(),
         };
 
         assert_eq!(expected_zdr_profile_config, zdr_profile_config);
@@ -3552,6 +3793,10 @@ model_verbosity = "high"
             council_critic_gpt_model: "gpt-5.1-codex".to_string(),
             council_critic_gemini_model: "gemini-3-pro-preview".to_string(),
             council_implementer_model: "gemini-3-flash-preview".to_string(),
+            council: CouncilSettings::default(),
+            network_proxy: NetworkProxySettings::default(),
+            resource_limits: ResourceLimits::default(),
+            lsp_servers: LspServerConfig::builtins(),
         };
 
         assert_eq!(expected_gpt5_profile_config, gpt5_profile_config);
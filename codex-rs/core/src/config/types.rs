@@ -16,6 +16,11 @@ use serde::Serialize;
 use serde::de::Error as SerdeError;
 
 pub const DEFAULT_OTEL_ENVIRONMENT: &str = "dev";
+pub const DEFAULT_COUNCIL_MAX_CONCURRENT_JOBS: usize = 2;
+pub const DEFAULT_COUNCIL_WORKTREE_POOL_SIZE: usize = 2;
+pub const DEFAULT_COUNCIL_KEEP_RUNS: usize = 20;
+pub const DEFAULT_COUNCIL_MAX_AGE_HOURS: u64 = 7 * 24;
+pub const DEFAULT_MCP_SAMPLING_TOKEN_BUDGET: u64 = 50_000;
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct McpServerConfig {
@@ -45,6 +50,11 @@ pub struct McpServerConfig {
     /// Explicit deny-list of tools. These tools will be removed after applying `enabled_tools`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disabled_tools: Option<Vec<String>>,
+
+    /// Tokens this server may spend through `sampling/createMessage` per session.
+    /// Defaults to [`DEFAULT_MCP_SAMPLING_TOKEN_BUDGET`]; `0` disables sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_token_budget: Option<u64>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -86,6 +96,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools: Option<Vec<String>>,
             #[serde(default)]
             disabled_tools: Option<Vec<String>>,
+            #[serde(default)]
+            sampling_token_budget: Option<u64>,
         }
 
         let mut raw = RawMcpServerConfig::deserialize(deserializer)?;
@@ -102,6 +114,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let enabled = raw.enabled.unwrap_or_else(default_enabled);
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
+        let sampling_token_budget = raw.sampling_token_budget;
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -155,6 +168,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled,
             enabled_tools,
             disabled_tools,
+            sampling_token_budget,
         })
     }
 }
@@ -359,6 +373,274 @@ impl Default for OtelConfig {
     }
 }
 
+// ===== Council configuration =====
+
+/// Token prices for a single model, in USD per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    /// Price for cached input tokens. Falls back to `input_per_mtok` when unset.
+    #[serde(default)]
+    pub cached_input_per_mtok: Option<f64>,
+    pub output_per_mtok: f64,
+}
+
+/// Lowest finding severity that makes a council git hook fail. `P0` is the most severe.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum CouncilHookSeverity {
+    P0,
+    #[default]
+    P1,
+    P2,
+    P3,
+}
+
+/// `[council.retention]` settings loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CouncilRetentionToml {
+    /// Number of most recent runs to keep (`0` disables the count limit).
+    pub keep_runs: Option<usize>,
+
+    /// Delete runs older than this many hours (`0` disables the age limit).
+    pub max_age_hours: Option<u64>,
+
+    /// Never delete runs that finished successfully.
+    pub keep_successful: Option<bool>,
+
+    /// Run ids that are never deleted, in addition to runs pinned with `codex council pin`.
+    #[serde(default)]
+    pub pinned: Vec<String>,
+
+    /// Garbage-collect runs automatically when the TUI starts a job (default `true`).
+    pub auto_gc: Option<bool>,
+}
+
+/// Effective retention policy for `.council/runs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CouncilRetention {
+    pub keep_runs: usize,
+    pub max_age_hours: u64,
+    pub keep_successful: bool,
+    pub pinned: Vec<String>,
+    pub auto_gc: bool,
+}
+
+impl Default for CouncilRetention {
+    fn default() -> Self {
+        Self {
+            keep_runs: DEFAULT_COUNCIL_KEEP_RUNS,
+            max_age_hours: DEFAULT_COUNCIL_MAX_AGE_HOURS,
+            keep_successful: false,
+            pinned: Vec::new(),
+            auto_gc: true,
+        }
+    }
+}
+
+impl From<CouncilRetentionToml> for CouncilRetention {
+    fn from(toml: CouncilRetentionToml) -> Self {
+        Self {
+            keep_runs: toml.keep_runs.unwrap_or(DEFAULT_COUNCIL_KEEP_RUNS),
+            max_age_hours: toml.max_age_hours.unwrap_or(DEFAULT_COUNCIL_MAX_AGE_HOURS),
+            keep_successful: toml.keep_successful.unwrap_or(false),
+            pinned: toml.pinned,
+            auto_gc: toml.auto_gc.unwrap_or(true),
+        }
+    }
+}
+
+/// `[council]` settings loaded from config.toml. Fields are optional so we can apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CouncilConfigToml {
+    /// Abort a council run before a phase would push total token usage past this limit.
+    pub max_tokens_per_run: Option<u64>,
+
+    /// Abort a council run before a phase would push its estimated cost (USD) past this limit.
+    pub max_cost_per_run: Option<f64>,
+
+    /// Price table keyed by model id (or model id prefix).
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+
+    /// How many council jobs the TUI runs in parallel; further jobs are queued.
+    pub max_concurrent_jobs: Option<usize>,
+
+    /// Pre-commit/pre-push hooks block on findings at or above this severity (default `P1`).
+    pub hook_block_severity: Option<CouncilHookSeverity>,
+
+    /// Number of reusable worktrees kept under `.council/worktrees/pool-<n>`. `0` gives every
+    /// run a fresh worktree.
+    pub worktree_pool_size: Option<usize>,
+
+    /// Point council verification at a shared, per-toolchain `CARGO_TARGET_DIR` under
+    /// `.council/target` instead of a cold `target/` in each worktree (default `true`).
+    pub shared_build_cache: Option<bool>,
+
+    /// Retention policy for `.council/runs`.
+    pub retention: Option<CouncilRetentionToml>,
+}
+
+/// Effective council settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CouncilSettings {
+    pub max_tokens_per_run: Option<u64>,
+    pub max_cost_per_run: Option<f64>,
+    pub prices: HashMap<String, ModelPrice>,
+    pub max_concurrent_jobs: usize,
+    pub hook_block_severity: CouncilHookSeverity,
+    pub worktree_pool_size: usize,
+    pub shared_build_cache: bool,
+    pub retention: CouncilRetention,
+}
+
+impl Default for CouncilSettings {
+    fn default() -> Self {
+        Self {
+            max_tokens_per_run: None,
+            max_cost_per_run: None,
+            prices: HashMap::new(),
+            max_concurrent_jobs: DEFAULT_COUNCIL_MAX_CONCURRENT_JOBS,
+            hook_block_severity: CouncilHookSeverity::default(),
+            worktree_pool_size: DEFAULT_COUNCIL_WORKTREE_POOL_SIZE,
+            shared_build_cache: true,
+            retention: CouncilRetention::default(),
+        }
+    }
+}
+
+impl From<CouncilConfigToml> for CouncilSettings {
+    fn from(toml: CouncilConfigToml) -> Self {
+        Self {
+            max_tokens_per_run: toml.max_tokens_per_run,
+            max_cost_per_run: toml.max_cost_per_run,
+            prices: toml.prices,
+            max_concurrent_jobs: toml
+                .max_concurrent_jobs
+                .unwrap_or(DEFAULT_COUNCIL_MAX_CONCURRENT_JOBS)
+                .max(1),
+            hook_block_severity: toml.hook_block_severity.unwrap_or_default(),
+            worktree_pool_size: toml
+                .worktree_pool_size
+                .unwrap_or(DEFAULT_COUNCIL_WORKTREE_POOL_SIZE),
+            shared_build_cache: toml.shared_build_cache.unwrap_or(true),
+            retention: toml.retention.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+// ===== Network proxy configuration =====
+
+/// `[network_proxy]` settings loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NetworkProxyToml {
+    /// Route network access of sandboxed commands through a filtering proxy (Linux only).
+    pub enabled: Option<bool>,
+
+    /// Destinations the proxy lets through: `host`, `host:port`, `*.domain` or `*.domain:port`.
+    /// Entries without a port allow 80 and 443.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+/// Effective network proxy settings.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NetworkProxySettings {
+    pub enabled: bool,
+    pub allowlist: crate::network_proxy::NetworkAllowlist,
+}
+
+impl TryFrom<NetworkProxyToml> for NetworkProxySettings {
+    type Error = String;
+
+    fn try_from(toml: NetworkProxyToml) -> Result<Self, Self::Error> {
+        Ok(Self {
+            enabled: toml.enabled.unwrap_or(false),
+            allowlist: crate::network_proxy::NetworkAllowlist::parse(&toml.allowed_hosts)?,
+        })
+    }
+}
+
+// ===== Resource limits =====
+
+/// `[resource_limits]`: per-command limits for sandboxed commands, enforced on
+/// Linux by `codex-linux-sandbox`. Unset fields are not limited.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceLimits {
+    /// Memory the command and its children may use together.
+    pub max_memory_mb: Option<u64>,
+
+    /// CPU time each process may consume.
+    pub max_cpu_seconds: Option<u64>,
+
+    /// Processes (and threads) the command may have running at once.
+    pub max_processes: Option<u64>,
+
+    /// File descriptors each process may have open.
+    pub max_open_files: Option<u64>,
+
+    /// Largest file a process may write.
+    pub max_file_size_mb: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// ===== Language servers =====
+
+/// `[lsp_servers.<name>]`: a language server the LSP tools can start for files
+/// with one of `extensions`. Entries override the built-in servers of the same
+/// name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LspServerConfig {
+    pub command: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// File extensions (without the dot) this server handles.
+    pub extensions: Vec<String>,
+
+    /// When `false`, Codex never starts this server.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl LspServerConfig {
+    fn builtin(command: &str, args: &[&str], extensions: &[&str]) -> Self {
+        Self {
+            command: command.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+            extensions: extensions.iter().map(ToString::to_string).collect(),
+            enabled: true,
+        }
+    }
+
+    /// Servers available without any configuration.
+    pub fn builtins() -> BTreeMap<String, LspServerConfig> {
+        BTreeMap::from([
+            (
+                "rust-analyzer".to_string(),
+                Self::builtin("rust-analyzer", &[], &["rs"]),
+            ),
+            (
+                "typescript".to_string(),
+                Self::builtin(
+                    "typescript-language-server",
+                    &["--stdio"],
+                    &["ts", "tsx", "js", "jsx", "mts", "cts", "mjs", "cjs"],
+                ),
+            ),
+            (
+                "pyright".to_string(),
+                Self::builtin("pyright-langserver", &["--stdio"], &["py", "pyi"]),
+            ),
+        ])
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(untagged)]
 pub enum Notifications {
@@ -556,6 +838,14 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Paths that stay read-only inside the writable roots. Relative paths are
+    /// resolved against the session cwd and `~/` against the home directory.
+    #[serde(default)]
+    pub read_only_subpaths: Vec<PathBuf>,
+    /// Paths the sandboxed command can neither read nor write, resolved like
+    /// `read_only_subpaths`.
+    #[serde(default)]
+    pub deny_read_paths: Vec<PathBuf>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -894,6 +1184,19 @@ mod tests {
         assert_eq!(cfg.disabled_tools, Some(vec!["blocked".to_string()]));
     }
 
+    #[test]
+    fn deserialize_server_config_with_sampling_token_budget() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            sampling_token_budget = 0
+        "#,
+        )
+        .expect("should deserialize sampling budget");
+
+        assert_eq!(cfg.sampling_token_budget, Some(0));
+    }
+
     #[test]
     fn deserialize_rejects_command_and_url() {
         toml::from_str::<McpServerConfig>(
@@ -262,6 +262,8 @@ mod tests {
                     network_access: false,
                     exclude_tmpdir_env_var: false,
                     exclude_slash_tmp: false,
+                    read_only_subpaths: vec![],
+                    deny_read_paths: vec![],
                 })
                 .is_ok()
         );
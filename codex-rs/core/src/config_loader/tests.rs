@@ -252,6 +252,8 @@ allowed_sandbox_modes = ["read-only"]
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                read_only_subpaths: vec![],
+                deny_read_paths: vec![],
             })
             .is_err()
     );
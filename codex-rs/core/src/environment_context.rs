@@ -207,6 +207,8 @@ mod tests {
             network_access,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         }
     }
 
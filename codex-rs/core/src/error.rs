@@ -15,6 +15,7 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -33,6 +34,18 @@ pub enum SandboxErr {
     )]
     Denied { output: Box<ExecToolCallOutput> },
 
+    /// Sandbox denial attributed to a path carved out of the workspace by
+    /// `read_only_subpaths` or `deny_read_paths`.
+    #[error(
+        "sandbox denied access to {}: it is listed in sandbox_workspace_write.{setting}",
+        .path.display()
+    )]
+    PathDenied {
+        path: PathBuf,
+        setting: &'static str,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Error from linux seccomp filter setup
     #[cfg(target_os = "linux")]
     #[error("seccomp setup error")]
@@ -526,7 +539,8 @@ impl CodexErr {
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
     let message = match e {
-        CodexErr::Sandbox(SandboxErr::Denied { output }) => {
+        CodexErr::Sandbox(SandboxErr::Denied { output })
+        | CodexErr::Sandbox(SandboxErr::PathDenied { output, .. }) => {
             let aggregated = output.aggregated_output.text.trim();
             if !aggregated.is_empty() {
                 output.aggregated_output.text.clone()
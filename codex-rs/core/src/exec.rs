@@ -16,6 +16,7 @@ use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::types::ResourceLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -177,6 +178,8 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            None,
+            &ResourceLimits::default(),
         )
         .map_err(CodexErr::from)?;
 
@@ -200,6 +203,7 @@ pub(crate) async fn execute_exec_env(
         arg0,
     } = env;
 
+    let command_cwd = cwd.clone();
     let params = ExecParams {
         command,
         cwd,
@@ -214,6 +218,7 @@ pub(crate) async fn execute_exec_env(
     let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
     let duration = start.elapsed();
     finalize_exec_result(raw_output_result, sandbox, duration)
+        .map_err(|err| attribute_denial_to_carve_out(err, sandbox_policy, &command_cwd))
 }
 
 #[cfg(target_os = "windows")]
@@ -374,6 +379,81 @@ fn finalize_exec_result(
     }
 }
 
+/// If a sandbox denial names one of the paths carved out of the workspace via
+/// `read_only_subpaths` or `deny_read_paths`, report it as such (and say so in
+/// the output the model sees) instead of as a generic denial.
+pub(crate) fn attribute_denial_to_carve_out(
+    err: CodexErr,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> CodexErr {
+    let CodexErr::Sandbox(SandboxErr::Denied { mut output }) = err else {
+        return err;
+    };
+
+    let mut carve_outs = sandbox_policy
+        .get_deny_read_paths()
+        .iter()
+        .map(|path| (path, "deny_read_paths"))
+        .chain(
+            sandbox_policy
+                .get_read_only_subpaths()
+                .iter()
+                .map(|path| (path, "read_only_subpaths")),
+        );
+    let mentioned = carve_outs.find(|(path, _)| {
+        output_mentions_path(&output.stderr.text, path.as_path(), cwd)
+            || output_mentions_path(&output.aggregated_output.text, path.as_path(), cwd)
+    });
+    let Some((path, setting)) = mentioned else {
+        return CodexErr::Sandbox(SandboxErr::Denied { output });
+    };
+
+    let blocked = if setting == "deny_read_paths" {
+        "all access to it"
+    } else {
+        "writes to it"
+    };
+    let note = format!(
+        "codex sandbox: {} is listed in sandbox_workspace_write.{setting}, so the sandbox blocks {blocked}.\n",
+        path.display()
+    );
+    for text in [&mut output.stderr.text, &mut output.aggregated_output.text] {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&note);
+    }
+
+    CodexErr::Sandbox(SandboxErr::PathDenied {
+        path: path.to_path_buf(),
+        setting,
+        output,
+    })
+}
+
+/// Whether a word of `text` names `path` or something beneath it, either as an absolute path
+/// or relative to `cwd`. Paths are compared by whole components, so `.env` does not match
+/// `.envrc`.
+fn output_mentions_path(text: &str, path: &Path, cwd: &Path) -> bool {
+    // Relative words only count for carve-outs below the cwd; otherwise every word would.
+    let below_cwd = path
+        .strip_prefix(cwd)
+        .is_ok_and(|relative| !relative.as_os_str().is_empty());
+    let separator =
+        |c: char| c.is_whitespace() || matches!(c, '\'' | '"' | '`' | '(' | ')' | ',' | ';');
+    text.split(separator)
+        .map(|word| Path::new(word.trim_end_matches(':')))
+        .filter(|word| !word.as_os_str().is_empty())
+        .any(|word| {
+            if word.is_absolute() {
+                word.starts_with(path)
+            } else {
+                below_cwd && cwd.join(word).starts_with(path)
+            }
+        })
+}
+
 pub(crate) mod errors {
     use super::CodexErr;
     use crate::sandboxing::SandboxTransformError;
@@ -847,6 +927,85 @@ mod tests {
         ));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn sandbox_denial_is_attributed_to_carve_out() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            read_only_subpaths: vec!["/repo/.git/hooks".try_into().unwrap()],
+            deny_read_paths: vec![
+                "/home/user/.ssh".try_into().unwrap(),
+                "/repo/.env".try_into().unwrap(),
+            ],
+        };
+        let denied = |stderr: &str| {
+            CodexErr::Sandbox(SandboxErr::Denied {
+                output: Box::new(make_exec_output(1, "", stderr, stderr)),
+            })
+        };
+
+        let err = attribute_denial_to_carve_out(
+            denied("cp: cannot create '.git/hooks/pre-commit': Permission denied"),
+            &policy,
+            Path::new("/repo"),
+        );
+        let CodexErr::Sandbox(SandboxErr::PathDenied {
+            path,
+            setting,
+            output,
+        }) = err
+        else {
+            panic!("expected PathDenied, got {err:?}");
+        };
+        assert_eq!(path, PathBuf::from("/repo/.git/hooks"));
+        assert_eq!(setting, "read_only_subpaths");
+        assert!(output.aggregated_output.text.ends_with(
+            "codex sandbox: /repo/.git/hooks is listed in sandbox_workspace_write.read_only_subpaths, so the sandbox blocks writes to it.\n"
+        ));
+
+        let err = attribute_denial_to_carve_out(
+            denied("cat: /home/user/.ssh/id_ed25519: Permission denied"),
+            &policy,
+            Path::new("/repo"),
+        );
+        assert!(matches!(
+            err,
+            CodexErr::Sandbox(SandboxErr::PathDenied {
+                setting: "deny_read_paths",
+                ..
+            })
+        ));
+
+        let err = attribute_denial_to_carve_out(
+            denied("cat: ./.env: Permission denied"),
+            &policy,
+            Path::new("/repo"),
+        );
+        assert!(matches!(
+            err,
+            CodexErr::Sandbox(SandboxErr::PathDenied {
+                setting: "deny_read_paths",
+                ..
+            })
+        ));
+
+        for stderr in [
+            "touch: /etc/passwd: Permission denied",
+            // Only whole path components count.
+            "cat: .envrc: Permission denied",
+            "cp: cannot create '/repo/.git/hooks-old/pre-commit': Permission denied",
+        ] {
+            let err = attribute_denial_to_carve_out(denied(stderr), &policy, Path::new("/repo"));
+            assert!(
+                matches!(err, CodexErr::Sandbox(SandboxErr::Denied { .. })),
+                "{stderr}: {err:?}"
+            );
+        }
+    }
+
     #[cfg(unix)]
     #[test]
     fn sandbox_detection_flags_sigsys_exit_code() {
@@ -12,6 +12,7 @@ use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
@@ -112,6 +113,7 @@ impl ExecPolicyManager {
         approval_policy: AskForApproval,
         sandbox_policy: &SandboxPolicy,
         sandbox_permissions: SandboxPermissions,
+        cwd: &Path,
     ) -> ExecApprovalRequirement {
         let exec_policy = self.current();
         let commands =
@@ -123,7 +125,9 @@ impl ExecPolicyManager {
                 Decision::Allow
             }
         };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &heuristics_fallback);
+        let context = match_context(sandbox_policy, cwd);
+        let evaluation =
+            exec_policy.check_multiple_in_context(commands.iter(), &context, &heuristics_fallback);
 
         match evaluation.decision {
             Decision::Forbidden => ExecApprovalRequirement::Forbidden {
@@ -242,6 +246,29 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
     Ok(policy)
 }
 
+/// Facts path-constrained rules resolve against. Sandboxes without explicit writable roots
+/// (read-only, full access) treat the cwd as the only writable root.
+fn match_context(sandbox_policy: &SandboxPolicy, cwd: &Path) -> MatchContext {
+    let roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
+    let writable_roots = if roots.is_empty() {
+        vec![cwd.to_path_buf()]
+    } else {
+        roots.iter().map(|root| root.root.to_path_buf()).collect()
+    };
+    MatchContext {
+        cwd: Some(cwd.to_path_buf()),
+        writable_roots,
+        read_only_paths: roots
+            .iter()
+            .flat_map(|root| {
+                root.read_only_subpaths
+                    .iter()
+                    .map(|path| path.to_path_buf())
+            })
+            .collect(),
+    }
+}
+
 fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
@@ -637,6 +664,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -675,6 +703,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -704,6 +733,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -734,6 +764,7 @@ prefix_rule(
                 AskForApproval::Never,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -757,6 +788,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -791,6 +823,7 @@ prefix_rule(
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::DangerFullAccess,
                     SandboxPermissions::UseDefault,
+                    Path::new("/workspace"),
                 )
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -865,6 +898,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -892,6 +926,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -922,6 +957,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -949,6 +985,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -987,6 +1024,7 @@ prefix_rule(
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::ReadOnly,
                     SandboxPermissions::UseDefault,
+                    Path::new("/workspace"),
                 )
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1010,6 +1048,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
@@ -1040,6 +1079,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                Path::new("/workspace"),
             )
             .await;
 
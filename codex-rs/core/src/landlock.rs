@@ -1,4 +1,6 @@
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use std::collections::HashMap;
@@ -25,7 +27,13 @@ pub async fn spawn_command_under_linux_sandbox<P>(
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_linux_sandbox_command_args(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        None,
+        &ResourceLimits::default(),
+    );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
         codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -40,10 +48,16 @@ where
 }
 
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
+///
+/// When `network_proxy_socket` is set and the policy does not grant full network
+/// access, the helper gives the command network access only through that proxy.
+/// `resource_limits` are enforced by the helper for the whole command tree.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy_socket: Option<&Path>,
+    resource_limits: &ResourceLimits,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,10 +74,17 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
+    if let Some(socket) = network_proxy_socket
+        && !sandbox_policy.has_full_network_access()
+    {
+        linux_cmd.push("--network-proxy-socket".to_string());
+        linux_cmd.push(socket.to_string_lossy().to_string());
+    }
+    linux_cmd.extend(resource_limits::linux_sandbox_args(resource_limits));
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
 
     // Append the original tool command.
     linux_cmd.extend(command);
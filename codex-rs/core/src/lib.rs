@@ -7,6 +7,7 @@
 
 pub mod api_bridge;
 mod apply_patch;
+mod approval_history;
 pub mod auth;
 pub mod bash;
 mod client;
@@ -32,9 +33,11 @@ pub mod features;
 mod flags;
 pub mod git_info;
 pub mod landlock;
+mod lsp;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod models_manager;
+pub mod network_proxy;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
 pub use mcp_connection_manager::SandboxState;
@@ -78,6 +81,7 @@ pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
 pub mod project_doc;
+pub mod resource_limits;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
@@ -101,6 +105,13 @@ pub use rollout::list::ThreadItem;
 pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
+pub use rollout::search::SearchEntryKind;
+pub use rollout::search::SearchMatch;
+pub use rollout::search::SessionSearchHit;
+pub use rollout::search::SessionSearchQuery;
+pub use rollout::search::remove_session_index;
+pub use rollout::search::search_indexed_sessions;
+pub use rollout::search::search_sessions;
 mod function_tool;
 mod state;
 mod tasks;
@@ -0,0 +1,786 @@
+//! JSON-RPC over stdio with a single language server process.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::warn;
+use url::Url;
+
+use crate::config::types::LspServerConfig;
+
+/// How long the server may take to answer `initialize`. Servers index the
+/// workspace before answering, which takes a while on large repositories.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+/// Largest message we accept from a server. Anything bigger is a broken or
+/// hostile server rather than an answer worth buffering.
+const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+type PendingRequests = std::sync::Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>;
+
+/// Diagnostics the server last published for a document.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PublishedDiagnostics {
+    /// Number of times the server has published diagnostics for the document.
+    pub(crate) generation: u64,
+    pub(crate) diagnostics: Vec<Value>,
+}
+
+#[derive(Default)]
+struct DiagnosticsStore {
+    by_uri: std::sync::Mutex<HashMap<String, PublishedDiagnostics>>,
+    published: Notify,
+}
+
+struct OpenDocument {
+    version: i64,
+    text: String,
+}
+
+pub(crate) struct LspClient {
+    name: String,
+    root: PathBuf,
+    stdin: Arc<Mutex<ChildStdin>>,
+    child: Mutex<Child>,
+    next_id: AtomicI64,
+    pending: Arc<PendingRequests>,
+    diagnostics: Arc<DiagnosticsStore>,
+    documents: Mutex<HashMap<PathBuf, OpenDocument>>,
+    reader: JoinHandle<()>,
+}
+
+impl LspClient {
+    /// Starts the server and completes the `initialize` handshake for `root`.
+    pub(crate) async fn start(
+        name: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> Result<Self, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("failed to start `{}`: {err}", config.command))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(format!("`{}` has no stdio", config.command));
+        };
+
+        let stdin = Arc::new(Mutex::new(stdin));
+        let pending: Arc<PendingRequests> = Arc::default();
+        let diagnostics: Arc<DiagnosticsStore> = Arc::default();
+        let reader = tokio::spawn(read_messages(
+            name.to_string(),
+            stdout,
+            Arc::clone(&stdin),
+            Arc::clone(&pending),
+            Arc::clone(&diagnostics),
+        ));
+
+        let client = Self {
+            name: name.to_string(),
+            root: root.to_path_buf(),
+            stdin,
+            child: Mutex::new(child),
+            next_id: AtomicI64::new(1),
+            pending,
+            diagnostics,
+            documents: Mutex::new(HashMap::new()),
+            reader,
+        };
+        client.initialize().await?;
+        Ok(client)
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether the server process is still there to answer requests.
+    pub(crate) fn is_running(&self) -> bool {
+        !self.reader.is_finished()
+    }
+
+    async fn initialize(&self) -> Result<(), String> {
+        let root_uri = file_uri(&self.root)?;
+        let root_name = self
+            .root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| root_uri.clone());
+        let params = json!({
+            "processId": std::process::id(),
+            "clientInfo": { "name": "codex" },
+            "rootUri": root_uri,
+            "workspaceFolders": [{ "uri": root_uri, "name": root_name }],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": false },
+                    "hover": { "contentFormat": ["markdown", "plaintext"] },
+                    "definition": { "linkSupport": true },
+                    "references": {},
+                    "publishDiagnostics": { "versionSupport": true },
+                },
+                "workspace": {
+                    "workspaceFolders": true,
+                    "configuration": true,
+                    "symbol": {},
+                },
+            },
+        });
+        self.request_with_timeout("initialize", params, INITIALIZE_TIMEOUT)
+            .await?;
+        self.notify("initialized", json!({})).await
+    }
+
+    pub(crate) async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        self.request_with_timeout(method, params, REQUEST_TIMEOUT)
+            .await
+    }
+
+    async fn request_with_timeout(
+        &self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        lock(&self.pending).insert(id, tx);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = write_message(&self.stdin, &message).await {
+            lock(&self.pending).remove(&id);
+            return Err(format!("{} is not running: {err}", self.name));
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(format!("{} exited before answering {method}", self.name)),
+            Err(_) => {
+                lock(&self.pending).remove(&id);
+                let _ = self.notify("$/cancelRequest", json!({ "id": id })).await;
+                Err(format!(
+                    "{} did not answer {method} within {}s",
+                    self.name,
+                    timeout.as_secs()
+                ))
+            }
+        }
+    }
+
+    pub(crate) async fn notify(&self, method: &str, params: Value) -> Result<(), String> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&self.stdin, &message)
+            .await
+            .map_err(|err| format!("{} is not running: {err}", self.name))
+    }
+
+    /// Opens `path` in the server, or sends its current contents if they
+    /// changed since it was opened, so that answers reflect what is on disk.
+    /// Returns the document's URI.
+    pub(crate) async fn sync_document(&self, path: &Path) -> Result<String, String> {
+        let uri = file_uri(path)?;
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(path) {
+            Some(document) if document.text == text => {}
+            Some(document) => {
+                document.version += 1;
+                document.text.clone_from(&text);
+                let params = json!({
+                    "textDocument": { "uri": uri, "version": document.version },
+                    "contentChanges": [{ "text": text }],
+                });
+                self.notify("textDocument/didChange", params).await?;
+            }
+            None => {
+                let params = json!({
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": language_id(path),
+                        "version": 1,
+                        "text": text,
+                    },
+                });
+                self.notify("textDocument/didOpen", params).await?;
+                documents.insert(path.to_path_buf(), OpenDocument { version: 1, text });
+            }
+        }
+        Ok(uri)
+    }
+
+    /// Diagnostics for `path` after bringing the server up to date with it,
+    /// waiting up to `timeout` for the server to publish them.
+    pub(crate) async fn diagnostics(
+        &self,
+        path: &Path,
+        timeout: Duration,
+    ) -> Result<Vec<Value>, String> {
+        let uri = file_uri(path)?;
+        let before = self.published_diagnostics(&uri);
+        let changed = {
+            let documents = self.documents.lock().await;
+            match documents.get(path) {
+                Some(document) => tokio::fs::read_to_string(path)
+                    .await
+                    .is_ok_and(|text| text != document.text),
+                None => true,
+            }
+        };
+        self.sync_document(path).await?;
+        if !changed && before.is_some() {
+            return Ok(before
+                .map(|published| published.diagnostics)
+                .unwrap_or_default());
+        }
+
+        let generation = before.map(|published| published.generation).unwrap_or(0);
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let published = self.diagnostics.published.notified();
+            if let Some(current) = self.published_diagnostics(&uri)
+                && current.generation > generation
+            {
+                return Ok(current.diagnostics);
+            }
+            if tokio::time::timeout_at(deadline, published).await.is_err() {
+                // Servers do not publish anything for some files; report what we have.
+                return Ok(self
+                    .published_diagnostics(&uri)
+                    .map(|published| published.diagnostics)
+                    .unwrap_or_default());
+            }
+        }
+    }
+
+    fn published_diagnostics(&self, uri: &str) -> Option<PublishedDiagnostics> {
+        lock(&self.diagnostics.by_uri).get(uri).cloned()
+    }
+
+    /// Asks the server to exit, killing it if it does not.
+    pub(crate) async fn shutdown(&self) {
+        if self.is_running() {
+            let _ = self
+                .request_with_timeout("shutdown", Value::Null, SHUTDOWN_TIMEOUT)
+                .await;
+            let _ = self.notify("exit", Value::Null).await;
+        }
+        let mut child = self.child.lock().await;
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, child.wait())
+            .await
+            .is_err()
+        {
+            let _ = child.kill().await;
+        }
+        self.reader.abort();
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+fn lock<T>(mutex: &std::sync::Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    stdin: &Mutex<W>,
+    message: &Value,
+) -> std::io::Result<()> {
+    let body = message.to_string();
+    let mut stdin = stdin.lock().await;
+    stdin
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    stdin.write_all(body.as_bytes()).await?;
+    stdin.flush().await
+}
+
+/// Reads one `Content-Length` framed message. Returns `None` at end of stream.
+async fn read_message<R: AsyncBufRead + Unpin>(stdout: &mut R) -> std::io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if stdout.read_line(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            let value = value.trim();
+            let length = value.parse::<usize>().map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid Content-Length `{value}`"),
+                )
+            })?;
+            content_length = Some(length);
+        }
+    }
+    let Some(content_length) = content_length else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "message without Content-Length",
+        ));
+    };
+    if content_length > MAX_CONTENT_LENGTH {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Content-Length {content_length} exceeds the {MAX_CONTENT_LENGTH} byte limit"),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    stdout.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+async fn read_messages<R, W>(
+    name: String,
+    stdout: R,
+    stdin: Arc<Mutex<W>>,
+    pending: Arc<PendingRequests>,
+    diagnostics: Arc<DiagnosticsStore>,
+) where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut stdout = BufReader::new(stdout);
+    loop {
+        let message = match read_message(&mut stdout).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => {
+                warn!("failed to read from language server {name}: {err}");
+                break;
+            }
+        };
+
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            (None, Some(id)) => {
+                let Some(sender) = id.as_i64().and_then(|id| lock(&pending).remove(&id)) else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown error")
+                        .to_string()),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = sender.send(result);
+            }
+            (Some(method), Some(id)) => {
+                // The server asks the client something; answer so it does not wait on us.
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                if write_message(&stdin, &response).await.is_err() {
+                    break;
+                }
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let Some(uri) = message.pointer("/params/uri").and_then(Value::as_str) else {
+                    continue;
+                };
+                let published = message
+                    .pointer("/params/diagnostics")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                {
+                    let mut by_uri = lock(&diagnostics.by_uri);
+                    let entry = by_uri.entry(uri.to_string()).or_default();
+                    entry.generation += 1;
+                    entry.diagnostics = published;
+                }
+                diagnostics.published.notify_waiters();
+            }
+            (Some(method), None) => debug!("ignoring {method} from language server {name}"),
+            (None, None) => {}
+        }
+    }
+
+    // Nobody is going to answer the requests still waiting.
+    lock(&pending).clear();
+}
+
+pub(crate) fn file_uri(path: &Path) -> Result<String, String> {
+    Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| format!("{} is not an absolute path", path.display()))
+}
+
+pub(crate) fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+fn language_id(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "rs" => "rust",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "py" | "pyi" => "python",
+        other => other,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::io::DuplexStream;
+
+    /// The client's side of a connection whose server is played by the test.
+    struct Connection {
+        stdin: Arc<Mutex<DuplexStream>>,
+        pending: Arc<PendingRequests>,
+        diagnostics: Arc<DiagnosticsStore>,
+        reader: JoinHandle<()>,
+    }
+
+    struct FakeServer {
+        /// What the client writes to the server.
+        incoming: BufReader<DuplexStream>,
+        outgoing: Mutex<DuplexStream>,
+    }
+
+    fn connect() -> (Connection, FakeServer) {
+        let (client_stdin, server_stdin) = tokio::io::duplex(64 * 1024);
+        let (server_stdout, client_stdout) = tokio::io::duplex(64 * 1024);
+        let stdin = Arc::new(Mutex::new(client_stdin));
+        let pending: Arc<PendingRequests> = Arc::default();
+        let diagnostics: Arc<DiagnosticsStore> = Arc::default();
+        let reader = tokio::spawn(read_messages(
+            "test".to_string(),
+            client_stdout,
+            Arc::clone(&stdin),
+            Arc::clone(&pending),
+            Arc::clone(&diagnostics),
+        ));
+        let connection = Connection {
+            stdin,
+            pending,
+            diagnostics,
+            reader,
+        };
+        let server = FakeServer {
+            incoming: BufReader::new(server_stdin),
+            outgoing: Mutex::new(server_stdout),
+        };
+        (connection, server)
+    }
+
+    impl Connection {
+        /// Sends a request the way `LspClient::request_with_timeout` does.
+        async fn request(&self, id: i64, method: &str) -> oneshot::Receiver<Result<Value, String>> {
+            let (tx, rx) = oneshot::channel();
+            lock(&self.pending).insert(id, tx);
+            let message = json!({ "jsonrpc": "2.0", "id": id, "method": method });
+            write_message(&self.stdin, &message)
+                .await
+                .expect("write request");
+            rx
+        }
+    }
+
+    impl FakeServer {
+        async fn send(&self, message: Value) {
+            write_message(&self.outgoing, &message)
+                .await
+                .expect("write to client");
+        }
+
+        async fn receive(&mut self) -> Value {
+            read_message(&mut self.incoming)
+                .await
+                .expect("read from client")
+                .expect("client closed its stdin")
+        }
+    }
+
+    fn invalid_data(result: std::io::Result<Option<Value>>) -> String {
+        match result {
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => err.to_string(),
+            other => panic!("expected invalid data, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn write_message_frames_body_with_content_length() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let message = json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} });
+        write_message(&Mutex::new(client), &message)
+            .await
+            .expect("write message");
+
+        let mut framed = String::new();
+        server
+            .read_to_string(&mut framed)
+            .await
+            .expect("read frame");
+        let body = message.to_string();
+        assert_eq!(
+            framed,
+            format!("Content-Length: {}\r\n\r\n{body}", body.len())
+        );
+        assert_eq!(
+            read_message(&mut framed.as_bytes())
+                .await
+                .expect("read message"),
+            Some(message)
+        );
+    }
+
+    #[tokio::test]
+    async fn read_message_reassembles_partial_reads() {
+        // A tiny pipe hands the reader a few bytes at a time.
+        let (mut server, client) = tokio::io::duplex(3);
+        let first = json!({ "jsonrpc": "2.0", "id": 1, "result": "héllo" });
+        let second = json!({ "jsonrpc": "2.0", "method": "$/progress" });
+        let frames = [&first, &second]
+            .map(|message| {
+                let body = message.to_string();
+                format!(
+                    "content-length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{body}",
+                    body.len()
+                )
+            })
+            .concat();
+        let writer = tokio::spawn(async move {
+            for chunk in frames.as_bytes().chunks(5) {
+                server.write_all(chunk).await.expect("write chunk");
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut client = BufReader::new(client);
+        assert_eq!(
+            read_message(&mut client).await.expect("read first"),
+            Some(first)
+        );
+        assert_eq!(
+            read_message(&mut client).await.expect("read second"),
+            Some(second)
+        );
+        writer.await.expect("writer");
+        assert_eq!(read_message(&mut client).await.expect("read end"), None);
+    }
+
+    #[tokio::test]
+    async fn read_message_rejects_missing_or_malformed_content_length() {
+        assert_eq!(
+            invalid_data(read_message(&mut "Content-Type: json\r\n\r\n{}".as_bytes()).await),
+            "message without Content-Length"
+        );
+        assert_eq!(
+            invalid_data(read_message(&mut "Content-Length: ten\r\n\r\n{}".as_bytes()).await),
+            "invalid Content-Length `ten`"
+        );
+        assert_eq!(
+            invalid_data(read_message(&mut "Content-Length: -1\r\n\r\n{}".as_bytes()).await),
+            "invalid Content-Length `-1`"
+        );
+        let oversized = format!("Content-Length: {}\r\n\r\n", MAX_CONTENT_LENGTH + 1);
+        assert_eq!(
+            invalid_data(read_message(&mut oversized.as_bytes()).await),
+            format!(
+                "Content-Length {} exceeds the {MAX_CONTENT_LENGTH} byte limit",
+                MAX_CONTENT_LENGTH + 1
+            )
+        );
+        // The body is shorter than announced.
+        let truncated = read_message(&mut "Content-Length: 10\r\n\r\n{}".as_bytes()).await;
+        assert!(matches!(truncated, Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof));
+    }
+
+    #[tokio::test]
+    async fn responses_are_routed_by_id() {
+        let (connection, mut server) = connect();
+        let first = connection.request(1, "textDocument/hover").await;
+        let second = connection.request(2, "textDocument/definition").await;
+        let unanswered = connection.request(3, "workspace/symbol").await;
+        for _ in 0..3 {
+            server.receive().await;
+        }
+
+        server
+            .send(json!({ "jsonrpc": "2.0", "id": 7, "result": "nobody asked" }))
+            .await;
+        server
+            .send(json!({ "jsonrpc": "2.0", "id": 2, "result": [{ "uri": "file:///a.rs" }] }))
+            .await;
+        server
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32601, "message": "hover not supported" },
+            }))
+            .await;
+        assert_eq!(
+            second.await.expect("second answered"),
+            Ok(json!([{ "uri": "file:///a.rs" }]))
+        );
+        assert_eq!(
+            first.await.expect("first answered"),
+            Err("hover not supported".to_string())
+        );
+
+        // Once the server goes away, requests still waiting learn that nobody
+        // will answer them.
+        drop(server);
+        connection.reader.await.expect("reader");
+        assert!(unanswered.await.is_err());
+        assert!(lock(&connection.pending).is_empty());
+    }
+
+    #[tokio::test]
+    async fn notifications_and_server_requests_do_not_answer_requests() {
+        let (connection, mut server) = connect();
+        let pending = connection.request(1, "textDocument/hover").await;
+        server.receive().await;
+
+        server
+            .send(json!({ "jsonrpc": "2.0", "method": "window/logMessage", "params": {} }))
+            .await;
+        server
+            .send(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": { "uri": "file:///a.rs", "diagnostics": [{ "message": "unused" }] },
+            }))
+            .await;
+        // A server request may reuse an id the client is waiting on.
+        server
+            .send(json!({ "jsonrpc": "2.0", "id": 1, "method": "client/registerCapability" }))
+            .await;
+        assert_eq!(
+            server.receive().await,
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null })
+        );
+        server
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": "config",
+                "method": "workspace/configuration",
+                "params": { "items": [{ "section": "rust-analyzer" }, { "section": "files" }] },
+            }))
+            .await;
+        assert_eq!(
+            server.receive().await,
+            json!({ "jsonrpc": "2.0", "id": "config", "result": [null, null] })
+        );
+
+        assert!(lock(&connection.pending).contains_key(&1));
+        let published = lock(&connection.diagnostics.by_uri)
+            .get("file:///a.rs")
+            .cloned()
+            .expect("diagnostics published");
+        assert_eq!(published.generation, 1);
+        assert_eq!(published.diagnostics, vec![json!({ "message": "unused" })]);
+
+        server
+            .send(json!({ "jsonrpc": "2.0", "id": 1, "result": "docs" }))
+            .await;
+        assert_eq!(pending.await.expect("answered"), Ok(json!("docs")));
+    }
+
+    #[tokio::test]
+    async fn requests_get_the_answers_a_fake_server_gives_them() {
+        let (connection, mut server) = connect();
+        let server = tokio::spawn(async move {
+            // Answer in reverse order, echoing each request's method.
+            let mut requests = Vec::new();
+            for _ in 0..3 {
+                requests.push(server.receive().await);
+            }
+            for request in requests.into_iter().rev() {
+                server
+                    .send(json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": request["method"],
+                    }))
+                    .await;
+            }
+            server
+        });
+
+        let methods = [
+            "initialize",
+            "textDocument/hover",
+            "textDocument/references",
+        ];
+        let mut answers = Vec::new();
+        for (id, method) in (1..).zip(methods) {
+            answers.push(connection.request(id, method).await);
+        }
+        for (answer, method) in answers.into_iter().zip(methods) {
+            assert_eq!(answer.await.expect("answered"), Ok(json!(method)));
+        }
+        drop(server.await.expect("server"));
+        connection.reader.await.expect("reader");
+    }
+}
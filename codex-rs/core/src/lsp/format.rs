@@ -0,0 +1,342 @@
+//! Turns LSP positions and results into the text the model sees.
+//!
+//! Tools take and report 1-based lines and columns counted in characters; LSP
+//! counts 0-based lines and UTF-16 code units.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use serde_json::json;
+
+use super::uri_to_path;
+
+/// LSP position for a 1-based `line` and character `column` in `text`.
+pub(crate) fn lsp_position(text: &str, line: usize, column: usize) -> Value {
+    let line_index = line.saturating_sub(1);
+    let character: usize = text
+        .lines()
+        .nth(line_index)
+        .map(|source| {
+            source
+                .chars()
+                .take(column.saturating_sub(1))
+                .map(char::len_utf16)
+                .sum()
+        })
+        .unwrap_or(0);
+    json!({ "line": line_index, "character": character })
+}
+
+/// 1-based character column for an LSP `character` offset into `source`.
+fn character_column(source: &str, character: u64) -> usize {
+    let mut units = 0;
+    let mut column = 1;
+    for ch in source.chars() {
+        if units >= character {
+            break;
+        }
+        units += ch.len_utf16() as u64;
+        column += 1;
+    }
+    column
+}
+
+/// Reads files at most once while formatting a result.
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    fn line(&mut self, path: &Path, line: u64) -> Option<&str> {
+        let lines = self.files.entry(path.to_path_buf()).or_insert_with(|| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|text| text.lines().map(str::to_string).collect())
+        });
+        let index = usize::try_from(line).ok()?;
+        lines.as_ref()?.get(index).map(String::as_str)
+    }
+}
+
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+/// `path:line:col` for a location, with the line of source it points at.
+fn format_location(uri: &str, range: &Value, cwd: &Path, sources: &mut SourceCache) -> String {
+    let Some(path) = uri_to_path(uri) else {
+        return uri.to_string();
+    };
+    let line = range
+        .pointer("/start/line")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let character = range
+        .pointer("/start/character")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let display = display_path(&path, cwd);
+    match sources.line(&path, line) {
+        Some(source) => {
+            let column = character_column(source, character);
+            format!("{display}:{}:{column}: {}", line + 1, source.trim())
+        }
+        None => format!("{display}:{}:{}", line + 1, character + 1),
+    }
+}
+
+/// Formats a `Location`, `Location[]` or `LocationLink[]` result, one per line.
+pub(crate) fn format_locations(result: &Value, cwd: &Path) -> String {
+    let items = match result {
+        Value::Array(items) => items.clone(),
+        Value::Null => Vec::new(),
+        single => vec![single.clone()],
+    };
+    let mut sources = SourceCache::default();
+    let lines: Vec<String> = items
+        .iter()
+        .filter_map(|item| {
+            if let Some(uri) = item.get("targetUri").and_then(Value::as_str) {
+                let range = item
+                    .get("targetSelectionRange")
+                    .or_else(|| item.get("targetRange"))?;
+                Some(format_location(uri, range, cwd, &mut sources))
+            } else {
+                let uri = item.get("uri").and_then(Value::as_str)?;
+                Some(format_location(uri, item.get("range")?, cwd, &mut sources))
+            }
+        })
+        .collect();
+    if lines.is_empty() {
+        "No locations found.".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Formats the contents of a `Hover` result.
+pub(crate) fn format_hover(result: &Value) -> String {
+    fn marked(value: &Value) -> Option<String> {
+        match value {
+            Value::String(text) => Some(text.clone()),
+            Value::Object(object) => {
+                let text = object.get("value")?.as_str()?;
+                match object.get("language").and_then(Value::as_str) {
+                    Some(language) => Some(format!("```{language}\n{text}\n```")),
+                    None => Some(text.to_string()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    let text = match result.get("contents") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(marked)
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Some(contents) => marked(contents).unwrap_or_default(),
+        None => String::new(),
+    };
+    if text.trim().is_empty() {
+        "No hover information.".to_string()
+    } else {
+        text.trim().to_string()
+    }
+}
+
+fn symbol_kind(kind: u64) -> &'static str {
+    match kind {
+        1 => "file",
+        2 => "module",
+        3 => "namespace",
+        4 => "package",
+        5 => "class",
+        6 => "method",
+        7 => "property",
+        8 => "field",
+        9 => "constructor",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        15 => "string",
+        16 => "number",
+        17 => "boolean",
+        18 => "array",
+        19 => "object",
+        20 => "key",
+        21 => "null",
+        22 => "enum member",
+        23 => "struct",
+        24 => "event",
+        25 => "operator",
+        26 => "type parameter",
+        _ => "symbol",
+    }
+}
+
+/// Formats a `SymbolInformation[]` or `WorkspaceSymbol[]` result, one per line.
+pub(crate) fn format_symbols(result: &Value, cwd: &Path) -> Vec<String> {
+    let Some(symbols) = result.as_array() else {
+        return Vec::new();
+    };
+    let mut sources = SourceCache::default();
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let name = symbol.get("name")?.as_str()?;
+            let kind = symbol_kind(symbol.get("kind").and_then(Value::as_u64).unwrap_or(0));
+            let container = symbol
+                .get("containerName")
+                .and_then(Value::as_str)
+                .filter(|container| !container.is_empty())
+                .map(|container| format!(" in {container}"))
+                .unwrap_or_default();
+            let location = symbol.get("location")?;
+            let uri = location.get("uri")?.as_str()?;
+            let place = match location.get("range") {
+                Some(range) => format_location(uri, range, cwd, &mut sources),
+                // `WorkspaceSymbol` may leave the range to be resolved later.
+                None => uri_to_path(uri)
+                    .map(|path| display_path(&path, cwd))
+                    .unwrap_or_else(|| uri.to_string()),
+            };
+            Some(format!("{kind} {name}{container} — {place}"))
+        })
+        .collect()
+}
+
+/// Formats published diagnostics for `path`, one per line.
+pub(crate) fn format_diagnostics(path: &Path, diagnostics: &[Value], cwd: &Path) -> Vec<String> {
+    let mut sources = SourceCache::default();
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            let message = diagnostic.get("message")?.as_str()?;
+            let line = diagnostic
+                .pointer("/range/start/line")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            let character = diagnostic
+                .pointer("/range/start/character")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            let column = sources
+                .line(path, line)
+                .map(|source| character_column(source, character))
+                .unwrap_or_else(|| usize::try_from(character).unwrap_or(0) + 1);
+            let severity = match diagnostic.get("severity").and_then(Value::as_u64) {
+                Some(1) => "error",
+                Some(2) => "warning",
+                Some(3) => "info",
+                Some(4) => "hint",
+                _ => "diagnostic",
+            };
+            let source = diagnostic.get("source").and_then(Value::as_str);
+            let code = diagnostic.get("code").and_then(|code| match code {
+                Value::String(code) => Some(code.clone()),
+                Value::Number(code) => Some(code.to_string()),
+                _ => None,
+            });
+            let origin = match (source, code) {
+                (Some(source), Some(code)) => format!(" [{source} {code}]"),
+                (Some(source), None) => format!(" [{source}]"),
+                (None, Some(code)) => format!(" [{code}]"),
+                (None, None) => String::new(),
+            };
+            let message = message.lines().next().unwrap_or_default();
+            Some(format!(
+                "{}:{}:{column}: {severity}{origin}: {message}",
+                display_path(path, cwd),
+                line + 1
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn positions_convert_between_characters_and_utf16() {
+        let text = "fn main() {}\nlet s = \"😀\"; s.len()\n";
+        assert_eq!(
+            lsp_position(text, 2, 10),
+            json!({ "line": 1, "character": 9 })
+        );
+        // The emoji is two UTF-16 code units but one character.
+        assert_eq!(
+            lsp_position(text, 2, 14),
+            json!({ "line": 1, "character": 14 })
+        );
+        assert_eq!(character_column("let s = \"😀\"; s.len()", 14), 14);
+        assert_eq!(
+            lsp_position(text, 9, 3),
+            json!({ "line": 8, "character": 0 })
+        );
+    }
+
+    #[test]
+    fn formats_locations_with_source_lines() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("src").join("lib.rs");
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&path, "mod a;\n    pub fn answer() -> u32 { 42 }\n").expect("write");
+        let uri = url::Url::from_file_path(&path).expect("uri").to_string();
+
+        let location = json!({
+            "uri": uri,
+            "range": { "start": { "line": 1, "character": 11 }, "end": { "line": 1, "character": 17 } },
+        });
+        let link = json!([{
+            "targetUri": uri,
+            "targetRange": { "start": { "line": 1, "character": 4 } },
+            "targetSelectionRange": { "start": { "line": 1, "character": 11 } },
+        }]);
+
+        let expected = "src/lib.rs:2:12: pub fn answer() -> u32 { 42 }";
+        assert_eq!(format_locations(&location, dir.path()), expected);
+        assert_eq!(format_locations(&link, dir.path()), expected);
+        assert_eq!(
+            format_locations(&Value::Null, dir.path()),
+            "No locations found."
+        );
+    }
+
+    #[test]
+    fn formats_hover_and_diagnostics() {
+        let hover = json!({
+            "contents": [{ "language": "rust", "value": "pub fn answer() -> u32" }, "Returns 42."],
+        });
+        assert_eq!(
+            format_hover(&hover),
+            "```rust\npub fn answer() -> u32\n```\n\nReturns 42."
+        );
+        assert_eq!(
+            format_hover(&json!({ "contents": { "kind": "markdown", "value": "" } })),
+            "No hover information."
+        );
+
+        let cwd = Path::new("/repo");
+        let diagnostics = [json!({
+            "range": { "start": { "line": 3, "character": 8 } },
+            "severity": 1,
+            "source": "rustc",
+            "code": "E0308",
+            "message": "mismatched types\nexpected `u32`",
+        })];
+        assert_eq!(
+            format_diagnostics(Path::new("/repo/src/lib.rs"), &diagnostics, cwd),
+            vec!["src/lib.rs:4:9: error [rustc E0308]: mismatched types".to_string()]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Language servers backing the code navigation tools.
+//!
+//! Servers are started lazily, one per configured server and workspace root, the first time a
+//! tool asks about a file they handle and once the caller has approved the start. They live as
+//! long as the session and are shut down with it.
+
+mod client;
+pub(crate) mod format;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+
+use crate::config::types::LspServerConfig;
+use crate::git_info::get_git_repo_root;
+
+pub(crate) use client::LspClient;
+pub(crate) use client::uri_to_path;
+
+/// A client that is running or still being started. Callers wait on the cell rather than on
+/// the map lock, so a slow `initialize` holds up only the requests for that server.
+type ClientSlot = Arc<OnceCell<Arc<LspClient>>>;
+
+pub(crate) struct LspManager {
+    servers: BTreeMap<String, LspServerConfig>,
+    clients: Mutex<HashMap<(String, PathBuf), ClientSlot>>,
+}
+
+impl LspManager {
+    pub(crate) fn new(servers: BTreeMap<String, LspServerConfig>) -> Self {
+        Self {
+            servers,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The enabled server that handles files like `path`.
+    fn server_for(&self, path: &Path) -> Option<(&str, &LspServerConfig)> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        self.servers
+            .iter()
+            .filter(|(_, config)| config.enabled)
+            .find(|(_, config)| {
+                config
+                    .extensions
+                    .iter()
+                    .any(|candidate| candidate.trim_start_matches('.') == extension)
+            })
+            .map(|(name, config)| (name.as_str(), config))
+    }
+
+    /// The running client for `path`. When its server has to be started, `approve_start` is
+    /// called first with the server's command line and workspace root, and the server is only
+    /// started if it returns `Ok`.
+    pub(crate) async fn client_for<F, Fut>(
+        &self,
+        path: &Path,
+        approve_start: F,
+    ) -> Result<Arc<LspClient>, String>
+    where
+        F: FnOnce(Vec<String>, PathBuf) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let Some((name, config)) = self.server_for(path) else {
+            return Err(format!(
+                "no language server is configured for {}; add one under [lsp_servers]",
+                path.display()
+            ));
+        };
+        let base = path.parent().unwrap_or(path);
+        let root = get_git_repo_root(base).unwrap_or_else(|| base.to_path_buf());
+        let key = (name.to_string(), root);
+
+        let (slot, dead) = {
+            let mut clients = self.clients.lock().await;
+            let slot = clients.entry(key.clone()).or_default();
+            // A server that died is replaced by a fresh slot, so the next caller starts it again.
+            let dead = slot.get().filter(|client| !client.is_running()).cloned();
+            if dead.is_some() {
+                *slot = ClientSlot::default();
+            }
+            (Arc::clone(slot), dead)
+        };
+        if let Some(dead) = dead {
+            dead.shutdown().await;
+        }
+
+        let root = key.1;
+        slot.get_or_try_init(|| async {
+            let mut command = vec![config.command.clone()];
+            command.extend(config.args.iter().cloned());
+            approve_start(command, root.clone())
+                .await
+                .map_err(|err| format!("{name} was not started: {err}"))?;
+            let client = LspClient::start(name, config, &root)
+                .await
+                .map_err(|err| format!("{err}; configure it under [lsp_servers.{name}]"))?;
+            Ok::<_, String>(Arc::new(client))
+        })
+        .await
+        .cloned()
+    }
+
+    /// Clients that are currently running, for requests not tied to a file.
+    pub(crate) async fn running_clients(&self) -> Vec<Arc<LspClient>> {
+        let clients = self.clients.lock().await;
+        let mut running: Vec<Arc<LspClient>> = clients
+            .values()
+            .filter_map(|slot| slot.get())
+            .filter(|client| client.is_running())
+            .cloned()
+            .collect();
+        running.sort_by(|a, b| a.name().cmp(b.name()).then_with(|| a.root().cmp(b.root())));
+        running
+    }
+
+    pub(crate) async fn shutdown(&self) {
+        let clients: Vec<Arc<LspClient>> = self
+            .clients
+            .lock()
+            .await
+            .drain()
+            .filter_map(|(_, slot)| slot.get().cloned())
+            .collect();
+        for client in clients {
+            client.shutdown().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_enabled_server_by_extension() {
+        let mut servers = LspServerConfig::builtins();
+        if let Some(pyright) = servers.get_mut("pyright") {
+            pyright.enabled = false;
+        }
+        let manager = LspManager::new(servers);
+
+        let name = |path: &str| manager.server_for(Path::new(path)).map(|(name, _)| name);
+        assert_eq!(name("/repo/src/lib.rs"), Some("rust-analyzer"));
+        assert_eq!(name("/repo/web/App.TSX"), Some("typescript"));
+        assert_eq!(name("/repo/tool.py"), None);
+        assert_eq!(name("/repo/README"), None);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod auth;
+pub(crate) mod prompts;
+pub(crate) mod sampling;
+pub(crate) mod server_changes;
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
@@ -23,6 +26,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tools: HashMap::new(),
             resources: HashMap::new(),
             resource_templates: HashMap::new(),
+            prompts: HashMap::new(),
             auth_statuses: HashMap::new(),
         };
     }
@@ -53,6 +57,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tx_event,
             cancel_token.clone(),
             sandbox_state,
+            None,
         )
         .await;
 
@@ -97,10 +102,11 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
     mcp_connection_manager: &McpConnectionManager,
     auth_status_entries: HashMap<String, crate::mcp::auth::McpAuthStatusEntry>,
 ) -> McpListToolsResponseEvent {
-    let (tools, resources, resource_templates) = tokio::join!(
+    let (tools, resources, resource_templates, prompts) = tokio::join!(
         mcp_connection_manager.list_all_tools(),
         mcp_connection_manager.list_all_resources(),
         mcp_connection_manager.list_all_resource_templates(),
+        mcp_connection_manager.list_all_prompts(),
     );
 
     let auth_statuses = auth_status_entries
@@ -115,6 +121,7 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
             .collect(),
         resources,
         resource_templates,
+        prompts,
         auth_statuses,
     }
 }
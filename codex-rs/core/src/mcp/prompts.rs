@@ -0,0 +1,178 @@
+//! Turning the messages of an MCP `prompts/get` result into turn input.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::PromptMessage;
+use mcp_types::Role;
+
+/// Input for the turn started by an MCP prompt.
+#[derive(Debug, PartialEq)]
+pub(crate) struct McpPromptInput {
+    /// Messages before the trailing user messages, recorded as prior
+    /// conversation so that few-shot prompts keep their assistant turns.
+    pub history: Vec<ResponseItem>,
+    /// The trailing user messages, submitted as the user's input.
+    pub items: Vec<UserInput>,
+}
+
+pub(crate) fn mcp_prompt_input(mut messages: Vec<PromptMessage>) -> Result<McpPromptInput, String> {
+    let split = messages
+        .iter()
+        .rposition(|message| matches!(message.role, Role::Assistant))
+        .map_or(0, |index| index + 1);
+    if split == messages.len() {
+        return Err("MCP prompt did not end with a user message".to_string());
+    }
+
+    let trailing = messages.split_off(split);
+    let history = messages
+        .into_iter()
+        .map(|message| {
+            let content = match message.role {
+                Role::User => match image_url(&message.content) {
+                    Some(image_url) => ContentItem::InputImage { image_url },
+                    None => ContentItem::InputText {
+                        text: content_text(message.content),
+                    },
+                },
+                Role::Assistant => ContentItem::OutputText {
+                    text: content_text(message.content),
+                },
+            };
+            let role = match message.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+            };
+            ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+            }
+        })
+        .collect();
+    let items = trailing
+        .into_iter()
+        .map(|message| match image_url(&message.content) {
+            Some(image_url) => UserInput::Image { image_url },
+            None => UserInput::Text {
+                text: content_text(message.content),
+            },
+        })
+        .collect();
+
+    Ok(McpPromptInput { history, items })
+}
+
+fn image_url(content: &ContentBlock) -> Option<String> {
+    match content {
+        ContentBlock::ImageContent(image) => {
+            Some(format!("data:{};base64,{}", image.mime_type, image.data))
+        }
+        _ => None,
+    }
+}
+
+/// Text for content blocks that are not sent as images. Resources are inlined
+/// with their URI so the model can tell where the text came from.
+fn content_text(content: ContentBlock) -> String {
+    match content {
+        ContentBlock::TextContent(text) => text.text,
+        ContentBlock::ImageContent(image) => format!("[image: {}]", image.mime_type),
+        ContentBlock::AudioContent(audio) => format!("[audio: {}]", audio.mime_type),
+        ContentBlock::ResourceLink(link) => format!("[resource: {} ({})]", link.name, link.uri),
+        ContentBlock::EmbeddedResource(embedded) => match embedded.resource {
+            EmbeddedResourceResource::TextResourceContents(resource) => {
+                format!(
+                    "<resource uri=\"{}\">\n{}\n</resource>",
+                    resource.uri, resource.text
+                )
+            }
+            EmbeddedResourceResource::BlobResourceContents(resource) => {
+                format!("[resource: {}]", resource.uri)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::ImageContent;
+    use mcp_types::TextContent;
+    use pretty_assertions::assert_eq;
+
+    fn text(role: Role, text: &str) -> PromptMessage {
+        PromptMessage {
+            content: ContentBlock::TextContent(TextContent {
+                annotations: None,
+                text: text.to_string(),
+                r#type: "text".to_string(),
+            }),
+            role,
+        }
+    }
+
+    #[test]
+    fn trailing_user_messages_become_input() {
+        let image = PromptMessage {
+            content: ContentBlock::ImageContent(ImageContent {
+                annotations: None,
+                data: "AAAA".to_string(),
+                mime_type: "image/png".to_string(),
+                r#type: "image".to_string(),
+            }),
+            role: Role::User,
+        };
+        let messages = vec![
+            text(Role::User, "Summarize: the sky is blue."),
+            text(Role::Assistant, "Sky: blue."),
+            text(Role::User, "Summarize the attached diagram."),
+            image,
+        ];
+
+        assert_eq!(
+            mcp_prompt_input(messages),
+            Ok(McpPromptInput {
+                history: vec![
+                    ResponseItem::Message {
+                        id: None,
+                        role: "user".to_string(),
+                        content: vec![ContentItem::InputText {
+                            text: "Summarize: the sky is blue.".to_string(),
+                        }],
+                    },
+                    ResponseItem::Message {
+                        id: None,
+                        role: "assistant".to_string(),
+                        content: vec![ContentItem::OutputText {
+                            text: "Sky: blue.".to_string(),
+                        }],
+                    },
+                ],
+                items: vec![
+                    UserInput::Text {
+                        text: "Summarize the attached diagram.".to_string(),
+                    },
+                    UserInput::Image {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_prompts_without_trailing_user_message() {
+        assert_eq!(
+            mcp_prompt_input(vec![text(Role::User, "hi"), text(Role::Assistant, "hello")]),
+            Err("MCP prompt did not end with a user message".to_string())
+        );
+        assert_eq!(
+            mcp_prompt_input(Vec::new()),
+            Err("MCP prompt did not end with a user message".to_string())
+        );
+    }
+}
@@ -0,0 +1,440 @@
+//! MCP sampling: servers ask Codex to run a completion with the session's
+//! model through `sampling/createMessage`. Every request needs the user's
+//! approval, is charged to the server's token budget, and is recorded in the
+//! rollout as an `McpSampling` event. A request's output is capped at what
+//! is left of the budget, which is reserved while the request runs.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpSamplingEvent;
+use codex_protocol::protocol::McpSamplingStatus;
+use codex_rmcp_client::ElicitationAction;
+use futures::prelude::*;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::RequestId;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::DEFAULT_MCP_SAMPLING_TOKEN_BUDGET;
+use crate::config::types::McpServerConfig;
+
+/// Longest excerpt of the request shown in the approval prompt.
+const APPROVAL_EXCERPT_CHARS: usize = 500;
+
+pub(crate) fn sampling_token_budget(config: &McpServerConfig) -> u64 {
+    config
+        .sampling_token_budget
+        .unwrap_or(DEFAULT_MCP_SAMPLING_TOKEN_BUDGET)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SamplingBudget {
+    limit: u64,
+    used: u64,
+}
+
+impl SamplingBudget {
+    fn remaining(self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+/// Runs the sampling requests of one session's MCP servers.
+pub(crate) struct McpSampler {
+    session: Weak<Session>,
+    budgets: Mutex<HashMap<String, SamplingBudget>>,
+}
+
+impl McpSampler {
+    pub(crate) fn new(
+        session: Weak<Session>,
+        mcp_servers: &HashMap<String, McpServerConfig>,
+    ) -> Self {
+        let budgets = mcp_servers
+            .iter()
+            .map(|(name, config)| {
+                let budget = SamplingBudget {
+                    limit: sampling_token_budget(config),
+                    used: 0,
+                };
+                (name.clone(), budget)
+            })
+            .collect();
+        Self {
+            session,
+            budgets: Mutex::new(budgets),
+        }
+    }
+
+    fn remaining(&self, server_name: &str) -> u64 {
+        self.budgets
+            .lock()
+            .ok()
+            .and_then(|budgets| budgets.get(server_name).map(|budget| budget.remaining()))
+            .unwrap_or(0)
+    }
+
+    /// Reserves up to `max_tokens` of the server's budget for one request and
+    /// returns how many were reserved, which is 0 once the budget is used up.
+    fn reserve(&self, server_name: &str, max_tokens: u64) -> u64 {
+        let Ok(mut budgets) = self.budgets.lock() else {
+            return 0;
+        };
+        match budgets.get_mut(server_name) {
+            Some(budget) => {
+                let reserved = max_tokens.min(budget.remaining());
+                budget.used = budget.used.saturating_add(reserved);
+                reserved
+            }
+            None => 0,
+        }
+    }
+
+    /// Replaces a reservation with the `tokens` the request actually used and
+    /// returns what is left of the server's budget.
+    fn settle(&self, server_name: &str, reserved: u64, tokens: u64) -> u64 {
+        let Ok(mut budgets) = self.budgets.lock() else {
+            return 0;
+        };
+        match budgets.get_mut(server_name) {
+            Some(budget) => {
+                budget.used = budget.used.saturating_sub(reserved).saturating_add(tokens);
+                budget.remaining()
+            }
+            None => 0,
+        }
+    }
+
+    pub(crate) async fn create_message(
+        &self,
+        server_name: String,
+        id: RequestId,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult> {
+        let session = self
+            .session
+            .upgrade()
+            .ok_or_else(|| anyhow!("session has ended"))?;
+        let turn_context = session.new_default_turn().await;
+        let audit = |status, model, tokens_used, budget_remaining| {
+            EventMsg::McpSampling(McpSamplingEvent {
+                server: server_name.clone(),
+                status,
+                model,
+                tokens_used,
+                budget_remaining,
+            })
+        };
+
+        let max_tokens = u64::try_from(params.max_tokens)
+            .ok()
+            .filter(|max_tokens| *max_tokens > 0)
+            .ok_or_else(|| anyhow!("max_tokens must be positive"))?;
+        let remaining = self.remaining(&server_name);
+        let reserved = self.reserve(&server_name, max_tokens);
+        if reserved == 0 {
+            let event = audit(McpSamplingStatus::BudgetExhausted, None, 0, 0);
+            session.send_event(&turn_context, event).await;
+            bail!("sampling token budget for MCP server `{server_name}` is used up");
+        }
+
+        // There is nobody to ask when approvals are off, so decline.
+        let approved = if matches!(turn_context.approval_policy, AskForApproval::Never) {
+            false
+        } else {
+            let message = sampling_approval_message(&server_name, &params, reserved, remaining);
+            let request = session
+                .services
+                .mcp_connection_manager
+                .read()
+                .await
+                .request_elicitation(server_name.clone(), id, message, session.get_tx_event());
+            match request.await {
+                Ok(response) => matches!(response.action, ElicitationAction::Accept),
+                Err(err) => {
+                    self.settle(&server_name, reserved, 0);
+                    return Err(err);
+                }
+            }
+        };
+        if !approved {
+            let remaining = self.settle(&server_name, reserved, 0);
+            let event = audit(McpSamplingStatus::Declined, None, 0, remaining);
+            session.send_event(&turn_context, event).await;
+            bail!("user declined the sampling request");
+        }
+
+        let model = turn_context.client.get_model();
+        let prompt = Prompt {
+            input: sampling_input(params.messages),
+            base_instructions_override: params.system_prompt,
+            max_output_tokens: Some(reserved),
+            ..Default::default()
+        };
+        match run_sampling(&turn_context, &prompt).await {
+            Ok((text, tokens_used)) => {
+                let remaining = self.settle(&server_name, reserved, tokens_used);
+                let event = audit(
+                    McpSamplingStatus::Completed,
+                    Some(model.clone()),
+                    tokens_used,
+                    remaining,
+                );
+                session.send_event(&turn_context, event).await;
+                Ok(CreateMessageResult {
+                    content: CreateMessageResultContent::TextContent(TextContent {
+                        annotations: None,
+                        text,
+                        r#type: "text".to_string(),
+                    }),
+                    model,
+                    role: Role::Assistant,
+                    stop_reason: Some("endTurn".to_string()),
+                })
+            }
+            Err(err) => {
+                let remaining = self.settle(&server_name, reserved, 0);
+                let status = McpSamplingStatus::Failed {
+                    error: err.to_string(),
+                };
+                let event = audit(status, Some(model), 0, remaining);
+                session.send_event(&turn_context, event).await;
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Streams the completion and returns its text with the tokens it used.
+async fn run_sampling(turn_context: &TurnContext, prompt: &Prompt) -> Result<(String, u64)> {
+    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut text = String::new();
+    loop {
+        let Some(event) = stream.next().await else {
+            bail!("stream closed before response.completed");
+        };
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" =>
+            {
+                for item in content {
+                    if let ContentItem::OutputText { text: chunk } = item {
+                        text.push_str(&chunk);
+                    }
+                }
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                let tokens_used = token_usage
+                    .and_then(|usage| u64::try_from(usage.total_tokens).ok())
+                    .unwrap_or_default();
+                return Ok((text, tokens_used));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sampling_input(messages: Vec<SamplingMessage>) -> Vec<ResponseItem> {
+    messages
+        .into_iter()
+        .map(|message| {
+            let (role, content) = match (message.role, message.content) {
+                (Role::User, SamplingMessageContent::ImageContent(image)) => (
+                    "user",
+                    ContentItem::InputImage {
+                        image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+                    },
+                ),
+                (Role::User, content) => (
+                    "user",
+                    ContentItem::InputText {
+                        text: content_text(&content),
+                    },
+                ),
+                (Role::Assistant, content) => (
+                    "assistant",
+                    ContentItem::OutputText {
+                        text: content_text(&content),
+                    },
+                ),
+            };
+            ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+            }
+        })
+        .collect()
+}
+
+fn content_text(content: &SamplingMessageContent) -> String {
+    match content {
+        SamplingMessageContent::TextContent(text) => text.text.clone(),
+        SamplingMessageContent::ImageContent(image) => format!("[image: {}]", image.mime_type),
+        SamplingMessageContent::AudioContent(audio) => format!("[audio: {}]", audio.mime_type),
+    }
+}
+
+fn sampling_approval_message(
+    server_name: &str,
+    params: &CreateMessageRequestParams,
+    max_tokens: u64,
+    remaining: u64,
+) -> String {
+    let mut sections = vec![format!(
+        "{server_name} wants to run a completion with your model \
+         (up to {max_tokens} output tokens, {remaining} tokens left in its budget)."
+    )];
+    if let Some(system_prompt) = &params.system_prompt {
+        sections.push(format!("System prompt: {}", excerpt(system_prompt)));
+    }
+    if let Some(last) = params.messages.last() {
+        sections.push(format!(
+            "Last message: {}",
+            excerpt(&content_text(&last.content))
+        ));
+    }
+    sections.join("\n\n")
+}
+
+fn excerpt(text: &str) -> String {
+    match text.char_indices().nth(APPROVAL_EXCERPT_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::ImageContent;
+    use pretty_assertions::assert_eq;
+
+    fn text(role: Role, text: &str) -> SamplingMessage {
+        SamplingMessage {
+            content: SamplingMessageContent::TextContent(TextContent {
+                annotations: None,
+                text: text.to_string(),
+                r#type: "text".to_string(),
+            }),
+            role,
+        }
+    }
+
+    #[test]
+    fn sampling_messages_become_model_input() {
+        let image = SamplingMessage {
+            content: SamplingMessageContent::ImageContent(ImageContent {
+                annotations: None,
+                data: "AAAA".to_string(),
+                mime_type: "image/png".to_string(),
+                r#type: "image".to_string(),
+            }),
+            role: Role::User,
+        };
+
+        assert_eq!(
+            sampling_input(vec![
+                text(Role::User, "Name this color."),
+                text(Role::Assistant, "Blue."),
+                image,
+            ]),
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "Name this color.".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "Blue.".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn approval_message_describes_request() {
+        let params = CreateMessageRequestParams {
+            include_context: None,
+            max_tokens: 200,
+            messages: vec![text(Role::User, "Summarize the open issues.")],
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: Some("You triage issues.".to_string()),
+            temperature: None,
+        };
+
+        assert_eq!(
+            sampling_approval_message("tracker", &params, 200, 1_000),
+            "tracker wants to run a completion with your model \
+             (up to 200 output tokens, 1000 tokens left in its budget).\n\n\
+             System prompt: You triage issues.\n\n\
+             Last message: Summarize the open issues."
+        );
+    }
+
+    #[test]
+    fn budget_is_tracked_per_server() {
+        let mut config: McpServerConfig = toml::from_str("command = \"echo\"").expect("config");
+        config.sampling_token_budget = Some(100);
+        let servers = HashMap::from([
+            ("docs".to_string(), config.clone()),
+            ("tracker".to_string(), config),
+        ]);
+        let sampler = McpSampler::new(Weak::new(), &servers);
+
+        assert_eq!(sampler.reserve("docs", 60), 60);
+        assert_eq!(sampler.settle("docs", 60, 60), 40);
+        assert_eq!(sampler.remaining("tracker"), 100);
+        assert_eq!(sampler.reserve("unknown", 60), 0);
+        assert_eq!(sampler.remaining("unknown"), 0);
+    }
+
+    #[test]
+    fn requests_are_capped_at_the_remaining_budget() {
+        let mut config: McpServerConfig = toml::from_str("command = \"echo\"").expect("config");
+        config.sampling_token_budget = Some(100);
+        let servers = HashMap::from([("docs".to_string(), config)]);
+        let sampler = McpSampler::new(Weak::new(), &servers);
+
+        // A request for more than the budget gets what is left, and holds it
+        // until it completes.
+        assert_eq!(sampler.reserve("docs", 500), 100);
+        assert_eq!(sampler.reserve("docs", 10), 0);
+
+        // The unused part of the reservation is returned.
+        assert_eq!(sampler.settle("docs", 100, 30), 70);
+        assert_eq!(sampler.reserve("docs", 500), 70);
+    }
+}
@@ -0,0 +1,132 @@
+//! Change notifications from MCP servers. They arrive while a turn runs and
+//! are applied before the next model request: tool lists are fetched again and
+//! resource changes are passed on to the model as a short note.
+
+use std::collections::BTreeSet;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_rmcp_client::ServerChange;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ResourceChange {
+    ListChanged { server: String },
+    Updated { server: String, uri: String },
+}
+
+/// Notifications received since the last model request.
+#[derive(Debug, Default)]
+pub(crate) struct PendingServerChanges {
+    /// Servers whose tool list changed.
+    pub tools: BTreeSet<String>,
+    pub resources: Vec<ResourceChange>,
+}
+
+impl PendingServerChanges {
+    pub(crate) fn record(&mut self, server: &str, change: ServerChange) {
+        let change = match change {
+            ServerChange::ToolListChanged => {
+                self.tools.insert(server.to_string());
+                return;
+            }
+            ServerChange::ResourceListChanged => ResourceChange::ListChanged {
+                server: server.to_string(),
+            },
+            ServerChange::ResourceUpdated { uri } => ResourceChange::Updated {
+                server: server.to_string(),
+                uri,
+            },
+        };
+        // Servers may repeat a notification before the model gets to see it.
+        if !self.resources.contains(&change) {
+            self.resources.push(change);
+        }
+    }
+}
+
+/// The note telling the model which MCP resources changed.
+pub(crate) fn resource_changes_message(changes: &[ResourceChange]) -> Option<ResponseItem> {
+    if changes.is_empty() {
+        return None;
+    }
+    let mut lines = vec![
+        "<mcp_resource_changes>".to_string(),
+        "These MCP resources changed since you last saw them. Use read_mcp_resource or list_mcp_resources if you need the new contents.".to_string(),
+    ];
+    lines.extend(changes.iter().map(|change| match change {
+        ResourceChange::ListChanged { server } => format!("- {server}: resource list changed"),
+        ResourceChange::Updated { server, uri } => format!("- {server}: {uri} updated"),
+    }));
+    lines.push("</mcp_resource_changes>".to_string());
+
+    Some(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: lines.join("\n"),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn records_each_change_once() {
+        let mut pending = PendingServerChanges::default();
+        let updated = || ServerChange::ResourceUpdated {
+            uri: "file:///notes.md".to_string(),
+        };
+
+        pending.record("docs", updated());
+        pending.record("docs", updated());
+        pending.record("docs", ServerChange::ToolListChanged);
+        pending.record("tracker", ServerChange::ResourceListChanged);
+
+        assert_eq!(pending.tools, BTreeSet::from(["docs".to_string()]));
+        assert_eq!(
+            pending.resources,
+            vec![
+                ResourceChange::Updated {
+                    server: "docs".to_string(),
+                    uri: "file:///notes.md".to_string(),
+                },
+                ResourceChange::ListChanged {
+                    server: "tracker".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_changes_become_a_note() {
+        assert_eq!(resource_changes_message(&[]), None);
+
+        let message = resource_changes_message(&[
+            ResourceChange::Updated {
+                server: "docs".to_string(),
+                uri: "file:///notes.md".to_string(),
+            },
+            ResourceChange::ListChanged {
+                server: "tracker".to_string(),
+            },
+        ]);
+        assert_eq!(
+            message,
+            Some(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "<mcp_resource_changes>\n\
+                           These MCP resources changed since you last saw them. Use read_mcp_resource or list_mcp_resources if you need the new contents.\n\
+                           - docs: file:///notes.md updated\n\
+                           - tracker: resource list changed\n\
+                           </mcp_resource_changes>"
+                        .to_string(),
+                }],
+            })
+        );
+    }
+}
@@ -31,22 +31,31 @@ use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::OnServerChange;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
 use mcp_types::ClientCapabilities;
+use mcp_types::ClientCapabilitiesRoots;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::Implementation;
+use mcp_types::ListPromptsRequestParams;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
+use mcp_types::Prompt;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
 use mcp_types::Resource;
 use mcp_types::ResourceTemplate;
+use mcp_types::Root;
+use mcp_types::SubscribeRequestParams;
 use mcp_types::Tool;
 
 use serde::Deserialize;
@@ -64,6 +73,10 @@ use tracing::warn;
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::mcp::sampling::McpSampler;
+use crate::mcp::sampling::sampling_token_budget;
+use crate::mcp::server_changes::PendingServerChanges;
+use crate::mcp::server_changes::ResourceChange;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -178,48 +191,67 @@ impl ElicitationRequestManager {
             .map_err(|e| anyhow!("failed to send elicitation response: {e:?}"))
     }
 
+    /// Shows `message` to the user and waits for their answer.
+    async fn request(
+        self,
+        server_name: String,
+        id: RequestId,
+        message: String,
+        tx_event: Sender<Event>,
+    ) -> Result<ElicitationResponse> {
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut lock = self.requests.lock().await;
+            lock.insert((server_name.clone(), id.clone()), tx);
+        }
+        let _ = tx_event
+            .send(Event {
+                id: "mcp_elicitation_request".to_string(),
+                msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                    server_name,
+                    id,
+                    message,
+                }),
+            })
+            .await;
+        rx.await
+            .context("elicitation request channel closed unexpectedly")
+    }
+
     fn make_sender(&self, server_name: String, tx_event: Sender<Event>) -> SendElicitation {
-        let elicitation_requests = self.requests.clone();
+        let elicitation_requests = self.clone();
         Box::new(move |id, elicitation| {
-            let elicitation_requests = elicitation_requests.clone();
-            let tx_event = tx_event.clone();
-            let server_name = server_name.clone();
-            async move {
-                let (tx, rx) = oneshot::channel();
-                {
-                    let mut lock = elicitation_requests.lock().await;
-                    lock.insert((server_name.clone(), id.clone()), tx);
-                }
-                let _ = tx_event
-                    .send(Event {
-                        id: "mcp_elicitation_request".to_string(),
-                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
-                            server_name,
-                            id,
-                            message: elicitation.message,
-                        }),
-                    })
-                    .await;
-                rx.await
-                    .context("elicitation request channel closed unexpectedly")
-            }
-            .boxed()
+            elicitation_requests
+                .clone()
+                .request(
+                    server_name.clone(),
+                    id,
+                    elicitation.message,
+                    tx_event.clone(),
+                )
+                .boxed()
         })
     }
 }
 
+type SharedServerChanges = Arc<std::sync::Mutex<PendingServerChanges>>;
+
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
-    tools: Vec<ToolInfo>,
+    /// Fetched again when the server reports that its tool list changed.
+    tools: Arc<Mutex<Vec<ToolInfo>>>,
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
+    server_supports_prompts: bool,
+    server_supports_resource_subscriptions: bool,
     server_supports_sandbox_state_capability: bool,
 }
 
 impl ManagedClient {
     /// Returns once the server has ack'd the sandbox state update.
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        self.client.set_roots(mcp_roots(sandbox_state)).await?;
         if !self.server_supports_sandbox_state_capability {
             return Ok(());
         }
@@ -248,8 +280,13 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampler: Option<Arc<McpSampler>>,
+        roots: Vec<Root>,
+        server_changes: SharedServerChanges,
     ) -> Self {
         let tool_filter = ToolFilter::from_config(&config);
+        // A zero budget turns sampling off for the server.
+        let sampler = sampler.filter(|_| sampling_token_budget(&config) > 0);
         let fut = async move {
             if let Err(error) = validate_mcp_server_name(&server_name) {
                 return Err(error.into());
@@ -257,6 +294,7 @@ impl AsyncManagedClient {
 
             let client =
                 Arc::new(make_rmcp_client(&server_name, config.transport, store_mode).await?);
+            client.set_roots(roots).await?;
             match start_server_task(
                 server_name,
                 client,
@@ -265,6 +303,8 @@ impl AsyncManagedClient {
                 tool_filter,
                 tx_event,
                 elicitation_requests,
+                sampler,
+                server_changes,
             )
             .or_cancel(&cancel_token)
             .await
@@ -302,11 +342,39 @@ pub struct SandboxState {
     pub sandbox_cwd: PathBuf,
 }
 
+/// Roots exposed to MCP servers through `roots/list`: the session's working
+/// directory plus any extra writable roots (e.g. from `--add-dir`).
+fn mcp_roots(sandbox_state: &SandboxState) -> Vec<Root> {
+    let mut paths = vec![sandbox_state.sandbox_cwd.clone()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &sandbox_state.sandbox_policy {
+        paths.extend(writable_roots.iter().map(|root| root.to_path_buf()));
+    }
+
+    let mut roots: Vec<Root> = Vec::new();
+    for path in paths {
+        let Ok(uri) = url::Url::from_directory_path(&path) else {
+            continue;
+        };
+        let uri = uri.to_string();
+        if roots.iter().any(|root| root.uri == uri) {
+            continue;
+        }
+        roots.push(Root {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            uri,
+        });
+    }
+    roots
+}
+
 /// A thin wrapper around a set of running [`RmcpClient`] instances.
 #[derive(Default)]
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     elicitation_requests: ElicitationRequestManager,
+    server_changes: SharedServerChanges,
 }
 
 impl McpConnectionManager {
@@ -318,6 +386,7 @@ impl McpConnectionManager {
         tx_event: Sender<Event>,
         cancel_token: CancellationToken,
         initial_sandbox_state: SandboxState,
+        sampler: Option<Arc<McpSampler>>,
     ) {
         if cancel_token.is_cancelled() {
             return;
@@ -325,6 +394,8 @@ impl McpConnectionManager {
         let mut clients = HashMap::new();
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::default();
+        let roots = mcp_roots(&initial_sandbox_state);
+        let server_changes = SharedServerChanges::default();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
             let _ = emit_update(
@@ -342,6 +413,9 @@ impl McpConnectionManager {
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                sampler.clone(),
+                roots.clone(),
+                Arc::clone(&server_changes),
             );
             clients.insert(server_name.clone(), async_managed_client.clone());
             let tx_event = tx_event.clone();
@@ -389,6 +463,7 @@ impl McpConnectionManager {
         }
         self.clients = clients;
         self.elicitation_requests = elicitation_requests.clone();
+        self.server_changes = server_changes;
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
             let mut summary = McpStartupCompleteEvent::default();
@@ -433,6 +508,22 @@ impl McpConnectionManager {
             .await
     }
 
+    /// Asks the user to answer `message` on behalf of `server_name`, through
+    /// the same flow as server-initiated elicitations. The returned future does
+    /// not borrow the manager.
+    pub fn request_elicitation(
+        &self,
+        server_name: String,
+        id: RequestId,
+        message: String,
+        tx_event: Sender<Event>,
+    ) -> BoxFuture<'static, Result<ElicitationResponse>> {
+        self.elicitation_requests
+            .clone()
+            .request(server_name, id, message, tx_event)
+            .boxed()
+    }
+
     /// Returns a single map that contains all tools. Each key is the
     /// fully-qualified name for the tool.
     #[instrument(level = "trace", skip_all)]
@@ -440,8 +531,9 @@ impl McpConnectionManager {
         let mut tools = HashMap::new();
         for managed_client in self.clients.values() {
             if let Ok(client) = managed_client.client().await {
+                let server_tools = client.tools.lock().await.clone();
                 tools.extend(qualify_tools(filter_tools(
-                    client.tools,
+                    server_tools,
                     client.tool_filter,
                 )));
             }
@@ -449,6 +541,27 @@ impl McpConnectionManager {
         tools
     }
 
+    /// Applies the change notifications received since the last call. Tool
+    /// lists that changed are fetched again, so the next tool registry built
+    /// by [`Self::list_all_tools`] picks them up; resource changes are
+    /// returned for the model to hear about.
+    pub async fn apply_server_changes(&self) -> Vec<ResourceChange> {
+        let PendingServerChanges { tools, resources } = match self.server_changes.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return Vec::new(),
+        };
+        for server_name in tools {
+            let Ok(managed) = self.client_by_name(&server_name).await else {
+                continue;
+            };
+            match list_tools_for_client(&server_name, &managed.client, managed.tool_timeout).await {
+                Ok(server_tools) => *managed.tools.lock().await = server_tools,
+                Err(err) => warn!("failed to refresh tools for MCP server {server_name}: {err:#}"),
+            }
+        }
+        resources
+    }
+
     /// Returns a single map that contains all resources. Each key is the
     /// server name and the value is a vector of resources.
     pub async fn list_all_resources(&self) -> HashMap<String, Vec<Resource>> {
@@ -585,6 +698,94 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns a single map that contains all prompts. Each key is the
+    /// server name and the value is a vector of prompts. Servers that do not
+    /// advertise the prompts capability are skipped.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+            let timeout = managed_client.tool_timeout;
+            let client = managed_client.client.clone();
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| ListPromptsRequestParams {
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Fetch the prompt `name` from the specified server, rendered with `arguments`.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let params = GetPromptRequestParams {
+            arguments: (!arguments.is_empty()).then(|| json!(arguments)),
+            name: name.to_string(),
+        };
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}/{name}`"))
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -655,6 +856,24 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Subscribes to `resources/updated` notifications for `uri`.
+    pub async fn subscribe_resource(&self, server: &str, uri: String) -> Result<()> {
+        let managed = self.client_by_name(server).await?;
+        if !managed.server_supports_resource_subscriptions {
+            return Err(anyhow!(
+                "MCP server `{server}` does not support resource subscriptions"
+            ));
+        }
+        managed
+            .client
+            .subscribe_resource(
+                SubscribeRequestParams { uri: uri.clone() },
+                managed.tool_timeout,
+            )
+            .await
+            .with_context(|| format!("resources/subscribe failed for `{server}` ({uri})"))
+    }
+
     pub async fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.list_all_tools()
             .await
@@ -798,12 +1017,16 @@ async fn start_server_task(
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampler: Option<Arc<McpSampler>>,
+    server_changes: SharedServerChanges,
 ) -> Result<ManagedClient, StartupOutcomeError> {
     let params = mcp_types::InitializeRequestParams {
         capabilities: ClientCapabilities {
             experimental: None,
-            roots: None,
-            sampling: None,
+            roots: Some(ClientCapabilitiesRoots {
+                list_changed: Some(true),
+            }),
+            sampling: sampler.as_ref().map(|_| json!({})),
             // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
             // indicates this should be an empty object.
             elicitation: Some(json!({})),
@@ -821,9 +1044,32 @@ async fn start_server_task(
     };
 
     let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    let send_sampling = sampler.map(|sampler| -> SendSampling {
+        let server_name = server_name.clone();
+        Box::new(move |id, params| {
+            let sampler = Arc::clone(&sampler);
+            let server_name = server_name.clone();
+            async move { sampler.create_message(server_name, id, params).await }.boxed()
+        })
+    });
+
+    let on_change: OnServerChange = {
+        let server_name = server_name.clone();
+        Box::new(move |change| {
+            if let Ok(mut pending) = server_changes.lock() {
+                pending.record(&server_name, change);
+            }
+        })
+    };
 
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            send_sampling,
+            Some(on_change),
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
@@ -831,6 +1077,13 @@ async fn start_server_task(
         .await
         .map_err(StartupOutcomeError::from)?;
 
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
+    let server_supports_resource_subscriptions = initialize_result
+        .capabilities
+        .resources
+        .as_ref()
+        .and_then(|resources| resources.subscribe)
+        .unwrap_or(false);
     let server_supports_sandbox_state_capability = initialize_result
         .capabilities
         .experimental
@@ -840,9 +1093,11 @@ async fn start_server_task(
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
-        tools,
+        tools: Arc::new(Mutex::new(tools)),
         tool_timeout: Some(tool_timeout),
         tool_filter,
+        server_supports_prompts,
+        server_supports_resource_subscriptions,
         server_supports_sandbox_state_capability,
     };
 
@@ -1174,6 +1429,7 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1218,6 +1474,7 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling_token_budget: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1242,4 +1499,43 @@ mod tests {
             display
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn mcp_roots_include_cwd_and_writable_roots() {
+        let extra = codex_utils_absolute_path::AbsolutePathBuf::from_absolute_path("/srv/shared")
+            .expect("absolute path");
+        let sandbox_state = SandboxState {
+            sandbox_policy: SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![extra],
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                read_only_subpaths: Vec::new(),
+                deny_read_paths: Vec::new(),
+            },
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: PathBuf::from("/home/dev/project"),
+        };
+
+        assert_eq!(
+            mcp_roots(&sandbox_state),
+            vec![
+                Root {
+                    name: Some("project".to_string()),
+                    uri: "file:///home/dev/project/".to_string(),
+                },
+                Root {
+                    name: Some("shared".to_string()),
+                    uri: "file:///srv/shared/".to_string(),
+                },
+            ]
+        );
+
+        let read_only = SandboxState {
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            ..sandbox_state
+        };
+        assert_eq!(mcp_roots(&read_only).len(), 1);
+    }
 }
@@ -0,0 +1,488 @@
+//! Filtering HTTP proxy that gives sandboxed commands network access to an allowlist of hosts.
+//!
+//! The proxy listens on a Unix socket. On Linux, `codex-linux-sandbox` runs the command in a
+//! private network namespace and bridges a loopback port to that socket, so the proxy is the
+//! command's only way out. It understands `CONNECT host:port` (used for HTTPS) and plain HTTP
+//! requests in absolute form (`GET http://host/path`).
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::net::UnixListener;
+use tokio::net::UnixStream;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::warn;
+use url::Url;
+
+/// Ports allowed for an allowlist entry that does not name one.
+pub const DEFAULT_PORTS: [u16; 2] = [80, 443];
+
+/// Requests with a longer head than this are rejected.
+const MAX_REQUEST_HEAD_BYTES: usize = 64 * 1024;
+
+/// One `network_proxy.allowed_hosts` entry: `host`, `host:port`, `*.domain` or `*.domain:port`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostPattern {
+    host: String,
+    /// `*.domain` matches subdomains of `host`, but not `host` itself.
+    any_subdomain: bool,
+    port: Option<u16>,
+}
+
+impl HostPattern {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.trim();
+        let invalid = |reason: &str| format!("invalid allowed host `{raw}`: {reason}");
+        if trimmed.is_empty() {
+            return Err(invalid("entry is empty"));
+        }
+        if trimmed.contains("://") || trimmed.contains('/') {
+            return Err(invalid("expected a host, not a URL"));
+        }
+
+        let (host, port) = split_host_port(trimmed).map_err(|reason| invalid(&reason))?;
+        let (host, any_subdomain) = match host.strip_prefix("*.") {
+            Some(domain) => (domain, true),
+            None => (host, false),
+        };
+        if host.is_empty() || host.contains('*') {
+            return Err(invalid("`*.` is only allowed at the start of a domain"));
+        }
+        Ok(Self {
+            host: normalize_host(host),
+            any_subdomain,
+            port,
+        })
+    }
+
+    pub fn matches(&self, host: &str, port: u16) -> bool {
+        let host = normalize_host(host);
+        let host_matches = if self.any_subdomain {
+            host.strip_suffix(&self.host)
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.'))
+        } else {
+            host == self.host
+        };
+        let port_matches = match self.port {
+            Some(allowed) => allowed == port,
+            None => DEFAULT_PORTS.contains(&port),
+        };
+        host_matches && port_matches
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.any_subdomain {
+            f.write_str("*.")?;
+        }
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?;
+        } else {
+            f.write_str(&self.host)?;
+        }
+        match self.port {
+            Some(port) => write!(f, ":{port}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The destinations sandboxed commands may reach through the proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NetworkAllowlist {
+    patterns: Vec<HostPattern>,
+}
+
+impl NetworkAllowlist {
+    pub fn parse(entries: &[String]) -> Result<Self, String> {
+        let patterns = entries
+            .iter()
+            .map(|entry| HostPattern::parse(entry))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn patterns(&self) -> &[HostPattern] {
+        &self.patterns
+    }
+
+    pub fn allows(&self, host: &str, port: u16) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches(host, port))
+    }
+}
+
+/// A connection the proxy refused because its destination is not allowlisted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeniedConnection {
+    pub host: String,
+    pub port: u16,
+}
+
+type DeniedCallback = Arc<dyn Fn(&DeniedConnection) + Send + Sync>;
+
+/// A running proxy. Dropping it stops accepting connections and removes the socket.
+pub struct NetworkProxy {
+    socket_path: PathBuf,
+    _socket_dir: TempDir,
+    accept_loop: JoinHandle<()>,
+}
+
+impl NetworkProxy {
+    /// Binds the proxy socket in a fresh private directory and starts serving. `on_denied`
+    /// is called for every refused connection.
+    pub fn start(
+        allowlist: NetworkAllowlist,
+        on_denied: impl Fn(&DeniedConnection) + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        // Unix socket paths are limited to ~100 bytes, so stay in the system temp dir
+        // rather than under CODEX_HOME.
+        let socket_dir = tempfile::Builder::new()
+            .prefix("codex-network-proxy-")
+            .tempdir()?;
+        let socket_path = socket_dir.path().join("proxy.sock");
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let allowlist = Arc::new(allowlist);
+        let on_denied: DeniedCallback = Arc::new(on_denied);
+        let accept_loop = tokio::spawn(async move {
+            loop {
+                let client = match listener.accept().await {
+                    Ok((client, _)) => client,
+                    Err(err) => {
+                        warn!("network proxy failed to accept a connection: {err}");
+                        continue;
+                    }
+                };
+                let allowlist = Arc::clone(&allowlist);
+                let on_denied = Arc::clone(&on_denied);
+                tokio::spawn(async move {
+                    if let Err(err) = handle_client(client, &allowlist, &on_denied).await {
+                        debug!("network proxy connection ended with an error: {err}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            socket_path,
+            _socket_dir: socket_dir,
+            accept_loop,
+        })
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.accept_loop.abort();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ProxyRequest {
+    /// `CONNECT host:port`: tunnel raw bytes once the upstream connection is up.
+    Connect { host: String, port: u16 },
+    /// Plain HTTP: forward `head`, rewritten to origin form, then relay the rest.
+    Forward {
+        host: String,
+        port: u16,
+        head: Vec<u8>,
+    },
+}
+
+impl ProxyRequest {
+    fn destination(&self) -> (&str, u16) {
+        match self {
+            Self::Connect { host, port } | Self::Forward { host, port, .. } => (host, *port),
+        }
+    }
+}
+
+async fn handle_client(
+    mut client: UnixStream,
+    allowlist: &NetworkAllowlist,
+    on_denied: &DeniedCallback,
+) -> io::Result<()> {
+    let Some((head, leftover)) = read_request_head(&mut client).await? else {
+        return Ok(());
+    };
+    let request = match parse_request(&head) {
+        Ok(request) => request,
+        Err(reason) => {
+            return write_response(&mut client, "400 Bad Request", &reason).await;
+        }
+    };
+
+    let (host, port) = request.destination();
+    if !allowlist.allows(host, port) {
+        warn!("network proxy denied a connection to {host}:{port}");
+        on_denied(&DeniedConnection {
+            host: host.to_string(),
+            port,
+        });
+        let reason = format!("{host}:{port} is not in network_proxy.allowed_hosts");
+        return write_response(&mut client, "403 Forbidden", &reason).await;
+    }
+
+    let mut upstream = match TcpStream::connect((host, port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let reason = format!("failed to connect to {host}:{port}: {err}");
+            return write_response(&mut client, "502 Bad Gateway", &reason).await;
+        }
+    };
+    match &request {
+        ProxyRequest::Connect { .. } => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+        }
+        ProxyRequest::Forward { head, .. } => upstream.write_all(head).await?,
+    }
+    upstream.write_all(&leftover).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to the blank line that ends the request head. Returns the head and any bytes the
+/// client already sent past it, or `None` if the client hung up first.
+async fn read_request_head(client: &mut UnixStream) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let search_from = buf.len().saturating_sub(3);
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buf[search_from..]
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+        {
+            let end = search_from + pos + 4;
+            let leftover = buf.split_off(end);
+            return Ok(Some((buf, leftover)));
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+    }
+}
+
+fn parse_request(head: &[u8]) -> Result<ProxyRequest, String> {
+    let head = std::str::from_utf8(head).map_err(|_| "request head is not UTF-8".to_string())?;
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("malformed request line `{request_line}`"));
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_host_port(target)?;
+        let port = port.ok_or_else(|| format!("CONNECT target `{target}` has no port"))?;
+        return Ok(ProxyRequest::Connect {
+            host: normalize_host(host),
+            port,
+        });
+    }
+
+    let url = Url::parse(target)
+        .ok()
+        .filter(|url| url.scheme() == "http")
+        .ok_or_else(|| format!("expected an absolute http:// URL, got `{target}`"))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("URL `{target}` has no host"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format!("URL `{target}` has no port"))?;
+
+    let mut forwarded = format!(
+        "{method} {} {version}\r\n",
+        &url[url::Position::BeforePath..]
+    );
+    for line in lines.filter(|line| !line.is_empty()) {
+        // Proxy-Authorization and friends are meant for us, not the origin server.
+        if line
+            .get(..6)
+            .is_some_and(|name| name.eq_ignore_ascii_case("proxy-"))
+        {
+            continue;
+        }
+        forwarded.push_str(line);
+        forwarded.push_str("\r\n");
+    }
+    forwarded.push_str("\r\n");
+
+    Ok(ProxyRequest::Forward {
+        host: normalize_host(host.trim_start_matches('[').trim_end_matches(']')),
+        port,
+        head: forwarded.into_bytes(),
+    })
+}
+
+/// Splits `host`, `host:port`, `[v6]` or `[v6]:port`.
+fn split_host_port(raw: &str) -> Result<(&str, Option<u16>), String> {
+    let (host, port) = if let Some(rest) = raw.strip_prefix('[') {
+        let (host, after) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("unterminated `[` in `{raw}`"))?;
+        match after {
+            "" => (host, None),
+            _ => match after.strip_prefix(':') {
+                Some(port) => (host, Some(port)),
+                None => return Err(format!("unexpected `{after}` after `]` in `{raw}`")),
+            },
+        }
+    } else {
+        match raw.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => (host, Some(port)),
+            Some(_) => return Err(format!("IPv6 addresses must be in brackets: `{raw}`")),
+            None => (raw, None),
+        }
+    };
+    let port = port
+        .map(|port| {
+            port.parse::<u16>()
+                .ok()
+                .filter(|port| *port != 0)
+                .ok_or_else(|| format!("invalid port `{port}` in `{raw}`"))
+        })
+        .transpose()?;
+    Ok((host, port))
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+async fn write_response(client: &mut UnixStream, status: &str, body: &str) -> io::Result<()> {
+    let body = format!("codex network proxy: {body}\n");
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn allowlist_matches_hosts_subdomains_and_ports() {
+        let allowlist = NetworkAllowlist::parse(&[
+            "crates.io".to_string(),
+            "*.crates.io".to_string(),
+            "Registry.Example.com:8443".to_string(),
+            "[::1]:9000".to_string(),
+        ])
+        .expect("allowlist should parse");
+
+        assert!(allowlist.allows("crates.io", 443));
+        assert!(allowlist.allows("static.crates.io", 443));
+        assert!(allowlist.allows("index.crates.io.", 80));
+        assert!(!allowlist.allows("crates.io", 22));
+        assert!(!allowlist.allows("evilcrates.io", 443));
+        assert!(allowlist.allows("registry.example.com", 8443));
+        assert!(!allowlist.allows("registry.example.com", 443));
+        assert!(allowlist.allows("::1", 9000));
+
+        assert_eq!(
+            vec![
+                "crates.io",
+                "*.crates.io",
+                "registry.example.com:8443",
+                "[::1]:9000"
+            ],
+            allowlist
+                .patterns()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert!(HostPattern::parse("https://crates.io").is_err());
+        assert!(HostPattern::parse("crates.*.io").is_err());
+        assert!(HostPattern::parse("crates.io:0").is_err());
+    }
+
+    #[tokio::test]
+    async fn proxy_tunnels_allowed_hosts_and_reports_denied_ones() -> anyhow::Result<()> {
+        let upstream = TcpListener::bind("127.0.0.1:0").await?;
+        let upstream_port = upstream.local_addr()?.port();
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = upstream.accept().await {
+                let mut buf = [0u8; 4];
+                if stream.read_exact(&mut buf).await.is_ok() {
+                    let _ = stream.write_all(&buf).await;
+                }
+            }
+        });
+
+        let denied = Arc::new(Mutex::new(Vec::new()));
+        let denied_sink = Arc::clone(&denied);
+        let allowlist = NetworkAllowlist::parse(&[format!("127.0.0.1:{upstream_port}")])
+            .map_err(anyhow::Error::msg)?;
+        let proxy = NetworkProxy::start(allowlist, move |connection| {
+            if let Ok(mut denied) = denied_sink.lock() {
+                denied.push(connection.clone());
+            }
+        })?;
+
+        let mut client = UnixStream::connect(proxy.socket_path()).await?;
+        client
+            .write_all(format!("CONNECT 127.0.0.1:{upstream_port} HTTP/1.1\r\n\r\nping").as_bytes())
+            .await?;
+        let established = b"HTTP/1.1 200 Connection Established\r\n\r\n";
+        let mut response = vec![0u8; established.len() + 4];
+        client.read_exact(&mut response).await?;
+        assert_eq!(
+            [established.as_slice(), b"ping"].concat(),
+            response,
+            "CONNECT to an allowed host should tunnel bytes"
+        );
+
+        let mut client = UnixStream::connect(proxy.socket_path()).await?;
+        client
+            .write_all(b"GET http://example.com/ HTTP/1.1\r\nHost: example.com\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        client.read_to_string(&mut response).await?;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+
+        let denied = denied.lock().map_err(|_| anyhow::anyhow!("poisoned"))?;
+        assert_eq!(
+            vec![DeniedConnection {
+                host: "example.com".to_string(),
+                port: 80,
+            }],
+            *denied
+        );
+        Ok(())
+    }
+}
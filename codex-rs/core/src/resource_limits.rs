@@ -0,0 +1,124 @@
+//! Per-command resource limits: the `codex-linux-sandbox` flags that enforce
+//! them, and working out afterwards whether one of them stopped a command.
+
+use crate::config::types::ResourceLimits;
+use crate::exec::ExecToolCallOutput;
+use crate::protocol::ResourceLimitKind;
+
+/// Prefix of the line `codex-linux-sandbox` writes to stderr when it knows
+/// that a limit stopped the command, followed by the [`ResourceLimitKind`].
+pub const RESOURCE_LIMIT_EXCEEDED_PREFIX: &str = "codex-linux-sandbox: resource limit exceeded: ";
+
+/// Arguments for `codex-linux-sandbox` that apply `limits`.
+pub(crate) fn linux_sandbox_args(limits: &ResourceLimits) -> Vec<String> {
+    let flags = [
+        ("--max-memory-mb", limits.max_memory_mb),
+        ("--max-cpu-seconds", limits.max_cpu_seconds),
+        ("--max-processes", limits.max_processes),
+        ("--max-open-files", limits.max_open_files),
+        ("--max-file-size-mb", limits.max_file_size_mb),
+    ];
+    flags
+        .into_iter()
+        .filter_map(|(flag, value)| value.map(|value| [flag.to_string(), value.to_string()]))
+        .flatten()
+        .collect()
+}
+
+/// Returns the limit the sandbox reported as having stopped the command, if any.
+pub(crate) fn detect_exceeded_limit(
+    limits: &ResourceLimits,
+    output: &ExecToolCallOutput,
+) -> Option<ResourceLimitKind> {
+    if limits.is_empty() || output.exit_code == 0 {
+        return None;
+    }
+
+    // Only the sandbox knows that a limit was reached: an error like "Too many
+    // open files" may just as well come from a limit set outside it.
+    [&output.stderr.text, &output.aggregated_output.text]
+        .iter()
+        .flat_map(|text| text.lines())
+        .find_map(|line| parse_kind(line.trim().strip_prefix(RESOURCE_LIMIT_EXCEEDED_PREFIX)?))
+}
+
+fn parse_kind(reported: &str) -> Option<ResourceLimitKind> {
+    let name = reported.split_whitespace().next()?;
+    [
+        ResourceLimitKind::Memory,
+        ResourceLimitKind::CpuTime,
+        ResourceLimitKind::Processes,
+        ResourceLimitKind::OpenFiles,
+        ResourceLimitKind::FileSize,
+    ]
+    .into_iter()
+    .find(|kind| kind.to_string() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::StreamOutput;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn output(exit_code: i32, stderr: &str) -> ExecToolCallOutput {
+        ExecToolCallOutput {
+            exit_code,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(stderr.to_string()),
+            aggregated_output: StreamOutput::new(stderr.to_string()),
+            duration: Duration::from_millis(1),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn linux_sandbox_args_include_only_set_limits() {
+        let limits = ResourceLimits {
+            max_memory_mb: Some(512),
+            max_open_files: Some(256),
+            ..Default::default()
+        };
+        assert_eq!(
+            linux_sandbox_args(&limits),
+            vec!["--max-memory-mb", "512", "--max-open-files", "256"]
+        );
+        assert!(linux_sandbox_args(&ResourceLimits::default()).is_empty());
+    }
+
+    #[test]
+    fn detects_limit_reported_by_linux_sandbox() {
+        let limits = ResourceLimits {
+            max_cpu_seconds: Some(10),
+            ..Default::default()
+        };
+        let stderr = format!(
+            "working...\n{RESOURCE_LIMIT_EXCEEDED_PREFIX}cpu_time (max_cpu_seconds = 10)\n"
+        );
+        assert_eq!(
+            detect_exceeded_limit(&limits, &output(152, &stderr)),
+            Some(ResourceLimitKind::CpuTime)
+        );
+    }
+
+    #[test]
+    fn ignores_limit_errors_the_sandbox_did_not_report() {
+        let limits = ResourceLimits {
+            max_open_files: Some(64),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_exceeded_limit(
+                &limits,
+                &output(1, "error: Too many open files (os error 24)")
+            ),
+            None
+        );
+        let stderr = format!("{RESOURCE_LIMIT_EXCEEDED_PREFIX}open_files (max_open_files = 64)\n");
+        assert_eq!(
+            detect_exceeded_limit(&ResourceLimits::default(), &output(1, &stderr)),
+            None
+        );
+    }
+}
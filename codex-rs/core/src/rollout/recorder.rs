@@ -23,6 +23,7 @@ use super::list::Cursor;
 use super::list::ThreadsPage;
 use super::list::get_threads;
 use super::policy::is_persisted_response_item;
+use super::search::SessionIndexWriter;
 use crate::config::Config;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
@@ -163,6 +164,7 @@ impl RolloutRecorder {
 
         // Clone the cwd for the spawned task to collect git info asynchronously
         let cwd = config.cwd.clone();
+        let codex_home = config.codex_home.clone();
 
         // A reasonably-sized bounded channel. If the buffer fills up the send
         // future will yield, which is fine – we only need to ensure we do not
@@ -172,7 +174,14 @@ impl RolloutRecorder {
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
+        tokio::task::spawn(rollout_writer(
+            file,
+            rx,
+            meta,
+            cwd,
+            codex_home,
+            rollout_path.clone(),
+        ));
 
         Ok(Self { tx, rollout_path })
     }
@@ -348,8 +357,18 @@ async fn rollout_writer(
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
     cwd: std::path::PathBuf,
+    codex_home: PathBuf,
+    rollout_path: PathBuf,
 ) -> std::io::Result<()> {
     let mut writer = JsonlWriter { file };
+    // The search index is best effort; the rollout is written without it.
+    let mut index = match SessionIndexWriter::open(&codex_home, &rollout_path).await {
+        Ok(index) => Some(index),
+        Err(e) => {
+            warn!("failed to open session search index: {e}");
+            None
+        }
+    };
 
     // If we have a meta, collect git info asynchronously and write meta first
     if let Some(session_meta) = meta.take() {
@@ -360,9 +379,9 @@ async fn rollout_writer(
         };
 
         // Write the SessionMeta as the first item in the file, wrapped in a rollout line
-        writer
-            .write_rollout_item(RolloutItem::SessionMeta(session_meta_line))
-            .await?;
+        let item = RolloutItem::SessionMeta(session_meta_line);
+        record_in_index(&mut index, &item).await;
+        writer.write_rollout_item(item).await?;
     }
 
     // Process rollout commands
@@ -371,6 +390,7 @@ async fn rollout_writer(
             RolloutCmd::AddItems(items) => {
                 for item in items {
                     if is_persisted_response_item(&item) {
+                        record_in_index(&mut index, &item).await;
                         writer.write_rollout_item(item).await?;
                     }
                 }
@@ -392,6 +412,16 @@ async fn rollout_writer(
     Ok(())
 }
 
+async fn record_in_index(index: &mut Option<SessionIndexWriter>, item: &RolloutItem) {
+    let Some(writer) = index.as_mut() else {
+        return;
+    };
+    if let Err(e) = writer.record(item).await {
+        warn!("failed to update session search index: {e}");
+        *index = None;
+    }
+}
+
 struct JsonlWriter {
     file: tokio::fs::File,
 }
@@ -0,0 +1,1324 @@
+//! Local search index over recorded sessions.
+//!
+//! Every rollout gets a small JSONL index under `~/.codex/session_index` with
+//! the parts of a session worth searching for: user and agent messages,
+//! commands the agent ran and files it patched. The rollout writer appends to
+//! the index as it records items; rollouts written before the index existed
+//! are indexed the first time a search runs.
+//!
+//! Next to the session indexes, `terms/` maps every word to the sessions it
+//! appears in, sharded by the word's first bytes, so a search only reads the
+//! sessions that contain its words. Archived and deleted sessions lose their
+//! index; words that still point at them are skipped.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_apply_patch::parse_patch;
+use codex_protocol::ThreadId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellCommandToolCallParams;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+
+use super::SESSIONS_SUBDIR;
+use crate::parse_command::extract_shell_command;
+use crate::parse_command::shlex_join;
+
+pub const SESSION_INDEX_SUBDIR: &str = "session_index";
+const TERMS_SUBDIR: &str = "terms";
+
+/// Matching entries reported for each session.
+const MAX_MATCHES_PER_SESSION: usize = 3;
+/// Characters kept before the first match in a snippet.
+const SNIPPET_LEAD_CHARS: usize = 40;
+const SNIPPET_MAX_CHARS: usize = 160;
+/// Commands are cut to this length; heredocs can carry whole files.
+const MAX_COMMAND_CHARS: usize = 2_000;
+/// Shorter words are not indexed, and terms are sharded by this many leading bytes.
+const MIN_TERM_BYTES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchEntryKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    File,
+}
+
+/// One line of a session index file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum IndexLine {
+    Session {
+        thread_id: ThreadId,
+        rollout_path: PathBuf,
+        cwd: PathBuf,
+        created_at: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        git_branch: Option<String>,
+        #[serde(default)]
+        source: SessionSource,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model_provider: Option<String>,
+    },
+    Model {
+        model: String,
+    },
+    Entry {
+        kind: SearchEntryKind,
+        text: String,
+    },
+}
+
+/// A line of a `terms/` shard: the session indexed in the file named
+/// `session` contains the word `term`.
+#[derive(Debug, Serialize, Deserialize)]
+struct TermPosting {
+    term: String,
+    session: String,
+}
+
+/// Filters for [`search_sessions`]. Every word of `text` has to start a word
+/// somewhere in a session for it to match; an empty `text` matches every
+/// session that passes the other filters.
+#[derive(Debug, Clone, Default)]
+pub struct SessionSearchQuery {
+    pub text: String,
+    /// Only sessions started in this directory or below it.
+    pub cwd: Option<PathBuf>,
+    /// Only sessions started on or after this day (UTC).
+    pub since: Option<NaiveDate>,
+    /// Only sessions started on or before this day (UTC).
+    pub until: Option<NaiveDate>,
+    /// Only sessions that used a model whose name contains this.
+    pub model: Option<String>,
+    /// Only sessions that patched a file whose path contains this.
+    pub file: Option<String>,
+    /// Only sessions started from one of these sources; empty allows any source.
+    pub sources: Vec<SessionSource>,
+    /// Only sessions recorded with one of these model providers. Sessions that
+    /// recorded none count as `default_provider`.
+    pub model_providers: Option<Vec<String>>,
+    pub default_provider: String,
+    /// Maximum number of sessions returned; 0 returns all of them.
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSearchHit {
+    pub thread_id: ThreadId,
+    pub rollout_path: PathBuf,
+    pub cwd: PathBuf,
+    /// RFC3339 timestamp of when the session started.
+    pub created_at: String,
+    /// RFC3339 timestamp of the last write to the rollout.
+    pub updated_at: Option<String>,
+    pub git_branch: Option<String>,
+    pub models: Vec<String>,
+    /// The entries that matched best, most matching words first.
+    pub matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchMatch {
+    pub kind: SearchEntryKind,
+    pub snippet: String,
+}
+
+/// Searches the sessions recorded under `codex_home`, newest first.
+pub async fn search_sessions(
+    codex_home: &Path,
+    query: SessionSearchQuery,
+) -> io::Result<Vec<SessionSearchHit>> {
+    let codex_home = codex_home.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        index_unindexed_rollouts(&codex_home)?;
+        search_index(&codex_home, &query)
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// Like [`search_sessions`], without first indexing rollouts that have no
+/// index yet; for callers that search repeatedly and have done that once.
+pub async fn search_indexed_sessions(
+    codex_home: &Path,
+    query: SessionSearchQuery,
+) -> io::Result<Vec<SessionSearchHit>> {
+    let codex_home = codex_home.to_path_buf();
+    tokio::task::spawn_blocking(move || search_index(&codex_home, &query))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Removes the index of a rollout that was archived or deleted.
+pub async fn remove_session_index(codex_home: &Path, rollout_path: &Path) -> io::Result<()> {
+    let index_path = session_index_path(codex_home, rollout_path)?;
+    match tokio::fs::remove_file(&index_path).await {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn session_index_path(codex_home: &Path, rollout_path: &Path) -> io::Result<PathBuf> {
+    let name = rollout_path
+        .file_name()
+        .ok_or_else(|| io::Error::other("rollout path has no file name"))?;
+    Ok(codex_home.join(SESSION_INDEX_SUBDIR).join(name))
+}
+
+/// Turns rollout items into index lines.
+#[derive(Debug)]
+struct SessionIndexer {
+    rollout_path: PathBuf,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    files: HashSet<PathBuf>,
+    /// Words already added to the term index.
+    terms: HashSet<String>,
+}
+
+impl SessionIndexer {
+    fn new(rollout_path: PathBuf) -> Self {
+        Self {
+            rollout_path,
+            cwd: None,
+            model: None,
+            files: HashSet::new(),
+            terms: HashSet::new(),
+        }
+    }
+
+    /// Picks up where an existing index left off.
+    fn restore(&mut self, line: &IndexLine) {
+        match line {
+            IndexLine::Session { cwd, .. } => self.cwd = Some(cwd.clone()),
+            IndexLine::Model { model } => self.model = Some(model.clone()),
+            IndexLine::Entry { kind, text } => {
+                if *kind == SearchEntryKind::File {
+                    self.files.insert(PathBuf::from(text));
+                }
+                self.terms.extend(index_terms(text));
+            }
+        }
+    }
+
+    /// Words of `lines` that are not in the term index yet.
+    fn new_terms(&mut self, lines: &[IndexLine]) -> Vec<String> {
+        lines
+            .iter()
+            .filter_map(|line| match line {
+                IndexLine::Entry { text, .. } => Some(text),
+                _ => None,
+            })
+            .flat_map(|text| index_terms(text))
+            .filter(|term| self.terms.insert(term.clone()))
+            .collect()
+    }
+
+    fn index_item(&mut self, item: &RolloutItem) -> Vec<IndexLine> {
+        match item {
+            // Forked rollouts repeat the parent's meta; the first one names the session.
+            RolloutItem::SessionMeta(meta_line) if self.cwd.is_none() => {
+                let meta = &meta_line.meta;
+                self.cwd = Some(meta.cwd.clone());
+                vec![IndexLine::Session {
+                    thread_id: meta.id,
+                    rollout_path: self.rollout_path.clone(),
+                    cwd: meta.cwd.clone(),
+                    created_at: meta.timestamp.clone(),
+                    git_branch: meta_line.git.as_ref().and_then(|git| git.branch.clone()),
+                    source: meta.source.clone(),
+                    model_provider: meta.model_provider.clone(),
+                }]
+            }
+            RolloutItem::TurnContext(context)
+                if self.model.as_deref() != Some(context.model.as_str()) =>
+            {
+                self.model = Some(context.model.clone());
+                vec![IndexLine::Model {
+                    model: context.model.clone(),
+                }]
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                entry(SearchEntryKind::UserMessage, &event.message)
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                entry(SearchEntryKind::AgentMessage, &event.message)
+            }
+            RolloutItem::ResponseItem(item) => self.index_tool_call(item),
+            _ => Vec::new(),
+        }
+    }
+
+    fn index_tool_call(&mut self, item: &ResponseItem) -> Vec<IndexLine> {
+        match item {
+            ResponseItem::LocalShellCall {
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => self.index_command(&exec.command),
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => match name.as_str() {
+                "shell" | "container.exec" => {
+                    serde_json::from_str::<ShellToolCallParams>(arguments)
+                        .map(|params| self.index_command(&params.command))
+                        .unwrap_or_default()
+                }
+                "shell_command" => serde_json::from_str::<ShellCommandToolCallParams>(arguments)
+                    .map(|params| command_entry(&params.command))
+                    .unwrap_or_default(),
+                "exec_command" => serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .and_then(|arguments| arguments["cmd"].as_str().map(command_entry))
+                    .unwrap_or_default(),
+                "apply_patch" => serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .and_then(|arguments| {
+                        arguments["input"]
+                            .as_str()
+                            .map(|patch| self.index_patch(patch))
+                    })
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            },
+            ResponseItem::CustomToolCall { name, input, .. } if name == "apply_patch" => {
+                self.index_patch(input)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn index_command(&mut self, command: &[String]) -> Vec<IndexLine> {
+        match command {
+            [program, patch] if program == "apply_patch" || program == "applypatch" => {
+                self.index_patch(patch)
+            }
+            _ => match extract_shell_command(command) {
+                Some((_, script)) => command_entry(script),
+                None => command_entry(&shlex_join(command)),
+            },
+        }
+    }
+
+    /// One `File` entry per path the patch touches, the first time the session touches it.
+    fn index_patch(&mut self, patch: &str) -> Vec<IndexLine> {
+        let Ok(args) = parse_patch(patch) else {
+            return Vec::new();
+        };
+        let mut base = self.cwd.clone().unwrap_or_default();
+        if let Some(workdir) = &args.workdir {
+            base = base.join(workdir);
+        }
+        args.hunks
+            .iter()
+            .map(|hunk| hunk.resolve_path(&base))
+            .filter(|path| self.files.insert(path.clone()))
+            .map(|path| IndexLine::Entry {
+                kind: SearchEntryKind::File,
+                text: path.display().to_string(),
+            })
+            .collect()
+    }
+}
+
+fn entry(kind: SearchEntryKind, text: &str) -> Vec<IndexLine> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Vec::new();
+    }
+    vec![IndexLine::Entry {
+        kind,
+        text: text.to_string(),
+    }]
+}
+
+fn command_entry(command: &str) -> Vec<IndexLine> {
+    match command.char_indices().nth(MAX_COMMAND_CHARS) {
+        Some((index, _)) => entry(SearchEntryKind::Command, &command[..index]),
+        None => entry(SearchEntryKind::Command, command),
+    }
+}
+
+/// The words of `text` the term index keys on: lowercased runs of letters and digits.
+fn index_terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(fold_case)
+        .filter(|term| term.len() >= MIN_TERM_BYTES)
+}
+
+fn term_shard_path(index_dir: &Path, term: &str) -> PathBuf {
+    let prefix: String = term
+        .bytes()
+        .take(MIN_TERM_BYTES)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    index_dir.join(TERMS_SUBDIR).join(format!("{prefix}.jsonl"))
+}
+
+/// Adds `terms` of the session indexed at `index_path` to the term index.
+fn post_terms(index_path: &Path, terms: Vec<String>) -> io::Result<()> {
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let (Some(index_dir), Some(session)) = (
+        index_path.parent(),
+        index_path.file_name().and_then(|name| name.to_str()),
+    ) else {
+        return Err(io::Error::other("index path has no file name"));
+    };
+    let mut shards: BTreeMap<PathBuf, Vec<u8>> = BTreeMap::new();
+    for term in terms {
+        let buf = shards.entry(term_shard_path(index_dir, &term)).or_default();
+        let posting = TermPosting {
+            term,
+            session: session.to_string(),
+        };
+        serde_json::to_writer(&mut *buf, &posting)?;
+        buf.push(b'\n');
+    }
+    fs::create_dir_all(index_dir.join(TERMS_SUBDIR))?;
+    // One append per shard keeps the lines of concurrent writers apart.
+    for (shard, buf) in shards {
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(shard)?
+            .write_all(&buf)?;
+    }
+    Ok(())
+}
+
+/// Appends to the index of one rollout as the rollout writer records items.
+pub(crate) struct SessionIndexWriter {
+    index_path: PathBuf,
+    file: tokio::fs::File,
+    indexer: SessionIndexer,
+}
+
+impl SessionIndexWriter {
+    /// Opens the index of `rollout_path`. A resumed session without an index
+    /// is indexed from its rollout first.
+    pub(crate) async fn open(codex_home: &Path, rollout_path: &Path) -> io::Result<Self> {
+        let codex_home = codex_home.to_path_buf();
+        let rollout_path = rollout_path.to_path_buf();
+        let (index_path, file, indexer) =
+            tokio::task::spawn_blocking(move || open_index(&codex_home, &rollout_path))
+                .await
+                .map_err(io::Error::other)??;
+        Ok(Self {
+            index_path,
+            file: tokio::fs::File::from_std(file),
+            indexer,
+        })
+    }
+
+    pub(crate) async fn record(&mut self, item: &RolloutItem) -> io::Result<()> {
+        let lines = self.indexer.index_item(item);
+        if lines.is_empty() {
+            return Ok(());
+        }
+        self.file.write_all(&serialize_lines(&lines)?).await?;
+        self.file.flush().await?;
+        let terms = self.indexer.new_terms(&lines);
+        if terms.is_empty() {
+            return Ok(());
+        }
+        let index_path = self.index_path.clone();
+        tokio::task::spawn_blocking(move || post_terms(&index_path, terms))
+            .await
+            .map_err(io::Error::other)?
+    }
+}
+
+fn open_index(
+    codex_home: &Path,
+    rollout_path: &Path,
+) -> io::Result<(PathBuf, File, SessionIndexer)> {
+    let index_path = session_index_path(codex_home, rollout_path)?;
+    let mut indexer = SessionIndexer::new(rollout_path.to_path_buf());
+    if index_path.exists() {
+        for line in read_index_lines(&index_path)? {
+            indexer.restore(&line);
+        }
+    } else {
+        write_new_index(&mut indexer, &index_path)?;
+    }
+    if let Some(parent) = index_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&index_path)?;
+    Ok((index_path, file, indexer))
+}
+
+/// Indexes a whole rollout that has no index yet.
+fn write_new_index(indexer: &mut SessionIndexer, index_path: &Path) -> io::Result<()> {
+    let rollout_path = indexer.rollout_path.clone();
+    let lines = index_rollout(indexer, &rollout_path)?;
+    if lines.is_empty() {
+        return Ok(());
+    }
+    write_index(index_path, &lines)?;
+    let terms = indexer.new_terms(&lines);
+    post_terms(index_path, terms)
+}
+
+fn index_rollout(indexer: &mut SessionIndexer, rollout_path: &Path) -> io::Result<Vec<IndexLine>> {
+    let text = match fs::read_to_string(rollout_path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RolloutLine>(line).ok())
+        .flat_map(|line| indexer.index_item(&line.item))
+        .collect())
+}
+
+fn serialize_lines(lines: &[IndexLine]) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    for line in lines {
+        serde_json::to_writer(&mut buf, line)?;
+        buf.push(b'\n');
+    }
+    Ok(buf)
+}
+
+/// Writes a whole index, through a temporary file so a concurrent search never
+/// reads half of it.
+fn write_index(index_path: &Path, lines: &[IndexLine]) -> io::Result<()> {
+    let Some(dir) = index_path.parent() else {
+        return Err(io::Error::other("index path has no parent directory"));
+    };
+    fs::create_dir_all(dir)?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(&serialize_lines(lines)?)?;
+    tmp.persist(index_path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+fn remove_index_file(index_path: &Path) -> io::Result<()> {
+    match fs::remove_file(index_path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn read_index_lines(index_path: &Path) -> io::Result<Vec<IndexLine>> {
+    Ok(fs::read_to_string(index_path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Indexes rollouts that have no index yet, such as those recorded before the
+/// index existed.
+fn index_unindexed_rollouts(codex_home: &Path) -> io::Result<()> {
+    let mut pending = vec![codex_home.join(SESSIONS_SUBDIR)];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let is_rollout = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"));
+            if !is_rollout {
+                continue;
+            }
+            let index_path = session_index_path(codex_home, &path)?;
+            if index_path.exists() {
+                continue;
+            }
+            write_new_index(&mut SessionIndexer::new(path), &index_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// A session read back from its index.
+struct IndexedSession {
+    thread_id: ThreadId,
+    rollout_path: PathBuf,
+    cwd: PathBuf,
+    created_at: String,
+    git_branch: Option<String>,
+    source: SessionSource,
+    model_provider: Option<String>,
+    models: Vec<String>,
+    entries: Vec<(SearchEntryKind, String)>,
+}
+
+impl IndexedSession {
+    fn from_header(header: IndexLine) -> Option<Self> {
+        let IndexLine::Session {
+            thread_id,
+            rollout_path,
+            cwd,
+            created_at,
+            git_branch,
+            source,
+            model_provider,
+        } = header
+        else {
+            return None;
+        };
+        Some(Self {
+            thread_id,
+            rollout_path,
+            cwd,
+            created_at,
+            git_branch,
+            source,
+            model_provider,
+            models: Vec::new(),
+            entries: Vec::new(),
+        })
+    }
+
+    fn push(&mut self, line: IndexLine) {
+        match line {
+            IndexLine::Model { model } => {
+                if !self.models.contains(&model) {
+                    self.models.push(model);
+                }
+            }
+            IndexLine::Entry { kind, text } => self.entries.push((kind, text)),
+            IndexLine::Session { .. } => {}
+        }
+    }
+
+    /// The filters the header alone can answer.
+    fn passes_header_filters(&self, query: &SessionSearchQuery) -> bool {
+        if let Some(cwd) = &query.cwd
+            && !self.cwd.starts_with(cwd)
+        {
+            return false;
+        }
+        if query.since.is_some() || query.until.is_some() {
+            let Some(date) = self
+                .created_at
+                .get(..10)
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            else {
+                return false;
+            };
+            if query.since.is_some_and(|since| date < since)
+                || query.until.is_some_and(|until| date > until)
+            {
+                return false;
+            }
+        }
+        if !query.sources.is_empty() && !query.sources.contains(&self.source) {
+            return false;
+        }
+        if let Some(providers) = &query.model_providers {
+            let provider = self
+                .model_provider
+                .as_deref()
+                .unwrap_or(&query.default_provider);
+            if !providers.iter().any(|candidate| candidate == provider) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn passes_entry_filters(&self, query: &SessionSearchQuery) -> bool {
+        if let Some(model) = &query.model {
+            let model = fold_case(model);
+            if !self.models.iter().any(|m| fold_case(m).contains(&model)) {
+                return false;
+            }
+        }
+        if let Some(file) = &query.file {
+            let file = fold_case(file);
+            if !self.entries.iter().any(|(kind, text)| {
+                *kind == SearchEntryKind::File && fold_case(text).contains(&file)
+            }) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The best matching entries, or `None` when some word appears nowhere in the session.
+    fn matches(&self, terms: &[String]) -> Option<Vec<SearchMatch>> {
+        let mut found = vec![false; terms.len()];
+        let mut scored = Vec::new();
+        for (kind, text) in &self.entries {
+            let folded = fold_case(text);
+            let hits: Vec<usize> = terms
+                .iter()
+                .enumerate()
+                .filter(|(_, term)| folded.contains(term.as_str()))
+                .map(|(index, _)| index)
+                .collect();
+            let Some(first) = hits.first() else {
+                continue;
+            };
+            for index in &hits {
+                found[*index] = true;
+            }
+            scored.push((hits.len(), *kind, snippet(text, &terms[*first])));
+        }
+        if found.contains(&false) {
+            return None;
+        }
+        // Stable, so equally good entries stay in session order.
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        Some(
+            scored
+                .into_iter()
+                .take(MAX_MATCHES_PER_SESSION)
+                .map(|(_, kind, snippet)| SearchMatch { kind, snippet })
+                .collect(),
+        )
+    }
+}
+
+fn search_index(
+    codex_home: &Path,
+    query: &SessionSearchQuery,
+) -> io::Result<Vec<SessionSearchHit>> {
+    let index_dir = codex_home.join(SESSION_INDEX_SUBDIR);
+    let terms: Vec<String> = query.text.split_whitespace().map(fold_case).collect();
+    let mut sessions: Vec<String> = match sessions_with_terms(&index_dir, &terms)? {
+        Some(sessions) => sessions.into_iter().collect(),
+        None => all_sessions(&index_dir)?,
+    };
+    // Index files are named after their rollouts, whose names start with the
+    // time the session started.
+    sessions.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut hits = Vec::new();
+    for session in sessions {
+        if query.limit > 0 && hits.len() >= query.limit {
+            break;
+        }
+        if let Some(hit) = search_session(&index_dir.join(session), query, &terms)? {
+            hits.push(hit);
+        }
+    }
+    hits.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(hits)
+}
+
+/// The sessions that have a word starting with each word of `terms`, or
+/// `None` when no word is long enough to look up.
+fn sessions_with_terms(index_dir: &Path, terms: &[String]) -> io::Result<Option<HashSet<String>>> {
+    let mut sessions: Option<HashSet<String>> = None;
+    for word in terms.iter().flat_map(|term| index_terms(term)) {
+        let shard = match fs::read_to_string(term_shard_path(index_dir, &word)) {
+            Ok(shard) => shard,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let with_word: HashSet<String> = shard
+            .lines()
+            .filter_map(|line| serde_json::from_str::<TermPosting>(line).ok())
+            .filter(|posting| posting.term.starts_with(&word))
+            .map(|posting| posting.session)
+            .collect();
+        let narrowed = match sessions.take() {
+            Some(mut sessions) => {
+                sessions.retain(|session| with_word.contains(session));
+                sessions
+            }
+            None => with_word,
+        };
+        let none_left = narrowed.is_empty();
+        sessions = Some(narrowed);
+        if none_left {
+            break;
+        }
+    }
+    Ok(sessions)
+}
+
+fn all_sessions(index_dir: &Path) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(index_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".jsonl"))
+        .collect())
+}
+
+/// Reads one session index, checking the filters its header answers before
+/// reading the rest of it.
+fn search_session(
+    index_path: &Path,
+    query: &SessionSearchQuery,
+    terms: &[String],
+) -> io::Result<Option<SessionSearchHit>> {
+    let file = match File::open(index_path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut lines = BufReader::new(file).lines();
+    let Some(mut session) = lines
+        .next()
+        .transpose()?
+        .and_then(|line| serde_json::from_str(&line).ok())
+        .and_then(IndexedSession::from_header)
+    else {
+        return Ok(None);
+    };
+    if !session.passes_header_filters(query) {
+        return Ok(None);
+    }
+    // The rollout may have been archived or deleted without its index.
+    let metadata = match fs::metadata(&session.rollout_path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            remove_index_file(index_path)?;
+            return Ok(None);
+        }
+        Err(err) => return Err(err),
+    };
+
+    for line in lines {
+        if let Ok(line) = serde_json::from_str(&line?) {
+            session.push(line);
+        }
+    }
+    if !session.passes_entry_filters(query) {
+        return Ok(None);
+    }
+    let Some(matches) = session.matches(terms) else {
+        return Ok(None);
+    };
+    let updated_at = metadata
+        .modified()
+        .ok()
+        .map(|modified| DateTime::<Utc>::from(modified).to_rfc3339_opts(SecondsFormat::Secs, true));
+    Ok(Some(SessionSearchHit {
+        thread_id: session.thread_id,
+        rollout_path: session.rollout_path,
+        cwd: session.cwd,
+        created_at: session.created_at,
+        updated_at,
+        git_branch: session.git_branch,
+        models: session.models,
+        matches,
+    }))
+}
+
+/// Lowercases one char at a time so the result has as many chars as `text`.
+fn fold_case(text: &str) -> String {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// A single line of `text` around the first occurrence of `term`.
+fn snippet(text: &str, term: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = flat.chars().collect();
+    let folded = fold_case(&flat);
+    let start = folded
+        .find(term)
+        .map(|byte| folded[..byte].chars().count())
+        .unwrap_or(0)
+        .saturating_sub(SNIPPET_LEAD_CHARS);
+    let end = (start + SNIPPET_MAX_CHARS).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::config_types::ReasoningSummary;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::GitInfo;
+    use codex_protocol::protocol::SandboxPolicy;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use codex_protocol::protocol::TurnContextItem;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn session_meta(id: ThreadId, timestamp: &str, cwd: &str) -> RolloutItem {
+        RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta {
+                id,
+                timestamp: timestamp.to_string(),
+                cwd: PathBuf::from(cwd),
+                ..Default::default()
+            },
+            git: Some(GitInfo {
+                commit_hash: None,
+                branch: Some("main".to_string()),
+                repository_url: None,
+            }),
+        })
+    }
+
+    fn turn_context(model: &str) -> RolloutItem {
+        RolloutItem::TurnContext(TurnContextItem {
+            cwd: PathBuf::from("/repo"),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: model.to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            base_instructions: None,
+            user_instructions: None,
+            developer_instructions: None,
+            final_output_json_schema: None,
+            truncation_policy: None,
+        })
+    }
+
+    fn user_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+        }))
+    }
+
+    fn agent_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        }))
+    }
+
+    fn function_call(name: &str, arguments: Value) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: "call-1".to_string(),
+        })
+    }
+
+    fn apply_patch(path: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id: "call-2".to_string(),
+            name: "apply_patch".to_string(),
+            input: format!("*** Begin Patch\n*** Add File: {path}\n+fixed\n*** End Patch"),
+        })
+    }
+
+    fn write_rollout(codex_home: &Path, day: &str, name: &str, items: &[RolloutItem]) -> PathBuf {
+        let dir = codex_home.join(SESSIONS_SUBDIR).join(day);
+        fs::create_dir_all(&dir).expect("create sessions dir");
+        let path = dir.join(name);
+        let mut contents = String::new();
+        for item in items {
+            let line = RolloutLine {
+                timestamp: "2025-01-02T10:00:00.000Z".to_string(),
+                item: item.clone(),
+            };
+            contents.push_str(&serde_json::to_string(&line).expect("serialize line"));
+            contents.push('\n');
+        }
+        fs::write(&path, contents).expect("write rollout");
+        path
+    }
+
+    fn entry_line(kind: SearchEntryKind, text: &str) -> IndexLine {
+        IndexLine::Entry {
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn indexes_messages_commands_and_patched_files() {
+        let id = ThreadId::new();
+        let mut indexer = SessionIndexer::new(PathBuf::from("/sessions/rollout-a.jsonl"));
+        let items = [
+            session_meta(id, "2025-01-02T10:00:00.000Z", "/repo"),
+            turn_context("gpt-5"),
+            user_message("Fix the OAuth refresh bug"),
+            turn_context("gpt-5"),
+            function_call(
+                "shell",
+                serde_json::json!({"command": ["bash", "-lc", "rg refresh_token src"]}),
+            ),
+            function_call(
+                "shell_command",
+                serde_json::json!({"command": "cargo test -p auth"}),
+            ),
+            apply_patch("src/auth.rs"),
+            apply_patch("src/auth.rs"),
+            agent_message("The refresh token is now rotated before it expires."),
+        ];
+        let lines: Vec<IndexLine> = items
+            .iter()
+            .flat_map(|item| indexer.index_item(item))
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                IndexLine::Session {
+                    thread_id: id,
+                    rollout_path: PathBuf::from("/sessions/rollout-a.jsonl"),
+                    cwd: PathBuf::from("/repo"),
+                    created_at: "2025-01-02T10:00:00.000Z".to_string(),
+                    git_branch: Some("main".to_string()),
+                    source: SessionSource::VSCode,
+                    model_provider: None,
+                },
+                IndexLine::Model {
+                    model: "gpt-5".to_string(),
+                },
+                entry_line(SearchEntryKind::UserMessage, "Fix the OAuth refresh bug"),
+                entry_line(SearchEntryKind::Command, "rg refresh_token src"),
+                entry_line(SearchEntryKind::Command, "cargo test -p auth"),
+                entry_line(SearchEntryKind::File, "/repo/src/auth.rs"),
+                entry_line(
+                    SearchEntryKind::AgentMessage,
+                    "The refresh token is now rotated before it expires.",
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn search_indexes_old_rollouts_and_applies_filters() {
+        let home = TempDir::new().expect("tempdir");
+        let oauth_id = ThreadId::new();
+        let docs_id = ThreadId::new();
+        let oauth_path = write_rollout(
+            home.path(),
+            "2025/01/02",
+            "rollout-2025-01-02T10-00-00-a.jsonl",
+            &[
+                session_meta(oauth_id, "2025-01-02T10:00:00.000Z", "/repo"),
+                turn_context("gpt-5-codex"),
+                user_message("Why does the OAuth token refresh fail?"),
+                apply_patch("src/auth.rs"),
+                agent_message("Fixed the refresh race in auth.rs."),
+            ],
+        );
+        write_rollout(
+            home.path(),
+            "2025/01/05",
+            "rollout-2025-01-05T10-00-00-b.jsonl",
+            &[
+                session_meta(docs_id, "2025-01-05T10:00:00.000Z", "/docs"),
+                turn_context("gpt-5-mini"),
+                user_message("Document the token refresh flow"),
+            ],
+        );
+        let search = |query: SessionSearchQuery| {
+            let codex_home = home.path().to_path_buf();
+            async move {
+                search_sessions(&codex_home, query)
+                    .await
+                    .expect("search")
+                    .into_iter()
+                    .map(|hit| hit.thread_id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let hits = search_sessions(
+            home.path(),
+            SessionSearchQuery {
+                text: "oauth REFRESH".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].thread_id, oauth_id);
+        assert_eq!(hits[0].rollout_path, oauth_path);
+        assert_eq!(hits[0].models, vec!["gpt-5-codex".to_string()]);
+        assert_eq!(
+            hits[0].matches,
+            vec![
+                SearchMatch {
+                    kind: SearchEntryKind::UserMessage,
+                    snippet: "Why does the OAuth token refresh fail?".to_string(),
+                },
+                SearchMatch {
+                    kind: SearchEntryKind::AgentMessage,
+                    snippet: "Fixed the refresh race in auth.rs.".to_string(),
+                },
+            ]
+        );
+        assert!(
+            home.path()
+                .join(SESSION_INDEX_SUBDIR)
+                .join("rollout-2025-01-02T10-00-00-a.jsonl")
+                .exists()
+        );
+
+        let refresh = |query: SessionSearchQuery| SessionSearchQuery {
+            text: "refresh".to_string(),
+            ..query
+        };
+        assert_eq!(
+            search(refresh(SessionSearchQuery::default())).await,
+            vec![docs_id, oauth_id]
+        );
+        assert_eq!(
+            search(refresh(SessionSearchQuery {
+                limit: 1,
+                ..Default::default()
+            }))
+            .await,
+            vec![docs_id]
+        );
+        assert_eq!(
+            search(refresh(SessionSearchQuery {
+                cwd: Some(PathBuf::from("/repo")),
+                ..Default::default()
+            }))
+            .await,
+            vec![oauth_id]
+        );
+        assert_eq!(
+            search(refresh(SessionSearchQuery {
+                since: NaiveDate::from_ymd_opt(2025, 1, 3),
+                ..Default::default()
+            }))
+            .await,
+            vec![docs_id]
+        );
+        assert_eq!(
+            search(refresh(SessionSearchQuery {
+                until: NaiveDate::from_ymd_opt(2025, 1, 2),
+                ..Default::default()
+            }))
+            .await,
+            vec![oauth_id]
+        );
+        assert_eq!(
+            search(refresh(SessionSearchQuery {
+                model: Some("mini".to_string()),
+                ..Default::default()
+            }))
+            .await,
+            vec![docs_id]
+        );
+        assert_eq!(
+            search(SessionSearchQuery {
+                file: Some("auth.rs".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec![oauth_id]
+        );
+    }
+
+    #[tokio::test]
+    async fn writer_appends_to_a_resumed_session() {
+        let home = TempDir::new().expect("tempdir");
+        let id = ThreadId::new();
+        let rollout_path = write_rollout(
+            home.path(),
+            "2025/01/02",
+            "rollout-2025-01-02T10-00-00-a.jsonl",
+            &[
+                session_meta(id, "2025-01-02T10:00:00.000Z", "/repo"),
+                apply_patch("src/auth.rs"),
+            ],
+        );
+
+        let mut writer = SessionIndexWriter::open(home.path(), &rollout_path)
+            .await
+            .expect("open index");
+        writer
+            .record(&user_message("Now rotate the signing keys"))
+            .await
+            .expect("record");
+        drop(writer);
+
+        // A reopened index remembers the files it has already seen.
+        let mut writer = SessionIndexWriter::open(home.path(), &rollout_path)
+            .await
+            .expect("reopen index");
+        writer
+            .record(&apply_patch("src/auth.rs"))
+            .await
+            .expect("record");
+
+        let index_path = session_index_path(home.path(), &rollout_path).expect("index path");
+        let lines = read_index_lines(&index_path).expect("read index");
+        assert_eq!(
+            lines[1..],
+            [
+                entry_line(SearchEntryKind::File, "/repo/src/auth.rs"),
+                entry_line(SearchEntryKind::UserMessage, "Now rotate the signing keys"),
+            ]
+        );
+
+        // The writer adds its words to the term index as well.
+        let hits = search_indexed_sessions(
+            home.path(),
+            SessionSearchQuery {
+                text: "signing".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].thread_id, id);
+    }
+
+    #[tokio::test]
+    async fn search_applies_session_filters_and_forgets_removed_rollouts() {
+        let home = TempDir::new().expect("tempdir");
+        let meta = |id, source, model_provider: Option<&str>| {
+            RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id,
+                    timestamp: "2025-01-02T10:00:00.000Z".to_string(),
+                    cwd: PathBuf::from("/repo"),
+                    source,
+                    model_provider: model_provider.map(str::to_string),
+                    ..Default::default()
+                },
+                git: None,
+            })
+        };
+        let cli_id = ThreadId::new();
+        let exec_id = ThreadId::new();
+        let ollama_id = ThreadId::new();
+        let cli_path = write_rollout(
+            home.path(),
+            "2025/01/02",
+            "rollout-2025-01-02T10-00-00-a.jsonl",
+            &[
+                meta(cli_id, SessionSource::Cli, None),
+                user_message("Fix the token refresh"),
+            ],
+        );
+        write_rollout(
+            home.path(),
+            "2025/01/02",
+            "rollout-2025-01-02T11-00-00-b.jsonl",
+            &[
+                meta(exec_id, SessionSource::Exec, Some("openai")),
+                user_message("Fix the token refresh"),
+            ],
+        );
+        let ollama_path = write_rollout(
+            home.path(),
+            "2025/01/02",
+            "rollout-2025-01-02T12-00-00-c.jsonl",
+            &[
+                meta(ollama_id, SessionSource::Cli, Some("ollama")),
+                user_message("Fix the token refresh"),
+            ],
+        );
+        let search = |text: &str, model_providers: Option<Vec<String>>| {
+            let codex_home = home.path().to_path_buf();
+            let query = SessionSearchQuery {
+                text: text.to_string(),
+                sources: vec![SessionSource::Cli],
+                model_providers,
+                default_provider: "openai".to_string(),
+                ..Default::default()
+            };
+            async move {
+                search_sessions(&codex_home, query)
+                    .await
+                    .expect("search")
+                    .into_iter()
+                    .map(|hit| hit.thread_id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(search("refresh", None).await, vec![ollama_id, cli_id]);
+        assert_eq!(
+            search("refresh", Some(vec!["openai".to_string()])).await,
+            vec![cli_id]
+        );
+        // Words match at their start only.
+        assert_eq!(search("refr TOK", None).await, vec![ollama_id, cli_id]);
+        assert_eq!(search("efresh", None).await, Vec::<ThreadId>::new());
+
+        fs::remove_file(&cli_path).expect("delete rollout");
+        remove_session_index(home.path(), &ollama_path)
+            .await
+            .expect("remove index");
+        fs::remove_file(&ollama_path).expect("archive rollout");
+        assert_eq!(search("refresh", None).await, Vec::<ThreadId>::new());
+        assert!(
+            !session_index_path(home.path(), &cli_path)
+                .expect("index path")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn snippet_is_one_line_around_the_match() {
+        let text = format!(
+            "{}\n\nthe OAuth refresh failed\n{}",
+            "a".repeat(100),
+            "b".repeat(200)
+        );
+
+        assert_eq!(
+            snippet(&text, "oauth"),
+            format!(
+                "…{} the OAuth refresh failed {}…",
+                "a".repeat(35),
+                "b".repeat(99)
+            )
+        );
+        assert_eq!(snippet("Short message", "message"), "Short message");
+    }
+}
@@ -189,11 +189,26 @@ fn is_write_patch_constrained_to_writable_paths(
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::Delete { .. }
+            | ApplyPatchFileChange::Copy { .. }
+            | ApplyPatchFileChange::Mode { .. }
+            | ApplyPatchFileChange::Binary { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
             }
+            ApplyPatchFileChange::Symlink { target } => {
+                // Later patches write through the link, so its target must be
+                // writable too. A relative target is relative to the link's directory.
+                let target = match path.parent() {
+                    Some(parent) => parent.join(target),
+                    None => target.clone(),
+                };
+                if !is_path_writable(path) || !is_path_writable(&target) {
+                    return false;
+                }
+            }
             ApplyPatchFileChange::Update { move_path, .. } => {
                 if !is_path_writable(path) {
                     return false;
@@ -237,6 +252,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -258,6 +275,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
@@ -105,10 +105,32 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
-    let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
-        "; allow read-only file operations\n(allow file-read*)"
-    } else {
-        ""
+    // Reads are allowed everywhere; `deny_read_paths` are carved back out
+    // below. Seatbelt applies the last matching rule, so the deny has to come
+    // after both the read and the write allowances.
+    let file_read_policy = "; allow read-only file operations\n(allow file-read*)";
+
+    let (file_deny_policy, file_deny_params) = {
+        let mut denied_path_policies: Vec<String> = Vec::new();
+        let mut file_deny_params = Vec::new();
+        for (index, denied) in sandbox_policy.get_deny_read_paths().iter().enumerate() {
+            let canonical_denied = denied
+                .as_path()
+                .canonicalize()
+                .unwrap_or_else(|_| denied.to_path_buf());
+            let denied_param = format!("DENY_READ_{index}");
+            denied_path_policies.push(format!("(subpath (param \"{denied_param}\"))"));
+            file_deny_params.push((denied_param, canonical_denied));
+        }
+        if denied_path_policies.is_empty() {
+            (String::new(), Vec::new())
+        } else {
+            let file_deny_policy = format!(
+                "\n(deny file-read* file-write*\n{}\n)",
+                denied_path_policies.join(" ")
+            );
+            (file_deny_policy, file_deny_params)
+        }
     };
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
@@ -119,10 +141,10 @@ pub(crate) fn create_seatbelt_command_args(
     };
 
     let full_policy = format!(
-        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
+        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}{file_deny_policy}\n{network_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, file_deny_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -200,6 +222,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         };
 
         // Create the Seatbelt command to wrap a shell command that tries to
@@ -385,6 +409,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         };
 
         let shell_command: Vec<String> = [
@@ -470,6 +496,77 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn create_seatbelt_args_with_configured_carve_outs() {
+        let tmp = TempDir::new().expect("tempdir");
+        let PopulatedTmp {
+            empty_root,
+            empty_root_canonical,
+            ..
+        } = populate_tmpdir(tmp.path());
+        let secrets = empty_root.join("secrets");
+        fs::create_dir_all(&secrets).expect("create secrets");
+        let dot_env = empty_root.join(".env");
+        fs::write(&dot_env, "TOKEN=1").expect("write .env");
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            read_only_subpaths: vec![secrets.as_path().try_into().unwrap()],
+            deny_read_paths: vec![dot_env.as_path().try_into().unwrap()],
+        };
+
+        let shell_command: Vec<String> = vec!["cat".to_string(), ".env".to_string()];
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &empty_root);
+
+        // Denied paths are read-only under their writable root and are also
+        // excluded from reads by a trailing deny rule.
+        let expected_policy = format!(
+            r#"{MACOS_SEATBELT_BASE_POLICY}
+; allow read-only file operations
+(allow file-read*)
+(allow file-write*
+(require-all (subpath (param "WRITABLE_ROOT_0")) (require-not (subpath (param "WRITABLE_ROOT_0_RO_0"))) (require-not (subpath (param "WRITABLE_ROOT_0_RO_1"))) )
+)
+(deny file-read* file-write*
+(subpath (param "DENY_READ_0"))
+)
+"#,
+        );
+
+        let mut expected_args = vec![
+            "-p".to_string(),
+            expected_policy,
+            format!(
+                "-DWRITABLE_ROOT_0={}",
+                empty_root_canonical.to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_0_RO_0={}",
+                empty_root_canonical.join("secrets").to_string_lossy()
+            ),
+            format!(
+                "-DWRITABLE_ROOT_0_RO_1={}",
+                empty_root_canonical.join(".env").to_string_lossy()
+            ),
+            format!(
+                "-DDENY_READ_0={}",
+                empty_root_canonical.join(".env").to_string_lossy()
+            ),
+        ];
+        expected_args.extend(
+            macos_dir_params()
+                .into_iter()
+                .map(|(key, value)| format!("-D{key}={value}", value = value.to_string_lossy())),
+        );
+        expected_args.push("--".to_string());
+        expected_args.extend(shell_command);
+
+        assert_eq!(expected_args, args);
+    }
+
     struct PopulatedTmp {
         /// Path containing a .git and .codex subfolder.
         /// For the purposes of this test, we consider this a "vulnerable" root
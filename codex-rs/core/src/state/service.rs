@@ -4,8 +4,10 @@ use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::exec_policy::ExecPolicyManager;
+use crate::lsp::LspManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::network_proxy::NetworkProxy;
 use crate::skills::SkillsManager;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
@@ -30,4 +32,8 @@ pub(crate) struct SessionServices {
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
     pub(crate) agent_control: AgentControl,
+    /// Filtering proxy that sandboxed commands reach the network through, when enabled.
+    pub(crate) network_proxy: Option<NetworkProxy>,
+    /// Language servers started by the code navigation tools.
+    pub(crate) lsp: LspManager,
 }
@@ -1,5 +1,6 @@
 //! Session-wide mutable state.
 
+use codex_git::GhostCommit;
 use codex_protocol::models::ResponseItem;
 
 use crate::codex::SessionConfiguration;
@@ -14,6 +15,17 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Working tree states replaced by undo, newest last.
+    pub(crate) redo_stack: Vec<RedoEntry>,
+}
+
+/// A working tree state replaced by an undo, kept for redo.
+#[derive(Debug, Clone)]
+pub(crate) struct RedoEntry {
+    /// Snapshot of the working tree taken just before the undo.
+    pub(crate) ghost_commit: GhostCommit,
+    /// History items the undo removed, by their index in ascending order.
+    pub(crate) removed_items: Vec<(usize, ResponseItem)>,
 }
 
 impl SessionState {
@@ -24,6 +36,7 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            redo_stack: Vec::new(),
         }
     }
 
@@ -40,8 +53,17 @@ impl SessionState {
         self.history.clone()
     }
 
+    /// Replaces the history. Redo entries locate their items by index in the
+    /// history they were taken from, so the redo stack is dropped with it.
     pub(crate) fn replace_history(&mut self, items: Vec<ResponseItem>) {
         self.history.replace(items);
+        self.redo_stack.clear();
+    }
+
+    /// Replaces the history on behalf of undo and redo, which keep the redo
+    /// stack in step with the history themselves.
+    pub(crate) fn replace_history_keeping_redo(&mut self, items: Vec<ResponseItem>) {
+        self.history.replace(items);
     }
 
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
@@ -110,6 +110,7 @@ impl SessionTask for GhostSnapshotTask {
                                     ghost_commit: ghost_commit.clone(),
                                 }])
                                 .await;
+                            session.session.clear_redo().await;
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
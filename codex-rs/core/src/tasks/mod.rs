@@ -34,6 +34,9 @@ pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
 pub(crate) use undo::UndoTask;
+pub(crate) use undo::UndoTarget;
+pub(crate) use undo::undo_snapshot_diff;
+pub(crate) use undo::undo_timeline;
 pub(crate) use user_shell::UserShellCommandTask;
 
 const GRACEFULL_INTERRUPTION_TIMEOUT_MS: u64 = 100;
@@ -206,7 +209,7 @@ impl Session {
     async fn close_unified_exec_processes(&self) {
         self.services
             .unified_exec_manager
-            .terminate_all_processes()
+            .terminate_turn_processes()
             .await;
     }
 
@@ -1,15 +1,31 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::event_mapping::parse_turn_item;
 use crate::protocol::EventMsg;
 use crate::protocol::UndoCompletedEvent;
+use crate::protocol::UndoSnapshot;
+use crate::protocol::UndoSnapshotDiffEvent;
+use crate::protocol::UndoSnapshotFile;
 use crate::protocol::UndoStartedEvent;
+use crate::state::RedoEntry;
 use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
+use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
+use codex_git::GitToolingError;
 use codex_git::RestoreGhostCommitOptions;
+use codex_git::SnapshotFileChange;
+use codex_git::create_ghost_commit;
+use codex_git::diff_ghost_commits;
+use codex_git::ghost_commit_changes;
+use codex_git::restore_ghost_commit_paths;
 use codex_git::restore_ghost_commit_with_options;
+use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
@@ -17,11 +33,30 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-pub(crate) struct UndoTask;
+/// What an `UndoTask` brings the working tree back to.
+pub(crate) enum UndoTarget {
+    /// The most recent ghost snapshot.
+    Latest,
+    /// A given ghost snapshot, or only `paths` from it when that is not empty.
+    Snapshot {
+        commit_id: String,
+        paths: Vec<PathBuf>,
+    },
+    /// The working tree replaced by the last undo.
+    Redo,
+}
+
+pub(crate) struct UndoTask {
+    target: UndoTarget,
+}
 
 impl UndoTask {
     pub(crate) fn new() -> Self {
-        Self
+        Self::with_target(UndoTarget::Latest)
+    }
+
+    pub(crate) fn with_target(target: UndoTarget) -> Self {
+        Self { target }
     }
 }
 
@@ -39,10 +74,14 @@ impl SessionTask for UndoTask {
         cancellation_token: CancellationToken,
     ) -> Option<String> {
         let sess = session.clone_session();
+        let label = match self.target {
+            UndoTarget::Redo => "Redo",
+            UndoTarget::Latest | UndoTarget::Snapshot { .. } => "Undo",
+        };
         sess.send_event(
             ctx.as_ref(),
             EventMsg::UndoStarted(UndoStartedEvent {
-                message: Some("Undo in progress...".to_string()),
+                message: Some(format!("{label} in progress...")),
             }),
         )
         .await;
@@ -52,70 +91,410 @@ impl SessionTask for UndoTask {
                 ctx.as_ref(),
                 EventMsg::UndoCompleted(UndoCompletedEvent {
                     success: false,
-                    message: Some("Undo cancelled.".to_string()),
+                    message: Some(format!("{label} cancelled.")),
                 }),
             )
             .await;
             return None;
         }
 
-        let mut history = sess.clone_history().await;
-        let mut items = history.get_history();
-        let mut completed = UndoCompletedEvent {
-            success: false,
-            message: None,
+        let result = match &self.target {
+            UndoTarget::Latest => restore_snapshot(&sess, &ctx, None, &[]).await,
+            UndoTarget::Snapshot { commit_id, paths } => {
+                restore_snapshot(&sess, &ctx, Some(commit_id), paths).await
+            }
+            UndoTarget::Redo => redo(&sess, &ctx).await,
         };
-
-        let Some((idx, ghost_commit)) =
-            items
-                .iter()
-                .enumerate()
-                .rev()
-                .find_map(|(idx, item)| match item {
-                    ResponseItem::GhostSnapshot { ghost_commit } => {
-                        Some((idx, ghost_commit.clone()))
-                    }
-                    _ => None,
-                })
-        else {
-            completed.message = Some("No ghost snapshot available to undo.".to_string());
-            sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
-                .await;
-            return None;
+        let completed = match result {
+            Ok(message) => UndoCompletedEvent {
+                success: true,
+                message: Some(message),
+            },
+            Err(message) => UndoCompletedEvent {
+                success: false,
+                message: Some(message),
+            },
         };
 
-        let commit_id = ghost_commit.id().to_string();
-        let repo_path = ctx.cwd.clone();
-        let ghost_snapshot = ctx.ghost_snapshot.clone();
-        let restore_result = tokio::task::spawn_blocking(move || {
-            let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
-            restore_ghost_commit_with_options(&options, &ghost_commit)
-        })
-        .await;
+        sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+            .await;
+        None
+    }
+}
 
-        match restore_result {
-            Ok(Ok(())) => {
-                items.remove(idx);
-                sess.replace_history(items).await;
-                let short_id: String = commit_id.chars().take(7).collect();
-                info!(commit_id = commit_id, "Undo restored ghost snapshot");
-                completed.success = true;
-                completed.message = Some(format!("Undo restored snapshot {short_id}."));
-            }
-            Ok(Err(err)) => {
-                let message = format!("Failed to restore snapshot {commit_id}: {err}");
-                warn!("{message}");
-                completed.message = Some(message);
-            }
+/// Restores a snapshot (the latest one without `commit_id`) and keeps the
+/// replaced working tree for redo.
+async fn restore_snapshot(
+    sess: &Session,
+    ctx: &TurnContext,
+    commit_id: Option<&str>,
+    paths: &[PathBuf],
+) -> Result<String, String> {
+    let mut items = sess.clone_history().await.get_history();
+    let snapshots = ghost_snapshots(&items);
+    let snapshot = match commit_id {
+        Some(commit_id) => snapshots
+            .into_iter()
+            .find(|snapshot| snapshot.ghost_commit.id() == commit_id)
+            .ok_or_else(|| format!("No ghost snapshot {commit_id} in this session."))?,
+        None => snapshots
+            .into_iter()
+            .last()
+            .ok_or_else(|| "No ghost snapshot available to undo.".to_string())?,
+    };
+
+    let GhostSnapshotEntry {
+        index,
+        ghost_commit,
+        ..
+    } = snapshot;
+    let commit_id = ghost_commit.id().to_string();
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let paths = paths.to_vec();
+    let restored_files = paths.len();
+    let restore_result = tokio::task::spawn_blocking(move || {
+        // Captured first so that redo can bring the replaced state back.
+        let current = create_ghost_commit(
+            &CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot.clone()),
+        )?;
+        let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+        if paths.is_empty() {
+            restore_ghost_commit_with_options(&options, &ghost_commit)?;
+        } else {
+            restore_ghost_commit_paths(&options, &ghost_commit, &paths)?;
+        }
+        Ok::<_, GitToolingError>(current)
+    })
+    .await;
+
+    let current = match restore_result {
+        Ok(Ok(current)) => current,
+        Ok(Err(err)) => {
+            let message = format!("Failed to restore snapshot {commit_id}: {err}");
+            warn!("{message}");
+            return Err(message);
+        }
+        Err(err) => {
+            let message = format!("Failed to restore snapshot {commit_id}: {err}");
+            error!("{message}");
+            return Err(message);
+        }
+    };
+
+    // A full restore takes this snapshot and the later ones off the timeline.
+    let removed_items = if restored_files == 0 {
+        remove_snapshots_from(&mut items, index)
+    } else {
+        Vec::new()
+    };
+    if !removed_items.is_empty() {
+        sess.replace_history_keeping_redo(items).await;
+    }
+    sess.push_redo(RedoEntry {
+        ghost_commit: current,
+        removed_items,
+    })
+    .await;
+
+    let short_id = short_commit_id(&commit_id);
+    info!(commit_id = commit_id, "Undo restored ghost snapshot");
+    Ok(match restored_files {
+        0 => format!("Undo restored snapshot {short_id}."),
+        1 => format!("Undo restored 1 file from snapshot {short_id}."),
+        count => format!("Undo restored {count} files from snapshot {short_id}."),
+    })
+}
+
+/// Brings back the working tree replaced by the last undo, along with the
+/// snapshots that undo took off the timeline.
+async fn redo(sess: &Session, ctx: &TurnContext) -> Result<String, String> {
+    let Some(entry) = sess.pop_redo().await else {
+        return Err("Nothing to redo.".to_string());
+    };
+
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let ghost_commit = entry.ghost_commit.clone();
+    let restore_result = tokio::task::spawn_blocking(move || {
+        let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+        restore_ghost_commit_with_options(&options, &ghost_commit)
+    })
+    .await;
+
+    let failure = match restore_result {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err.to_string()),
+        Err(err) => Some(err.to_string()),
+    };
+    if let Some(err) = failure {
+        let message = format!("Failed to redo: {err}");
+        warn!("{message}");
+        sess.push_redo(entry).await;
+        return Err(message);
+    }
+
+    if !entry.removed_items.is_empty() {
+        let mut items = sess.clone_history().await.get_history();
+        reinsert_items(&mut items, entry.removed_items);
+        sess.replace_history_keeping_redo(items).await;
+    }
+    info!(
+        commit_id = entry.ghost_commit.id(),
+        "Redo restored ghost snapshot"
+    );
+    Ok("Redo restored the changes from before the undo.".to_string())
+}
+
+/// The timeline of ghost snapshots, each with the files its turn changed. The
+/// newest snapshot is compared with the current working tree.
+pub(crate) async fn undo_timeline(ctx: &TurnContext, items: &[ResponseItem]) -> Vec<UndoSnapshot> {
+    let snapshots = ghost_snapshots(items);
+    if snapshots.is_empty() {
+        return Vec::new();
+    }
+
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let timeline = tokio::task::spawn_blocking(move || {
+        let current = match create_ghost_commit(
+            &CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot),
+        ) {
+            Ok(current) => Some(current.id().to_string()),
             Err(err) => {
-                let message = format!("Failed to restore snapshot {commit_id}: {err}");
-                error!("{message}");
-                completed.message = Some(message);
+                warn!("failed to snapshot the working tree for the undo timeline: {err}");
+                None
             }
+        };
+        let next_ids: Vec<Option<String>> = snapshots
+            .iter()
+            .skip(1)
+            .map(|snapshot| Some(snapshot.ghost_commit.id().to_string()))
+            .chain(std::iter::once(current))
+            .collect();
+
+        snapshots
+            .into_iter()
+            .zip(next_ids)
+            .map(|(snapshot, next_id)| {
+                let commit_id = snapshot.ghost_commit.id().to_string();
+                let files = match next_id {
+                    Some(next_id) => match ghost_commit_changes(&repo_path, &commit_id, &next_id) {
+                        Ok(changes) => changes.into_iter().map(snapshot_file).collect(),
+                        Err(err) => {
+                            warn!("failed to diff ghost snapshot {commit_id}: {err}");
+                            Vec::new()
+                        }
+                    },
+                    None => Vec::new(),
+                };
+                UndoSnapshot {
+                    commit_id,
+                    summary: snapshot.summary,
+                    files,
+                }
+            })
+            .collect()
+    })
+    .await;
+
+    timeline.unwrap_or_else(|err| {
+        error!("undo timeline task failed: {err}");
+        Vec::new()
+    })
+}
+
+/// The diff from a ghost snapshot to the current working tree.
+pub(crate) async fn undo_snapshot_diff(
+    ctx: &TurnContext,
+    items: &[ResponseItem],
+    commit_id: String,
+) -> Result<UndoSnapshotDiffEvent, String> {
+    let Some(ghost_commit) = ghost_snapshots(items)
+        .into_iter()
+        .map(|snapshot| snapshot.ghost_commit)
+        .find(|ghost_commit| ghost_commit.id() == commit_id)
+    else {
+        return Err(format!("No ghost snapshot {commit_id} in this session."));
+    };
+
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let diff_result = tokio::task::spawn_blocking(move || {
+        let current = create_ghost_commit(
+            &CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot),
+        )?;
+        let diff = diff_ghost_commits(&repo_path, ghost_commit.id(), current.id())?;
+        let files = ghost_commit_changes(&repo_path, ghost_commit.id(), current.id())?;
+        Ok::<_, GitToolingError>((diff, files))
+    })
+    .await;
+
+    match diff_result {
+        Ok(Ok((diff, files))) => Ok(UndoSnapshotDiffEvent {
+            commit_id,
+            diff,
+            files: files.into_iter().map(snapshot_file).collect(),
+        }),
+        Ok(Err(err)) => Err(format!("Failed to diff snapshot {commit_id}: {err}")),
+        Err(err) => Err(format!("Failed to diff snapshot {commit_id}: {err}")),
+    }
+}
+
+/// A ghost snapshot found in the session history.
+#[derive(Debug, PartialEq)]
+struct GhostSnapshotEntry {
+    /// Position of the snapshot item in the history.
+    index: usize,
+    ghost_commit: GhostCommit,
+    /// First line of the user message of the turn the snapshot was taken for.
+    summary: Option<String>,
+}
+
+/// Snapshots in history order. Each turn records the user message before its
+/// snapshot, so the closest preceding user message names the turn.
+fn ghost_snapshots(items: &[ResponseItem]) -> Vec<GhostSnapshotEntry> {
+    let mut summary = None;
+    let mut snapshots = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if let ResponseItem::GhostSnapshot { ghost_commit } = item {
+            snapshots.push(GhostSnapshotEntry {
+                index,
+                ghost_commit: ghost_commit.clone(),
+                summary: summary.clone(),
+            });
+        } else if let Some(TurnItem::UserMessage(message)) = parse_turn_item(item) {
+            summary = message
+                .message()
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string);
+        }
+    }
+    snapshots
+}
+
+/// Removes the ghost snapshots at or after `index`, returning them with their
+/// positions so that redo can put them back.
+fn remove_snapshots_from(
+    items: &mut Vec<ResponseItem>,
+    index: usize,
+) -> Vec<(usize, ResponseItem)> {
+    let mut removed = Vec::new();
+    let mut kept = Vec::with_capacity(items.len());
+    for (position, item) in std::mem::take(items).into_iter().enumerate() {
+        if position >= index && matches!(item, ResponseItem::GhostSnapshot { .. }) {
+            removed.push((position, item));
+        } else {
+            kept.push(item);
         }
+    }
+    *items = kept;
+    removed
+}
 
-        sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
-            .await;
-        None
+fn reinsert_items(items: &mut Vec<ResponseItem>, removed: Vec<(usize, ResponseItem)>) {
+    for (position, item) in removed {
+        items.insert(position.min(items.len()), item);
+    }
+}
+
+fn snapshot_file(change: SnapshotFileChange) -> UndoSnapshotFile {
+    UndoSnapshotFile {
+        path: change.path,
+        insertions: change.insertions,
+        deletions: change.deletions,
+    }
+}
+
+fn short_commit_id(commit_id: &str) -> String {
+    commit_id.chars().take(7).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn assistant_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn snapshot(id: &str) -> ResponseItem {
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new()),
+        }
+    }
+
+    #[test]
+    fn snapshots_are_named_after_their_turn() {
+        let items = vec![
+            user_message("<environment_context>\n  <cwd>/repo</cwd>\n</environment_context>"),
+            user_message("Add a README\nwith install steps."),
+            snapshot("aaa"),
+            assistant_message("Done."),
+            user_message("Fix the typo"),
+            snapshot("bbb"),
+        ];
+
+        assert_eq!(
+            ghost_snapshots(&items),
+            vec![
+                GhostSnapshotEntry {
+                    index: 2,
+                    ghost_commit: GhostCommit::new("aaa".to_string(), None, Vec::new(), Vec::new()),
+                    summary: Some("Add a README".to_string()),
+                },
+                GhostSnapshotEntry {
+                    index: 5,
+                    ghost_commit: GhostCommit::new("bbb".to_string(), None, Vec::new(), Vec::new()),
+                    summary: Some("Fix the typo".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn redo_puts_removed_snapshots_back() {
+        let original = vec![
+            user_message("first"),
+            snapshot("aaa"),
+            user_message("second"),
+            snapshot("bbb"),
+            assistant_message("done"),
+        ];
+        let mut items = original.clone();
+
+        let removed = remove_snapshots_from(&mut items, 1);
+        assert_eq!(
+            items,
+            vec![
+                user_message("first"),
+                user_message("second"),
+                assistant_message("done"),
+            ]
+        );
+        assert_eq!(removed, vec![(1, snapshot("aaa")), (3, snapshot("bbb"))]);
+
+        reinsert_items(&mut items, removed);
+        assert_eq!(items, original);
     }
 }
@@ -154,6 +154,8 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: -1,
                             duration: Duration::ZERO,
                             formatted_output: aborted_message,
+                            resource_limit_exceeded: None,
+                            output_log: None,
                         }),
                     )
                     .await;
@@ -180,6 +182,8 @@ impl SessionTask for UserShellCommandTask {
                                 &output,
                                 turn_context.truncation_policy,
                             ),
+                            resource_limit_exceeded: None,
+                            output_log: None,
                         }),
                     )
                     .await;
@@ -225,6 +229,8 @@ impl SessionTask for UserShellCommandTask {
                                 &exec_output,
                                 turn_context.truncation_policy,
                             ),
+                            resource_limit_exceeded: None,
+                            output_log: None,
                         }),
                     )
                     .await;
@@ -12,7 +12,9 @@ use crate::protocol::ExecCommandSource;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
+use crate::protocol::ResourceLimitKind;
 use crate::protocol::TurnDiffEvent;
+use crate::resource_limits::detect_exceeded_limit;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
@@ -103,6 +105,7 @@ pub(crate) enum ToolEmitter {
         interaction_input: Option<String>,
         parsed_cmd: Vec<ParsedCommand>,
         process_id: Option<String>,
+        output_log: Option<PathBuf>,
     },
 }
 
@@ -135,6 +138,7 @@ impl ToolEmitter {
         cwd: PathBuf,
         source: ExecCommandSource,
         process_id: Option<String>,
+        output_log: Option<PathBuf>,
     ) -> Self {
         let parsed_cmd = parse_command(command);
         Self::UnifiedExec {
@@ -144,6 +148,7 @@ impl ToolEmitter {
             interaction_input: None, // TODO(jif) drop this field in the protocol.
             parsed_cmd,
             process_id,
+            output_log,
         }
     }
 
@@ -161,7 +166,15 @@ impl ToolEmitter {
             ) => {
                 emit_exec_stage(
                     ctx,
-                    ExecCommandInput::new(command, cwd.as_path(), parsed_cmd, *source, None, None),
+                    ExecCommandInput::new(
+                        command,
+                        cwd.as_path(),
+                        parsed_cmd,
+                        *source,
+                        None,
+                        None,
+                        None,
+                    ),
                     stage,
                 )
                 .await;
@@ -234,6 +247,7 @@ impl ToolEmitter {
                     interaction_input,
                     parsed_cmd,
                     process_id,
+                    output_log,
                 },
                 stage,
             ) => {
@@ -246,6 +260,7 @@ impl ToolEmitter {
                         *source,
                         interaction_input.as_deref(),
                         process_id.as_deref(),
+                        output_log.as_deref(),
                     ),
                     stage,
                 )
@@ -289,7 +304,8 @@ impl ToolEmitter {
                 (event, result)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output })))
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::PathDenied { output, .. }))) => {
                 let response = self.format_exec_output_for_model(&output, ctx);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
@@ -331,6 +347,7 @@ struct ExecCommandInput<'a> {
     source: ExecCommandSource,
     interaction_input: Option<&'a str>,
     process_id: Option<&'a str>,
+    output_log: Option<&'a Path>,
 }
 
 impl<'a> ExecCommandInput<'a> {
@@ -341,6 +358,7 @@ impl<'a> ExecCommandInput<'a> {
         source: ExecCommandSource,
         interaction_input: Option<&'a str>,
         process_id: Option<&'a str>,
+        output_log: Option<&'a Path>,
     ) -> Self {
         Self {
             command,
@@ -349,6 +367,7 @@ impl<'a> ExecCommandInput<'a> {
             source,
             interaction_input,
             process_id,
+            output_log,
         }
     }
 }
@@ -360,6 +379,7 @@ struct ExecCommandResult {
     exit_code: i32,
     duration: Duration,
     formatted_output: String,
+    resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 async fn emit_exec_stage(
@@ -389,6 +409,7 @@ async fn emit_exec_stage(
                 exit_code: output.exit_code,
                 duration: output.duration,
                 formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                resource_limit_exceeded: detect_exceeded_limit(&ctx.turn.resource_limits, &output),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -401,6 +422,7 @@ async fn emit_exec_stage(
                 exit_code: -1,
                 duration: Duration::ZERO,
                 formatted_output: text,
+                resource_limit_exceeded: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -430,6 +452,8 @@ async fn emit_exec_end(
                 exit_code: exec_result.exit_code,
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
+                resource_limit_exceeded: exec_result.resource_limit_exceeded,
+                output_log: exec_input.output_log.map(Path::to_path_buf),
             }),
         )
         .await;
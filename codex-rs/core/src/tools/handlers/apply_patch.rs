@@ -300,11 +300,15 @@ Your patch language is a stripped‑down, file‑oriented diff format designed t
 
 Within that envelope, you get a sequence of file operations.
 You MUST include a header to specify the action you are taking.
-Each operation starts with one of three headers:
+Each operation starts with one of these headers:
 
 *** Add File: <path> - create a new file. Every following line is a + line (the initial contents).
 *** Delete File: <path> - remove an existing file. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
+*** Copy File: <path> - copy an existing file. Must be followed by *** Copy to: <new path>, then optionally hunks to apply to the copy.
+*** Set Mode: <path> - change the permissions of an existing file. Must be followed by *** Mode: <octal mode>, e.g. 755.
+*** Add Symlink: <path> - create a symbolic link. Must be followed by *** Link to: <target>.
+*** Add Binary File: <path> / *** Replace Binary File: <path> - write binary contents, base64-encoded on the following + lines.
 
 May be immediately followed by *** Move to: <new path> if you want to rename the file.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
@@ -332,11 +336,15 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
+FileOp := AddFile | DeleteFile | UpdateFile | CopyFile | SetMode | AddSymlink | BinaryFile
 AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
 DeleteFile := "*** Delete File: " path NEWLINE
 UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+CopyFile := "*** Copy File: " path NEWLINE "*** Copy to: " newPath NEWLINE { Hunk }
+SetMode := "*** Set Mode: " path NEWLINE "*** Mode: " octalMode NEWLINE
+AddSymlink := "*** Add Symlink: " path NEWLINE "*** Link to: " target NEWLINE
+BinaryFile := ("*** Add Binary File: " | "*** Replace Binary File: ") path NEWLINE { "+" base64 NEWLINE }
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
@@ -351,11 +359,13 @@ A full patch can combine several operations:
 -print("Hi")
 +print("Hello, world!")
 *** Delete File: obsolete.txt
+*** Set Mode: scripts/run.sh
+*** Mode: 755
 *** End Patch
 
 It is important to remember:
 
-- You must include a header with your intended action (Add/Delete/Update)
+- You must include a header with your intended action (Add/Delete/Update/Copy/Set Mode/Add Symlink/Binary)
 - You must prefix new lines with `+` even when creating a new file
 - File references can only be relative, NEVER ABSOLUTE.
 "#
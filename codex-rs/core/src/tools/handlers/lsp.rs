@@ -0,0 +1,366 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+use crate::lsp::LspClient;
+use crate::lsp::format::format_diagnostics;
+use crate::lsp::format::format_hover;
+use crate::lsp::format::format_locations;
+use crate::lsp::format::format_symbols;
+use crate::lsp::format::lsp_position;
+use crate::protocol::SandboxPolicy;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use crate::tools::sandboxing::with_cached_approval;
+use crate::truncate::formatted_truncate_text;
+
+pub struct LspHandler;
+
+/// How long to wait for a server to publish diagnostics for a file it was just told about.
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Session approvals for starting a server are remembered per command line and root.
+#[derive(Serialize)]
+struct ServerStartKey {
+    server_command: Vec<String>,
+    root: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct PositionArgs {
+    file_path: String,
+    line: usize,
+    column: usize,
+}
+
+fn default_include_declaration() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct FindReferencesArgs {
+    #[serde(flatten)]
+    position: PositionArgs,
+    #[serde(default = "default_include_declaration")]
+    include_declaration: bool,
+}
+
+#[derive(Deserialize)]
+struct WorkspaceSymbolsArgs {
+    query: String,
+    #[serde(default)]
+    file_path: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticsArgs {
+    #[serde(default)]
+    file_paths: Option<Vec<String>>,
+}
+
+#[async_trait]
+impl ToolHandler for LspHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            tool_name,
+            payload,
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let servers = Servers {
+            session: &session,
+            turn: &turn,
+            call_id: &call_id,
+        };
+        let content = match tool_name.as_str() {
+            "goto_definition" => {
+                let args: PositionArgs = parse_arguments(&arguments)?;
+                let result =
+                    position_request(&servers, &args, "textDocument/definition", json!({})).await?;
+                format_locations(&result, &turn.cwd)
+            }
+            "find_references" => {
+                let args: FindReferencesArgs = parse_arguments(&arguments)?;
+                let context = json!({
+                    "context": { "includeDeclaration": args.include_declaration },
+                });
+                let result =
+                    position_request(&servers, &args.position, "textDocument/references", context)
+                        .await?;
+                format_locations(&result, &turn.cwd)
+            }
+            "hover" => {
+                let args: PositionArgs = parse_arguments(&arguments)?;
+                let result =
+                    position_request(&servers, &args, "textDocument/hover", json!({})).await?;
+                format_hover(&result)
+            }
+            "workspace_symbols" => {
+                let args: WorkspaceSymbolsArgs = parse_arguments(&arguments)?;
+                workspace_symbols(&servers, args).await?
+            }
+            "diagnostics" => {
+                let args: DiagnosticsArgs = parse_arguments(&arguments)?;
+                let paths = match args.file_paths {
+                    Some(paths) => paths
+                        .into_iter()
+                        .map(|path| turn.resolve_path(Some(path)))
+                        .collect(),
+                    None => tracker.lock().await.changed_paths(),
+                };
+                diagnostics(&servers, paths).await?
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported language server tool: {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content: formatted_truncate_text(&content, turn.truncation_policy),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn parse_arguments<T: for<'de> Deserialize<'de>>(arguments: &str) -> Result<T, FunctionCallError> {
+    serde_json::from_str(arguments).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err:?}"))
+    })
+}
+
+/// The language servers a tool call can reach, on behalf of the call asking for them.
+struct Servers<'a> {
+    session: &'a Session,
+    turn: &'a TurnContext,
+    call_id: &'a str,
+}
+
+impl Servers<'_> {
+    async fn client_for(&self, path: &Path) -> Result<Arc<LspClient>, String> {
+        self.session
+            .services
+            .lsp
+            .client_for(path, |command, root| self.approve_start(command, root))
+            .await
+    }
+
+    /// Servers run outside the sandbox, so starting one is approved like a command asking for
+    /// escalated permissions: rules in the exec policy apply, and otherwise the user is asked
+    /// unless the session is not sandboxed to begin with.
+    async fn approve_start(&self, command: Vec<String>, root: PathBuf) -> Result<(), String> {
+        let turn = self.turn;
+        let sandboxed = !matches!(
+            turn.sandbox_policy,
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. }
+        );
+        let sandbox_permissions = if sandboxed {
+            SandboxPermissions::RequireEscalated
+        } else {
+            SandboxPermissions::UseDefault
+        };
+        let requirement = self
+            .session
+            .services
+            .exec_policy
+            .create_exec_approval_requirement_for_command(
+                &self.session.features(),
+                &command,
+                turn.approval_policy,
+                &turn.sandbox_policy,
+                sandbox_permissions,
+                &root,
+            )
+            .await;
+        let reason = match requirement {
+            ExecApprovalRequirement::Forbidden { reason } => return Err(reason),
+            ExecApprovalRequirement::Skip { bypass_sandbox, .. }
+                if bypass_sandbox || !sandboxed =>
+            {
+                return Ok(());
+            }
+            ExecApprovalRequirement::Skip { .. }
+                if turn.approval_policy == AskForApproval::Never =>
+            {
+                return Err(
+                    "language servers run outside the sandbox and the approval policy is never"
+                        .to_string(),
+                );
+            }
+            ExecApprovalRequirement::Skip { .. } => None,
+            ExecApprovalRequirement::NeedsApproval { reason, .. } => reason,
+        };
+        let reason = reason.unwrap_or_else(|| {
+            "start a language server for code navigation; it runs outside the sandbox".to_string()
+        });
+
+        let key = ServerStartKey {
+            server_command: command.clone(),
+            root: root.clone(),
+        };
+        let decision = with_cached_approval(&self.session.services, vec![key], || async {
+            self.session
+                .request_command_approval(
+                    turn,
+                    self.call_id.to_string(),
+                    command,
+                    root,
+                    Some(reason),
+                    None,
+                )
+                .await
+        })
+        .await;
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession => Ok(()),
+            ReviewDecision::Denied | ReviewDecision::Abort => Err("the user declined".to_string()),
+        }
+    }
+}
+
+async fn client_for(
+    servers: &Servers<'_>,
+    path: &Path,
+) -> Result<Arc<LspClient>, FunctionCallError> {
+    servers
+        .client_for(path)
+        .await
+        .map_err(FunctionCallError::RespondToModel)
+}
+
+/// Sends a `textDocument/*` request for the position in `args`, merging `extra` into its params.
+async fn position_request(
+    servers: &Servers<'_>,
+    args: &PositionArgs,
+    method: &str,
+    extra: Value,
+) -> Result<Value, FunctionCallError> {
+    if args.line == 0 || args.column == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "line and column are 1-indexed".to_string(),
+        ));
+    }
+    let path = servers.turn.resolve_path(Some(args.file_path.clone()));
+    let client = client_for(servers, &path).await?;
+    let uri = client
+        .sync_document(&path)
+        .await
+        .map_err(FunctionCallError::RespondToModel)?;
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read file: {err}")))?;
+
+    let mut params = json!({
+        "textDocument": { "uri": uri },
+        "position": lsp_position(&text, args.line, args.column),
+    });
+    if let (Some(params), Value::Object(extra)) = (params.as_object_mut(), extra) {
+        params.extend(extra);
+    }
+    client
+        .request(method, params)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("{method} failed: {err}")))
+}
+
+async fn workspace_symbols(
+    servers: &Servers<'_>,
+    args: WorkspaceSymbolsArgs,
+) -> Result<String, FunctionCallError> {
+    let turn = servers.turn;
+    let clients = match args.file_path {
+        Some(path) => vec![client_for(servers, &turn.resolve_path(Some(path))).await?],
+        None => servers.session.services.lsp.running_clients().await,
+    };
+    if clients.is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "no language server is running yet; pass file_path to pick the server for that file"
+                .to_string(),
+        ));
+    }
+
+    let mut lines = Vec::new();
+    for client in clients {
+        let result = client
+            .request("workspace/symbol", json!({ "query": args.query }))
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("workspace/symbol failed: {err}"))
+            })?;
+        lines.extend(format_symbols(&result, &turn.cwd));
+    }
+    if lines.is_empty() {
+        Ok(format!("No symbols match {:?}.", args.query))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+async fn diagnostics(
+    servers: &Servers<'_>,
+    paths: Vec<PathBuf>,
+) -> Result<String, FunctionCallError> {
+    let turn = servers.turn;
+    if paths.is_empty() {
+        return Ok("No files were changed this turn.".to_string());
+    }
+
+    let mut sections = Vec::new();
+    for path in paths {
+        let display = path
+            .strip_prefix(&turn.cwd)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        // Files no server handles are reported rather than failing the whole call.
+        let published = match servers.client_for(&path).await {
+            Ok(client) => client.diagnostics(&path, DIAGNOSTICS_TIMEOUT).await,
+            Err(err) => Err(err),
+        };
+        match published {
+            Ok(published) if published.is_empty() => {
+                sections.push(format!("{display}: no problems"))
+            }
+            Ok(published) => sections.extend(format_diagnostics(&path, &published, &turn.cwd)),
+            Err(err) => sections.push(format!("{display}: {err}")),
+        }
+    }
+    Ok(sections.join("\n"))
+}
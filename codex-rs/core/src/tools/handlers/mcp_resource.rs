@@ -57,6 +57,9 @@ struct ListResourceTemplatesArgs {
 struct ReadResourceArgs {
     server: String,
     uri: String,
+    /// Also subscribe to updates of the resource.
+    #[serde(default)]
+    subscribe: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -176,6 +179,8 @@ impl ListResourceTemplatesPayload {
 struct ReadResourcePayload {
     server: String,
     uri: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    subscribed: bool,
     #[serde(flatten)]
     result: ReadResourceResult,
 }
@@ -467,7 +472,11 @@ async fn handle_read_resource(
     arguments: Option<Value>,
 ) -> Result<ToolOutput, FunctionCallError> {
     let args: ReadResourceArgs = parse_args(arguments.clone())?;
-    let ReadResourceArgs { server, uri } = args;
+    let ReadResourceArgs {
+        server,
+        uri,
+        subscribe,
+    } = args;
     let server = normalize_required_string("server", server)?;
     let uri = normalize_required_string("uri", uri)?;
 
@@ -487,10 +496,17 @@ async fn handle_read_resource(
             .map_err(|err| {
                 FunctionCallError::RespondToModel(format!("resources/read failed: {err:#}"))
             })?;
+        if subscribe {
+            session
+                .subscribe_resource(&server, uri.clone())
+                .await
+                .map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?;
+        }
 
         Ok(ReadResourcePayload {
             server,
             uri,
+            subscribed: subscribe,
             result,
         })
     }
@@ -1,9 +1,11 @@
 pub mod apply_patch;
 mod grep_files;
 mod list_dir;
+mod lsp;
 mod mcp;
 mod mcp_resource;
 mod plan;
+mod processes;
 mod read_file;
 mod shell;
 mod test_sync;
@@ -15,9 +17,11 @@ pub use plan::PLAN_TOOL;
 pub use apply_patch::ApplyPatchHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
+pub use lsp::LspHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
+pub use processes::ProcessesHandler;
 pub use read_file::ReadFileHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
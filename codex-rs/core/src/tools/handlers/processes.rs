@@ -0,0 +1,227 @@
+use async_trait::async_trait;
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use crate::bash::extract_bash_command;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::ProcessSummary;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::formatted_truncate_text;
+use crate::unified_exec::DEFAULT_READ_LIMIT;
+use crate::unified_exec::LogQuery;
+
+pub struct ProcessesHandler;
+
+#[derive(Debug, Deserialize)]
+struct KillProcessArgs {
+    // Matches `write_stdin`, which the model is trained to call with `session_id`.
+    session_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReadProcessOutputArgs {
+    session_id: i32,
+    /// 1-indexed line to start from; defaults to 1.
+    #[serde(default = "default_offset")]
+    offset: usize,
+    /// Maximum number of lines (or matches) to return.
+    #[serde(default = "default_limit")]
+    limit: usize,
+    /// Regex that returned lines must match.
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+fn default_offset() -> usize {
+    1
+}
+
+fn default_limit() -> usize {
+    DEFAULT_READ_LIMIT
+}
+
+#[async_trait]
+impl ToolHandler for ProcessesHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        invocation.tool_name == "kill_process"
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "processes handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let manager = &session.services.unified_exec_manager;
+        let content = match tool_name.as_str() {
+            "list_processes" => {
+                let processes = manager.list_processes().await;
+                if processes.is_empty() {
+                    "No background processes.".to_string()
+                } else {
+                    processes
+                        .iter()
+                        .map(format_process)
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                }
+            }
+            "kill_process" => {
+                let args: KillProcessArgs = serde_json::from_str(&arguments).map_err(|err| {
+                    FunctionCallError::RespondToModel(format!(
+                        "failed to parse kill_process arguments: {err:?}"
+                    ))
+                })?;
+                let summary = manager
+                    .kill_process(&args.session_id.to_string())
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("kill_process failed: {err}"))
+                    })?;
+                format!("Killed process.\n{}", format_process(&summary))
+            }
+            "read_process_output" => {
+                let args: ReadProcessOutputArgs =
+                    serde_json::from_str(&arguments).map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "failed to parse read_process_output arguments: {err:?}"
+                        ))
+                    })?;
+                let query = log_query(args.offset, args.limit, args.pattern.as_deref())?;
+                let text = manager
+                    .read_process_output(&args.session_id.to_string(), query)
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "read_process_output failed: {err}"
+                        ))
+                    })?;
+                formatted_truncate_text(&text, turn.truncation_policy)
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported process tool {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn log_query(
+    offset: usize,
+    limit: usize,
+    pattern: Option<&str>,
+) -> Result<LogQuery, FunctionCallError> {
+    if offset == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "offset must be a 1-indexed line number".to_string(),
+        ));
+    }
+    if limit == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "limit must be greater than zero".to_string(),
+        ));
+    }
+    let pattern = pattern
+        .map(Regex::new)
+        .transpose()
+        .map_err(|err| FunctionCallError::RespondToModel(format!("invalid pattern: {err}")))?;
+    Ok(LogQuery {
+        offset,
+        limit,
+        pattern,
+    })
+}
+
+fn format_process(process: &ProcessSummary) -> String {
+    let command = match extract_bash_command(&process.command) {
+        Some((_, script)) => script.to_string(),
+        None => process.command.join(" "),
+    };
+    let mut lines = vec![format!("Session ID: {}", process.process_id)];
+    if let Some(service) = &process.service {
+        lines.push(format!("Service: {service}"));
+    }
+    lines.push(format!("Command: {command}"));
+    lines.push(format!("Cwd: {}", process.cwd.display()));
+    lines.push(match process.exit_code {
+        Some(code) => format!("Status: exited with code {code}"),
+        None => format!("Status: running for {}s", process.age_secs),
+    });
+    if !process.listening_ports.is_empty() {
+        let ports: Vec<String> = process
+            .listening_ports
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        lines.push(format!("Listening on ports: {}", ports.join(", ")));
+    }
+    if !process.output_tail.is_empty() {
+        lines.push("Recent output:".to_string());
+        lines.push(process.output_tail.clone());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn formats_running_service() {
+        let process = ProcessSummary {
+            process_id: "1000".to_string(),
+            command: vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "npm run dev".to_string(),
+            ],
+            cwd: PathBuf::from("/repo/web"),
+            service: Some("web dev server".to_string()),
+            age_secs: 75,
+            exit_code: None,
+            output_tail: "ready on http://localhost:3000".to_string(),
+            listening_ports: vec![3000, 9229],
+        };
+
+        assert_eq!(
+            format_process(&process),
+            "Session ID: 1000\n\
+             Service: web dev server\n\
+             Command: npm run dev\n\
+             Cwd: /repo/web\n\
+             Status: running for 75s\n\
+             Listening on ports: 3000, 9229\n\
+             Recent output:\n\
+             ready on http://localhost:3000"
+        );
+    }
+}
@@ -260,6 +260,7 @@ impl ShellHandler {
                 turn.approval_policy,
                 &turn.sandbox_policy,
                 exec_params.sandbox_permissions,
+                &exec_params.cwd,
             )
             .await;
 
@@ -40,6 +40,8 @@ struct ExecCommandArgs {
     sandbox_permissions: SandboxPermissions,
     #[serde(default)]
     justification: Option<String>,
+    #[serde(default)]
+    service: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -131,6 +133,7 @@ impl ToolHandler for UnifiedExecHandler {
                     max_output_tokens,
                     sandbox_permissions,
                     justification,
+                    service,
                     ..
                 } = args;
 
@@ -178,6 +181,7 @@ impl ToolHandler for UnifiedExecHandler {
                             workdir,
                             sandbox_permissions,
                             justification,
+                            service: service.filter(|label| !label.trim().is_empty()),
                         },
                         &context,
                     )
@@ -267,6 +271,14 @@ fn format_response(response: &UnifiedExecResponse) -> String {
         sections.push(format!("Original token count: {original_token_count}"));
     }
 
+    if let Some(output_log) = &response.output_log {
+        sections.push(format!(
+            "Full output saved to {} (read_process_output session ID {})",
+            output_log.path.display(),
+            output_log.process_id
+        ));
+    }
+
     sections.push("Output:".to_string());
     sections.push(response.output.clone());
 
@@ -8,6 +8,7 @@ retry without sandbox on denial (no re‑approval thanks to caching).
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::network_proxy::NetworkProxy;
 use crate::sandboxing::SandboxManager;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -100,6 +101,13 @@ impl ToolOrchestrator {
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
+            network_proxy_socket: tool_ctx
+                .session
+                .services
+                .network_proxy
+                .as_ref()
+                .map(NetworkProxy::socket_path),
+            resource_limits: &turn_ctx.resource_limits,
         };
 
         match tool.run(req, &initial_attempt, tool_ctx).await {
@@ -150,6 +158,8 @@ impl ToolOrchestrator {
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
+                    network_proxy_socket: None,
+                    resource_limits: &turn_ctx.resource_limits,
                 };
 
                 // Second attempt.
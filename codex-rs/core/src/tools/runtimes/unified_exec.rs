@@ -7,6 +7,7 @@ the process manager to spawn PTYs once an ExecEnv is prepared.
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecExpiration;
+use crate::exec::attribute_denial_to_carve_out;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
 use crate::sandboxing::SandboxPermissions;
@@ -193,9 +194,13 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             .await
             .map_err(|err| match err {
                 UnifiedExecError::SandboxDenied { output, .. } => {
-                    ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                        output: Box::new(output),
-                    }))
+                    ToolError::Codex(attribute_denial_to_carve_out(
+                        CodexErr::Sandbox(SandboxErr::Denied {
+                            output: Box::new(output),
+                        }),
+                        attempt.policy,
+                        &req.cwd,
+                    ))
                 }
                 other => ToolError::Rejected(other.to_string()),
             })
@@ -6,6 +6,7 @@
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::ResourceLimits;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
@@ -263,6 +264,10 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    /// Unix socket of the session's network proxy, if one is running.
+    pub(crate) network_proxy_socket: Option<&'a Path>,
+    /// Per-command limits applied by `codex-linux-sandbox`.
+    pub(crate) resource_limits: &'a ResourceLimits,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -276,6 +281,8 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.network_proxy_socket,
+            self.resource_limits,
         )
     }
 }
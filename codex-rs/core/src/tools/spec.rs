@@ -22,6 +22,7 @@ pub(crate) struct ToolsConfig {
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_request: bool,
     pub web_search_cached: bool,
+    pub lsp_tools: bool,
     pub experimental_supported_tools: Vec<String>,
 }
 
@@ -70,6 +71,7 @@ impl ToolsConfig {
             apply_patch_tool_type,
             web_search_request: include_web_search_request,
             web_search_cached: include_web_search_cached,
+            lsp_tools: features.enabled(Feature::Lsp),
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
     }
@@ -196,6 +198,15 @@ fn create_exec_command_tool() -> ToolSpec {
             ),
         },
     );
+    properties.insert(
+        "service".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Label for a long-running service such as a dev server. Services keep running after the turn ends; other processes are stopped."
+                    .to_string(),
+            ),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "exec_command".to_string(),
@@ -211,6 +222,87 @@ fn create_exec_command_tool() -> ToolSpec {
     })
 }
 
+fn create_list_processes_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "list_processes".to_string(),
+        description: "Lists the processes started with exec_command that are still tracked, with their session ID, command, cwd, status, listening ports and recent output.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_kill_process_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "session_id".to_string(),
+        JsonSchema::Number {
+            description: Some("Identifier of the unified exec session to kill.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "kill_process".to_string(),
+        description: "Kills a process started with exec_command and returns its last output."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["session_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_process_output_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "session_id".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Identifier of the unified exec session, running or exited.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "offset".to_string(),
+        JsonSchema::Number {
+            description: Some("1-indexed line to start reading from; defaults to 1.".to_string()),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Maximum number of lines (or matching lines) to return; defaults to 200."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "pattern".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional regular expression; only matching lines are returned.".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_process_output".to_string(),
+        description: "Reads the full, untruncated output of a command started with exec_command. Lines are numbered; page with offset and limit, or search with pattern.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["session_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_write_stdin_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -671,6 +763,140 @@ fn create_list_dir_tool() -> ToolSpec {
     })
 }
 
+fn lsp_position_properties() -> BTreeMap<String, JsonSchema> {
+    BTreeMap::from([
+        (
+            "file_path".to_string(),
+            JsonSchema::String {
+                description: Some("Path to the source file.".to_string()),
+            },
+        ),
+        (
+            "line".to_string(),
+            JsonSchema::Number {
+                description: Some("1-indexed line of the symbol.".to_string()),
+            },
+        ),
+        (
+            "column".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "1-indexed column (in characters) of any character of the symbol.".to_string(),
+                ),
+            },
+        ),
+    ])
+}
+
+fn create_lsp_position_tool(
+    name: &str,
+    description: &str,
+    properties: BTreeMap<String, JsonSchema>,
+) -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: name.to_string(),
+        description: description.to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec![
+                "file_path".to_string(),
+                "line".to_string(),
+                "column".to_string(),
+            ]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_goto_definition_tool() -> ToolSpec {
+    create_lsp_position_tool(
+        "goto_definition",
+        "Finds where the symbol at a position is defined, using the language server for the file. Returns `path:line:column` locations with the source line.",
+        lsp_position_properties(),
+    )
+}
+
+fn create_find_references_tool() -> ToolSpec {
+    let mut properties = lsp_position_properties();
+    properties.insert(
+        "include_declaration".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Whether to include the declaration itself. Defaults to true.".to_string(),
+            ),
+        },
+    );
+    create_lsp_position_tool(
+        "find_references",
+        "Lists every reference to the symbol at a position, using the language server for the file. Returns `path:line:column` locations with the source line.",
+        properties,
+    )
+}
+
+fn create_hover_tool() -> ToolSpec {
+    create_lsp_position_tool(
+        "hover",
+        "Shows the type signature and documentation of the symbol at a position, using the language server for the file.",
+        lsp_position_properties(),
+    )
+}
+
+fn create_workspace_symbols_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some("Symbol name or fragment to search for.".to_string()),
+            },
+        ),
+        (
+            "file_path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional file whose language server should answer. When omitted, asks the language servers already running in this session."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "workspace_symbols".to_string(),
+        description: "Searches the workspace for symbols (types, functions, constants, ...) by name using the language server index.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_diagnostics_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "file_paths".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some(
+                "Files to check. When omitted, checks the files changed with apply_patch during this turn."
+                    .to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "diagnostics".to_string(),
+        description: "Reports the errors and warnings the language servers see in files, as `path:line:column severity: message` lines. Use it after editing to check the changes compile.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_mcp_resources_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -757,6 +983,15 @@ fn create_read_mcp_resource_tool() -> ToolSpec {
             ),
         },
     );
+    properties.insert(
+        "subscribe".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Also subscribe to the resource. You will be told when it changes later in the conversation."
+                    .to_string(),
+            ),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "read_mcp_resource".to_string(),
@@ -983,9 +1218,11 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
+    use crate::tools::handlers::LspHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::ProcessesHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
@@ -1015,8 +1252,15 @@ pub(crate) fn build_specs(
         ConfigShellToolType::UnifiedExec => {
             builder.push_spec(create_exec_command_tool());
             builder.push_spec(create_write_stdin_tool());
+            builder.push_spec(create_list_processes_tool());
+            builder.push_spec(create_kill_process_tool());
+            builder.push_spec(create_read_process_output_tool());
             builder.register_handler("exec_command", unified_exec_handler.clone());
             builder.register_handler("write_stdin", unified_exec_handler);
+            let processes_handler = Arc::new(ProcessesHandler);
+            builder.register_handler("list_processes", processes_handler.clone());
+            builder.register_handler("kill_process", processes_handler.clone());
+            builder.register_handler("read_process_output", processes_handler);
         }
         ConfigShellToolType::Disabled => {
             // Do nothing.
@@ -1093,6 +1337,20 @@ pub(crate) fn build_specs(
         builder.register_handler("test_sync_tool", test_sync_handler);
     }
 
+    if config.lsp_tools {
+        let lsp_handler = Arc::new(LspHandler);
+        builder.push_spec_with_parallel_support(create_goto_definition_tool(), true);
+        builder.push_spec_with_parallel_support(create_find_references_tool(), true);
+        builder.push_spec_with_parallel_support(create_hover_tool(), true);
+        builder.push_spec_with_parallel_support(create_workspace_symbols_tool(), true);
+        builder.push_spec_with_parallel_support(create_diagnostics_tool(), true);
+        builder.register_handler("goto_definition", lsp_handler.clone());
+        builder.register_handler("find_references", lsp_handler.clone());
+        builder.register_handler("hover", lsp_handler.clone());
+        builder.register_handler("workspace_symbols", lsp_handler.clone());
+        builder.register_handler("diagnostics", lsp_handler);
+    }
+
     // Prefer web_search_cached flag over web_search_request
     if config.web_search_cached {
         builder.push_spec(ToolSpec::WebSearch {
@@ -1387,6 +1645,9 @@ mod tests {
             &[
                 "exec_command",
                 "write_stdin",
+                "list_processes",
+                "kill_process",
+                "read_process_output",
                 "list_mcp_resources",
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
@@ -1408,6 +1669,9 @@ mod tests {
             &[
                 "exec_command",
                 "write_stdin",
+                "list_processes",
+                "kill_process",
+                "read_process_output",
                 "list_mcp_resources",
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
@@ -1493,6 +1757,9 @@ mod tests {
             &[
                 "exec_command",
                 "write_stdin",
+                "list_processes",
+                "kill_process",
+                "read_process_output",
                 "list_mcp_resources",
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
@@ -1513,6 +1780,9 @@ mod tests {
             &[
                 "exec_command",
                 "write_stdin",
+                "list_processes",
+                "kill_process",
+                "read_process_output",
                 "list_mcp_resources",
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
@@ -1593,6 +1863,43 @@ mod tests {
         assert!(tools.iter().any(|tool| tool_name(&tool.spec) == "list_dir"));
     }
 
+    #[test]
+    fn test_lsp_tools_follow_feature_flag() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let lsp_tools = [
+            "goto_definition",
+            "find_references",
+            "hover",
+            "workspace_symbols",
+            "diagnostics",
+        ];
+
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| lsp_tools.contains(&tool_name(&tool.spec)))
+        );
+
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Lsp);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert_contains_tool_names(&tools, &lsp_tools);
+        for name in lsp_tools {
+            assert!(find_tool(&tools, name).supports_parallel_tool_calls);
+        }
+    }
+
     #[test]
     fn test_build_specs_mcp_tools_converted() {
         let config = test_config();
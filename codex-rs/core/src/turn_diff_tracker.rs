@@ -219,6 +219,19 @@ impl TurnDiffTracker {
         if s.len() == 40 { Some(s) } else { None }
     }
 
+    /// Paths touched by apply_patch during this turn that still exist, in their current location.
+    pub fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .temp_name_to_current_path
+            .values()
+            .filter(|path| path.exists())
+            .cloned()
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
     /// Recompute the aggregated unified diff by comparing all of the in-memory snapshots that were
     /// collected before the first time they were touched by apply_patch during this turn with
     /// the current repo state.
@@ -647,6 +660,47 @@ index {left_oid}..{right_oid}
         assert_eq!(out, expected);
     }
 
+    #[test]
+    fn changed_paths_follow_moves_and_skip_deleted_files() {
+        let dir = tempdir().unwrap();
+        let added = dir.path().join("added.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let src = dir.path().join("src.txt");
+        let dest = dir.path().join("dst.txt");
+        fs::write(&deleted, "x\n").unwrap();
+        fs::write(&src, "line\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([
+            (
+                added.clone(),
+                FileChange::Add {
+                    content: "new\n".to_string(),
+                },
+            ),
+            (
+                deleted.clone(),
+                FileChange::Delete {
+                    content: "x\n".to_string(),
+                },
+            ),
+            (
+                src.clone(),
+                FileChange::Update {
+                    unified_diff: "".to_owned(),
+                    move_path: Some(dest.clone()),
+                },
+            ),
+        ]));
+
+        // Simulate apply.
+        fs::write(&added, "new\n").unwrap();
+        fs::remove_file(&deleted).unwrap();
+        fs::rename(&src, &dest).unwrap();
+
+        assert_eq!(acc.changed_paths(), vec![added, dest]);
+    }
+
     #[test]
     fn move_without_1change_yields_no_diff() {
         let dir = tempdir().unwrap();
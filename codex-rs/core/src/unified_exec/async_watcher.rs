@@ -8,6 +8,7 @@ use tokio::time::Instant;
 use tokio::time::Sleep;
 
 use super::UnifiedExecContext;
+use super::output_log::OutputLog;
 use super::process::UnifiedExecProcess;
 use crate::codex::Session;
 use crate::codex::TurnContext;
@@ -34,12 +35,13 @@ pub(crate) const TRAILING_OUTPUT_GRACE: Duration = Duration::from_millis(100);
 const UNIFIED_EXEC_OUTPUT_DELTA_MAX_BYTES: usize = 8192;
 
 /// Spawn a background task that continuously reads from the PTY, appends to the
-/// shared transcript, and emits ExecCommandOutputDelta events on UTF‑8
-/// boundaries.
+/// shared transcript and the on-disk output log, and emits
+/// ExecCommandOutputDelta events on UTF‑8 boundaries.
 pub(crate) fn start_streaming_output(
     process: &UnifiedExecProcess,
     context: &UnifiedExecContext,
     transcript: Arc<Mutex<HeadTailBuffer>>,
+    mut output_log: Option<OutputLog>,
 ) {
     let mut receiver = process.output_receiver();
     let output_drained = process.output_drained_notify();
@@ -85,6 +87,10 @@ pub(crate) fn start_streaming_output(
                         }
                     };
 
+                    if let Some(log) = output_log.as_mut() {
+                        log.append(&chunk).await;
+                    }
+
                     process_chunk(
                         &mut pending,
                         &transcript,
@@ -112,6 +118,7 @@ pub(crate) fn spawn_exit_watcher(
     cwd: PathBuf,
     process_id: String,
     transcript: Arc<Mutex<HeadTailBuffer>>,
+    output_log: Option<PathBuf>,
     started_at: Instant,
 ) {
     let exit_token = process.cancellation_token();
@@ -131,6 +138,7 @@ pub(crate) fn spawn_exit_watcher(
             cwd,
             Some(process_id),
             transcript,
+            output_log,
             String::new(),
             exit_code,
             duration,
@@ -183,6 +191,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
     cwd: PathBuf,
     process_id: Option<String>,
     transcript: Arc<Mutex<HeadTailBuffer>>,
+    output_log: Option<PathBuf>,
     fallback_output: String,
     exit_code: i32,
     duration: Duration,
@@ -202,6 +211,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
         cwd,
         ExecCommandSource::UnifiedExecStartup,
         process_id,
+        output_log,
     );
     emitter
         .emit(event_ctx, ToolEventStage::Success(output))
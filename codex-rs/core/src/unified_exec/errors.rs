@@ -8,6 +8,10 @@ pub(crate) enum UnifiedExecError {
     // The model is trained on `session_id`, but internally we track a `process_id`.
     #[error("Unknown process id {process_id}")]
     UnknownProcessId { process_id: String },
+    #[error("No output was saved for process id {process_id}")]
+    NoOutputLog { process_id: String },
+    #[error("Failed to read output log: {message}")]
+    ReadOutputLog { message: String },
     #[error("failed to write to stdin")]
     WriteToStdin,
     #[error("missing command line for unified exec request")]
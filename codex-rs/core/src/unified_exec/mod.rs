@@ -34,14 +34,22 @@ use tokio::sync::Mutex;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::sandboxing::SandboxPermissions;
+use head_tail_buffer::HeadTailBuffer;
+use output_log::OutputLogIndex;
 
 mod async_watcher;
 mod errors;
 mod head_tail_buffer;
+mod output_log;
+mod ports;
 mod process;
 mod process_manager;
 
 pub(crate) use errors::UnifiedExecError;
+pub(crate) use output_log::DEFAULT_READ_LIMIT;
+pub(crate) use output_log::LogQuery;
+pub(crate) use output_log::OutputLogLocation;
+pub(crate) use output_log::remove_stale_session_logs;
 pub(crate) use process::UnifiedExecProcess;
 
 pub(crate) const MIN_YIELD_TIME_MS: u64 = 250;
@@ -79,6 +87,8 @@ pub(crate) struct ExecCommandRequest {
     pub workdir: Option<PathBuf>,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
+    /// Label that keeps the process running after the turn ends.
+    pub service: Option<String>,
 }
 
 #[derive(Debug)]
@@ -101,6 +111,8 @@ pub(crate) struct UnifiedExecResponse {
     pub exit_code: Option<i32>,
     pub original_token_count: Option<usize>,
     pub session_command: Option<Vec<String>>,
+    /// Where to read what `output` left out; only set when it left something out.
+    pub output_log: Option<OutputLogLocation>,
 }
 
 #[derive(Default)]
@@ -118,16 +130,28 @@ impl ProcessStore {
 
 pub(crate) struct UnifiedExecProcessManager {
     process_store: Mutex<ProcessStore>,
+    /// Directory the full output of each process is spooled to, if any.
+    output_log_dir: Option<PathBuf>,
+    /// Logs written so far, kept after their processes exit.
+    output_logs: Mutex<OutputLogIndex>,
 }
 
-impl Default for UnifiedExecProcessManager {
-    fn default() -> Self {
+impl UnifiedExecProcessManager {
+    pub(crate) fn new(output_log_dir: Option<PathBuf>) -> Self {
         Self {
             process_store: Mutex::new(ProcessStore::default()),
+            output_log_dir,
+            output_logs: Mutex::new(OutputLogIndex::default()),
         }
     }
 }
 
+impl Default for UnifiedExecProcessManager {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
 struct ProcessEntry {
     process: Arc<UnifiedExecProcess>,
     session_ref: Arc<Session>,
@@ -135,6 +159,11 @@ struct ProcessEntry {
     call_id: String,
     process_id: String,
     command: Vec<String>,
+    cwd: PathBuf,
+    service: Option<String>,
+    transcript: Arc<Mutex<HeadTailBuffer>>,
+    output_log: Option<OutputLogLocation>,
+    started_at: tokio::time::Instant,
     last_used: tokio::time::Instant,
 }
 
@@ -202,6 +231,7 @@ mod tests {
                     workdir: None,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     justification: None,
+                    service: None,
                 },
                 &context,
             )
@@ -0,0 +1,329 @@
+//! Full output of unified exec processes, spooled to disk.
+//!
+//! The in-memory transcript only keeps the head and tail of long outputs, so
+//! unless history persistence is off, every process also appends everything
+//! it prints to a log file under the session's log directory. `read_process_output` pages through these files.
+//! Directories of sessions that have not written a log for
+//! `OUTPUT_LOG_RETENTION` are removed when a new session starts.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use regex_lite::Regex;
+use tokio::io::AsyncWriteExt;
+
+/// Stop spooling once a single log reaches this size.
+pub(crate) const OUTPUT_LOG_MAX_BYTES: u64 = 64 * 1024 * 1024; // 64 MiB
+/// How long the logs of a session are kept after it last wrote one.
+const OUTPUT_LOG_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Lines returned by `read_process_output` when no limit is given.
+pub(crate) const DEFAULT_READ_LIMIT: usize = 200;
+/// Longer lines are cut when read back, like `read_file` does.
+const MAX_LINE_LENGTH: usize = 500;
+
+/// Where the full output of a process lives, reported back to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OutputLogLocation {
+    pub process_id: String,
+    pub path: PathBuf,
+}
+
+/// Logs created so far, by the process id they belong to.
+#[derive(Default)]
+pub(crate) struct OutputLogIndex {
+    created: usize,
+    latest: HashMap<String, PathBuf>,
+}
+
+impl OutputLogIndex {
+    /// Path for a new log of `process_id` in `dir`, which becomes its latest.
+    /// Process ids are reused, so names carry a sequence number too.
+    pub(crate) fn next_path(&mut self, dir: &Path, process_id: &str) -> PathBuf {
+        self.created += 1;
+        let path = dir.join(format!("{:04}-{process_id}.log", self.created));
+        self.latest.insert(process_id.to_string(), path.clone());
+        path
+    }
+
+    pub(crate) fn latest(&self, process_id: &str) -> Option<&PathBuf> {
+        self.latest.get(process_id)
+    }
+}
+
+/// Append-only writer for one process's log.
+pub(crate) struct OutputLog {
+    path: PathBuf,
+    file: Option<tokio::fs::File>,
+    written: u64,
+}
+
+impl OutputLog {
+    /// Creates the log, readable only by the user since commands print secrets
+    /// as readily as anything else.
+    pub(crate) async fn create(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            let mut builder = tokio::fs::DirBuilder::new();
+            builder.recursive(true);
+            #[cfg(unix)]
+            builder.mode(0o700);
+            builder.create(parent).await?;
+        }
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let file = options.open(&path).await?;
+        Ok(Self {
+            path,
+            file: Some(file),
+            written: 0,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `chunk`, giving up on the log after the first write error or
+    /// once it reaches `OUTPUT_LOG_MAX_BYTES`.
+    pub(crate) async fn append(&mut self, chunk: &[u8]) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let remaining = OUTPUT_LOG_MAX_BYTES.saturating_sub(self.written);
+        let take = usize::try_from(remaining).map_or(chunk.len(), |r| r.min(chunk.len()));
+        let mut result = file.write_all(&chunk[..take]).await;
+        if result.is_ok() && take < chunk.len() {
+            result = file
+                .write_all(b"\n[output log truncated: size limit reached]\n")
+                .await;
+            self.written = OUTPUT_LOG_MAX_BYTES;
+        } else {
+            self.written += take as u64;
+        }
+        // Flush so readers see output while the process is still running.
+        if let Err(err) = result.and(file.flush().await) {
+            tracing::warn!("failed to write output log {}: {err}", self.path.display());
+            self.file = None;
+        } else if self.written >= OUTPUT_LOG_MAX_BYTES {
+            self.file = None;
+        }
+    }
+}
+
+/// Removes the session log directories under `logs_root`, other than `current`,
+/// in which nothing was written for `OUTPUT_LOG_RETENTION`.
+pub(crate) fn remove_stale_session_logs(logs_root: &Path, current: &Path) {
+    let Ok(sessions) = std::fs::read_dir(logs_root) else {
+        return;
+    };
+    for session in sessions.flatten() {
+        let dir = session.path();
+        if dir == current || !dir.is_dir() {
+            continue;
+        }
+        let stale = last_modified(&dir)
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > OUTPUT_LOG_RETENTION);
+        if stale && let Err(err) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!("failed to remove output logs {}: {err}", dir.display());
+        }
+    }
+}
+
+/// When a log in `dir` was last written to, or `dir` itself for sessions without logs.
+fn last_modified(dir: &Path) -> Option<SystemTime> {
+    let modified = |path: &Path| {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+    };
+    let logs = std::fs::read_dir(dir).ok()?;
+    logs.flatten()
+        .filter_map(|log| modified(&log.path()))
+        .max()
+        .or_else(|| modified(dir))
+}
+
+/// A `read_process_output` request over a log.
+#[derive(Debug)]
+pub(crate) struct LogQuery {
+    /// 1-indexed line to start from.
+    pub offset: usize,
+    /// Maximum number of lines (or matches, with a pattern) to return.
+    pub limit: usize,
+    pub pattern: Option<Regex>,
+}
+
+/// Reads the lines selected by `query` from a log, prefixed with their line numbers.
+pub(crate) fn read_log<R: BufRead>(mut reader: R, query: &LogQuery) -> std::io::Result<String> {
+    let mut selected = Vec::new();
+    let mut total_lines = 0;
+    let mut more = false;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        total_lines += 1;
+        if total_lines < query.offset {
+            continue;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if let Some(pattern) = &query.pattern
+            && !pattern.is_match(line)
+        {
+            continue;
+        }
+        if selected.len() == query.limit {
+            more = true;
+            continue;
+        }
+        selected.push(format!("L{total_lines}: {}", truncate_line(line)));
+    }
+
+    let mut sections = vec![format!("Total lines: {total_lines}")];
+    if selected.is_empty() {
+        sections.push(match &query.pattern {
+            Some(pattern) => format!("No lines match {:?}.", pattern.as_str()),
+            None => format!("No lines at or after line {}.", query.offset),
+        });
+    } else {
+        sections.extend(selected);
+        if more {
+            sections.push(match query.pattern {
+                Some(_) => "More matches follow; raise limit or offset to see them.".to_string(),
+                None => "More lines follow; raise offset to continue.".to_string(),
+            });
+        }
+    }
+    Ok(sections.join("\n"))
+}
+
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_LENGTH) {
+        Some((index, _)) => format!("{}…", &line[..index]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    fn query(offset: usize, limit: usize, pattern: Option<&str>) -> LogQuery {
+        LogQuery {
+            offset,
+            limit,
+            pattern: pattern.map(|pattern| Regex::new(pattern).expect("valid regex")),
+        }
+    }
+
+    #[test]
+    fn reads_line_ranges() {
+        let log = "running 3 tests\r\ntest a ... ok\r\ntest b ... FAILED\r\ntest c ... ok\r\n";
+
+        assert_eq!(
+            read_log(Cursor::new(log), &query(2, 2, None)).expect("read"),
+            "Total lines: 4\n\
+             L2: test a ... ok\n\
+             L3: test b ... FAILED\n\
+             More lines follow; raise offset to continue."
+        );
+        assert_eq!(
+            read_log(Cursor::new(log), &query(9, 2, None)).expect("read"),
+            "Total lines: 4\nNo lines at or after line 9."
+        );
+    }
+
+    #[test]
+    fn searches_with_regex() {
+        let log = "compiling\nerror[E0308]: mismatched types\nwarning: unused\nerror: aborting\n";
+
+        assert_eq!(
+            read_log(Cursor::new(log), &query(1, 10, Some(r"^error"))).expect("read"),
+            "Total lines: 4\n\
+             L2: error[E0308]: mismatched types\n\
+             L4: error: aborting"
+        );
+        assert_eq!(
+            read_log(Cursor::new(log), &query(1, 10, Some("panicked"))).expect("read"),
+            "Total lines: 4\nNo lines match \"panicked\"."
+        );
+    }
+
+    #[test]
+    fn removes_only_stale_session_logs() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let eight_days_ago = SystemTime::now() - Duration::from_secs(8 * 24 * 60 * 60);
+        let session = |name: &str, modified: SystemTime| {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).expect("create session dir");
+            let log = dir.join("0001-1000.log");
+            std::fs::write(&log, "output\n").expect("write log");
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&log)
+                .and_then(|file| file.set_modified(modified))
+                .expect("set mtime");
+            dir
+        };
+        let stale = session("stale", eight_days_ago);
+        let current = session("current", eight_days_ago);
+        let recent = session("recent", SystemTime::now());
+
+        remove_stale_session_logs(root.path(), &current);
+
+        assert!(!stale.exists());
+        assert!(current.exists());
+        assert!(recent.exists());
+    }
+
+    #[tokio::test]
+    async fn stops_spooling_at_size_limit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut log = OutputLog::create(dir.path().join("logs").join("1000.log"))
+            .await
+            .expect("create log");
+        log.written = OUTPUT_LOG_MAX_BYTES - 3;
+
+        log.append(b"abcdef").await;
+        log.append(b"ghi").await;
+
+        let contents = std::fs::read_to_string(log.path()).expect("read log");
+        assert_eq!(
+            contents,
+            "abc\n[output log truncated: size limit reached]\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn logs_are_private_to_the_user() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let session = dir.path().join("exec_logs").join("session");
+        let log = OutputLog::create(session.join("0001-1000.log"))
+            .await
+            .expect("create log");
+
+        let mode = |path: &Path| {
+            std::fs::metadata(path)
+                .expect("metadata")
+                .permissions()
+                .mode()
+                & 0o777
+        };
+        assert_eq!(mode(&session), 0o700);
+        assert_eq!(mode(log.path()), 0o600);
+    }
+}
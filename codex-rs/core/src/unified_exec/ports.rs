@@ -0,0 +1,141 @@
+//! TCP ports a process tree listens on, read from `/proc` on Linux.
+
+/// Ports that `pid` or any of its descendants listen on, sorted and deduplicated.
+#[cfg(target_os = "linux")]
+pub(crate) fn listening_ports(pid: u32) -> Vec<u16> {
+    use std::collections::HashSet;
+
+    let processes = process_tree(pid);
+    let mut inodes = HashSet::new();
+    for process in &processes {
+        let Ok(entries) = std::fs::read_dir(format!("/proc/{process}/fd")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Ok(target) = std::fs::read_link(entry.path())
+                && let Some(inode) = target
+                    .to_str()
+                    .and_then(|target| target.strip_prefix("socket:["))
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .and_then(|inode| inode.parse::<u64>().ok())
+            {
+                inodes.insert(inode);
+            }
+        }
+    }
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    // Read the tables of the process's own network namespace, which differs
+    // from ours when the command runs under the sandbox.
+    let mut ports: Vec<u16> = ["tcp", "tcp6"]
+        .iter()
+        .filter_map(|table| std::fs::read_to_string(format!("/proc/{pid}/net/{table}")).ok())
+        .flat_map(|table| parse_listening_sockets(&table))
+        .filter(|(inode, _)| inodes.contains(inode))
+        .map(|(_, port)| port)
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn listening_ports(_pid: u32) -> Vec<u16> {
+    Vec::new()
+}
+
+/// `pid` followed by all of its descendants.
+#[cfg(target_os = "linux")]
+fn process_tree(pid: u32) -> Vec<u32> {
+    use std::collections::HashMap;
+
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let Some(child) = entry
+                .file_name()
+                .to_str()
+                .and_then(|n| n.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+            if let Some(parent) = parent_pid(&stat) {
+                children.entry(parent).or_default().push(child);
+            }
+        }
+    }
+
+    let mut tree = vec![pid];
+    let mut index = 0;
+    while let Some(&current) = tree.get(index) {
+        if let Some(kids) = children.get(&current) {
+            tree.extend(kids);
+        }
+        index += 1;
+    }
+    tree
+}
+
+/// Parent pid from the contents of `/proc/<pid>/stat`. The command name in
+/// parentheses may contain spaces, so fields are counted from the last `)`.
+#[cfg(any(target_os = "linux", test))]
+fn parent_pid(stat: &str) -> Option<u32> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// `(inode, port)` of the sockets in the `LISTEN` state in a `/proc/net/tcp`
+/// or `/proc/net/tcp6` table.
+#[cfg(any(target_os = "linux", test))]
+fn parse_listening_sockets(table: &str) -> Vec<(u64, u16)> {
+    const TCP_LISTEN: &str = "0A";
+
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&TCP_LISTEN) {
+                return None;
+            }
+            let (_, port) = fields.get(1)?.rsplit_once(':')?;
+            let port = u16::from_str_radix(port, 16).ok()?;
+            let inode = fields.get(9)?.parse().ok()?;
+            Some((inode, port))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_listening_sockets_from_proc_net_tcp() {
+        let table = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 41234 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 41240 1 0000000000000000 20 4 30 10 -1
+   2: 00000000:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 41250 1 0000000000000000 100 0 0 10 0
+";
+
+        assert_eq!(
+            parse_listening_sockets(table),
+            vec![(41234, 8080), (41250, 3000)]
+        );
+    }
+
+    #[test]
+    fn parent_pid_skips_command_names_with_spaces() {
+        assert_eq!(
+            parent_pid("4242 (npm run dev) S 4100 4242 4100 0 -1"),
+            Some(4100)
+        );
+    }
+}
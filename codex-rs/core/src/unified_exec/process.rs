@@ -111,6 +111,10 @@ impl UnifiedExecProcess {
         self.process_handle.exit_code()
     }
 
+    pub(super) fn pid(&self) -> Option<u32> {
+        self.process_handle.pid()
+    }
+
     pub(super) fn terminate(&self) {
         self.process_handle.terminate();
         self.cancellation_token.cancel();
@@ -17,6 +17,7 @@ use crate::exec_env::create_env;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::ProcessSummary;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
 use crate::tools::events::ToolEmitter;
@@ -30,7 +31,9 @@ use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
 use crate::unified_exec::ExecCommandRequest;
+use crate::unified_exec::LogQuery;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
+use crate::unified_exec::OutputLogLocation;
 use crate::unified_exec::ProcessEntry;
 use crate::unified_exec::ProcessStore;
 use crate::unified_exec::UnifiedExecContext;
@@ -45,6 +48,9 @@ use crate::unified_exec::async_watcher::start_streaming_output;
 use crate::unified_exec::clamp_yield_time;
 use crate::unified_exec::generate_chunk_id;
 use crate::unified_exec::head_tail_buffer::HeadTailBuffer;
+use crate::unified_exec::output_log::OutputLog;
+use crate::unified_exec::output_log::read_log;
+use crate::unified_exec::ports::listening_ports;
 use crate::unified_exec::process::OutputBuffer;
 use crate::unified_exec::process::OutputHandles;
 use crate::unified_exec::process::UnifiedExecProcess;
@@ -62,6 +68,10 @@ const UNIFIED_EXEC_ENV: [(&str, &str); 9] = [
     ("GH_PAGER", "cat"),
 ];
 
+/// Lines of recent output included in a process summary.
+const PROCESS_OUTPUT_TAIL_LINES: usize = 10;
+const PROCESS_OUTPUT_TAIL_MAX_BYTES: usize = 4096;
+
 fn apply_unified_exec_env(mut env: HashMap<String, String>) -> HashMap<String, String> {
     for (key, value) in UNIFIED_EXEC_ENV {
         env.insert(key.to_string(), value.to_string());
@@ -78,6 +88,7 @@ struct PreparedProcessHandles {
     turn_ref: Arc<TurnContext>,
     command: Vec<String>,
     process_id: String,
+    output_log: Option<OutputLogLocation>,
 }
 
 impl UnifiedExecProcessManager {
@@ -144,6 +155,14 @@ impl UnifiedExecProcessManager {
         };
 
         let transcript = Arc::new(tokio::sync::Mutex::new(HeadTailBuffer::default()));
+        let output_log = self.create_output_log(&request.process_id).await;
+        let output_log_location = output_log.as_ref().map(|log| OutputLogLocation {
+            process_id: request.process_id.clone(),
+            path: log.path().to_path_buf(),
+        });
+        let output_log_path = output_log_location
+            .as_ref()
+            .map(|location| location.path.clone());
         let event_ctx = ToolEventCtx::new(
             context.session.as_ref(),
             context.turn.as_ref(),
@@ -155,10 +174,11 @@ impl UnifiedExecProcessManager {
             cwd.clone(),
             ExecCommandSource::UnifiedExecStartup,
             Some(request.process_id.clone()),
+            output_log_path.clone(),
         );
         emitter.emit(event_ctx, ToolEventStage::Begin).await;
 
-        start_streaming_output(&process, context, Arc::clone(&transcript));
+        start_streaming_output(&process, context, Arc::clone(&transcript), output_log);
 
         let max_tokens = resolve_max_tokens(request.max_output_tokens);
         let yield_time_ms = clamp_yield_time(request.yield_time_ms);
@@ -173,7 +193,7 @@ impl UnifiedExecProcessManager {
             cancellation_token,
         } = process.output_handles();
         let deadline = start + Duration::from_millis(yield_time_ms);
-        let collected = Self::collect_output_until_deadline(
+        let (collected, omitted) = Self::collect_output_until_deadline(
             &output_buffer,
             &output_notify,
             &cancellation_token,
@@ -184,6 +204,7 @@ impl UnifiedExecProcessManager {
 
         let text = String::from_utf8_lossy(&collected).to_string();
         let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let output_dropped = omitted || output != text;
         let exit_code = process.exit_code();
         let has_exited = process.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
@@ -201,6 +222,7 @@ impl UnifiedExecProcessManager {
                 cwd,
                 Some(process_id),
                 Arc::clone(&transcript),
+                output_log_path,
                 output.clone(),
                 exit,
                 wall_time,
@@ -219,9 +241,11 @@ impl UnifiedExecProcessManager {
                 context,
                 &request.command,
                 cwd.clone(),
+                request.service.clone(),
                 start,
                 process_id,
                 Arc::clone(&transcript),
+                output_log_location.clone(),
             )
             .await;
 
@@ -243,6 +267,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(request.command.clone()),
+            output_log: output_log_location.filter(|_| output_dropped),
         };
 
         Ok(response)
@@ -263,6 +288,7 @@ impl UnifiedExecProcessManager {
             turn_ref,
             command: session_command,
             process_id,
+            output_log,
             ..
         } = self.prepare_process_handles(process_id.as_str()).await?;
 
@@ -277,7 +303,7 @@ impl UnifiedExecProcessManager {
         let yield_time_ms = clamp_yield_time(request.yield_time_ms);
         let start = Instant::now();
         let deadline = start + Duration::from_millis(yield_time_ms);
-        let collected = Self::collect_output_until_deadline(
+        let (collected, omitted) = Self::collect_output_until_deadline(
             &output_buffer,
             &output_notify,
             &cancellation_token,
@@ -288,6 +314,7 @@ impl UnifiedExecProcessManager {
 
         let text = String::from_utf8_lossy(&collected).to_string();
         let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let output_dropped = omitted || output != text;
         let original_token_count = approx_token_count(&text);
         let chunk_id = generate_chunk_id();
 
@@ -323,6 +350,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(session_command.clone()),
+            output_log: output_log.filter(|_| output_dropped),
         };
 
         if response.process_id.is_some() {
@@ -386,6 +414,7 @@ impl UnifiedExecProcessManager {
             turn_ref: Arc::clone(&entry.turn_ref),
             command: entry.command.clone(),
             process_id: entry.process_id.clone(),
+            output_log: entry.output_log.clone(),
         })
     }
 
@@ -406,10 +435,13 @@ impl UnifiedExecProcessManager {
         context: &UnifiedExecContext,
         command: &[String],
         cwd: PathBuf,
+        service: Option<String>,
         started_at: Instant,
         process_id: String,
         transcript: Arc<tokio::sync::Mutex<HeadTailBuffer>>,
+        output_log: Option<OutputLogLocation>,
     ) {
+        let output_log_path = output_log.as_ref().map(|location| location.path.clone());
         let entry = ProcessEntry {
             process: Arc::clone(&process),
             session_ref: Arc::clone(&context.session),
@@ -417,6 +449,11 @@ impl UnifiedExecProcessManager {
             call_id: context.call_id.clone(),
             process_id: process_id.clone(),
             command: command.to_vec(),
+            cwd: cwd.clone(),
+            service,
+            transcript: Arc::clone(&transcript),
+            output_log,
+            started_at,
             last_used: started_at,
         };
         let number_processes = {
@@ -445,10 +482,49 @@ impl UnifiedExecProcessManager {
             cwd,
             process_id,
             transcript,
+            output_log_path,
             started_at,
         );
     }
 
+    /// Opens a new log for the full output of `process_id`, when spooling is enabled.
+    async fn create_output_log(&self, process_id: &str) -> Option<OutputLog> {
+        let dir = self.output_log_dir.as_ref()?;
+        let path = self.output_logs.lock().await.next_path(dir, process_id);
+        match OutputLog::create(path).await {
+            Ok(log) => Some(log),
+            Err(err) => {
+                tracing::warn!("failed to create output log for process {process_id}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Reads from the full output saved for `process_id`, which may have exited.
+    pub(crate) async fn read_process_output(
+        &self,
+        process_id: &str,
+        query: LogQuery,
+    ) -> Result<String, UnifiedExecError> {
+        let path = self
+            .output_logs
+            .lock()
+            .await
+            .latest(process_id)
+            .cloned()
+            .ok_or_else(|| UnifiedExecError::NoOutputLog {
+                process_id: process_id.to_string(),
+            })?;
+        let read_error = |message: String| UnifiedExecError::ReadOutputLog { message };
+        tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::open(&path)?;
+            read_log(std::io::BufReader::new(file), &query)
+        })
+        .await
+        .map_err(|err| read_error(err.to_string()))?
+        .map_err(|err| read_error(err.to_string()))
+    }
+
     async fn emit_waiting_status(
         session: &Arc<Session>,
         turn: &Arc<TurnContext>,
@@ -511,6 +587,7 @@ impl UnifiedExecProcessManager {
                 context.turn.approval_policy,
                 &context.turn.sandbox_policy,
                 sandbox_permissions,
+                &cwd,
             )
             .await;
         let req = UnifiedExecToolRequest::new(
@@ -539,21 +616,24 @@ impl UnifiedExecProcessManager {
             .map_err(|e| UnifiedExecError::create_process(format!("{e:?}")))
     }
 
+    /// Drains output until `deadline`, also reporting whether the buffer had to drop any of it.
     pub(super) async fn collect_output_until_deadline(
         output_buffer: &OutputBuffer,
         output_notify: &Arc<Notify>,
         cancellation_token: &CancellationToken,
         deadline: Instant,
-    ) -> Vec<u8> {
+    ) -> (Vec<u8>, bool) {
         const POST_EXIT_OUTPUT_GRACE: Duration = Duration::from_millis(50);
 
         let mut collected: Vec<u8> = Vec::with_capacity(4096);
+        let mut omitted = false;
         let mut exit_signal_received = cancellation_token.is_cancelled();
         loop {
             let drained_chunks: Vec<Vec<u8>>;
             let mut wait_for_output = None;
             {
                 let mut guard = output_buffer.lock().await;
+                omitted |= guard.omitted_bytes() > 0;
                 drained_chunks = guard.drain_chunks();
                 if drained_chunks.is_empty() {
                     wait_for_output = Some(output_notify.notified());
@@ -597,7 +677,7 @@ impl UnifiedExecProcessManager {
             }
         }
 
-        collected
+        (collected, omitted)
     }
 
     fn prune_processes_if_needed(store: &mut ProcessStore) -> bool {
@@ -650,6 +730,62 @@ impl UnifiedExecProcessManager {
             .map(|(process_id, _, _)| process_id)
     }
 
+    /// Processes still tracked, oldest first.
+    pub(crate) async fn list_processes(&self) -> Vec<ProcessSummary> {
+        let mut entries: Vec<(Instant, ProcessSummaryParts)> = {
+            let store = self.process_store.lock().await;
+            store
+                .processes
+                .values()
+                .map(|entry| (entry.started_at, ProcessSummaryParts::from(entry)))
+                .collect()
+        };
+        entries.sort_by_key(|(started_at, _)| *started_at);
+
+        let mut summaries = Vec::with_capacity(entries.len());
+        for (_, parts) in entries {
+            summaries.push(parts.into_summary().await);
+        }
+        summaries
+    }
+
+    /// Stops a process and forgets it, returning what it looked like last.
+    pub(crate) async fn kill_process(
+        &self,
+        process_id: &str,
+    ) -> Result<ProcessSummary, UnifiedExecError> {
+        let entry = {
+            let mut store = self.process_store.lock().await;
+            store
+                .remove(process_id)
+                .ok_or(UnifiedExecError::UnknownProcessId {
+                    process_id: process_id.to_string(),
+                })?
+        };
+        // Read the ports before the process is gone.
+        let summary = ProcessSummaryParts::from(&entry).into_summary().await;
+        entry.process.terminate();
+        Ok(summary)
+    }
+
+    /// Stops the processes started during a turn. Long-running services keep running.
+    pub(crate) async fn terminate_turn_processes(&self) {
+        let entries: Vec<ProcessEntry> = {
+            let mut store = self.process_store.lock().await;
+            let ids: Vec<String> = store
+                .processes
+                .iter()
+                .filter(|(_, entry)| entry.service.is_none())
+                .map(|(process_id, _)| process_id.clone())
+                .collect();
+            ids.iter().filter_map(|id| store.remove(id)).collect()
+        };
+
+        for entry in entries {
+            entry.process.terminate();
+        }
+    }
+
     pub(crate) async fn terminate_all_processes(&self) {
         let entries: Vec<ProcessEntry> = {
             let mut processes = self.process_store.lock().await;
@@ -668,6 +804,71 @@ impl UnifiedExecProcessManager {
     }
 }
 
+/// What a summary needs from a `ProcessEntry`, taken while the store is locked.
+struct ProcessSummaryParts {
+    process_id: String,
+    command: Vec<String>,
+    cwd: PathBuf,
+    service: Option<String>,
+    started_at: Instant,
+    exit_code: Option<i32>,
+    has_exited: bool,
+    pid: Option<u32>,
+    transcript: Arc<tokio::sync::Mutex<HeadTailBuffer>>,
+}
+
+impl From<&ProcessEntry> for ProcessSummaryParts {
+    fn from(entry: &ProcessEntry) -> Self {
+        Self {
+            process_id: entry.process_id.clone(),
+            command: entry.command.clone(),
+            cwd: entry.cwd.clone(),
+            service: entry.service.clone(),
+            started_at: entry.started_at,
+            exit_code: entry.process.exit_code(),
+            has_exited: entry.process.has_exited(),
+            pid: entry.process.pid(),
+            transcript: Arc::clone(&entry.transcript),
+        }
+    }
+}
+
+impl ProcessSummaryParts {
+    async fn into_summary(self) -> ProcessSummary {
+        let output = self.transcript.lock().await.to_bytes();
+        let listening_ports = match self.pid {
+            Some(pid) if !self.has_exited => listening_ports(pid),
+            _ => Vec::new(),
+        };
+        ProcessSummary {
+            process_id: self.process_id,
+            command: self.command,
+            cwd: self.cwd,
+            service: self.service,
+            age_secs: self.started_at.elapsed().as_secs(),
+            exit_code: if self.has_exited {
+                Some(self.exit_code.unwrap_or(-1))
+            } else {
+                None
+            },
+            output_tail: output_tail(&output),
+            listening_ports,
+        }
+    }
+}
+
+/// The last `PROCESS_OUTPUT_TAIL_LINES` non-empty lines of `output`.
+fn output_tail(output: &[u8]) -> String {
+    let start = output.len().saturating_sub(PROCESS_OUTPUT_TAIL_MAX_BYTES);
+    let text = String::from_utf8_lossy(&output[start..]);
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(PROCESS_OUTPUT_TAIL_LINES)..].join("\n")
+}
+
 enum ProcessStatus {
     Alive {
         exit_code: Option<i32>,
@@ -718,6 +919,17 @@ mod tests {
         assert_eq!(env.get("PATH"), Some(&"/usr/bin".to_string()));
     }
 
+    #[test]
+    fn output_tail_keeps_last_non_empty_lines() {
+        let output: String = (1..=15).map(|n| format!("line {n}\r\n\n")).collect();
+
+        let tail = output_tail(output.as_bytes());
+
+        let expected: Vec<String> = (6..=15).map(|n| format!("line {n}")).collect();
+        assert_eq!(tail, expected.join("\n"));
+        assert_eq!(output_tail(b""), "");
+    }
+
     #[test]
     fn pruning_prefers_exited_processes_outside_recently_used() {
         let now = Instant::now();
@@ -578,6 +578,8 @@ async fn apply_patch_cli_rejects_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
     harness
         .submit_with_policy(
@@ -634,6 +636,8 @@ async fn apply_patch_cli_rejects_move_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
     harness
         .submit_with_policy("attempt move traversal via apply_patch", sandbox_policy)
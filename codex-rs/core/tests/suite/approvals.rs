@@ -626,6 +626,8 @@ fn scenarios() -> Vec<ScenarioSpec> {
         network_access,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
 
     vec![
@@ -1573,6 +1575,8 @@ async fn approving_apply_patch_for_session_skips_future_prompts_for_same_file()
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
     let sandbox_policy_for_config = sandbox_policy.clone();
 
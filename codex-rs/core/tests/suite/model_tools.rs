@@ -127,6 +127,9 @@ async fn model_selects_expected_tools() {
         vec![
             "exec_command".to_string(),
             "write_stdin".to_string(),
+            "list_processes".to_string(),
+            "kill_process".to_string(),
+            "read_process_output".to_string(),
             "list_mcp_resources".to_string(),
             "list_mcp_resource_templates".to_string(),
             "read_mcp_resource".to_string(),
@@ -327,6 +327,8 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                read_only_subpaths: vec![],
+                deny_read_paths: vec![],
             }),
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
@@ -532,6 +534,8 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() -> anyhow::Res
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                read_only_subpaths: vec![],
+                deny_read_paths: vec![],
             },
             model: "o3".to_string(),
             effort: Some(ReasoningEffort::High),
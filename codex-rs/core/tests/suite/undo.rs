@@ -2,6 +2,7 @@
 
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
 
@@ -13,6 +14,8 @@ use codex_core::features::Feature;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::UndoCompletedEvent;
+use codex_core::protocol::UndoSnapshotFile;
+use codex_core::protocol::UndoTimelineEvent;
 use core_test_support::responses::ev_apply_patch_function_call;
 use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
@@ -118,6 +121,31 @@ async fn invoke_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
     Ok(event)
 }
 
+async fn submit_undo_op(codex: &Arc<CodexThread>, op: Op) -> Result<UndoCompletedEvent> {
+    codex.submit(op).await?;
+    let event = wait_for_event_match(codex, |msg| match msg {
+        EventMsg::UndoCompleted(done) => Some(done.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(
+        event.success,
+        "expected undo to succeed but failed with message {:?}",
+        event.message
+    );
+    Ok(event)
+}
+
+async fn undo_timeline(codex: &Arc<CodexThread>) -> Result<UndoTimelineEvent> {
+    codex.submit(Op::ListUndoSnapshots).await?;
+    let event = wait_for_event_match(codex, |msg| match msg {
+        EventMsg::UndoTimeline(timeline) => Some(timeline.clone()),
+        _ => None,
+    })
+    .await;
+    Ok(event)
+}
+
 async fn expect_successful_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
     let event = invoke_undo(codex).await?;
     assert!(
@@ -548,3 +576,149 @@ async fn undo_preserves_unrelated_staged_changes() -> Result<()> {
 
     Ok(())
 }
+
+async fn run_story_turns(harness: &TestCodexHarness) -> Result<PathBuf> {
+    let story = harness.path("story.txt");
+    fs::write(&story, "initial\n")?;
+    git(harness.cwd(), &["add", "story.txt"])?;
+    git(harness.cwd(), &["commit", "-m", "seed story"])?;
+
+    run_apply_patch_turn(
+        harness,
+        "first change",
+        "story-turn-1",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-initial\n+turn one\n*** End Patch",
+        "ok",
+    )
+    .await?;
+    run_apply_patch_turn(
+        harness,
+        "second change",
+        "story-turn-2",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-turn one\n+turn two\n*** Add File: notes.txt\n+from turn two\n*** End Patch",
+        "ok",
+    )
+    .await?;
+    assert_eq!(fs::read_to_string(&story)?, "turn two\n");
+
+    Ok(story)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_timeline_lists_snapshots_with_turn_changes() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    run_story_turns(&harness).await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    let timeline = undo_timeline(&codex).await?;
+
+    assert!(!timeline.redo_available);
+    let summaries: Vec<_> = timeline
+        .snapshots
+        .iter()
+        .map(|snapshot| snapshot.summary.as_deref())
+        .collect();
+    assert_eq!(summaries, vec![Some("first change"), Some("second change")]);
+    assert_eq!(
+        timeline.snapshots[0].files,
+        vec![UndoSnapshotFile {
+            path: PathBuf::from("story.txt"),
+            insertions: 1,
+            deletions: 1,
+        }]
+    );
+    assert_eq!(
+        timeline.snapshots[1].files,
+        vec![
+            UndoSnapshotFile {
+                path: PathBuf::from("notes.txt"),
+                insertions: 1,
+                deletions: 0,
+            },
+            UndoSnapshotFile {
+                path: PathBuf::from("story.txt"),
+                insertions: 1,
+                deletions: 1,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restore_to_earlier_snapshot_can_be_redone() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    let story = run_story_turns(&harness).await?;
+    let notes = harness.path("notes.txt");
+
+    let codex = Arc::clone(&harness.test().codex);
+    let first = undo_timeline(&codex).await?.snapshots[0].commit_id.clone();
+    submit_undo_op(
+        &codex,
+        Op::RestoreUndoSnapshot {
+            commit_id: first,
+            paths: Vec::new(),
+        },
+    )
+    .await?;
+
+    assert_eq!(fs::read_to_string(&story)?, "initial\n");
+    assert!(!notes.exists());
+    let timeline = undo_timeline(&codex).await?;
+    assert!(timeline.snapshots.is_empty());
+    assert!(timeline.redo_available);
+
+    submit_undo_op(&codex, Op::Redo).await?;
+
+    assert_eq!(fs::read_to_string(&story)?, "turn two\n");
+    assert_eq!(fs::read_to_string(&notes)?, "from turn two\n");
+    let timeline = undo_timeline(&codex).await?;
+    assert_eq!(timeline.snapshots.len(), 2);
+    assert!(!timeline.redo_available);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restore_selected_files_from_snapshot() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    let story = run_story_turns(&harness).await?;
+    let notes = harness.path("notes.txt");
+
+    let codex = Arc::clone(&harness.test().codex);
+    let second = undo_timeline(&codex).await?.snapshots[1].commit_id.clone();
+    let completed = submit_undo_op(
+        &codex,
+        Op::RestoreUndoSnapshot {
+            commit_id: second,
+            paths: vec![PathBuf::from("notes.txt")],
+        },
+    )
+    .await?;
+
+    assert!(!notes.exists());
+    assert_eq!(fs::read_to_string(&story)?, "turn two\n");
+    assert_eq!(
+        completed
+            .message
+            .as_deref()
+            .map(|message| message.starts_with("Undo restored 1 file")),
+        Some(true)
+    );
+    assert_eq!(undo_timeline(&codex).await?.snapshots.len(), 2);
+
+    submit_undo_op(&codex, Op::Redo).await?;
+    assert_eq!(fs::read_to_string(&notes)?, "from turn two\n");
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+//! Read access to the artifacts a council run leaves under `.council/runs/<run-id>`.
+//!
+//! These helpers are synchronous because they back interactive views that load a
+//! handful of small files on demand.
+
+use crate::parsing;
+use crate::parsing::Finding;
+use crate::types::CouncilMode;
+use crate::types::JobOutcome;
+use crate::usage::RunUsage;
+use crate::verify::VerifyResult;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const METADATA_FILE: &str = "job_metadata.json";
+pub const STATUS_FILE: &str = "job_status.json";
+/// Empty marker file written by `codex council pin`; pinned runs are never garbage-collected.
+pub const PINNED_FILE: &str = "pinned";
+
+/// Critique artifacts in display order, with the label used for each critic.
+pub const CRITIQUE_FILES: &[(&str, &str)] =
+    &[("GPT", "critique_gpt.md"), ("Gemini", "critique_gemini.md")];
+
+/// Subset of `job_metadata.json` needed to describe or re-run a job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobMetadata {
+    pub job_id: String,
+    pub mode: CouncilMode,
+    pub target: PathBuf,
+    #[serde(default)]
+    pub head_sha_at_start: String,
+    #[serde(default)]
+    pub repo_dirty_at_start: bool,
+    #[serde(default)]
+    pub timestamp: u64,
+}
+
+/// How a run ended. Written by the runner when it emits `JobFinished`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub outcome: JobOutcome,
+    pub summary_line: String,
+    pub finished_at: u64,
+}
+
+/// A run directory with whatever metadata could be read from it.
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub dir: PathBuf,
+    pub metadata: Option<JobMetadata>,
+    pub status: Option<JobStatus>,
+    pub pinned: bool,
+}
+
+/// A finding attributed to the critic that raised it.
+#[derive(Debug, Clone)]
+pub struct CriticFinding {
+    pub critic: String,
+    pub finding: Finding,
+}
+
+/// Everything a run produced, loaded from its directory.
+#[derive(Debug, Clone, Default)]
+pub struct RunArtifacts {
+    /// `(critic label, critique text)` pairs.
+    pub critiques: Vec<(String, String)>,
+    pub plan: Option<String>,
+    /// The extracted apply_patch payload.
+    pub patch: Option<String>,
+    pub verify_baseline: Option<Vec<VerifyResult>>,
+    pub verify_final: Option<Vec<VerifyResult>>,
+    pub usage: Option<RunUsage>,
+}
+
+impl RunArtifacts {
+    pub fn load(run_dir: &Path) -> Self {
+        let critiques = CRITIQUE_FILES
+            .iter()
+            .filter_map(|(label, file)| {
+                fs::read_to_string(run_dir.join(file))
+                    .ok()
+                    .map(|text| (label.to_string(), text))
+            })
+            .collect();
+        let patch = fs::read_to_string(run_dir.join("implementation.patch"))
+            .ok()
+            .map(|raw| parsing::extract_patch_lenient(&raw));
+
+        Self {
+            critiques,
+            plan: fs::read_to_string(run_dir.join("plan.md")).ok(),
+            patch,
+            verify_baseline: read_json(&run_dir.join("verify_baseline.json")),
+            verify_final: read_json(&run_dir.join("verify_final.json")),
+            usage: read_json(&run_dir.join("usage.json")),
+        }
+    }
+
+    /// All findings across critiques, most severe first.
+    pub fn findings(&self) -> Vec<CriticFinding> {
+        let mut out: Vec<CriticFinding> = self
+            .critiques
+            .iter()
+            .flat_map(|(critic, text)| {
+                parsing::extract_findings(text)
+                    .into_iter()
+                    .map(|finding| CriticFinding {
+                        critic: critic.clone(),
+                        finding,
+                    })
+            })
+            .collect();
+        out.sort_by_key(|f| severity_rank(&f.finding.severity));
+        out
+    }
+}
+
+fn severity_rank(severity: &parsing::Severity) -> u8 {
+    match severity {
+        parsing::Severity::P0 => 0,
+        parsing::Severity::P1 => 1,
+        parsing::Severity::P2 => 2,
+        parsing::Severity::P3 => 3,
+        parsing::Severity::Unknown => 4,
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+pub fn runs_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join(".council").join("runs")
+}
+
+pub fn load_run(run_dir: &Path) -> Option<RunRecord> {
+    let run_id = run_dir.file_name()?.to_str()?.to_string();
+    Some(RunRecord {
+        run_id,
+        dir: run_dir.to_path_buf(),
+        metadata: read_json(&run_dir.join(METADATA_FILE)),
+        status: read_json(&run_dir.join(STATUS_FILE)),
+        pinned: run_dir.join(PINNED_FILE).exists(),
+    })
+}
+
+/// Lists the runs under `.council/runs`, newest first.
+pub fn list_runs(repo_root: &Path) -> Vec<RunRecord> {
+    let Ok(entries) = fs::read_dir(runs_dir(repo_root)) else {
+        return Vec::new();
+    };
+    let mut runs: Vec<RunRecord> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| load_run(&entry.path()))
+        .collect();
+    runs.sort_by(|a, b| {
+        let a_ts = a.metadata.as_ref().map(|m| m.timestamp).unwrap_or_default();
+        let b_ts = b.metadata.as_ref().map(|m| m.timestamp).unwrap_or_default();
+        b_ts.cmp(&a_ts).then_with(|| b.run_id.cmp(&a.run_id))
+    });
+    runs
+}
+
+/// Renders a run as a single Markdown report (critiques, findings, plan, patch and
+/// verification output).
+pub fn export_markdown(record: &RunRecord, artifacts: &RunArtifacts) -> String {
+    let mut out = format!("# Council run {}\n\n", record.run_id);
+    if let Some(meta) = &record.metadata {
+        out.push_str(&format!(
+            "- Mode: {:?}\n- Target: `{}`\n- HEAD: `{}`\n",
+            meta.mode,
+            meta.target.display(),
+            meta.head_sha_at_start
+        ));
+    }
+    if let Some(status) = &record.status {
+        out.push_str(&format!(
+            "- Outcome: {:?} ({})\n",
+            status.outcome, status.summary_line
+        ));
+    }
+    if let Some(usage) = &artifacts.usage {
+        out.push_str(&format!("- Usage: {}\n", usage.summary_line()));
+    }
+
+    let findings = artifacts.findings();
+    if !findings.is_empty() {
+        out.push_str(
+            "\n## Findings\n\n| Severity | Critic | Location | Issue |\n|---|---|---|---|\n",
+        );
+        for f in &findings {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                f.finding.severity.label(),
+                f.critic,
+                f.finding.field("location").unwrap_or_default(),
+                f.finding.field("issue").unwrap_or_default()
+            ));
+        }
+    }
+
+    for (critic, text) in &artifacts.critiques {
+        out.push_str(&format!("\n## {critic} critique\n\n{}\n", text.trim()));
+    }
+    if let Some(plan) = &artifacts.plan {
+        out.push_str(&format!("\n## Plan\n\n{}\n", plan.trim()));
+    }
+    if let Some(patch) = &artifacts.patch {
+        out.push_str(&format!("\n## Patch\n\n```\n{}\n```\n", patch.trim()));
+    }
+    for (title, results) in [
+        ("Verification (before)", &artifacts.verify_baseline),
+        ("Verification (after)", &artifacts.verify_final),
+    ] {
+        let Some(results) = results else {
+            continue;
+        };
+        out.push_str(&format!("\n## {title}\n"));
+        for r in results {
+            let status = if r.success { "pass" } else { "fail" };
+            out.push_str(&format!("\n### `{}` — {status}\n", r.command));
+            let output = format!("{}{}", r.stdout, r.stderr);
+            if !output.trim().is_empty() {
+                out.push_str(&format!("\n```\n{}\n```\n", output.trim()));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn lists_runs_newest_first_and_loads_findings() {
+        let repo = TempDir::new().unwrap();
+        for (id, ts) in [("run-1", 100), ("run-2", 200)] {
+            let dir = runs_dir(repo.path()).join(id);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join(METADATA_FILE),
+                format!(
+                    r#"{{"job_id":"{id}","mode":"Review","target":"src/lib.rs","timestamp":{ts}}}"#
+                ),
+            )
+            .unwrap();
+        }
+        let newest = runs_dir(repo.path()).join("run-2");
+        fs::write(
+            newest.join("critique_gpt.md"),
+            r#"<finding severity="P2"><issue>Naming</issue></finding>
+<finding severity="P0"><location>src/lib.rs:3</location><issue>Panic</issue></finding>"#,
+        )
+        .unwrap();
+
+        let runs = list_runs(repo.path());
+        let ids: Vec<&str> = runs.iter().map(|r| r.run_id.as_str()).collect();
+        assert_eq!(ids, vec!["run-2", "run-1"]);
+
+        let findings = RunArtifacts::load(&newest).findings();
+        let issues: Vec<String> = findings
+            .iter()
+            .filter_map(|f| f.finding.field("issue"))
+            .collect();
+        assert_eq!(issues, vec!["Panic".to_string(), "Naming".to_string()]);
+        assert_eq!(findings[0].critic, "GPT");
+    }
+}
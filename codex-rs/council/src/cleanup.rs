@@ -1,4 +1,11 @@
+use crate::artifacts;
+use crate::artifacts::RunRecord;
+use crate::types::JobOutcome;
 use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::types::CouncilRetention;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::time::SystemTime;
@@ -6,103 +13,236 @@ use tokio::fs;
 use tracing::info;
 use tracing::warn;
 
-pub async fn cleanup_old_jobs(repo_root: PathBuf) -> Result<()> {
-    let runs_dir = repo_root.join(".council").join("runs");
-    if !runs_dir.exists() {
+/// What `gc` removed (or would remove, for a dry run).
+#[derive(Debug, Default)]
+pub struct GcReport {
+    pub removed_runs: Vec<String>,
+    /// Dedicated worktrees whose run directory is gone.
+    pub orphaned_worktrees: Vec<PathBuf>,
+    pub kept_runs: usize,
+}
+
+/// Runs the implicit cleanup the TUI does before starting a job. Does nothing when
+/// `auto_gc` is disabled.
+pub async fn cleanup_old_jobs(repo_root: PathBuf, retention: CouncilRetention) -> Result<()> {
+    if !retention.auto_gc {
         return Ok(());
     }
+    gc(&repo_root, &retention, false).await.map(|_| ())
+}
 
-    let mut entries = Vec::new();
-    let mut dir = fs::read_dir(&runs_dir).await?;
-
-    while let Some(entry) = dir.next_entry().await? {
-        if let Ok(meta) = entry.metadata().await
-            && meta.is_dir()
-            && let Ok(created) = meta.created().or_else(|_| meta.modified())
-        {
-            entries.push((entry.path(), created));
-        }
+/// Pins (or unpins) a run so retention never deletes it.
+pub async fn set_pinned(repo_root: &Path, run_id: &str, pinned: bool) -> Result<()> {
+    let run_dir = artifacts::runs_dir(repo_root).join(run_id);
+    if run_id.contains(['/', '\\']) || run_id.starts_with('.') || !run_dir.is_dir() {
+        bail!("no council run named {run_id}");
     }
+    let marker = run_dir.join(artifacts::PINNED_FILE);
+    if pinned {
+        fs::write(&marker, "").await?;
+    } else if marker.exists() {
+        fs::remove_file(&marker).await?;
+    }
+    Ok(())
+}
 
-    // Sort by creation time (newest first)
-    entries.sort_by(|a, b| b.1.cmp(&a.1));
-
-    let now = SystemTime::now();
-    let retention_limit = 20;
-    let age_limit = Duration::from_secs(24 * 60 * 60); // 24 hours
+fn run_age(run: &RunRecord, now: SystemTime) -> Option<Duration> {
+    let created = match &run.metadata {
+        Some(meta) if meta.timestamp > 0 => {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(meta.timestamp)
+        }
+        _ => {
+            let meta = std::fs::metadata(&run.dir).ok()?;
+            meta.created().or_else(|_| meta.modified()).ok()?
+        }
+    };
+    now.duration_since(created).ok()
+}
 
-    let mut to_remove = Vec::new();
+/// Picks the runs to delete. `runs` must be ordered newest first.
+fn select_expired(
+    runs: &[RunRecord],
+    retention: &CouncilRetention,
+    now: SystemTime,
+) -> Vec<String> {
+    let pinned: HashSet<&str> = retention.pinned.iter().map(String::as_str).collect();
+    let age_limit = Duration::from_secs(retention.max_age_hours * 60 * 60);
+
+    let mut expired = Vec::new();
+    for (i, run) in runs.iter().enumerate() {
+        if run.pinned || pinned.contains(run.run_id.as_str()) {
+            continue;
+        }
+        let finished = run.status.as_ref();
+        if retention.keep_successful && finished.is_some_and(|s| s.outcome == JobOutcome::Success) {
+            continue;
+        }
 
-    for (i, (path, created)) in entries.iter().enumerate() {
-        let mut should_remove = false;
+        let over_count = retention.keep_runs > 0 && i >= retention.keep_runs;
+        let over_age =
+            retention.max_age_hours > 0 && run_age(run, now).is_some_and(|age| age > age_limit);
 
-        // Rule 1: Count limit
-        if i >= retention_limit {
-            should_remove = true;
+        // Unfinished runs may still be queued or running; only the age limit removes them.
+        if over_age || (over_count && finished.is_some()) {
+            expired.push(run.run_id.clone());
         }
+    }
+    expired
+}
 
-        // Rule 2: Age limit
-        if let Ok(age) = now.duration_since(*created)
-            && age > age_limit
-        {
-            should_remove = true;
+async fn remove_worktree(repo_root: &Path, worktree_path: &Path) {
+    // git worktree remove --force <path>
+    let status = tokio::process::Command::new("git")
+        .arg("worktree")
+        .arg("remove")
+        .arg("--force")
+        .arg(worktree_path)
+        .current_dir(repo_root)
+        .status()
+        .await;
+
+    match status {
+        Ok(s) if s.success() => {}
+        Ok(s) => {
+            warn!(
+                "Failed to remove worktree {:?} (status {}). Attempting manual cleanup.",
+                worktree_path, s
+            );
+            // If git fails (e.g. index locked or already gone), we try to remove dir.
+            let _ = fs::remove_dir_all(worktree_path).await;
         }
-
-        if should_remove {
-            to_remove.push(path.clone());
+        Err(e) => {
+            warn!("Failed to execute git worktree remove: {}", e);
+            let _ = fs::remove_dir_all(worktree_path).await;
         }
     }
+}
 
-    for run_path in to_remove {
-        let run_id = run_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or_default();
-        if run_id.is_empty() {
+/// Dedicated worktrees under `.council/worktrees` whose run directory no longer exists.
+/// Pool slots (`pool-<n>`) are recycled instead, so they are never reported.
+async fn orphaned_worktrees(repo_root: &Path, removed: &HashSet<&str>) -> Result<Vec<PathBuf>> {
+    let worktrees_dir = repo_root.join(".council").join("worktrees");
+    let runs_dir = artifacts::runs_dir(repo_root);
+    let mut orphans = Vec::new();
+    let Ok(mut dir) = fs::read_dir(&worktrees_dir).await else {
+        return Ok(orphans);
+    };
+    while let Some(entry) = dir.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("pool-") || !entry.file_type().await?.is_dir() {
             continue;
         }
+        if removed.contains(name.as_str()) || !runs_dir.join(&name).exists() {
+            orphans.push(entry.path());
+        }
+    }
+    orphans.sort();
+    Ok(orphans)
+}
 
-        info!("Cleaning up old council run: {}", run_id);
-
-        // 1. Check for worktree
-        let worktree_path = repo_root.join(".council").join("worktrees").join(run_id);
-        if worktree_path.exists() {
-            info!("Removing associated worktree: {:?}", worktree_path);
-            // git worktree remove --force <path>
-            // We use a simple command here.
-            let status = tokio::process::Command::new("git")
-                .arg("worktree")
-                .arg("remove")
-                .arg("--force")
-                .arg(&worktree_path)
-                .current_dir(&repo_root) // Run from repo root
-                .status()
-                .await;
-
-            match status {
-                Ok(s) => {
-                    if !s.success() {
-                        warn!(
-                            "Failed to remove worktree {} (status {}). Attempting manual cleanup.",
-                            run_id, s
-                        );
-                        // If git fails (e.g. index locked or already gone), we try to remove dir.
-                        let _ = fs::remove_dir_all(&worktree_path).await;
-                        // And verify git prune? "git worktree prune" might be needed later.
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to execute git worktree remove: {}", e);
-                    let _ = fs::remove_dir_all(&worktree_path).await;
-                }
-            }
+/// Applies the retention policy to `.council/runs`, removes worktrees left behind by
+/// deleted runs, and prunes git's worktree list. With `dry_run`, only reports.
+pub async fn gc(repo_root: &Path, retention: &CouncilRetention, dry_run: bool) -> Result<GcReport> {
+    if !dry_run {
+        // Release pool slots left behind by finished or crashed runs.
+        if let Err(e) = crate::worktree::recycle_pool(repo_root).await {
+            warn!("Failed to recycle pooled worktrees: {}", e);
         }
+    }
+
+    let runs = artifacts::list_runs(repo_root);
+    let expired = select_expired(&runs, retention, SystemTime::now());
+    let expired_set: HashSet<&str> = expired.iter().map(String::as_str).collect();
+    let orphaned = orphaned_worktrees(repo_root, &expired_set).await?;
+
+    let report = GcReport {
+        kept_runs: runs.len() - expired.len(),
+        removed_runs: expired.clone(),
+        orphaned_worktrees: orphaned,
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    for worktree_path in &report.orphaned_worktrees {
+        info!("Removing orphaned worktree: {:?}", worktree_path);
+        remove_worktree(repo_root, worktree_path).await;
+    }
 
-        // 2. Remove run artifacts
+    for run_id in &report.removed_runs {
+        info!("Cleaning up old council run: {}", run_id);
+        let run_path = artifacts::runs_dir(repo_root).join(run_id);
         if let Err(e) = fs::remove_dir_all(&run_path).await {
             warn!("Failed to remove run directory {:?}: {}", run_path, e);
         }
     }
 
-    Ok(())
+    let prune = tokio::process::Command::new("git")
+        .arg("worktree")
+        .arg("prune")
+        .current_dir(repo_root)
+        .status()
+        .await;
+    if let Err(e) = prune {
+        warn!("Failed to execute git worktree prune: {}", e);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::JobMetadata;
+    use crate::artifacts::JobStatus;
+    use crate::types::CouncilMode;
+
+    fn run(id: &str, timestamp: u64, outcome: Option<JobOutcome>, pinned: bool) -> RunRecord {
+        RunRecord {
+            run_id: id.to_string(),
+            dir: PathBuf::from(id),
+            metadata: Some(JobMetadata {
+                job_id: id.to_string(),
+                mode: CouncilMode::Fix,
+                target: PathBuf::from("src/lib.rs"),
+                head_sha_at_start: String::new(),
+                repo_dirty_at_start: false,
+                timestamp,
+            }),
+            status: outcome.map(|outcome| JobStatus {
+                outcome,
+                summary_line: String::new(),
+                finished_at: timestamp,
+            }),
+            pinned,
+        }
+    }
+
+    #[test]
+    fn retention_respects_count_age_pins_and_successful_runs() {
+        let hour = 60 * 60;
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 * hour);
+        let at = |hours_ago: u64| (1_000 - hours_ago) * hour;
+        let runs = vec![
+            run("run-new", at(1), Some(JobOutcome::Failure), false),
+            run("run-running", at(2), None, false),
+            run("run-failed", at(3), Some(JobOutcome::Failure), false),
+            run("run-ok", at(4), Some(JobOutcome::Success), false),
+            run("run-pinned", at(500), Some(JobOutcome::Failure), true),
+            run("run-config-pin", at(500), Some(JobOutcome::Failure), false),
+            run("run-ancient", at(500), None, false),
+        ];
+        let retention = CouncilRetention {
+            keep_runs: 1,
+            max_age_hours: 48,
+            keep_successful: true,
+            pinned: vec!["run-config-pin".to_string()],
+            auto_gc: true,
+        };
+
+        assert_eq!(
+            select_expired(&runs, &retention, now),
+            vec!["run-failed".to_string(), "run-ancient".to_string()]
+        );
+    }
 }
@@ -7,6 +7,8 @@ use codex_core::default_client::build_reqwest_client;
 use codex_core::model_provider_info::ModelProviderInfo;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use std::time::Instant;
 
 #[derive(Clone)]
 pub struct SimpleAuthProvider {
@@ -19,6 +21,13 @@ impl codex_api::auth::AuthProvider for SimpleAuthProvider {
     }
 }
 
+/// A completed model call: the response text plus the usage reported by the provider.
+pub struct CouncilReply {
+    pub content: String,
+    pub token_usage: Option<TokenUsage>,
+    pub latency_ms: u64,
+}
+
 pub struct CouncilClient {
     pub model_id: String,
     pub client: ChatClient<ReqwestTransport, SimpleAuthProvider>,
@@ -52,6 +61,18 @@ impl CouncilClient {
         system_prompt: String,
         user_message: String,
     ) -> Result<String> {
+        Ok(self
+            .send_message_with_usage(system_prompt, user_message)
+            .await?
+            .content)
+    }
+
+    pub async fn send_message_with_usage(
+        &self,
+        system_prompt: String,
+        user_message: String,
+    ) -> Result<CouncilReply> {
+        let started = Instant::now();
         let input = vec![ResponseItem::Message {
             id: None,
             role: "user".to_string(),
@@ -64,6 +85,7 @@ impl CouncilClient {
         // Send request
         let mut stream = self.client.stream_request(request).await?;
         let mut full_content = String::new();
+        let mut usage = None;
 
         use codex_api::ResponseEvent;
         use futures::StreamExt;
@@ -83,6 +105,9 @@ impl CouncilClient {
                         }
                     }
                 }
+                ResponseEvent::Completed { token_usage, .. } => {
+                    usage = token_usage;
+                }
                 _ => {}
             }
         }
@@ -92,6 +117,10 @@ impl CouncilClient {
             anyhow::bail!("No content in response from {model_id}");
         }
 
-        Ok(full_content)
+        Ok(CouncilReply {
+            content: full_content,
+            token_usage: usage,
+            latency_ms: started.elapsed().as_millis() as u64,
+        })
     }
 }
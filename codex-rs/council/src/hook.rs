@@ -0,0 +1,519 @@
+//! Git pre-commit / pre-push integration.
+//!
+//! `codex council hook install` writes small shell hooks that call back into
+//! `codex council hook run <kind>`. The run reviews only the staged (pre-commit) or
+//! outgoing (pre-push) diff with the critic models, caches the findings by the diff's
+//! git object hash, and exits non-zero when a finding meets the configured severity.
+//! The hooks fail open: when a critic cannot be reached the commit or push goes
+//! through with a warning.
+
+use crate::client::CouncilClient;
+use crate::parsing;
+use crate::parsing::Severity;
+use crate::prompts::PromptPack;
+use crate::types::CouncilConfig;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::types::CouncilHookSeverity;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Set to any non-empty value to skip the council hooks for one commit or push.
+pub const BYPASS_ENV_VAR: &str = "CODEX_COUNCIL_SKIP";
+
+/// Marker line identifying hooks written by `hook install`.
+const HOOK_MARKER: &str = "# Installed by `codex council hook install`.";
+
+/// The all-zero object id git uses for "no commit" in pre-push input.
+const ZERO_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// Diffs larger than this are truncated before being sent to the critics.
+const MAX_DIFF_BYTES: usize = 200 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    PreCommit,
+    PrePush,
+}
+
+impl HookKind {
+    pub const ALL: [HookKind; 2] = [HookKind::PreCommit, HookKind::PrePush];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "pre-commit",
+            HookKind::PrePush => "pre-push",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.file_name() == name)
+    }
+}
+
+/// A finding as cached and printed by the hook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookFinding {
+    pub severity: Severity,
+    pub critic: String,
+    pub location: String,
+    pub issue: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedReview {
+    diff_hash: String,
+    findings: Vec<HookFinding>,
+}
+
+/// Outcome of a hook run.
+#[derive(Debug, Clone)]
+pub struct HookReport {
+    pub diff_hash: Option<String>,
+    pub cached: bool,
+    pub findings: Vec<HookFinding>,
+    /// Findings at or above the blocking threshold.
+    pub blocking: usize,
+    /// Why the critics could not review the diff. The hook does not block then.
+    pub review_error: Option<String>,
+}
+
+impl HookReport {
+    /// Lines for the hook's stderr: one per finding, then a one-line verdict.
+    pub fn stderr_lines(&self, threshold: CouncilHookSeverity) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .findings
+            .iter()
+            .map(|f| {
+                let location = if f.location.is_empty() {
+                    "-"
+                } else {
+                    f.location.as_str()
+                };
+                format!(
+                    "council: {} {location} {} ({})",
+                    f.severity.label(),
+                    f.issue,
+                    f.critic
+                )
+            })
+            .collect();
+        let cache = if self.cached { " (cached)" } else { "" };
+        let threshold = format!("{threshold:?}");
+        if self.diff_hash.is_none() {
+            lines.push("council: no changes to review".to_string());
+        } else if let Some(error) = &self.review_error {
+            lines.push(format!(
+                "council: warning: review failed, not blocking: {error}"
+            ));
+        } else if self.blocking > 0 {
+            lines.push(format!(
+                "council: blocked{cache}: {} finding(s) at or above {threshold}. Set {BYPASS_ENV_VAR}=1 to bypass.",
+                self.blocking
+            ));
+        } else {
+            lines.push(format!(
+                "council: ok{cache}: {} finding(s), none at or above {threshold}",
+                self.findings.len()
+            ));
+        }
+        lines
+    }
+}
+
+/// Returns true when the bypass variable is set to a non-empty value.
+pub fn bypassed() -> bool {
+    std::env::var(BYPASS_ENV_VAR).is_ok_and(|v| !v.is_empty())
+}
+
+fn blocks(finding: &HookFinding, threshold: CouncilHookSeverity) -> bool {
+    let threshold = match threshold {
+        CouncilHookSeverity::P0 => Severity::P0,
+        CouncilHookSeverity::P1 => Severity::P1,
+        CouncilHookSeverity::P2 => Severity::P2,
+        CouncilHookSeverity::P3 => Severity::P3,
+    };
+    finding.severity <= threshold
+}
+
+fn hook_script(codex_bin: &Path, kind: HookKind) -> String {
+    let bin = codex_bin.display().to_string().replace('\'', r"'\''");
+    let name = kind.file_name();
+    format!(
+        "#!/bin/sh\n{HOOK_MARKER}\n[ -n \"${BYPASS_ENV_VAR}\" ] && exit 0\nexec '{bin}' council hook run {name} \"$@\"\n"
+    )
+}
+
+async fn git_output(repo_root: &Path, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .stderr(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        bail!("git {} failed", args.join(" "));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Writes the hooks into the repository's hooks directory (honouring `core.hooksPath`).
+///
+/// Existing hooks that were not written by `hook install` are left alone unless `force`.
+pub async fn install_hooks(
+    repo_root: &Path,
+    codex_bin: &Path,
+    kinds: &[HookKind],
+    force: bool,
+) -> Result<Vec<PathBuf>> {
+    let hooks_dir = git_output(repo_root, &["rev-parse", "--git-path", "hooks"]).await?;
+    let hooks_dir = repo_root.join(hooks_dir.trim());
+    fs::create_dir_all(&hooks_dir).await?;
+
+    let mut written = Vec::new();
+    for kind in kinds {
+        let path = hooks_dir.join(kind.file_name());
+        if !force
+            && let Ok(existing) = fs::read_to_string(&path).await
+            && !existing.contains(HOOK_MARKER)
+        {
+            bail!(
+                "{} already exists and was not installed by codex; rerun with --force to replace it",
+                path.display()
+            );
+        }
+        fs::write(&path, hook_script(codex_bin, *kind)).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&path).await?.permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&path, perms).await?;
+        }
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Returns the staged diff for pre-commit.
+async fn staged_diff(repo_root: &Path) -> Result<String> {
+    git_output(
+        repo_root,
+        &["diff", "--cached", "--no-color", "--unified=3"],
+    )
+    .await
+}
+
+/// Returns the outgoing diff for pre-push, given the hook's stdin
+/// (`<local ref> <local sha> <remote ref> <remote sha>` per line).
+async fn outgoing_diff(repo_root: &Path, push_refs: &str) -> Result<String> {
+    let mut diff = String::new();
+    for line in push_refs.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let [_, local_sha, _, remote_sha] = parts.as_slice() else {
+            continue;
+        };
+        if *local_sha == ZERO_SHA {
+            // Branch deletion: nothing to review.
+            continue;
+        }
+        let remote_known = *remote_sha != ZERO_SHA && commit_exists(repo_root, remote_sha).await;
+        let outgoing = if remote_known {
+            git_output(
+                repo_root,
+                &["diff", "--no-color", "--unified=3", remote_sha, local_sha],
+            )
+            .await?
+        } else {
+            // New branch, or a remote tip we have not fetched: review only the commits
+            // no remote-tracking ref has seen.
+            unpushed_commits_diff(repo_root, local_sha).await?
+        };
+        diff.push_str(&outgoing);
+    }
+    Ok(diff)
+}
+
+async fn commit_exists(repo_root: &Path, sha: &str) -> bool {
+    let object = format!("{sha}^{{commit}}");
+    git_output(repo_root, &["cat-file", "-e", &object])
+        .await
+        .is_ok()
+}
+
+/// Patches of the commits reachable from `local_sha` but from no remote, i.e.
+/// `git rev-list <local> --not --remotes`, oldest first.
+async fn unpushed_commits_diff(repo_root: &Path, local_sha: &str) -> Result<String> {
+    git_output(
+        repo_root,
+        &[
+            "log",
+            "--patch",
+            "--reverse",
+            "--no-color",
+            "--unified=3",
+            "--format=",
+            local_sha,
+            "--not",
+            "--remotes",
+        ],
+    )
+    .await
+}
+
+/// Hashes the diff with `git hash-object` so the cache key matches git's own object ids.
+async fn hash_diff(repo_root: &Path, diff: &str) -> Result<String> {
+    let mut child = tokio::process::Command::new("git")
+        .args(["hash-object", "--stdin"])
+        .current_dir(repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().context("git hash-object stdin")?;
+    stdin.write_all(diff.as_bytes()).await?;
+    drop(stdin);
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        bail!("git hash-object failed");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn cache_path(repo_root: &Path, diff_hash: &str) -> PathBuf {
+    repo_root
+        .join(".council")
+        .join("hook-cache")
+        .join(format!("{diff_hash}.json"))
+}
+
+async fn review_diff(config: &CouncilConfig, diff: &str) -> Result<Vec<HookFinding>> {
+    let diff = if diff.len() > MAX_DIFF_BYTES {
+        let mut end = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        &diff[..end]
+    } else {
+        diff
+    };
+    let system = PromptPack::resolve(&config.prompt_version, &config.repo_root)?.critic_system();
+    let message = format!(
+        "Review this diff for bugs, security issues and regressions. Only comment on the \
+changed lines. Report each issue as \
+<finding severity=\"P0|P1|P2|P3\"><location>path:line</location><issue>...</issue></finding>.\n\n\
+```diff\n{diff}\n```"
+    );
+
+    let critic_gpt = CouncilClient::new(&config.critic_gpt_model).await?;
+    let critic_gemini = CouncilClient::new(&config.critic_gemini_model).await?;
+    let (gpt, gemini) = tokio::join!(
+        critic_gpt.send_message(system.clone(), message.clone()),
+        critic_gemini.send_message(system, message),
+    );
+
+    let mut findings = Vec::new();
+    for (critic, reply) in [("GPT", gpt), ("Gemini", gemini)] {
+        let text = reply.with_context(|| format!("{critic} critic failed"))?;
+        for finding in parsing::extract_findings(&text) {
+            if finding.severity == Severity::Unknown {
+                continue;
+            }
+            findings.push(HookFinding {
+                severity: finding.severity,
+                critic: critic.to_string(),
+                location: finding.field("location").unwrap_or_default(),
+                issue: finding.field("issue").unwrap_or_default(),
+            });
+        }
+    }
+    findings.sort_by_key(|f| f.severity);
+    Ok(findings)
+}
+
+/// Runs the hook: collects the diff, reuses cached findings for an identical diff, and
+/// counts findings at or above the configured severity.
+pub async fn run_hook(
+    config: &CouncilConfig,
+    kind: HookKind,
+    push_refs: &str,
+) -> Result<HookReport> {
+    let repo_root = &config.repo_root;
+    let diff = match kind {
+        HookKind::PreCommit => staged_diff(repo_root).await?,
+        HookKind::PrePush => outgoing_diff(repo_root, push_refs).await?,
+    };
+    if diff.trim().is_empty() {
+        return Ok(HookReport {
+            diff_hash: None,
+            cached: false,
+            findings: Vec::new(),
+            blocking: 0,
+            review_error: None,
+        });
+    }
+
+    let diff_hash = hash_diff(repo_root, &diff).await?;
+    let cache = cache_path(repo_root, &diff_hash);
+    let cached: Option<CachedReview> = fs::read_to_string(&cache)
+        .await
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok());
+
+    let (findings, was_cached) = match cached {
+        Some(review) => (review.findings, true),
+        None => {
+            let findings = match review_diff(config, &diff).await {
+                Ok(findings) => findings,
+                Err(e) => {
+                    return Ok(HookReport {
+                        diff_hash: Some(diff_hash),
+                        cached: false,
+                        findings: Vec::new(),
+                        blocking: 0,
+                        review_error: Some(format!("{e:#}")),
+                    });
+                }
+            };
+            if let Some(parent) = cache.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            let review = CachedReview {
+                diff_hash: diff_hash.clone(),
+                findings,
+            };
+            fs::write(&cache, serde_json::to_string_pretty(&review)?).await?;
+            (review.findings, false)
+        }
+    };
+
+    let threshold = config.settings.hook_block_severity;
+    let blocking = findings.iter().filter(|f| blocks(f, threshold)).count();
+    Ok(HookReport {
+        diff_hash: Some(diff_hash),
+        cached: was_cached,
+        findings,
+        blocking,
+        review_error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(severity: Severity) -> HookFinding {
+        HookFinding {
+            severity,
+            critic: "GPT".to_string(),
+            location: "src/a.rs:3".to_string(),
+            issue: "Off by one".to_string(),
+        }
+    }
+
+    #[test]
+    fn threshold_blocks_at_or_above_severity() {
+        assert!(blocks(&finding(Severity::P0), CouncilHookSeverity::P1));
+        assert!(blocks(&finding(Severity::P1), CouncilHookSeverity::P1));
+        assert!(!blocks(&finding(Severity::P2), CouncilHookSeverity::P1));
+        assert!(blocks(&finding(Severity::P3), CouncilHookSeverity::P3));
+        assert!(!blocks(
+            &finding(Severity::Unknown),
+            CouncilHookSeverity::P3
+        ));
+    }
+
+    #[test]
+    fn stderr_output_lists_findings_and_verdict() {
+        let report = HookReport {
+            diff_hash: Some("abc".to_string()),
+            cached: true,
+            findings: vec![finding(Severity::P1)],
+            blocking: 1,
+            review_error: None,
+        };
+        assert_eq!(
+            report.stderr_lines(CouncilHookSeverity::P1),
+            vec![
+                "council: P1 src/a.rs:3 Off by one (GPT)".to_string(),
+                "council: blocked (cached): 1 finding(s) at or above P1. Set CODEX_COUNCIL_SKIP=1 to bypass."
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_review_warns_instead_of_blocking() {
+        let report = HookReport {
+            diff_hash: Some("abc".to_string()),
+            cached: false,
+            findings: Vec::new(),
+            blocking: 0,
+            review_error: Some("GPT critic failed".to_string()),
+        };
+        assert_eq!(
+            report.stderr_lines(CouncilHookSeverity::P1),
+            vec!["council: warning: review failed, not blocking: GPT critic failed".to_string()]
+        );
+    }
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args([
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .expect("run git");
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit_file(repo: &Path, name: &str) -> String {
+        std::fs::write(repo.join(name), format!("{name}\n")).expect("write file");
+        git(repo, &["add", name]);
+        git(repo, &["commit", "-q", "-m", name]);
+        git(repo, &["rev-parse", "HEAD"])
+    }
+
+    #[tokio::test]
+    async fn new_branch_pushes_only_commits_missing_from_remotes() {
+        let repo = tempfile::TempDir::new().expect("tempdir");
+        let repo = repo.path();
+        git(repo, &["init", "-q"]);
+        let pushed = commit_file(repo, "pushed.txt");
+        git(repo, &["update-ref", "refs/remotes/origin/main", &pushed]);
+        let local = commit_file(repo, "outgoing.txt");
+
+        // A brand-new branch has no remote sha.
+        let new_branch = format!("refs/heads/feature {local} refs/heads/feature {ZERO_SHA}\n");
+        let diff = outgoing_diff(repo, &new_branch).await.expect("diff");
+        assert!(diff.contains("+++ b/outgoing.txt"), "{diff}");
+        assert!(!diff.contains("pushed.txt"), "{diff}");
+
+        // A remote tip we never fetched falls back to the same commits.
+        let unknown = "1111111111111111111111111111111111111111";
+        let unfetched = format!("refs/heads/main {local} refs/heads/main {unknown}\n");
+        assert_eq!(outgoing_diff(repo, &unfetched).await.expect("diff"), diff);
+    }
+
+    #[test]
+    fn hook_script_checks_bypass_and_calls_back() {
+        let script = hook_script(Path::new("/usr/local/bin/codex"), HookKind::PrePush);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("[ -n \"$CODEX_COUNCIL_SKIP\" ] && exit 0"));
+        assert!(script.contains("exec '/usr/local/bin/codex' council hook run pre-push \"$@\""));
+    }
+}
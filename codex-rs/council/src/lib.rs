@@ -1,19 +1,28 @@
+pub mod artifacts;
 pub mod cleanup;
 pub mod client;
 pub mod context;
+pub mod hook;
 pub mod parsing;
 pub mod prompts;
 pub mod run;
 pub mod runner;
+pub mod sweep;
 pub mod types;
+pub mod usage;
 pub mod verify;
 pub mod worktree;
 
 pub use cleanup::cleanup_old_jobs;
+pub use cleanup::gc;
+pub use run::create_run_dir;
 pub use run::run_fix;
 pub use run::run_review;
 pub use runner::CouncilRunner;
+pub use sweep::SweepOptions;
+pub use sweep::run_sweep;
 pub use types::CouncilConfig;
 pub use types::CouncilEvent;
 pub use types::CouncilMode;
 pub use types::JobOutcome;
+pub use usage::RunUsage;
@@ -1,6 +1,9 @@
+use serde::Deserialize;
+use serde::Serialize;
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Finding severity, most severe first, so `Ord` ranks `P0` lowest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Severity {
     P0, // Blocker / Security
     P1, // Logic Error
@@ -19,6 +22,16 @@ impl Severity {
             _ => Severity::Unknown,
         }
     }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::P0 => "P0",
+            Severity::P1 => "P1",
+            Severity::P2 => "P2",
+            Severity::P3 => "P3",
+            Severity::Unknown => "??",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +41,13 @@ pub struct Finding {
     pub attrs: HashMap<String, String>,
 }
 
+impl Finding {
+    /// Text of the first `<tag>...</tag>` inside the finding body (e.g. `location`, `issue`).
+    pub fn field(&self, tag: &str) -> Option<String> {
+        extract_first_block(&self.body, tag).map(|s| unwrap_cdata(s).trim().to_string())
+    }
+}
+
 /// Extract patch content. Automatically unwraps <![CDATA[ ... ]]> if present.
 pub fn extract_patch(text: &str) -> Option<String> {
     let body = extract_first_block(text, "patch")?;
@@ -86,6 +106,18 @@ pub fn extract_findings(text: &str) -> Vec<Finding> {
     out
 }
 
+/// Extract the patch from raw implementer output, falling back to the first fenced block
+/// (v1 prompts) or the whole text.
+pub fn extract_patch_lenient(text: &str) -> String {
+    if let Some(p) = extract_patch(text) {
+        p
+    } else if text.contains("```") {
+        text.split("```").nth(1).unwrap_or(text).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
 /// Validates if the string looks like a valid apply_patch payload.
 pub fn looks_like_apply_patch(patch: &str) -> bool {
     let t = patch.trim();
@@ -319,6 +351,8 @@ mod tests {
         assert!(findings[0].body.contains("SQL Injection"));
         assert_eq!(findings[1].severity, Severity::P2);
         assert!(findings[1].body.contains("Spelling"));
+        assert_eq!(findings[0].field("issue").as_deref(), Some("SQL Injection"));
+        assert_eq!(findings[0].field("location"), None);
     }
 
     #[test]
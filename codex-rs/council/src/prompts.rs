@@ -1,4 +1,13 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
 use codex_apply_patch::APPLY_PATCH_TOOL_INSTRUCTIONS;
+use codex_core::config::find_codex_home;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::path::Path;
 
 // Embed v2 prompts
 const V2_CONSTITUTION: &str = include_str!("../assets/prompts/v2/constitution.txt");
@@ -74,3 +83,299 @@ Output the code changes using the following patch format:
 "###
     )
 }
+
+// ===== Prompt packs =====
+
+/// Manifest file every on-disk prompt pack must contain.
+pub const PACK_MANIFEST: &str = "manifest.json";
+
+/// Packs compiled into the binary.
+pub const BUILTIN_PACKS: &[&str] = &["v1", "v2"];
+
+/// Placeholder in implementer prompts replaced with the apply_patch tool instructions.
+const APPLY_PATCH_PLACEHOLDER: &str = "<APPLY_PATCH_TOOL_INSTRUCTIONS>";
+
+/// Tags each role prompt must ask for so `parsing.rs` can read the v2 output.
+const V2_REQUIRED_TAGS: &[(&str, &[&str])] = &[
+    ("critic", &["<finding", "<location>", "<issue>"]),
+    ("chair", &["<plan>", "<error>"]),
+    ("implementer", &["<patch>"]),
+];
+
+/// Output format the council expects back from the models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputSchema {
+    /// Free-form critiques and plans; the patch is extracted leniently.
+    V1,
+    /// Tagged `<finding>`, `<plan>`/`<error>` and `<patch>` blocks.
+    V2,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackManifest {
+    schema_version: OutputSchema,
+}
+
+/// Identity of a resolved pack, recorded in `job_metadata.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackInfo {
+    pub name: String,
+    pub schema_version: OutputSchema,
+    /// `builtin` or the directory the pack was loaded from.
+    pub source: String,
+    /// `sha256:<hex>` over the composed system prompts.
+    pub hash: String,
+}
+
+/// System prompts for every council role, resolved from a built-in or on-disk pack.
+#[derive(Debug, Clone)]
+pub struct PromptPack {
+    pub info: PackInfo,
+    critic: String,
+    chair: String,
+    implementer: String,
+}
+
+impl PromptPack {
+    /// Resolves `name` from `.council/prompts/<name>/`, then
+    /// `~/.codex/council/prompts/<name>/`, then the built-in packs.
+    pub fn resolve(name: &str, repo_root: &Path) -> Result<Self> {
+        if name.is_empty()
+            || name
+                .chars()
+                .any(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            || name.starts_with('.')
+        {
+            bail!("invalid prompt pack name {name:?}");
+        }
+
+        let mut searched = vec![repo_root.join(".council").join("prompts").join(name)];
+        if let Ok(codex_home) = find_codex_home() {
+            searched.push(codex_home.join("council").join("prompts").join(name));
+        }
+        for dir in &searched {
+            if dir.is_dir() {
+                return Self::load_dir(name, dir);
+            }
+        }
+
+        match name {
+            "v1" => Ok(Self::new(
+                name,
+                OutputSchema::V1,
+                "builtin".to_string(),
+                system_prompt_critic(name),
+                system_prompt_chair(name),
+                system_prompt_implementer(name),
+            )),
+            "v2" => Ok(Self::new(
+                name,
+                OutputSchema::V2,
+                "builtin".to_string(),
+                system_prompt_critic(name),
+                system_prompt_chair(name),
+                system_prompt_implementer(name),
+            )),
+            _ => {
+                let searched = searched
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                bail!(
+                    "unknown prompt pack {name:?}: not found in {searched} and not a built-in pack ({})",
+                    BUILTIN_PACKS.join(", ")
+                )
+            }
+        }
+    }
+
+    fn load_dir(name: &str, dir: &Path) -> Result<Self> {
+        let manifest_path = dir.join(PACK_MANIFEST);
+        let manifest: PackManifest = serde_json::from_str(
+            &std::fs::read_to_string(&manifest_path)
+                .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+        )
+        .with_context(|| format!("invalid {}", manifest_path.display()))?;
+
+        let read_role = |file: &str| {
+            let path = dir.join(file);
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("prompt pack {name:?} is missing {}", path.display()))
+        };
+        let constitution = std::fs::read_to_string(dir.join("constitution.txt")).ok();
+        let critic = read_role("critic.txt")?;
+        let chair = read_role("chair.txt")?;
+        let implementer = read_role("implementer.txt")?;
+
+        if manifest.schema_version == OutputSchema::V2 {
+            for (role, text) in [
+                ("critic", &critic),
+                ("chair", &chair),
+                ("implementer", &implementer),
+            ] {
+                let required = V2_REQUIRED_TAGS
+                    .iter()
+                    .find(|(r, _)| *r == role)
+                    .map(|(_, tags)| *tags)
+                    .unwrap_or_default();
+                let missing: Vec<&str> = required
+                    .iter()
+                    .copied()
+                    .filter(|tag| !text.contains(tag))
+                    .collect();
+                if !missing.is_empty() {
+                    bail!(
+                        "prompt pack {name:?}: {role}.txt must ask for {} (schema v2)",
+                        missing.join(", ")
+                    );
+                }
+            }
+        }
+
+        let compose = |role: &str| match &constitution {
+            Some(constitution) if !constitution.trim().is_empty() => {
+                format!("{constitution}\n\n{role}")
+            }
+            _ => role.to_string(),
+        };
+        let implementer = if implementer.contains(APPLY_PATCH_PLACEHOLDER) {
+            implementer.replace(APPLY_PATCH_PLACEHOLDER, APPLY_PATCH_TOOL_INSTRUCTIONS)
+        } else {
+            format!("{implementer}\n\n{APPLY_PATCH_TOOL_INSTRUCTIONS}")
+        };
+
+        Ok(Self::new(
+            name,
+            manifest.schema_version,
+            dir.display().to_string(),
+            compose(&critic),
+            compose(&chair),
+            compose(&implementer),
+        ))
+    }
+
+    fn new(
+        name: &str,
+        schema_version: OutputSchema,
+        source: String,
+        critic: String,
+        chair: String,
+        implementer: String,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        for prompt in [&critic, &chair, &implementer] {
+            hasher.update(prompt.as_bytes());
+            hasher.update([0u8]);
+        }
+        let hex = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        Self {
+            info: PackInfo {
+                name: name.to_string(),
+                schema_version,
+                source,
+                hash: format!("sha256:{hex}"),
+            },
+            critic,
+            chair,
+            implementer,
+        }
+    }
+
+    pub fn critic_system(&self) -> String {
+        self.critic.clone()
+    }
+
+    pub fn chair_system(&self) -> String {
+        self.chair.clone()
+    }
+
+    pub fn implementer_system(&self) -> String {
+        self.implementer.clone()
+    }
+
+    /// True when model output should be parsed as tagged v2 blocks.
+    pub fn is_v2(&self) -> bool {
+        self.info.schema_version == OutputSchema::V2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_pack(repo: &Path, name: &str, schema: &str, critic: &str) -> std::path::PathBuf {
+        let dir = repo.join(".council").join("prompts").join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(PACK_MANIFEST),
+            format!(r#"{{"schema_version":"{schema}"}}"#),
+        )
+        .unwrap();
+        std::fs::write(dir.join("constitution.txt"), "Be terse.").unwrap();
+        std::fs::write(dir.join("critic.txt"), critic).unwrap();
+        std::fs::write(dir.join("chair.txt"), "Answer with <plan> or <error>.").unwrap();
+        std::fs::write(dir.join("implementer.txt"), "Wrap it in <patch>.").unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolves_builtin_and_repo_packs() {
+        let repo = TempDir::new().unwrap();
+        let v2 = PromptPack::resolve("v2", repo.path()).unwrap();
+        assert!(v2.is_v2());
+        assert_eq!(v2.info.source, "builtin");
+        assert!(v2.info.hash.starts_with("sha256:"));
+
+        let dir = write_pack(
+            repo.path(),
+            "security",
+            "v2",
+            "Report <finding> blocks with <location> and <issue>.",
+        );
+        let pack = PromptPack::resolve("security", repo.path()).unwrap();
+        assert_eq!(pack.info.source, dir.display().to_string());
+        assert!(
+            pack.critic_system()
+                .starts_with("Be terse.\n\nReport <finding>")
+        );
+        assert!(
+            pack.implementer_system()
+                .contains(APPLY_PATCH_TOOL_INSTRUCTIONS)
+        );
+        assert_ne!(pack.info.hash, v2.info.hash);
+    }
+
+    #[test]
+    fn rejects_unknown_and_invalid_packs() {
+        let repo = TempDir::new().unwrap();
+        let err = PromptPack::resolve("v3", repo.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown prompt pack \"v3\""), "{err}");
+        assert!(PromptPack::resolve("../v2", repo.path()).is_err());
+
+        write_pack(repo.path(), "loose", "v2", "Just list problems.");
+        let err = PromptPack::resolve("loose", repo.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("critic.txt must ask for <finding, <location>, <issue>"),
+            "{err}"
+        );
+
+        // v1 packs are free-form, so the same critic is accepted.
+        write_pack(repo.path(), "loose-v1", "v1", "Just list problems.");
+        assert!(
+            !PromptPack::resolve("loose-v1", repo.path())
+                .unwrap()
+                .is_v2()
+        );
+    }
+}
@@ -2,7 +2,9 @@ use crate::runner::CouncilRunner;
 use crate::types::CouncilConfig;
 use crate::types::CouncilEvent;
 use crate::types::CouncilMode;
+use crate::types::JobOutcome;
 use anyhow::Result;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::fs;
 use tokio_util::sync::CancellationToken;
@@ -16,18 +18,46 @@ pub async fn run_fix(config: CouncilConfig, target: PathBuf) -> Result<()> {
     run_internal(config, target, CouncilMode::Fix).await
 }
 
+/// Allocates a fresh `.council/runs/<run-id>` directory.
+///
+/// Run ids are timestamp based; a numeric suffix keeps jobs started within the same
+/// second (e.g. queued or parallel jobs) apart.
+pub async fn create_run_dir(repo_root: &Path) -> Result<(String, PathBuf)> {
+    let runs_dir = repo_root.join(".council").join("runs");
+    fs::create_dir_all(&runs_dir).await?;
+
+    let timestamp = run_timestamp()?;
+    let mut suffix = 0;
+    loop {
+        let run_id = run_id_for(timestamp, suffix);
+        let job_dir = runs_dir.join(&run_id);
+        match fs::create_dir(&job_dir).await {
+            Ok(()) => return Ok((run_id, job_dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => suffix += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn run_timestamp() -> Result<u64> {
+    Ok(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs())
+}
+
+fn run_id_for(timestamp: u64, suffix: u32) -> String {
+    if suffix == 0 {
+        format!("run-{timestamp}")
+    } else {
+        format!("run-{timestamp}-{suffix}")
+    }
+}
+
 async fn run_internal(config: CouncilConfig, target: PathBuf, mode: CouncilMode) -> Result<()> {
     let (tx, mut rx) = tokio::sync::mpsc::channel(100);
     let cancel_token = CancellationToken::new();
 
-    let run_id = format!(
-        "run-{}",
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)?
-            .as_secs()
-    );
-    let job_dir = config.repo_root.join(".council").join("runs").join(&run_id);
-    fs::create_dir_all(&job_dir).await?;
+    let (run_id, job_dir) = create_run_dir(&config.repo_root).await?;
 
     let runner = CouncilRunner::new(config, tx, cancel_token, job_dir);
 
@@ -64,8 +94,21 @@ async fn run_internal(config: CouncilConfig, target: PathBuf, mode: CouncilMode)
                 CouncilEvent::JobFinished {
                     outcome,
                     summary_line,
+                    usage,
                 } => {
                     info!("Job Finished: {:?} - {}", outcome, summary_line);
+                    if let Some(usage) = &usage {
+                        info!("  Usage: {}", usage.summary_line());
+                        for (role, totals) in &usage.by_role {
+                            info!(
+                                "    {}: {} tokens, {} ms, ${:.4}",
+                                role,
+                                totals.token_usage.total_tokens,
+                                totals.latency_ms,
+                                totals.cost_usd
+                            );
+                        }
+                    }
                     if mode == CouncilMode::Review && outcome == JobOutcome::Success {
                          let plan_path = job_dir_print.join("plan.md");
                          if let Ok(plan) = fs::read_to_string(&plan_path).await {
@@ -85,3 +128,27 @@ async fn run_internal(config: CouncilConfig, target: PathBuf, mode: CouncilMode)
     let (res, _) = tokio::join!(runner_fut, printer_fut);
     res
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn concurrent_run_dirs_get_distinct_ids() {
+        let repo = TempDir::new().expect("tempdir");
+        let runs = futures::future::join_all((0..8).map(|_| create_run_dir(repo.path()))).await;
+
+        let mut ids = HashSet::new();
+        for run in runs {
+            let (run_id, job_dir) = run.expect("create run dir");
+            assert!(job_dir.is_dir());
+            assert_eq!(
+                job_dir,
+                repo.path().join(".council").join("runs").join(&run_id)
+            );
+            assert!(ids.insert(run_id));
+        }
+    }
+}
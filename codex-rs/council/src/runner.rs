@@ -1,11 +1,17 @@
+use crate::artifacts;
+use crate::artifacts::JobStatus;
 use crate::client::CouncilClient;
+use crate::client::CouncilReply;
 use crate::context::ContextBuilder;
 use crate::parsing;
-use crate::prompts;
+use crate::prompts::PromptPack;
 use crate::types::CouncilConfig;
 use crate::types::CouncilEvent;
 use crate::types::CouncilMode;
 use crate::types::JobOutcome;
+use crate::usage::UsageLedger;
+use crate::verify::BuildCache;
+use crate::verify::BuildCacheStats;
 use crate::verify::Verifier;
 use crate::worktree::Worktree;
 use anyhow::Result;
@@ -13,6 +19,7 @@ use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Mutex;
 use tokio::fs;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
@@ -23,6 +30,7 @@ pub struct CouncilRunner {
     pub event_tx: mpsc::Sender<CouncilEvent>,
     pub cancel_token: CancellationToken,
     pub job_dir: PathBuf,
+    usage: Mutex<UsageLedger>,
 }
 
 impl CouncilRunner {
@@ -32,11 +40,13 @@ impl CouncilRunner {
         cancel_token: CancellationToken,
         job_dir: PathBuf,
     ) -> Self {
+        let usage = Mutex::new(UsageLedger::new(config.settings.clone()));
         Self {
             config,
             event_tx,
             cancel_token,
             job_dir,
+            usage,
         }
     }
 
@@ -46,6 +56,101 @@ impl CouncilRunner {
         }
     }
 
+    fn ledger(&self) -> std::sync::MutexGuard<'_, UsageLedger> {
+        match self.usage.lock() {
+            Ok(ledger) => ledger,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Persists the usage rollup and final status, then emits `JobFinished`.
+    async fn finish(&self, outcome: JobOutcome, summary_line: String) {
+        let usage = self.ledger().usage().clone();
+        self.write_json_artifact("usage.json", &usage).await;
+
+        let status = JobStatus {
+            outcome: outcome.clone(),
+            summary_line: summary_line.clone(),
+            finished_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        self.write_json_artifact(artifacts::STATUS_FILE, &status)
+            .await;
+
+        self.emit(CouncilEvent::JobFinished {
+            outcome,
+            summary_line,
+            usage: Some(usage),
+        })
+        .await;
+    }
+
+    async fn write_json_artifact<T: serde::Serialize>(&self, filename: &str, value: &T) {
+        match serde_json::to_string_pretty(value) {
+            Ok(json) => {
+                if let Err(e) = fs::write(self.job_dir.join(filename), json).await {
+                    error!("Failed to write {}: {}", filename, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize {}: {}", filename, e),
+        }
+    }
+
+    /// Sends one council call and records its token usage and latency under `role`.
+    async fn ask(
+        &self,
+        role: &str,
+        client: &CouncilClient,
+        system_prompt: String,
+        user_message: String,
+    ) -> Result<String> {
+        let CouncilReply {
+            content,
+            token_usage,
+            latency_ms,
+        } = client
+            .send_message_with_usage(system_prompt, user_message)
+            .await?;
+        if token_usage.is_none() {
+            self.emit(CouncilEvent::Warning {
+                message: format!(
+                    "{} ({role}) did not report token usage; budget accounting will undercount.",
+                    client.model_id
+                ),
+            })
+            .await;
+        }
+        self.ledger().record(
+            role,
+            &client.model_id,
+            token_usage.unwrap_or_default(),
+            latency_ms,
+        );
+        Ok(content)
+    }
+
+    /// Aborts the run before `phase` if its projected usage would exceed the budget.
+    ///
+    /// Returns `false` (after emitting `JobFinished`) when the phase must not run.
+    async fn within_budget(&self, phase: &str, calls: &[(&str, usize)]) -> bool {
+        let check = self.ledger().check_next_phase(calls);
+        match check {
+            Ok(()) => true,
+            Err(exceeded) => {
+                self.emit(CouncilEvent::Error {
+                    phase: phase.to_string(),
+                    message: format!("Budget exceeded before {phase}: {exceeded}"),
+                })
+                .await;
+                self.finish(JobOutcome::Failure, "Budget exceeded".to_string())
+                    .await;
+                false
+            }
+        }
+    }
+
     async fn write_debug_log(&self, filename: &str, content: &str) -> Result<()> {
         if std::env::var("THINTHREAD_DEBUG").is_ok() {
             let path = self.job_dir.join(filename);
@@ -65,10 +170,7 @@ impl CouncilRunner {
     pub async fn run(&self, target: PathBuf, mode: CouncilMode) -> Result<()> {
         tokio::select! {
             _ = self.cancel_token.cancelled() => {
-                self.emit(CouncilEvent::JobFinished {
-                    outcome: JobOutcome::Cancelled,
-                    summary_line: "Job cancelled by user.".to_string(),
-                }).await;
+                self.finish(JobOutcome::Cancelled, "Job cancelled by user.".to_string()).await;
                 Ok(())
             }
             res = self.run_logic(target, mode) => {
@@ -77,10 +179,7 @@ impl CouncilRunner {
                         phase: "Job Execution".to_string(),
                         message: e.to_string(),
                     }).await;
-                    self.emit(CouncilEvent::JobFinished {
-                        outcome: JobOutcome::Failure,
-                        summary_line: format!("Internal Error: {e}"),
-                    }).await;
+                    self.finish(JobOutcome::Failure, format!("Internal Error: {e}")).await;
                 }
                 res
             },
@@ -109,6 +208,20 @@ impl CouncilRunner {
         })
         .await;
 
+        let pack = match PromptPack::resolve(&self.config.prompt_version, &self.config.repo_root) {
+            Ok(pack) => pack,
+            Err(e) => {
+                self.emit(CouncilEvent::Error {
+                    phase: "Prompts".to_string(),
+                    message: format!("{e:#}"),
+                })
+                .await;
+                self.finish(JobOutcome::Failure, "Invalid prompt pack".to_string())
+                    .await;
+                return Ok(());
+            }
+        };
+
         // Persist metadata
         let metadata = serde_json::json!({
             "job_id": run_id,
@@ -117,10 +230,11 @@ impl CouncilRunner {
             "head_sha_at_start": head_sha,
             "repo_dirty_at_start": repo_dirty,
             "prompt_version": self.config.prompt_version,
+            "prompt_pack": pack.info,
             "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
         });
         fs::write(
-            self.job_dir.join("job_metadata.json"),
+            self.job_dir.join(artifacts::METADATA_FILE),
             serde_json::to_string_pretty(&metadata)?,
         )
         .await?;
@@ -138,11 +252,8 @@ impl CouncilRunner {
                         ),
                     })
                     .await;
-                    self.emit(CouncilEvent::JobFinished {
-                        outcome: JobOutcome::Failure,
-                        summary_line: "Target outside repo root".to_string(),
-                    })
-                    .await;
+                    self.finish(JobOutcome::Failure, "Target outside repo root".to_string())
+                        .await;
                     return Ok(());
                 }
             }
@@ -156,11 +267,8 @@ impl CouncilRunner {
                 message: "Target path is empty.".to_string(),
             })
             .await;
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Invalid target path".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Invalid target path".to_string())
+                .await;
             return Ok(());
         }
 
@@ -176,11 +284,8 @@ impl CouncilRunner {
                 message: format!("Target path '{rel_target_display}' contains unsafe components."),
             })
             .await;
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Invalid target path".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Invalid target path".to_string())
+                .await;
             return Ok(());
         }
 
@@ -193,8 +298,26 @@ impl CouncilRunner {
         })
         .await;
 
-        let worktree = Worktree::create(&self.config.repo_root, &run_id).await?;
+        let worktree = Worktree::acquire(
+            &self.config.repo_root,
+            &run_id,
+            self.config.settings.worktree_pool_size,
+        )
+        .await?;
         let working_root = worktree.path.clone();
+
+        let build_cache = if self.config.settings.shared_build_cache {
+            BuildCache::shared(&self.config.repo_root).await
+        } else {
+            None
+        };
+        let mut cache_stats = BuildCacheStats {
+            pool_slot: worktree.lease.as_ref().map(|l| l.slot),
+            worktree_reused: worktree.lease.as_ref().is_some_and(|l| l.reused),
+            target_dir: build_cache.as_ref().map(|c| c.target_dir.clone()),
+            target_dir_warm: build_cache.as_ref().is_some_and(|c| c.target_dir.exists()),
+            ..Default::default()
+        };
         let _worktree_guard = worktree;
 
         // 2. Build Context (on isolated root)
@@ -219,11 +342,8 @@ impl CouncilRunner {
                 ),
             })
             .await;
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Target not found in HEAD".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Target not found in HEAD".to_string())
+                .await;
             return Ok(());
         }
 
@@ -250,7 +370,10 @@ impl CouncilRunner {
                 detail: "Running baseline verification...".to_string(),
             })
             .await;
-            baseline_results = Verifier::run_all(&working_root, Some(&isolated_target)).await?;
+            baseline_results =
+                Verifier::run_all(&working_root, Some(&isolated_target), build_cache.as_ref())
+                    .await?;
+            cache_stats.record(&baseline_results);
             fs::write(
                 self.job_dir.join("verify_baseline.json"),
                 serde_json::to_string_pretty(&baseline_results)?,
@@ -288,19 +411,54 @@ impl CouncilRunner {
             serde_json::to_string_pretty(&baseline_results)?
         );
 
+        let critic_system = pack.critic_system();
+        let critic_message = format!(
+            "Please review this code context and identify bugs or issues.\n\n{prompt_context}"
+        );
+
+        let unpriced = self.ledger().unpriced_models(&[
+            critic_gpt.model_id.as_str(),
+            critic_gemini.model_id.as_str(),
+            chair.model_id.as_str(),
+            implementer.model_id.as_str(),
+        ]);
+        if !unpriced.is_empty() {
+            self.emit(CouncilEvent::Warning {
+                message: format!(
+                    "No price configured for {}; max_cost_per_run cannot account for them.",
+                    unpriced.join(", ")
+                ),
+            })
+            .await;
+        }
+
+        let critic_bytes = critic_system.len() + critic_message.len();
+        if !self
+            .within_budget(
+                "Criticism",
+                &[
+                    (critic_gpt.model_id.as_str(), critic_bytes),
+                    (critic_gemini.model_id.as_str(), critic_bytes),
+                ],
+            )
+            .await
+        {
+            return Ok(());
+        }
+
         let critics_fut = async {
-            let gpt_fut = critic_gpt.send_message(
-                    prompts::system_prompt_critic(&self.config.prompt_version),
-                    format!(
-                        "Please review this code context and identify bugs or issues.\n\n{prompt_context}",
-                    ),
-                );
-            let gemini_fut = critic_gemini.send_message(
-                    prompts::system_prompt_critic(&self.config.prompt_version),
-                    format!(
-                        "Please review this code context and identify bugs or issues.\n\n{prompt_context}",
-                    ),
-                );
+            let gpt_fut = self.ask(
+                "critic_gpt",
+                &critic_gpt,
+                critic_system.clone(),
+                critic_message.clone(),
+            );
+            let gemini_fut = self.ask(
+                "critic_gemini",
+                &critic_gemini,
+                critic_system.clone(),
+                critic_message.clone(),
+            );
             tokio::join!(gpt_fut, gemini_fut)
         };
 
@@ -346,12 +504,8 @@ impl CouncilRunner {
             })
             .await;
 
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-
-                summary_line: "Critics failed".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Critics failed".to_string())
+                .await;
 
             return Ok(());
         }
@@ -359,12 +513,8 @@ impl CouncilRunner {
         if mode == CouncilMode::Review {
             // Review mode ends here
 
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Success,
-
-                summary_line: "Critique complete.".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Success, "Critique complete.".to_string())
+                .await;
 
             return Ok(());
         }
@@ -384,25 +534,32 @@ impl CouncilRunner {
         })
         .await;
 
-        let mut plan = chair
-
-                    .send_message(
-
-                        prompts::system_prompt_chair(&self.config.prompt_version),
-
-                        format!(
-                            "Review the following critiques and formulate a fix plan.\n\nContext:\n{prompt_context}\n\nCritiques:\n{all_critiques}",
-                        ),
-
-                    )
+        let chair_system = pack.chair_system();
+        let chair_message = format!(
+            "Review the following critiques and formulate a fix plan.\n\nContext:\n{prompt_context}\n\nCritiques:\n{all_critiques}",
+        );
+        if !self
+            .within_budget(
+                "Planning",
+                &[(
+                    chair.model_id.as_str(),
+                    chair_system.len() + chair_message.len(),
+                )],
+            )
+            .await
+        {
+            return Ok(());
+        }
 
-                    .await?;
+        let mut plan = self
+            .ask("chair", &chair, chair_system, chair_message)
+            .await?;
 
         self.write_debug_log("debug_plan_raw.log", &plan).await?;
 
         fs::write(self.job_dir.join("plan_raw.md"), &plan).await?;
 
-        if self.config.prompt_version == "v2" {
+        if pack.is_v2() {
             if let Some(clean_plan) = parsing::extract_plan(&plan) {
                 plan = clean_plan;
             } else if let Some(err_msg) = parsing::extract_error(&plan) {
@@ -413,12 +570,8 @@ impl CouncilRunner {
                 })
                 .await;
 
-                self.emit(CouncilEvent::JobFinished {
-                    outcome: JobOutcome::Failure,
-
-                    summary_line: "Chair refused plan".to_string(),
-                })
-                .await;
+                self.finish(JobOutcome::Failure, "Chair refused plan".to_string())
+                    .await;
 
                 return Ok(());
             }
@@ -439,12 +592,29 @@ impl CouncilRunner {
         })
         .await;
 
-        let code_change = implementer
-            .send_message(
-                prompts::system_prompt_implementer(&self.config.prompt_version),
-                format!(
-                    "Implement the following plan to fix the code.\n\nPlan:\n{plan}\n\nContext:\n{prompt_context}",
-                ),
+        let implementer_system = pack.implementer_system();
+        let implementer_message = format!(
+            "Implement the following plan to fix the code.\n\nPlan:\n{plan}\n\nContext:\n{prompt_context}",
+        );
+        if !self
+            .within_budget(
+                "Implementation",
+                &[(
+                    implementer.model_id.as_str(),
+                    implementer_system.len() + implementer_message.len(),
+                )],
+            )
+            .await
+        {
+            return Ok(());
+        }
+
+        let code_change = self
+            .ask(
+                "implementer",
+                &implementer,
+                implementer_system,
+                implementer_message,
             )
             .await?;
 
@@ -454,34 +624,18 @@ impl CouncilRunner {
         fs::write(self.job_dir.join("implementation.patch"), &code_change).await?;
 
         // Extract Patch
-        let patch_content = if let Some(p) = parsing::extract_patch(&code_change) {
-            p
-        } else {
-            // Fallback for v1 or loose parsing
-            if code_change.contains("```") {
-                code_change
-                    .split("```")
-                    .nth(1)
-                    .unwrap_or(&code_change)
-                    .to_string()
-            } else {
-                code_change.clone()
-            }
-        };
+        let patch_content = parsing::extract_patch_lenient(&code_change);
 
         // Guard: check if patch looks valid
-        if self.config.prompt_version == "v2" && !parsing::looks_like_apply_patch(&patch_content) {
+        if pack.is_v2() && !parsing::looks_like_apply_patch(&patch_content) {
             self.emit(CouncilEvent::Error {
                 phase: "Implementation".to_string(),
                 message: "Generated patch failed validation (missing markers).".to_string(),
             })
             .await;
             // Continue? Or abort? Abort.
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Patch validation failed".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Patch validation failed".to_string())
+                .await;
             return Ok(());
         }
 
@@ -491,11 +645,8 @@ impl CouncilRunner {
                 message: format!("Generated patch contained unsafe paths: {e}"),
             })
             .await;
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Patch safety check failed".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Patch safety check failed".to_string())
+                .await;
             return Ok(());
         }
 
@@ -528,16 +679,17 @@ impl CouncilRunner {
             })
             .await;
             // We persist artifacts but fail job
-            self.emit(CouncilEvent::JobFinished {
-                outcome: JobOutcome::Failure,
-                summary_line: "Patch application failed".to_string(),
-            })
-            .await;
+            self.finish(JobOutcome::Failure, "Patch application failed".to_string())
+                .await;
             return Ok(());
         }
 
         // Verify
-        let final_results = Verifier::run_all(&working_root, Some(&isolated_target)).await?;
+        let final_results =
+            Verifier::run_all(&working_root, Some(&isolated_target), build_cache.as_ref()).await?;
+        cache_stats.record(&final_results);
+        self.write_json_artifact("build_cache.json", &cache_stats)
+            .await;
         fs::write(
             self.job_dir.join("verify_final.json"),
             serde_json::to_string_pretty(&final_results)?,
@@ -563,11 +715,7 @@ impl CouncilRunner {
         let summary =
             format!("Base failures: {baseline_failures}, Final failures: {final_failures}");
 
-        self.emit(CouncilEvent::JobFinished {
-            outcome,
-            summary_line: summary,
-        })
-        .await;
+        self.finish(outcome, summary).await;
 
         Ok(())
     }
@@ -0,0 +1,691 @@
+//! Repository sweep: rank files by risk and review the top N under a shared budget.
+//!
+//! Files are ranked by git churn, size and how often past council runs on them started
+//! from failing verification. The selected files are reviewed in parallel (up to
+//! `max_concurrent_jobs`) and the findings are consolidated into a single report under
+//! `.council/sweeps/<sweep-id>/`.
+
+use crate::artifacts;
+use crate::artifacts::RunArtifacts;
+use crate::parsing::Severity;
+use crate::run::create_run_dir;
+use crate::runner::CouncilRunner;
+use crate::types::CouncilConfig;
+use crate::types::CouncilEvent;
+use crate::types::CouncilMode;
+use crate::types::JobOutcome;
+use crate::usage::RunUsage;
+use anyhow::Result;
+use codex_core::config::types::CouncilSettings;
+use futures::StreamExt;
+use ignore::WalkBuilder;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use tokio::fs;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing::warn;
+
+/// Extensions considered reviewable source files.
+const SOURCE_EXTENSIONS: &[&str] = &["rs", "py", "ts", "tsx", "js", "jsx", "go", "java"];
+
+/// How many commits of history feed the churn signal.
+const CHURN_COMMITS: usize = 500;
+
+/// Files larger than this are skipped; the council context bundle cannot hold them.
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct SweepOptions {
+    /// Restrict the walk to these paths (relative to the repo root). Empty means the whole repo.
+    pub paths: Vec<PathBuf>,
+    /// Number of top-ranked files to review.
+    pub top: usize,
+    /// Token budget shared by every run in the sweep.
+    pub max_tokens: Option<u64>,
+    /// Cost budget (USD) shared by every run in the sweep.
+    pub max_cost: Option<f64>,
+}
+
+/// Ranking signals for one file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileScore {
+    /// Path relative to the repo root.
+    pub path: PathBuf,
+    /// Commits touching the file in the last `CHURN_COMMITS` commits.
+    pub churn: usize,
+    pub lines: usize,
+    /// Past council runs on this file whose baseline verification failed.
+    pub recent_failures: usize,
+    pub score: f64,
+}
+
+impl FileScore {
+    fn new(path: PathBuf, churn: usize, lines: usize, recent_failures: usize) -> Self {
+        let score = churn as f64 * 2.0 + recent_failures as f64 * 5.0 + (lines as f64).ln_1p();
+        Self {
+            path,
+            churn,
+            lines,
+            recent_failures,
+            score,
+        }
+    }
+}
+
+/// Result of reviewing (or skipping) one ranked file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepEntry {
+    pub file: FileScore,
+    pub run_id: Option<String>,
+    pub outcome: Option<JobOutcome>,
+    pub usage: Option<RunUsage>,
+    /// Why the file was not reviewed, e.g. the shared budget ran out.
+    pub skipped: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepReport {
+    pub sweep_id: String,
+    pub entries: Vec<SweepEntry>,
+    pub total_tokens: i64,
+    pub total_cost_usd: f64,
+}
+
+/// Tokens and cost spent so far across the sweep.
+///
+/// Each run reserves its share of the budget when it starts and settles the
+/// difference when it ends, so parallel runs never start against the same remainder.
+/// A share is what is left split across the free slots, so one run cannot take the
+/// whole remainder while others wait for a slot.
+#[derive(Debug, Default)]
+struct SharedBudget {
+    max_tokens: Option<u64>,
+    max_cost: Option<f64>,
+    /// Runs allowed at once.
+    concurrency: usize,
+    /// Files that have not started a run yet.
+    files_left: usize,
+    spent_tokens: u64,
+    spent_cost: f64,
+    reserved_tokens: u64,
+    reserved_cost: f64,
+    in_flight: usize,
+}
+
+/// Budget held by a running review until it settles.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct BudgetReservation {
+    tokens: u64,
+    cost: f64,
+}
+
+#[derive(Debug, PartialEq)]
+enum Reserve {
+    /// Settings for the run with per-run limits capped at the reserved share.
+    Granted(CouncilSettings, BudgetReservation),
+    /// Everything left is reserved by running reviews; retry once one settles.
+    Wait,
+    /// The sweep budget is spent.
+    Exhausted,
+}
+
+impl SharedBudget {
+    fn reserve(&mut self, base: &CouncilSettings) -> Reserve {
+        let mut settings = base.clone();
+        let mut reservation = BudgetReservation::default();
+        let slots = self
+            .concurrency
+            .saturating_sub(self.in_flight)
+            .min(self.files_left)
+            .max(1);
+        if let Some(max) = self.max_tokens {
+            let remaining = max.saturating_sub(self.spent_tokens + self.reserved_tokens);
+            if remaining == 0 {
+                return self.nothing_left();
+            }
+            let share = (remaining / slots as u64).max(1);
+            let cap = settings
+                .max_tokens_per_run
+                .map_or(share, |limit| limit.min(share));
+            settings.max_tokens_per_run = Some(cap);
+            reservation.tokens = cap;
+        }
+        if let Some(max) = self.max_cost {
+            let remaining = max - self.spent_cost - self.reserved_cost;
+            if remaining <= 0.0 {
+                return self.nothing_left();
+            }
+            let share = remaining / slots as f64;
+            let cap = settings
+                .max_cost_per_run
+                .map_or(share, |limit| limit.min(share));
+            settings.max_cost_per_run = Some(cap);
+            reservation.cost = cap;
+        }
+        self.reserved_tokens += reservation.tokens;
+        self.reserved_cost += reservation.cost;
+        self.in_flight += 1;
+        self.files_left = self.files_left.saturating_sub(1);
+        Reserve::Granted(settings, reservation)
+    }
+
+    fn nothing_left(&self) -> Reserve {
+        if self.in_flight > 0 {
+            Reserve::Wait
+        } else {
+            Reserve::Exhausted
+        }
+    }
+
+    /// Releases a reservation, giving back what the run did not use, and charges
+    /// what it actually used.
+    fn settle(&mut self, reservation: BudgetReservation, usage: Option<&RunUsage>) {
+        self.reserved_tokens = self.reserved_tokens.saturating_sub(reservation.tokens);
+        self.reserved_cost = (self.reserved_cost - reservation.cost).max(0.0);
+        self.in_flight = self.in_flight.saturating_sub(1);
+        if let Some(usage) = usage {
+            self.spent_tokens += usage.total.token_usage.total_tokens.max(0) as u64;
+            self.spent_cost += usage.total.cost_usd;
+        }
+    }
+}
+
+fn lock_budget(budget: &Mutex<SharedBudget>) -> MutexGuard<'_, SharedBudget> {
+    match budget.lock() {
+        Ok(budget) => budget,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Counts how many commits touched each file, from `git log --name-only --format=` output.
+pub fn parse_churn(log_output: &str) -> HashMap<PathBuf, usize> {
+    let mut churn = HashMap::new();
+    for line in log_output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        *churn.entry(PathBuf::from(line)).or_insert(0) += 1;
+    }
+    churn
+}
+
+async fn git_churn(repo_root: &Path) -> HashMap<PathBuf, usize> {
+    let output = tokio::process::Command::new("git")
+        .args(["log", "--name-only", "--format=", "-n"])
+        .arg(CHURN_COMMITS.to_string())
+        .current_dir(repo_root)
+        .stderr(Stdio::null())
+        .output()
+        .await;
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_churn(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => {
+            warn!("git log failed; ranking without churn");
+            HashMap::new()
+        }
+    }
+}
+
+/// Counts past runs per target whose baseline verification had a failing command.
+fn recent_failures(repo_root: &Path) -> HashMap<PathBuf, usize> {
+    let mut failures = HashMap::new();
+    for run in artifacts::list_runs(repo_root) {
+        let Some(meta) = run.metadata else {
+            continue;
+        };
+        let baseline = RunArtifacts::load(&run.dir).verify_baseline;
+        if baseline.is_some_and(|results| results.iter().any(|r| !r.success)) {
+            let target = meta
+                .target
+                .strip_prefix(repo_root)
+                .map(Path::to_path_buf)
+                .unwrap_or(meta.target);
+            *failures.entry(target).or_insert(0) += 1;
+        }
+    }
+    failures
+}
+
+fn is_source_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// Walks the repo (respecting `.gitignore`) and ranks source files, highest score first.
+pub async fn rank_files(repo_root: &Path, paths: &[PathBuf]) -> Vec<FileScore> {
+    let churn = git_churn(repo_root).await;
+    let failures = recent_failures(repo_root);
+
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        vec![repo_root.to_path_buf()]
+    } else {
+        paths.iter().map(|p| repo_root.join(p)).collect()
+    };
+
+    let mut scores = Vec::new();
+    for root in roots {
+        let walker = WalkBuilder::new(&root)
+            .hidden(true)
+            .git_ignore(true)
+            .build();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    warn!("Error walking repo: {}", err);
+                    continue;
+                }
+            };
+            let path = entry.path();
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) || !is_source_file(path) {
+                continue;
+            }
+            if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(repo_root) else {
+                continue;
+            };
+            let lines = std::fs::read_to_string(path)
+                .map(|c| c.lines().count())
+                .unwrap_or(0);
+            scores.push(FileScore::new(
+                rel.to_path_buf(),
+                churn.get(rel).copied().unwrap_or(0),
+                lines,
+                failures.get(rel).copied().unwrap_or(0),
+            ));
+        }
+    }
+    scores.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    scores.dedup_by(|a, b| a.path == b.path);
+    scores
+}
+
+/// Runs a single review without printing, returning its run id, outcome and usage.
+async fn review_one(
+    config: CouncilConfig,
+    target: PathBuf,
+) -> Result<(String, Option<JobOutcome>, Option<RunUsage>)> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+    let (run_id, job_dir) = create_run_dir(&config.repo_root).await?;
+    let runner = CouncilRunner::new(config, tx, CancellationToken::new(), job_dir);
+
+    let collect = async {
+        let mut finished = (None, None);
+        while let Some(event) = rx.recv().await {
+            if let CouncilEvent::JobFinished { outcome, usage, .. } = event {
+                finished = (Some(outcome), usage);
+                break;
+            }
+        }
+        finished
+    };
+    let (res, (outcome, usage)) = tokio::join!(runner.run(target, CouncilMode::Review), collect);
+    res?;
+    Ok((run_id, outcome, usage))
+}
+
+/// Ranks files, reviews the top `options.top` and writes the consolidated report.
+pub async fn run_sweep(config: CouncilConfig, options: SweepOptions) -> Result<SweepReport> {
+    let repo_root = config.repo_root.clone();
+    let ranked = rank_files(&repo_root, &options.paths).await;
+    let selected: Vec<FileScore> = ranked.into_iter().take(options.top).collect();
+    info!("Sweep: reviewing {} files", selected.len());
+
+    let concurrency = config.settings.max_concurrent_jobs.max(1);
+    let budget = Arc::new(Mutex::new(SharedBudget {
+        max_tokens: options.max_tokens,
+        max_cost: options.max_cost,
+        concurrency,
+        files_left: selected.len(),
+        ..Default::default()
+    }));
+    let settled = Arc::new(Notify::new());
+
+    let entries: Vec<SweepEntry> = futures::stream::iter(selected)
+        .map(|file| {
+            let budget = Arc::clone(&budget);
+            let settled = Arc::clone(&settled);
+            let config = config.clone();
+            async move {
+                let (settings, reservation) = loop {
+                    // Registered before checking so a settle in between still wakes us.
+                    let notified = settled.notified();
+                    let reserve = lock_budget(&budget).reserve(&config.settings);
+                    match reserve {
+                        Reserve::Granted(settings, reservation) => break (settings, reservation),
+                        Reserve::Wait => notified.await,
+                        Reserve::Exhausted => {
+                            return SweepEntry {
+                                file,
+                                run_id: None,
+                                outcome: None,
+                                usage: None,
+                                skipped: Some("sweep budget exhausted".to_string()),
+                            };
+                        }
+                    }
+                };
+                let target = config.repo_root.join(&file.path);
+                info!("Sweep: reviewing {}", file.path.display());
+                let config = CouncilConfig { settings, ..config };
+                let result = review_one(config, target).await;
+                let usage = result
+                    .as_ref()
+                    .ok()
+                    .and_then(|(_, _, usage)| usage.as_ref());
+                lock_budget(&budget).settle(reservation, usage);
+                settled.notify_waiters();
+                match result {
+                    Ok((run_id, outcome, usage)) => SweepEntry {
+                        file,
+                        run_id: Some(run_id),
+                        outcome,
+                        usage,
+                        skipped: None,
+                    },
+                    Err(e) => SweepEntry {
+                        file,
+                        run_id: None,
+                        outcome: Some(JobOutcome::Failure),
+                        usage: None,
+                        skipped: Some(format!("run failed: {e}")),
+                    },
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    let mut entries = entries;
+    entries.sort_by(|a, b| b.file.score.total_cmp(&a.file.score));
+
+    let (sweep_id, sweep_dir) = create_sweep_dir(&repo_root).await?;
+    let report = SweepReport {
+        sweep_id,
+        total_tokens: entries
+            .iter()
+            .filter_map(|e| e.usage.as_ref())
+            .map(|u| u.total.token_usage.total_tokens)
+            .sum(),
+        total_cost_usd: entries
+            .iter()
+            .filter_map(|e| e.usage.as_ref())
+            .map(|u| u.total.cost_usd)
+            .sum(),
+        entries,
+    };
+
+    let findings = collect_findings(&repo_root, &report);
+    fs::write(
+        sweep_dir.join("report.md"),
+        render_report(&report, &findings),
+    )
+    .await?;
+    fs::write(
+        sweep_dir.join("report.json"),
+        serde_json::to_string_pretty(&report)?,
+    )
+    .await?;
+    info!("Sweep report written to {:?}", sweep_dir.join("report.md"));
+    Ok(report)
+}
+
+async fn create_sweep_dir(repo_root: &Path) -> Result<(String, PathBuf)> {
+    let sweeps_dir = repo_root.join(".council").join("sweeps");
+    fs::create_dir_all(&sweeps_dir).await?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let mut suffix = 0;
+    loop {
+        let sweep_id = if suffix == 0 {
+            format!("sweep-{timestamp}")
+        } else {
+            format!("sweep-{timestamp}-{suffix}")
+        };
+        let dir = sweeps_dir.join(&sweep_id);
+        match fs::create_dir(&dir).await {
+            Ok(()) => return Ok((sweep_id, dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => suffix += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// A finding in the consolidated report, tied back to its file and run.
+#[derive(Debug, Clone)]
+pub struct SweepFinding {
+    pub severity: Severity,
+    pub file: PathBuf,
+    pub run_id: String,
+    pub critic: String,
+    pub location: String,
+    pub issue: String,
+}
+
+fn collect_findings(repo_root: &Path, report: &SweepReport) -> Vec<SweepFinding> {
+    let mut findings = Vec::new();
+    for entry in &report.entries {
+        let Some(run_id) = &entry.run_id else {
+            continue;
+        };
+        let run_dir = artifacts::runs_dir(repo_root).join(run_id);
+        for f in RunArtifacts::load(&run_dir).findings() {
+            findings.push(SweepFinding {
+                severity: f.finding.severity,
+                file: entry.file.path.clone(),
+                run_id: run_id.clone(),
+                critic: f.critic,
+                location: f.finding.field("location").unwrap_or_default(),
+                issue: f.finding.field("issue").unwrap_or_default(),
+            });
+        }
+    }
+    findings
+}
+
+/// Renders the consolidated Markdown report. Run links are relative to
+/// `.council/sweeps/<sweep-id>/report.md`.
+pub fn render_report(report: &SweepReport, findings: &[SweepFinding]) -> String {
+    let mut out = format!("# Council sweep {}\n\n", report.sweep_id);
+    out.push_str(&format!(
+        "Reviewed {} of {} files; {} tokens, ${:.4}.\n\n",
+        report.entries.iter().filter(|e| e.run_id.is_some()).count(),
+        report.entries.len(),
+        report.total_tokens,
+        report.total_cost_usd
+    ));
+
+    out.push_str("## Files\n\n| File | Score | Churn | Lines | Failures | Run | Outcome |\n|---|---|---|---|---|---|---|\n");
+    for e in &report.entries {
+        let run = match &e.run_id {
+            Some(id) => format!("[{id}](../../runs/{id}/)"),
+            None => "—".to_string(),
+        };
+        let outcome = match (&e.outcome, &e.skipped) {
+            (_, Some(reason)) => format!("skipped: {reason}"),
+            (Some(outcome), None) => format!("{outcome:?}"),
+            (None, None) => "unknown".to_string(),
+        };
+        out.push_str(&format!(
+            "| `{}` | {:.1} | {} | {} | {} | {run} | {outcome} |\n",
+            e.file.path.display(),
+            e.file.score,
+            e.file.churn,
+            e.file.lines,
+            e.file.recent_failures
+        ));
+    }
+
+    out.push_str("\n## Findings\n");
+    if findings.is_empty() {
+        out.push_str("\nNo findings.\n");
+    }
+    for severity in [
+        Severity::P0,
+        Severity::P1,
+        Severity::P2,
+        Severity::P3,
+        Severity::Unknown,
+    ] {
+        let group: Vec<&SweepFinding> =
+            findings.iter().filter(|f| f.severity == severity).collect();
+        if group.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n### {} ({})\n\n", severity.label(), group.len()));
+        for f in group {
+            let location = if f.location.is_empty() {
+                f.file.display().to_string()
+            } else {
+                f.location.clone()
+            };
+            out.push_str(&format!(
+                "- `{location}` — {} ({}, [{}](../../runs/{}/))\n",
+                f.issue, f.critic, f.run_id, f.run_id
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn churn_counts_commits_per_file() {
+        let churn = parse_churn("src/a.rs\nsrc/b.rs\n\nsrc/a.rs\n");
+        assert_eq!(churn.get(Path::new("src/a.rs")), Some(&2));
+        assert_eq!(churn.get(Path::new("src/b.rs")), Some(&1));
+    }
+
+    fn usage_of(tokens: i64) -> RunUsage {
+        let mut usage = RunUsage::default();
+        usage.total.token_usage.total_tokens = tokens;
+        usage
+    }
+
+    /// Reserves a run and returns its token cap along with the reservation.
+    fn grant(
+        budget: &mut SharedBudget,
+        base: &CouncilSettings,
+    ) -> (Option<u64>, BudgetReservation) {
+        match budget.reserve(base) {
+            Reserve::Granted(settings, reservation) => (settings.max_tokens_per_run, reservation),
+            other => panic!("expected a reservation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn shared_budget_caps_runs_and_stops_when_spent() {
+        let base = CouncilSettings {
+            max_tokens_per_run: Some(50_000),
+            ..Default::default()
+        };
+        let mut budget = SharedBudget {
+            max_tokens: Some(80_000),
+            ..Default::default()
+        };
+        let (cap, first) = grant(&mut budget, &base);
+        assert_eq!(cap, Some(50_000));
+        budget.settle(first, Some(&usage_of(60_000)));
+
+        let (cap, second) = grant(&mut budget, &base);
+        assert_eq!(cap, Some(20_000));
+        budget.settle(second, Some(&usage_of(20_000)));
+
+        assert_eq!(budget.reserve(&base), Reserve::Exhausted);
+    }
+
+    #[test]
+    fn concurrent_runs_each_get_a_share() {
+        let base = CouncilSettings {
+            max_tokens_per_run: Some(50_000),
+            ..Default::default()
+        };
+        let mut budget = SharedBudget {
+            max_tokens: Some(50_000),
+            concurrency: 2,
+            files_left: 3,
+            ..Default::default()
+        };
+
+        // Both slots start at once, each with half of the budget.
+        let (cap, first) = grant(&mut budget, &base);
+        assert_eq!(cap, Some(25_000));
+        let (cap, second) = grant(&mut budget, &base);
+        assert_eq!(cap, Some(25_000));
+        assert_eq!(budget.reserve(&base), Reserve::Wait);
+
+        // What the first run did not use goes to the last file.
+        budget.settle(first, Some(&usage_of(10_000)));
+        let (cap, third) = grant(&mut budget, &base);
+        assert_eq!(cap, Some(15_000));
+
+        budget.settle(second, Some(&usage_of(25_000)));
+        budget.settle(third, Some(&usage_of(15_000)));
+        assert_eq!(budget.reserve(&base), Reserve::Exhausted);
+    }
+
+    #[test]
+    fn report_groups_findings_by_severity_with_run_links() {
+        let report = SweepReport {
+            sweep_id: "sweep-1".to_string(),
+            entries: vec![SweepEntry {
+                file: FileScore::new(PathBuf::from("src/a.rs"), 3, 10, 0),
+                run_id: Some("run-9".to_string()),
+                outcome: Some(JobOutcome::Success),
+                usage: None,
+                skipped: None,
+            }],
+            total_tokens: 0,
+            total_cost_usd: 0.0,
+        };
+        let findings = vec![
+            SweepFinding {
+                severity: Severity::P2,
+                file: PathBuf::from("src/a.rs"),
+                run_id: "run-9".to_string(),
+                critic: "Gemini".to_string(),
+                location: String::new(),
+                issue: "Naming".to_string(),
+            },
+            SweepFinding {
+                severity: Severity::P0,
+                file: PathBuf::from("src/a.rs"),
+                run_id: "run-9".to_string(),
+                critic: "GPT".to_string(),
+                location: "src/a.rs:4".to_string(),
+                issue: "Panic on empty input".to_string(),
+            },
+        ];
+
+        let md = render_report(&report, &findings);
+        let p0 = md.find("### P0 (1)").unwrap_or(usize::MAX);
+        let p2 = md.find("### P2 (1)").unwrap_or(usize::MAX);
+        assert!(p0 < p2, "{md}");
+        assert!(
+            md.contains("- `src/a.rs:4` — Panic on empty input (GPT, [run-9](../../runs/run-9/))")
+        );
+        assert!(md.contains("- `src/a.rs` — Naming (Gemini, [run-9](../../runs/run-9/))"));
+        assert!(
+            md.contains("| `src/a.rs` | 8.4 | 3 | 10 | 0 | [run-9](../../runs/run-9/) | Success |")
+        );
+    }
+}
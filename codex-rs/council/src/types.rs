@@ -1,3 +1,5 @@
+use crate::usage::RunUsage;
+use codex_core::config::types::CouncilSettings;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -11,6 +13,7 @@ pub struct CouncilConfig {
     pub critic_gpt_model: String,
     pub critic_gemini_model: String,
     pub implementer_model: String,
+    pub settings: CouncilSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +88,8 @@ pub enum CouncilEvent {
     JobFinished {
         outcome: JobOutcome,
         summary_line: String,
+        #[serde(default)]
+        usage: Option<RunUsage>,
     },
 }
 
@@ -0,0 +1,265 @@
+use codex_core::config::types::CouncilSettings;
+use codex_core::config::types::ModelPrice;
+use codex_protocol::protocol::TokenUsage;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Rough bytes-per-token ratio used to estimate the input size of a call before it is sent.
+const APPROX_BYTES_PER_TOKEN: u64 = 4;
+
+/// Usage recorded for a single model call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallUsage {
+    pub role: String,
+    pub model: String,
+    pub token_usage: TokenUsage,
+    pub latency_ms: u64,
+    /// `None` when the model has no entry in the price table.
+    pub cost_usd: Option<f64>,
+}
+
+/// Usage rolled up for one council role (or for the whole run).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub calls: usize,
+    pub token_usage: TokenUsage,
+    pub latency_ms: u64,
+    pub cost_usd: f64,
+    /// True when at least one call could not be priced, so `cost_usd` is a lower bound.
+    pub cost_incomplete: bool,
+}
+
+impl UsageTotals {
+    fn add(&mut self, call: &CallUsage) {
+        self.calls += 1;
+        self.token_usage.add_assign(&call.token_usage);
+        self.latency_ms += call.latency_ms;
+        match call.cost_usd {
+            Some(cost) => self.cost_usd += cost,
+            None => self.cost_incomplete = true,
+        }
+    }
+}
+
+/// Per-run usage summary persisted to `usage.json` and attached to `JobFinished`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunUsage {
+    pub calls: Vec<CallUsage>,
+    pub by_role: BTreeMap<String, UsageTotals>,
+    pub total: UsageTotals,
+}
+
+impl RunUsage {
+    pub fn summary_line(&self) -> String {
+        let tokens = self.total.token_usage.total_tokens;
+        let calls = self.total.calls;
+        let cost = self.total.cost_usd;
+        let approx = if self.total.cost_incomplete { "≥" } else { "" };
+        format!("{tokens} tokens across {calls} calls, {approx}${cost:.4}")
+    }
+}
+
+/// Tracks model usage for a run and enforces the configured per-run budget.
+#[derive(Debug, Clone, Default)]
+pub struct UsageLedger {
+    settings: CouncilSettings,
+    usage: RunUsage,
+}
+
+/// Reason a phase was refused by the budget check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetExceeded {
+    Tokens { projected: u64, limit: u64 },
+    Cost { projected: f64, limit: f64 },
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetExceeded::Tokens { projected, limit } => write!(
+                f,
+                "projected {projected} tokens would exceed max_tokens_per_run ({limit})"
+            ),
+            BudgetExceeded::Cost { projected, limit } => write!(
+                f,
+                "projected cost ${projected:.4} would exceed max_cost_per_run (${limit:.4})"
+            ),
+        }
+    }
+}
+
+impl UsageLedger {
+    pub fn new(settings: CouncilSettings) -> Self {
+        Self {
+            settings,
+            usage: RunUsage::default(),
+        }
+    }
+
+    pub fn usage(&self) -> &RunUsage {
+        &self.usage
+    }
+
+    /// Looks up the price for `model`, preferring an exact match and then the longest
+    /// configured prefix (so `gpt-5.1` prices `gpt-5.1-codex`).
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        if let Some(price) = self.settings.prices.get(model) {
+            return Some(price);
+        }
+        self.settings
+            .prices
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| price)
+    }
+
+    pub fn cost_of(&self, model: &str, usage: &TokenUsage) -> Option<f64> {
+        let price = self.price_for(model)?;
+        let cached_price = price.cached_input_per_mtok.unwrap_or(price.input_per_mtok);
+        let cost = usage.non_cached_input() as f64 * price.input_per_mtok
+            + usage.cached_input() as f64 * cached_price
+            + usage.output_tokens.max(0) as f64 * price.output_per_mtok;
+        Some(cost / 1_000_000.0)
+    }
+
+    pub fn record(&mut self, role: &str, model: &str, token_usage: TokenUsage, latency_ms: u64) {
+        let call = CallUsage {
+            role: role.to_string(),
+            model: model.to_string(),
+            cost_usd: self.cost_of(model, &token_usage),
+            token_usage,
+            latency_ms,
+        };
+        self.usage
+            .by_role
+            .entry(call.role.clone())
+            .or_default()
+            .add(&call);
+        self.usage.total.add(&call);
+        self.usage.calls.push(call);
+    }
+
+    /// Checks whether the next phase fits in the remaining budget.
+    ///
+    /// `calls` lists the `(model, prompt_bytes)` pairs the phase is about to send. Input
+    /// tokens are estimated from the prompt size; output is estimated from the average
+    /// output of the calls made so far in this run.
+    pub fn check_next_phase(&self, calls: &[(&str, usize)]) -> Result<(), BudgetExceeded> {
+        let avg_output = if self.usage.total.calls == 0 {
+            0
+        } else {
+            self.usage.total.token_usage.output_tokens.max(0) as u64
+                / self.usage.total.calls as u64
+        };
+
+        let mut projected_tokens = self.usage.total.token_usage.total_tokens.max(0) as u64;
+        let mut projected_cost = self.usage.total.cost_usd;
+        for (model, prompt_bytes) in calls {
+            let input = *prompt_bytes as u64 / APPROX_BYTES_PER_TOKEN;
+            projected_tokens += input + avg_output;
+            let estimate = TokenUsage {
+                input_tokens: input as i64,
+                output_tokens: avg_output as i64,
+                total_tokens: (input + avg_output) as i64,
+                ..Default::default()
+            };
+            projected_cost += self.cost_of(model, &estimate).unwrap_or_default();
+        }
+
+        if let Some(limit) = self.settings.max_tokens_per_run
+            && projected_tokens > limit
+        {
+            return Err(BudgetExceeded::Tokens {
+                projected: projected_tokens,
+                limit,
+            });
+        }
+        if let Some(limit) = self.settings.max_cost_per_run
+            && projected_cost > limit
+        {
+            return Err(BudgetExceeded::Cost {
+                projected: projected_cost,
+                limit,
+            });
+        }
+        Ok(())
+    }
+
+    /// Models that will be called but have no price entry while a cost budget is set.
+    pub fn unpriced_models<'a>(&self, models: &[&'a str]) -> Vec<&'a str> {
+        if self.settings.max_cost_per_run.is_none() {
+            return Vec::new();
+        }
+        models
+            .iter()
+            .copied()
+            .filter(|m| self.price_for(m).is_none())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn settings() -> CouncilSettings {
+        CouncilSettings {
+            max_tokens_per_run: Some(10_000),
+            max_cost_per_run: Some(1.0),
+            prices: HashMap::from([(
+                "gpt-5.1".to_string(),
+                ModelPrice {
+                    input_per_mtok: 2.0,
+                    cached_input_per_mtok: Some(0.5),
+                    output_per_mtok: 10.0,
+                },
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn prices_by_longest_prefix_and_rolls_up_per_role() {
+        let mut ledger = UsageLedger::new(settings());
+        ledger.record("critic_gpt", "gpt-5.1-codex", usage(1_000, 200, 100), 50);
+        ledger.record("critic_gemini", "gemini-3-pro", usage(500, 0, 50), 70);
+
+        let run = ledger.usage();
+        let gpt = &run.by_role["critic_gpt"];
+        // 800 * 2.0 + 200 * 0.5 + 100 * 10.0 = 2700 per million tokens.
+        assert_eq!(gpt.cost_usd, 0.0027);
+        assert!(!gpt.cost_incomplete);
+        assert!(run.by_role["critic_gemini"].cost_incomplete);
+        assert_eq!(run.total.calls, 2);
+        assert_eq!(run.total.token_usage.total_tokens, 1_650);
+        assert_eq!(run.total.latency_ms, 120);
+    }
+
+    #[test]
+    fn refuses_phase_that_would_exceed_token_budget() {
+        let mut ledger = UsageLedger::new(settings());
+        ledger.record("critic_gpt", "gpt-5.1-codex", usage(8_000, 0, 1_000), 10);
+
+        assert_eq!(ledger.check_next_phase(&[("gpt-5.1-codex", 2_000)]), Ok(()));
+        assert_eq!(
+            ledger.check_next_phase(&[("gpt-5.1-codex", 40_000)]),
+            Err(BudgetExceeded::Tokens {
+                projected: 20_000,
+                limit: 10_000,
+            })
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
 use std::path::Path;
+use std::path::PathBuf;
 use tokio::process::Command;
 use tracing::info;
 use tracing::warn;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerifyResult {
     pub command: String,
     pub success: bool,
@@ -12,11 +15,101 @@ pub struct VerifyResult {
     pub stderr: String,
 }
 
+/// A shared `CARGO_TARGET_DIR` under `.council/target`, keyed by toolchain so runs on
+/// different compilers never share (and invalidate) build output.
+#[derive(Debug, Clone)]
+pub struct BuildCache {
+    pub target_dir: PathBuf,
+}
+
+impl BuildCache {
+    /// Returns `None` when `rustc` cannot be queried (e.g. non-Rust repos).
+    pub async fn shared(repo_root: &Path) -> Option<Self> {
+        let output = Command::new("rustc")
+            .arg("-vV")
+            .current_dir(repo_root)
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let key = toolchain_key(&String::from_utf8_lossy(&output.stdout))?;
+        Some(Self {
+            target_dir: repo_root.join(".council").join("target").join(key),
+        })
+    }
+}
+
+/// `<release>-<host>` from `rustc -vV` output, e.g. `1.90.0-x86_64-unknown-linux-gnu`.
+fn toolchain_key(rustc_version: &str) -> Option<String> {
+    let field = |name: &str| {
+        rustc_version
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(|v| v.trim().to_string())
+    };
+    let key = format!("{}-{}", field("release:")?, field("host:")?);
+    Some(
+        key.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Build-cache statistics for a run, written to `build_cache.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildCacheStats {
+    /// Pool slot the run's worktree was leased from, if any.
+    pub pool_slot: Option<usize>,
+    /// True when an existing pooled worktree was reset and reused.
+    pub worktree_reused: bool,
+    pub target_dir: Option<PathBuf>,
+    /// True when the shared target dir already existed when the run started.
+    pub target_dir_warm: bool,
+    /// Cargo verification commands that were run.
+    pub cargo_commands: usize,
+    /// Cargo commands that compiled nothing, i.e. were served entirely from the cache.
+    pub cargo_commands_cached: usize,
+    /// Crates compiled across all cargo commands (`Compiling ...` lines).
+    pub compiled_units: usize,
+}
+
+impl BuildCacheStats {
+    pub fn record(&mut self, results: &[VerifyResult]) {
+        for result in results.iter().filter(|r| r.command.starts_with("cargo ")) {
+            let compiled = result
+                .stderr
+                .lines()
+                .filter(|line| line.trim_start().starts_with("Compiling "))
+                .count();
+            self.cargo_commands += 1;
+            self.compiled_units += compiled;
+            if compiled == 0 {
+                self.cargo_commands_cached += 1;
+            }
+        }
+    }
+}
+
 pub struct Verifier;
 
 impl Verifier {
-    pub async fn run_all(worktree_path: &Path, target: Option<&Path>) -> Result<Vec<VerifyResult>> {
+    pub async fn run_all(
+        worktree_path: &Path,
+        target: Option<&Path>,
+        build_cache: Option<&BuildCache>,
+    ) -> Result<Vec<VerifyResult>> {
         let mut results = Vec::new();
+        let cargo_env: Vec<(&str, &Path)> = build_cache
+            .map(|cache| vec![("CARGO_TARGET_DIR", cache.target_dir.as_path())])
+            .unwrap_or_default();
 
         let cargo_manifest = target
             .and_then(|t| find_nearest_cargo_toml(worktree_path, t))
@@ -35,6 +128,7 @@ impl Verifier {
                 Self::run_cmd(
                     worktree_path,
                     "cargo",
+                    &cargo_env,
                     &[
                         "check",
                         "--offline",
@@ -50,6 +144,7 @@ impl Verifier {
                 Self::run_cmd(
                     worktree_path,
                     "cargo",
+                    &cargo_env,
                     &[
                         "test",
                         "--offline",
@@ -63,19 +158,24 @@ impl Verifier {
             // Fallback (Python defaults)
 
             // 1. Ruff Format
-            results.push(Self::run_cmd(worktree_path, "ruff", &["format", "."]).await?);
+            results.push(Self::run_cmd(worktree_path, "ruff", &[], &["format", "."]).await?);
 
             // 2. Ruff Check
-            results.push(Self::run_cmd(worktree_path, "ruff", &["check", "."]).await?);
+            results.push(Self::run_cmd(worktree_path, "ruff", &[], &["check", "."]).await?);
 
             // 3. Pytest
-            results.push(Self::run_cmd(worktree_path, "pytest", &["-q"]).await?);
+            results.push(Self::run_cmd(worktree_path, "pytest", &[], &["-q"]).await?);
         }
 
         Ok(results)
     }
 
-    async fn run_cmd(cwd: &Path, program: &str, args: &[&str]) -> Result<VerifyResult> {
+    async fn run_cmd(
+        cwd: &Path,
+        program: &str,
+        envs: &[(&str, &Path)],
+        args: &[&str],
+    ) -> Result<VerifyResult> {
         info!("Running verification: {} {}", program, args.join(" "));
 
         // Check if program exists (optional, but good for error messages)
@@ -83,6 +183,7 @@ impl Verifier {
 
         let output = Command::new(program)
             .args(args)
+            .envs(envs.iter().copied())
             .current_dir(cwd)
             .output()
             .await;
@@ -149,3 +250,40 @@ fn find_nearest_cargo_toml(worktree_root: &Path, target: &Path) -> Option<std::p
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolchain_key_uses_release_and_host() {
+        let output = "rustc 1.90.0 (1159e78c4 2025-09-14)\nbinary: rustc\nhost: x86_64-unknown-linux-gnu\nrelease: 1.90.0\n";
+        assert_eq!(
+            toolchain_key(output).as_deref(),
+            Some("1.90.0-x86_64-unknown-linux-gnu")
+        );
+        assert_eq!(toolchain_key("rustc 1.90.0"), None);
+    }
+
+    #[test]
+    fn stats_count_compiled_units_per_cargo_command() {
+        let result = |command: &str, stderr: &str| VerifyResult {
+            command: command.to_string(),
+            success: true,
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+        };
+        let mut stats = BuildCacheStats::default();
+        stats.record(&[
+            result(
+                "cargo check --offline",
+                "   Compiling foo v0.1.0\n   Compiling bar v0.1.0\n    Finished",
+            ),
+            result("cargo test --offline", "    Finished\n     Running tests"),
+            result("pytest -q", "Compiling nothing"),
+        ]);
+        assert_eq!(stats.cargo_commands, 2);
+        assert_eq!(stats.cargo_commands_cached, 1);
+        assert_eq!(stats.compiled_units, 2);
+    }
+}
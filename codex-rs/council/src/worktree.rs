@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 use tokio::process::Command;
 use tracing::info;
+use tracing::warn;
 
 pub struct Snapshot {
     pub path: PathBuf,
@@ -67,6 +68,64 @@ impl Snapshot {
 pub struct Worktree {
     pub path: PathBuf,
     pub id: String,
+    /// Set when the worktree is leased from the pool; dropping it releases the slot.
+    pub lease: Option<PoolLease>,
+}
+
+/// A claimed slot in the worktree pool. The slot is held by a lock file next to the
+/// worktree (`pool-<n>.lock`, containing the run id) and released on drop.
+#[derive(Debug)]
+pub struct PoolLease {
+    pub slot: usize,
+    /// True when an existing pooled worktree was reset and reused.
+    pub reused: bool,
+    lock_path: PathBuf,
+}
+
+impl Drop for PoolLease {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.lock_path);
+    }
+}
+
+/// Leases older than this are treated as abandoned by a crashed process.
+const STALE_LEASE_AGE: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+fn pool_worktree_path(repo_root: &Path, slot: usize) -> PathBuf {
+    repo_root
+        .join(".council")
+        .join("worktrees")
+        .join(format!("pool-{slot}"))
+}
+
+fn pool_lock_path(repo_root: &Path, slot: usize) -> PathBuf {
+    repo_root
+        .join(".council")
+        .join("worktrees")
+        .join(format!("pool-{slot}.lock"))
+}
+
+async fn git(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .with_context(|| format!("Failed to execute git {}", args.join(" ")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resets a pooled worktree to `sha`, dropping tracked and untracked changes but keeping
+/// ignored files (build outputs) warm.
+async fn reset_pooled(path: &Path, sha: &str) -> Result<()> {
+    git(path, &["checkout", "--detach", "--force", sha]).await?;
+    git(path, &["reset", "--hard", sha]).await?;
+    git(path, &["clean", "-fd"]).await?;
+    Ok(())
 }
 
 impl Worktree {
@@ -105,10 +164,80 @@ impl Worktree {
         Ok(Self {
             path,
             id: run_id.to_string(),
+            lease: None,
         })
     }
 
+    /// Leases a pooled worktree reset to HEAD, falling back to a fresh per-run worktree
+    /// when the pool is disabled (`pool_size == 0`) or every slot is busy.
+    pub async fn acquire(repo_root: &Path, run_id: &str, pool_size: usize) -> Result<Self> {
+        if pool_size == 0 {
+            return Self::create(repo_root, run_id).await;
+        }
+        let worktrees_dir = repo_root.join(".council").join("worktrees");
+        tokio::fs::create_dir_all(&worktrees_dir)
+            .await
+            .context("Failed to create .council/worktrees directory")?;
+        let head = git(repo_root, &["rev-parse", "HEAD"]).await?;
+
+        for slot in 0..pool_size {
+            let lock_path = pool_lock_path(repo_root, slot);
+            if !claim_slot(&worktrees_dir, &lock_path, run_id)? {
+                continue;
+            }
+            let path = pool_worktree_path(repo_root, slot);
+            let mut lease = PoolLease {
+                slot,
+                reused: false,
+                lock_path,
+            };
+
+            if path.join(".git").exists() {
+                match reset_pooled(&path, &head).await {
+                    Ok(()) => {
+                        info!("Reusing pooled worktree {} for run {}", slot, run_id);
+                        lease.reused = true;
+                        return Ok(Self {
+                            path,
+                            id: run_id.to_string(),
+                            lease: Some(lease),
+                        });
+                    }
+                    Err(e) => {
+                        warn!("Pooled worktree {} is unusable ({}); recreating it", slot, e);
+                        let _ = git(
+                            repo_root,
+                            &["worktree", "remove", "--force", &path.to_string_lossy()],
+                        )
+                        .await;
+                        let _ = tokio::fs::remove_dir_all(&path).await;
+                        let _ = git(repo_root, &["worktree", "prune"]).await;
+                    }
+                }
+            }
+
+            info!("Creating pooled worktree {} at {:?}", slot, path);
+            git(
+                repo_root,
+                &["worktree", "add", "--detach", &path.to_string_lossy(), &head],
+            )
+            .await?;
+            return Ok(Self {
+                path,
+                id: run_id.to_string(),
+                lease: Some(lease),
+            });
+        }
+
+        info!("Worktree pool is busy; creating a dedicated worktree for run {}", run_id);
+        Self::create(repo_root, run_id).await
+    }
+
     pub async fn remove(&self) -> Result<()> {
+        if self.lease.is_some() {
+            // Pooled worktrees are recycled, not removed.
+            return Ok(());
+        }
         info!("Removing worktree at {:?}", self.path);
 
         // git worktree remove --force <path>
@@ -130,3 +259,132 @@ impl Worktree {
         Ok(())
     }
 }
+
+/// Claims a pool slot by creating its lock file with the run id already in it.
+///
+/// The id is written to a temp file that is then linked into place without
+/// replacing an existing lock, so nobody ever sees a claimed slot without an owner.
+fn claim_slot(worktrees_dir: &Path, lock_path: &Path, run_id: &str) -> Result<bool> {
+    let mut lock = tempfile::NamedTempFile::new_in(worktrees_dir)?;
+    std::io::Write::write_all(&mut lock, run_id.as_bytes())?;
+    match lock.persist_noclobber(lock_path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.error.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e.error.into()),
+    }
+}
+
+/// Releases pool slots whose lock is held by a run that no longer exists (e.g. after a
+/// crash) or whose lease is older than `STALE_LEASE_AGE`, and resets those worktrees to
+/// HEAD so they stay warm.
+///
+/// A finished run's status file does not free its slot: the lease is released by
+/// dropping it, after the run is done with the worktree.
+pub async fn recycle_pool(repo_root: &Path) -> Result<()> {
+    let worktrees_dir = repo_root.join(".council").join("worktrees");
+    let runs_dir = repo_root.join(".council").join("runs");
+    let Ok(mut dir) = tokio::fs::read_dir(&worktrees_dir).await else {
+        return Ok(());
+    };
+    let head = git(repo_root, &["rev-parse", "HEAD"]).await?;
+
+    while let Some(entry) = dir.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(slot) = name
+            .strip_prefix("pool-")
+            .and_then(|rest| rest.strip_suffix(".lock"))
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        let holder = tokio::fs::read_to_string(entry.path())
+            .await
+            .unwrap_or_default();
+        let holder = holder.trim();
+        let run_dir = runs_dir.join(holder);
+        let abandoned = entry
+            .metadata()
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > STALE_LEASE_AGE);
+        let stale = holder.is_empty() || !run_dir.exists() || abandoned;
+        if !stale {
+            continue;
+        }
+
+        info!("Recycling pooled worktree {} (held by {:?})", slot, holder);
+        let path = pool_worktree_path(repo_root, slot);
+        if path.join(".git").exists()
+            && let Err(e) = reset_pooled(&path, &head).await
+        {
+            warn!("Failed to reset pooled worktree {}: {}", slot, e);
+        }
+        let _ = tokio::fs::remove_file(entry.path()).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_sync(repo: &Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .expect("run git");
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn init_repo() -> TempDir {
+        let repo = TempDir::new().expect("tempdir");
+        git_sync(repo.path(), &["init", "-q"]);
+        std::fs::write(repo.path().join("a.txt"), "a\n").expect("write file");
+        git_sync(repo.path(), &["add", "a.txt"]);
+        git_sync(repo.path(), &["commit", "-q", "-m", "a"]);
+        repo
+    }
+
+    #[test]
+    fn claimed_slot_names_its_owner() {
+        let dir = TempDir::new().expect("tempdir");
+        let lock_path = dir.path().join("pool-0.lock");
+
+        assert!(claim_slot(dir.path(), &lock_path, "run-1").expect("claim"));
+        assert!(!claim_slot(dir.path(), &lock_path, "run-2").expect("claim"));
+        assert_eq!(
+            std::fs::read_to_string(&lock_path).expect("read lock"),
+            "run-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn recycle_keeps_slots_of_finished_runs_until_released() {
+        let repo = init_repo();
+        let repo_root = repo.path();
+        let run_dir = repo_root.join(".council").join("runs").join("run-1");
+        std::fs::create_dir_all(&run_dir).expect("create run dir");
+
+        let worktree = Worktree::acquire(repo_root, "run-1", 1)
+            .await
+            .expect("acquire");
+        let lock_path = pool_lock_path(repo_root, 0);
+        assert_eq!(
+            std::fs::read_to_string(&lock_path).expect("read lock"),
+            "run-1"
+        );
+
+        // The run has written its final status but still holds the worktree.
+        std::fs::write(run_dir.join(crate::artifacts::STATUS_FILE), "{}").expect("status");
+        recycle_pool(repo_root).await.expect("recycle");
+        assert!(lock_path.exists());
+
+        drop(worktree);
+        assert!(!lock_path.exists());
+    }
+}
@@ -295,6 +295,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 aggregated_output,
                 duration,
                 exit_code,
+                output_log,
                 ..
             }) => {
                 let duration = format!(" in {}", format_duration(duration));
@@ -315,6 +316,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     }
                 }
                 eprintln!("{}", truncated_output.style(self.dimmed));
+                if let Some(output_log) = output_log {
+                    eprintln!(
+                        "{}",
+                        format!("full output: {}", output_log.display()).style(self.dimmed)
+                    );
+                }
             }
             EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
                 call_id: _,
@@ -423,21 +430,51 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                                 format!("{} {}", format_file_change(change), path.to_string_lossy())
                             };
                             eprintln!("{}", header.style(self.magenta));
-
-                            // Colorize diff lines. We keep file header lines
-                            // (--- / +++) without extra coloring so they are
-                            // still readable.
-                            for diff_line in unified_diff.lines() {
-                                if diff_line.starts_with('+') && !diff_line.starts_with("+++") {
-                                    eprintln!("{}", diff_line.style(self.green));
-                                } else if diff_line.starts_with('-')
-                                    && !diff_line.starts_with("---")
-                                {
-                                    eprintln!("{}", diff_line.style(self.red));
-                                } else {
-                                    eprintln!("{diff_line}");
-                                }
-                            }
+                            self.print_unified_diff(unified_diff);
+                        }
+                        FileChange::Copy {
+                            source,
+                            unified_diff,
+                        } => {
+                            let header = format!(
+                                "{} {} -> {}",
+                                format_file_change(change),
+                                source.to_string_lossy(),
+                                path.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                            self.print_unified_diff(unified_diff);
+                        }
+                        FileChange::Mode { old_mode, new_mode } => {
+                            let header = format!(
+                                "{} {}",
+                                format_file_change(change),
+                                path.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                            let description = match old_mode {
+                                Some(old_mode) => format!("mode {old_mode:o} -> {new_mode:o}"),
+                                None => format!("mode {new_mode:o}"),
+                            };
+                            eprintln!("{}", description.style(self.dimmed));
+                        }
+                        FileChange::Symlink { target } => {
+                            let header = format!(
+                                "{} {} -> {}",
+                                format_file_change(change),
+                                path.to_string_lossy(),
+                                target.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
+                        FileChange::Binary { size, .. } => {
+                            let header = format!(
+                                "{} {}",
+                                format_file_change(change),
+                                path.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                            eprintln!("{}", format!("binary, {size} bytes").style(self.dimmed));
                         }
                     }
                 }
@@ -575,7 +612,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::McpSampling(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::ListMcpPromptsResponse(_)
+            | EventMsg::ListProcessesResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
@@ -592,11 +632,27 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
+            | EventMsg::UndoTimeline(_)
+            | EventMsg::UndoSnapshotDiff(_)
             | EventMsg::ThreadRolledBack(_) => {}
         }
         CodexStatus::Running
     }
 
+    fn print_unified_diff(&self, unified_diff: &str) {
+        // Colorize diff lines. We keep file header lines (--- / +++) without
+        // extra coloring so they are still readable.
+        for diff_line in unified_diff.lines() {
+            if diff_line.starts_with('+') && !diff_line.starts_with("+++") {
+                eprintln!("{}", diff_line.style(self.green));
+            } else if diff_line.starts_with('-') && !diff_line.starts_with("---") {
+                eprintln!("{}", diff_line.style(self.red));
+            } else {
+                eprintln!("{diff_line}");
+            }
+        }
+    }
+
     fn print_final_output(&mut self) {
         if let Some(usage_info) = &self.last_total_token_usage {
             eprintln!(
@@ -635,6 +691,17 @@ fn format_file_change(change: &FileChange) -> &'static str {
         FileChange::Update {
             move_path: None, ..
         } => "M",
+        FileChange::Copy { .. } => "C",
+        FileChange::Mode { .. } => "M",
+        FileChange::Symlink { .. } => "A",
+        FileChange::Binary {
+            previous_size: Some(_),
+            ..
+        } => "M",
+        FileChange::Binary {
+            previous_size: None,
+            ..
+        } => "A",
     }
 }
 
@@ -355,7 +355,12 @@ impl EventProcessorWithJsonOutput {
         match kind {
             FileChange::Add { .. } => PatchChangeKind::Add,
             FileChange::Delete { .. } => PatchChangeKind::Delete,
-            FileChange::Update { .. } => PatchChangeKind::Update,
+            FileChange::Update { .. } | FileChange::Mode { .. } => PatchChangeKind::Update,
+            FileChange::Copy { .. } | FileChange::Symlink { .. } => PatchChangeKind::Add,
+            FileChange::Binary { previous_size, .. } => match previous_size {
+                Some(_) => PatchChangeKind::Update,
+                None => PatchChangeKind::Add,
+            },
         }
     }
 
@@ -682,6 +682,8 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -769,6 +771,8 @@ fn command_execution_output_delta_updates_item_progress() {
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -842,6 +846,8 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -883,6 +889,8 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
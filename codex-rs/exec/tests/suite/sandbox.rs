@@ -74,6 +74,8 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
 
     let python_code = r#"import multiprocessing
@@ -173,6 +175,8 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
@@ -24,7 +24,9 @@ pub use error::Result;
 pub use exec_call::ExecCall;
 pub use execv_checker::ExecvChecker;
 pub use opt::Opt;
+pub use opt::OptMeta;
 pub use policy::Policy;
+pub use policy_parser::ForbiddenProgramRegex;
 pub use policy_parser::PolicyParser;
 pub use program::Forbidden;
 pub use program::MatchedExec;
@@ -15,6 +15,7 @@ use crate::program::PositiveExampleFailedCheck;
 pub struct Policy {
     programs: MultiMap<String, ProgramSpec>,
     forbidden_program_regexes: Vec<ForbiddenProgramRegex>,
+    forbidden_substrings: Vec<String>,
     forbidden_substrings_pattern: Option<Regex>,
 }
 
@@ -37,10 +38,29 @@ impl Policy {
         Ok(Self {
             programs,
             forbidden_program_regexes,
+            forbidden_substrings,
             forbidden_substrings_pattern,
         })
     }
 
+    /// All program specs, ordered by program name and then by definition order.
+    pub fn program_specs(&self) -> Vec<&ProgramSpec> {
+        let mut programs: Vec<(&String, &Vec<ProgramSpec>)> = self.programs.iter_all().collect();
+        programs.sort_by_key(|(program, _)| *program);
+        programs
+            .into_iter()
+            .flat_map(|(_, specs)| specs.iter())
+            .collect()
+    }
+
+    pub fn forbidden_program_regexes(&self) -> &[ForbiddenProgramRegex] {
+        &self.forbidden_program_regexes
+    }
+
+    pub fn forbidden_substrings(&self) -> &[String] {
+        &self.forbidden_substrings
+    }
+
     pub fn check(&self, exec_call: &ExecCall) -> Result<MatchedExec> {
         let ExecCall { program, args } = &exec_call;
         for ForbiddenProgramRegex { regex, reason } in &self.forbidden_program_regexes {
@@ -91,6 +91,18 @@ impl ProgramSpec {
     // TODO(mbolin): The idea is that there should be a set of rules defined for
     // a program and the args should be checked against the rules to determine
     // if the program should be allowed to run.
+    pub fn forbidden(&self) -> Option<&str> {
+        self.forbidden.as_deref()
+    }
+
+    pub fn should_match(&self) -> &[Vec<String>] {
+        &self.should_match
+    }
+
+    pub fn should_not_match(&self) -> &[Vec<String>] {
+        &self.should_not_match
+    }
+
     pub fn check(&self, exec_call: &ExecCall) -> Result<MatchedExec> {
         let mut expecting_option_value: Option<(String, ArgType)> = None;
         let mut args = Vec::<PositionalArg>::new();
@@ -72,6 +72,13 @@ pub fn blocking_append_allow_prefix_rule(
     let pattern = format!("[{}]", tokens.join(", "));
     let rule = format!(r#"prefix_rule(pattern={pattern}, decision="allow")"#);
 
+    blocking_append_rule(policy_path, &rule)
+}
+
+/// Appends already-rendered rule text (which may span several lines) to `policy_path`,
+/// creating the policy directory if needed. Same locking caveats as
+/// [`blocking_append_allow_prefix_rule`].
+pub fn blocking_append_rule(policy_path: &Path, rule: &str) -> Result<(), AmendError> {
     let dir = policy_path
         .parent()
         .ok_or_else(|| AmendError::MissingParent {
@@ -87,7 +94,7 @@ pub fn blocking_append_allow_prefix_rule(
             });
         }
     }
-    append_locked_line(policy_path, &rule)
+    append_locked_line(policy_path, rule)
 }
 
 fn append_locked_line(policy_path: &Path, line: &str) -> Result<(), AmendError> {
@@ -7,6 +7,7 @@ use clap::Parser;
 use serde::Serialize;
 
 use crate::Decision;
+use crate::MatchContext;
 use crate::Policy;
 use crate::PolicyParser;
 use crate::RuleMatch;
@@ -22,6 +23,15 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub pretty: bool,
 
+    /// Directory the command runs in, used by path-constrained rules. Defaults to the
+    /// current directory.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Writable root for path-constrained rules (repeatable). Defaults to the cwd.
+    #[arg(long = "writable-root", value_name = "DIR")]
+    pub writable_roots: Vec<PathBuf>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -36,13 +46,34 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
-        let matched_rules = policy.matches_for_command(&self.command, None);
+        let context = self.match_context()?;
+        let matched_rules = policy.matches_for_command_in_context(&self.command, &context, None);
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
         println!("{json}");
 
         Ok(())
     }
+
+    fn match_context(&self) -> Result<MatchContext> {
+        let cwd = match &self.cwd {
+            Some(cwd) => std::path::absolute(cwd)?,
+            None => std::env::current_dir()?,
+        };
+        let writable_roots = if self.writable_roots.is_empty() {
+            vec![cwd.clone()]
+        } else {
+            self.writable_roots
+                .iter()
+                .map(std::path::absolute)
+                .collect::<std::io::Result<_>>()?
+        };
+        Ok(MatchContext {
+            cwd: Some(cwd),
+            writable_roots,
+            read_only_paths: Vec::new(),
+        })
+    }
 }
 
 pub fn format_matches_json(matched_rules: &[RuleMatch], pretty: bool) -> Result<String> {
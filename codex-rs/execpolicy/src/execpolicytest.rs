@@ -0,0 +1,438 @@
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use serde::Deserialize;
+use serde::Serialize;
+use shlex::try_join;
+
+use crate::Decision;
+use crate::MatchContext;
+use crate::Policy;
+use crate::PolicyParser;
+use crate::RuleRef;
+use crate::rule::ArgRule;
+use crate::rule::PrefixPattern;
+use crate::rule::PrefixRule;
+
+/// Arguments for running a corpus of commands against one or more execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyTestCommand {
+    /// Paths to execpolicy rule files to test (repeatable; accepts several paths per flag).
+    #[arg(
+        short = 'r',
+        long = "rules",
+        value_name = "PATH",
+        required = true,
+        num_args = 1..
+    )]
+    pub rules: Vec<PathBuf>,
+
+    /// JSONL file with one case per line, e.g. `{"command": "git status", "decision": "allow"}`.
+    #[arg(long = "cases", value_name = "PATH")]
+    pub cases: PathBuf,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ExecPolicyTestCommand {
+    /// Run every case, print the report, and fail if any case got an unexpected decision.
+    pub fn run(&self) -> Result<()> {
+        let rules = load_sourced_rules(&self.rules)?;
+        let cases_contents = fs::read_to_string(&self.cases)
+            .with_context(|| format!("failed to read cases at {}", self.cases.display()))?;
+        let cases = parse_cases(&cases_contents)
+            .with_context(|| format!("failed to parse cases at {}", self.cases.display()))?;
+
+        let report = run_cases(&rules, &cases);
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print!("{report}");
+        }
+
+        if !report.mismatches.is_empty() {
+            bail!(
+                "{} of {} cases did not get the expected decision",
+                report.mismatches.len(),
+                report.cases
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A rule together with the policy file that defined it.
+#[derive(Clone, Debug)]
+pub struct SourcedRule {
+    pub source: String,
+    pub rule: RuleRef,
+}
+
+impl SourcedRule {
+    fn describe(&self) -> String {
+        format!("{}: {}", self.source, describe_rule(&self.rule))
+    }
+
+    fn prefix_rule(&self) -> Option<&PrefixRule> {
+        (self.rule.as_ref() as &dyn Any).downcast_ref::<PrefixRule>()
+    }
+
+    fn pattern_and_decision(&self) -> Option<(&PrefixPattern, Decision)> {
+        let rule_any = self.rule.as_ref() as &dyn Any;
+        if let Some(rule) = rule_any.downcast_ref::<PrefixRule>() {
+            Some((&rule.pattern, rule.decision))
+        } else {
+            rule_any
+                .downcast_ref::<ArgRule>()
+                .map(|rule| (&rule.pattern, rule.decision))
+        }
+    }
+}
+
+fn describe_rule(rule: &RuleRef) -> String {
+    let rule_any = rule.as_ref() as &dyn Any;
+    if let Some(rule) = rule_any.downcast_ref::<PrefixRule>() {
+        format!(
+            "prefix_rule {} -> {}",
+            rule.pattern.to_starlark(),
+            rule.decision.as_str()
+        )
+    } else if let Some(rule) = rule_any.downcast_ref::<ArgRule>() {
+        format!(
+            "arg_rule {} -> {}",
+            rule.pattern.to_starlark(),
+            rule.decision.as_str()
+        )
+    } else {
+        format!("{rule:?}")
+    }
+}
+
+/// Parses each file on its own so every rule can be traced back to its source. Rules are
+/// returned per file in program order, and in definition order within a program.
+pub fn load_sourced_rules(policy_paths: &[PathBuf]) -> Result<Vec<SourcedRule>> {
+    let mut rules = Vec::new();
+    for policy_path in policy_paths {
+        let contents = fs::read_to_string(policy_path)
+            .with_context(|| format!("failed to read policy at {}", policy_path.display()))?;
+        let identifier = policy_path.to_string_lossy().to_string();
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(&identifier, &contents)
+            .with_context(|| format!("failed to parse policy at {}", policy_path.display()))?;
+        rules.extend(sourced_rules(&identifier, &parser.build()));
+    }
+    Ok(rules)
+}
+
+pub fn sourced_rules(source: &str, policy: &Policy) -> Vec<SourcedRule> {
+    let mut programs: Vec<(&String, &Vec<RuleRef>)> = policy.rules().iter_all().collect();
+    programs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    programs
+        .into_iter()
+        .flat_map(|(_, rules)| rules.iter())
+        .map(|rule| SourcedRule {
+            source: source.to_string(),
+            rule: rule.clone(),
+        })
+        .collect()
+}
+
+/// What a command resolves to: the strictest matching rule, or the heuristics fallback when
+/// no rule matches.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+    Allow,
+    Prompt,
+    Forbidden,
+    Heuristics,
+}
+
+impl From<Decision> for Outcome {
+    fn from(decision: Decision) -> Self {
+        match decision {
+            Decision::Allow => Self::Allow,
+            Decision::Prompt => Self::Prompt,
+            Decision::Forbidden => Self::Forbidden,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Allow => "allow",
+            Self::Prompt => "prompt",
+            Self::Forbidden => "forbidden",
+            Self::Heuristics => "heuristics",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CaseCommand {
+    Tokens(Vec<String>),
+    Shell(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCase {
+    command: CaseCommand,
+    #[serde(default)]
+    decision: Option<Outcome>,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+}
+
+/// One command from the cases file. Cases without an expected decision only count towards
+/// coverage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+    /// 1-based line in the cases file.
+    pub line: usize,
+    pub command: Vec<String>,
+    pub expected: Option<Outcome>,
+    /// Directory the command runs in; defaults to `/workspace`, as for rule examples.
+    pub cwd: Option<PathBuf>,
+}
+
+impl TestCase {
+    pub(crate) fn context(&self) -> MatchContext {
+        match &self.cwd {
+            Some(cwd) => MatchContext {
+                cwd: Some(cwd.clone()),
+                writable_roots: vec![cwd.clone()],
+                read_only_paths: Vec::new(),
+            },
+            None => MatchContext::for_examples(),
+        }
+    }
+}
+
+/// Parses JSONL cases. Blank lines and lines starting with `#` or `//` are skipped.
+pub fn parse_cases(contents: &str) -> Result<Vec<TestCase>> {
+    let mut cases = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+        let raw: RawCase = serde_json::from_str(trimmed)
+            .with_context(|| format!("invalid case on line {line_number}"))?;
+        let command = match raw.command {
+            CaseCommand::Tokens(tokens) => tokens,
+            CaseCommand::Shell(command) => shlex::split(&command).with_context(|| {
+                format!("invalid shell syntax in command on line {line_number}")
+            })?,
+        };
+        if command.is_empty() {
+            bail!("empty command on line {line_number}");
+        }
+        cases.push(TestCase {
+            line: line_number,
+            command,
+            expected: raw.decision,
+            cwd: raw.cwd,
+        });
+    }
+    Ok(cases)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseMismatch {
+    pub line: usize,
+    pub command: String,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseRef {
+    pub line: usize,
+    pub command: String,
+}
+
+/// A pair of rules where `rule` never changes the outcome because of `by`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleOverlap {
+    pub rule: String,
+    pub by: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyTestReport {
+    pub cases: usize,
+    pub mismatches: Vec<CaseMismatch>,
+    /// Cases no rule matched, so the decision came from the heuristics fallback.
+    pub heuristics: Vec<CaseRef>,
+    /// Rules that matched none of the cases.
+    pub unused_rules: Vec<String>,
+    /// Identical prefixes with different decisions; the stricter rule always wins.
+    pub conflicts: Vec<RuleOverlap>,
+    /// Rules covered by a broader prefix rule whose decision is at least as strict.
+    pub shadowed: Vec<RuleOverlap>,
+}
+
+pub fn run_cases(rules: &[SourcedRule], cases: &[TestCase]) -> PolicyTestReport {
+    let mut report = PolicyTestReport {
+        cases: cases.len(),
+        ..Default::default()
+    };
+    let mut fired = vec![false; rules.len()];
+
+    for case in cases {
+        let context = case.context();
+        let mut decision: Option<Decision> = None;
+        for (idx, sourced) in rules.iter().enumerate() {
+            if sourced.rule.program() != case.command[0] {
+                continue;
+            }
+            if let Some(rule_match) = sourced.rule.matches_in_context(&case.command, &context) {
+                fired[idx] = true;
+                decision = decision.max(Some(rule_match.decision()));
+            }
+        }
+
+        let command = render_command(&case.command);
+        let actual = decision.map_or(Outcome::Heuristics, Outcome::from);
+        if actual == Outcome::Heuristics {
+            report.heuristics.push(CaseRef {
+                line: case.line,
+                command: command.clone(),
+            });
+        }
+        if let Some(expected) = case.expected
+            && expected != actual
+        {
+            report.mismatches.push(CaseMismatch {
+                line: case.line,
+                command,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    report.unused_rules = rules
+        .iter()
+        .zip(&fired)
+        .filter(|(_, fired)| !**fired)
+        .map(|(rule, _)| rule.describe())
+        .collect();
+    find_overlaps(rules, &mut report);
+    report
+}
+
+/// Only plain prefix rules can shadow others: an `arg_rule` matches a subset of what its
+/// prefix matches, so nothing is guaranteed to be covered by it.
+fn find_overlaps(rules: &[SourcedRule], report: &mut PolicyTestReport) {
+    for (i, broader) in rules.iter().enumerate() {
+        let Some(broader_rule) = broader.prefix_rule() else {
+            continue;
+        };
+        for (j, narrower) in rules.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let Some((pattern, decision)) = narrower.pattern_and_decision() else {
+                continue;
+            };
+            let same_prefix_rule =
+                narrower.prefix_rule().is_some() && broader_rule.pattern == *pattern;
+            let overlap = RuleOverlap {
+                rule: narrower.describe(),
+                by: broader.describe(),
+            };
+            if same_prefix_rule {
+                // Report each identical pair once.
+                if i > j {
+                    continue;
+                }
+                if broader_rule.decision == decision {
+                    report.shadowed.push(overlap);
+                } else {
+                    report.conflicts.push(overlap);
+                }
+            } else if broader_rule.pattern.covers(pattern) && broader_rule.decision >= decision {
+                report.shadowed.push(overlap);
+            }
+        }
+    }
+}
+
+pub(crate) fn render_command(command: &[String]) -> String {
+    try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+impl fmt::Display for PolicyTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let passed = self.cases - self.mismatches.len();
+        writeln!(f, "{passed}/{} cases passed", self.cases)?;
+        for mismatch in &self.mismatches {
+            writeln!(
+                f,
+                "  FAIL line {}: `{}` expected {}, got {}",
+                mismatch.line, mismatch.command, mismatch.expected, mismatch.actual
+            )?;
+        }
+        write_section(
+            f,
+            "Commands falling through to heuristics",
+            self.heuristics
+                .iter()
+                .map(|case| format!("line {}: `{}`", case.line, case.command)),
+        )?;
+        write_section(
+            f,
+            "Rules that never fired",
+            self.unused_rules.iter().cloned(),
+        )?;
+        write_section(
+            f,
+            "Conflicting rules",
+            self.conflicts
+                .iter()
+                .map(|overlap| format!("{} conflicts with {}", overlap.rule, overlap.by)),
+        )?;
+        write_section(
+            f,
+            "Shadowed rules",
+            self.shadowed
+                .iter()
+                .map(|overlap| format!("{} is shadowed by {}", overlap.rule, overlap.by)),
+        )
+    }
+}
+
+pub(crate) fn write_section(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    items: impl Iterator<Item = String>,
+) -> fmt::Result {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        return Ok(());
+    }
+    writeln!(f)?;
+    writeln!(f, "{title} ({}):", items.len())?;
+    for item in items {
+        writeln!(f, "  {item}")?;
+    }
+    Ok(())
+}
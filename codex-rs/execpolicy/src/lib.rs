@@ -2,19 +2,27 @@ pub mod amend;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
+pub mod execpolicytest;
+pub mod migrate;
 pub mod parser;
 pub mod policy;
 pub mod rule;
+pub mod suggest;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
+pub use amend::blocking_append_rule;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::Result;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use execpolicytest::ExecPolicyTestCommand;
+pub use migrate::ExecPolicyMigrateCommand;
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::Policy;
+pub use rule::MatchContext;
 pub use rule::Rule;
 pub use rule::RuleMatch;
 pub use rule::RuleRef;
+pub use suggest::ExecPolicySuggestCommand;
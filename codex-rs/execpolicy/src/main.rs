@@ -1,6 +1,10 @@
 use anyhow::Result;
 use clap::Parser;
 use codex_execpolicy::execpolicycheck::ExecPolicyCheckCommand;
+use codex_execpolicy::execpolicytest::ExecPolicyTestCommand;
+use codex_execpolicy::migrate::ExecPolicyMigrateCommand;
+use codex_execpolicy::suggest::ExecPolicySuggestCommand;
+use codex_utils_home_dir::find_codex_home;
 
 /// CLI for evaluating exec policies
 #[derive(Parser)]
@@ -8,11 +12,23 @@ use codex_execpolicy::execpolicycheck::ExecPolicyCheckCommand;
 enum Cli {
     /// Evaluate a command against a policy.
     Check(ExecPolicyCheckCommand),
+
+    /// Run a corpus of commands against policies and report coverage.
+    Test(ExecPolicyTestCommand),
+
+    /// Suggest prefix rules from past approval decisions.
+    Suggest(ExecPolicySuggestCommand),
+
+    /// Translate a legacy policy into rules and compare both engines' decisions.
+    Migrate(ExecPolicyMigrateCommand),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli {
         Cli::Check(cmd) => cmd.run(),
+        Cli::Test(cmd) => cmd.run(),
+        Cli::Suggest(cmd) => cmd.run(&find_codex_home()?),
+        Cli::Migrate(cmd) => cmd.run(),
     }
 }
@@ -0,0 +1,555 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_execpolicy_legacy::ArgMatcher;
+use codex_execpolicy_legacy::ArgType;
+use codex_execpolicy_legacy::ExecCall;
+use codex_execpolicy_legacy::ExecvChecker;
+use codex_execpolicy_legacy::MatchedExec;
+use codex_execpolicy_legacy::Opt;
+use codex_execpolicy_legacy::OptMeta;
+use codex_execpolicy_legacy::Policy as LegacyPolicy;
+use codex_execpolicy_legacy::PolicyParser as LegacyPolicyParser;
+use codex_execpolicy_legacy::ProgramSpec;
+use codex_execpolicy_legacy::get_default_policy;
+use serde::Serialize;
+
+use crate::Decision;
+use crate::MatchContext;
+use crate::Policy;
+use crate::PolicyParser;
+use crate::RuleMatch;
+use crate::RuleRef;
+use crate::execpolicytest::Outcome;
+use crate::execpolicytest::TestCase;
+use crate::execpolicytest::parse_cases;
+use crate::execpolicytest::render_command;
+use crate::execpolicytest::write_section;
+use crate::rule::ArgPattern;
+use crate::rule::ArgRule;
+use crate::rule::PathConstraint;
+use crate::rule::PathScope;
+use crate::rule::PatternToken;
+use crate::rule::PrefixPattern;
+use crate::rule::PrefixRule;
+
+/// Arguments for translating a legacy `define_program` policy into execpolicy rules.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyMigrateCommand {
+    /// Legacy policy to migrate. Defaults to the policy bundled with the legacy engine.
+    #[arg(long = "legacy", value_name = "PATH")]
+    pub legacy: Option<PathBuf>,
+
+    /// Write the migrated rules to this file instead of stdout.
+    #[arg(short = 'o', long = "output", value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Extra commands to compare, in the `execpolicy test` cases format. Expected decisions
+    /// are ignored.
+    #[arg(long = "cases", value_name = "PATH")]
+    pub cases: Option<PathBuf>,
+
+    /// Print the differential report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ExecPolicyMigrateCommand {
+    /// Migrate the policy and print the differential report. Without `--output`, the rules
+    /// go to stdout and the report to stderr.
+    pub fn run(&self) -> Result<()> {
+        let (source, legacy) = match &self.legacy {
+            Some(path) => {
+                let contents = fs::read_to_string(path).with_context(|| {
+                    format!("failed to read legacy policy at {}", path.display())
+                })?;
+                let source = path.to_string_lossy().to_string();
+                let policy = LegacyPolicyParser::new(&source, &contents)
+                    .parse()
+                    .map_err(starlark::Error::into_anyhow)
+                    .with_context(|| {
+                        format!("failed to parse legacy policy at {}", path.display())
+                    })?;
+                (source, policy)
+            }
+            None => (
+                "the default legacy policy".to_string(),
+                get_default_policy().map_err(starlark::Error::into_anyhow)?,
+            ),
+        };
+        let cases = match &self.cases {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .with_context(|| format!("failed to read cases at {}", path.display()))?;
+                parse_cases(&contents)
+                    .with_context(|| format!("failed to parse cases at {}", path.display()))?
+            }
+            None => Vec::new(),
+        };
+
+        let migration = migrate(&source, legacy, &cases)?;
+        let report = if self.json {
+            format!("{}\n", serde_json::to_string_pretty(&migration.report)?)
+        } else {
+            migration.report.to_string()
+        };
+        match &self.output {
+            Some(path) => {
+                fs::write(path, &migration.rules)
+                    .with_context(|| format!("failed to write rules to {}", path.display()))?;
+                print!("{report}");
+            }
+            None => {
+                print!("{}", migration.rules);
+                eprint!("{report}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The migrated rules file and how its decisions compare with the legacy engine.
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub rules: String,
+    pub report: MigrationReport,
+}
+
+/// A command the two engines decide differently.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Divergence {
+    pub command: String,
+    /// `allow` when the legacy engine verified the command as safe, `forbidden` when it
+    /// forbade it, and `heuristics` when it could not verify it.
+    pub legacy: Outcome,
+    pub migrated: Outcome,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    /// Program specs emitted as active rules.
+    pub migrated: usize,
+    /// Migrated specs the legacy policy allowed that became `prompt` rules, because the
+    /// rule drops checks the legacy spec made.
+    pub prompted: usize,
+    /// Program specs and policy-wide checks emitted as commented TODO stubs.
+    pub stubs: usize,
+    /// Commands run through both engines: every legacy example plus any extra cases.
+    pub commands: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} rules migrated ({} prompt instead of allow), {} left as TODO stubs",
+            self.migrated, self.prompted, self.stubs
+        )?;
+        writeln!(
+            f,
+            "{}/{} commands got the same decision from both engines",
+            self.commands - self.divergences.len(),
+            self.commands
+        )?;
+        write_section(
+            f,
+            "Diverging commands",
+            self.divergences.iter().map(|divergence| {
+                format!(
+                    "`{}`: legacy {}, migrated {}",
+                    divergence.command, divergence.legacy, divergence.migrated
+                )
+            }),
+        )
+    }
+}
+
+/// Translates `legacy` into execpolicy rules, checks that they parse, and runs both engines
+/// over the legacy examples plus `cases`.
+pub fn migrate(source: &str, legacy: LegacyPolicy, cases: &[TestCase]) -> Result<Migration> {
+    let mut rules = format!(
+        "# Migrated from {source} by `codex execpolicy migrate`.\n\
+         # TODO lines list checks the legacy policy made that these rules do not.\n"
+    );
+    let mut report = MigrationReport::default();
+
+    for spec in legacy.program_specs() {
+        let translation = translate_spec(spec);
+        if translation.stub_reason.is_some() {
+            report.stubs += 1;
+        } else {
+            report.migrated += 1;
+            if translation.prompted {
+                report.prompted += 1;
+            }
+        }
+        rules.push('\n');
+        rules.push_str(&translation.render());
+    }
+
+    for forbidden in legacy.forbidden_program_regexes() {
+        report.stubs += 1;
+        rules.push_str(&format!(
+            "\n# TODO: forbid_program_regex({}, {}) has no equivalent; add forbidden \
+             prefix_rule()s for the programs it should catch.\n",
+            quote(forbidden.regex.as_str()),
+            quote(&forbidden.reason)
+        ));
+    }
+    if !legacy.forbidden_substrings().is_empty() {
+        report.stubs += 1;
+        rules.push_str(&format!(
+            "\n# TODO: forbid_substrings({}) has no equivalent; add arg_rule()s with \
+             `any_arg = [\"*<substring>*\"]` and `decision = \"forbidden\"` per program.\n",
+            quote_list(legacy.forbidden_substrings())
+        ));
+    }
+
+    let mut parser = PolicyParser::new();
+    parser
+        .parse("migrated.codexpolicy", &rules)
+        .context("migrated rules failed to parse")?;
+    let migrated = parser.build();
+
+    let mut corpus: Vec<(Vec<String>, MatchContext)> = legacy_examples(&legacy)
+        .into_iter()
+        .map(|command| (command, MatchContext::for_examples()))
+        .collect();
+    corpus.extend(
+        cases
+            .iter()
+            .map(|case| (case.command.clone(), case.context())),
+    );
+    let mut seen = HashSet::new();
+    corpus.retain(|(command, context)| seen.insert((command.clone(), context.cwd.clone())));
+
+    let checker = ExecvChecker::new(legacy);
+    report.commands = corpus.len();
+    for (command, context) in &corpus {
+        let legacy_outcome = legacy_outcome(&checker, command, context);
+        let migrated_outcome = migrated_outcome(&migrated, command, context);
+        if legacy_outcome != migrated_outcome {
+            report.divergences.push(Divergence {
+                command: render_command(command),
+                legacy: legacy_outcome,
+                migrated: migrated_outcome,
+            });
+        }
+    }
+
+    Ok(Migration { rules, report })
+}
+
+/// Every `should_match` and `should_not_match` example in the legacy policy, as full argv.
+fn legacy_examples(legacy: &LegacyPolicy) -> Vec<Vec<String>> {
+    legacy
+        .program_specs()
+        .into_iter()
+        .flat_map(|spec| {
+            spec.should_match()
+                .iter()
+                .chain(spec.should_not_match())
+                .map(move |args| with_program(&spec.program, args))
+        })
+        .collect()
+}
+
+/// The legacy engine never prompts: commands are verified as safe, forbidden, or left to the
+/// caller. Writable paths are checked the way `ExecvChecker` does; reads are unrestricted.
+fn legacy_outcome(checker: &ExecvChecker, command: &[String], context: &MatchContext) -> Outcome {
+    let Some((program, args)) = command.split_first() else {
+        return Outcome::Heuristics;
+    };
+    let exec_call = ExecCall {
+        program: program.clone(),
+        args: args.to_vec(),
+    };
+    match checker.r#match(&exec_call) {
+        Ok(MatchedExec::Match { exec }) => {
+            let cwd = context.cwd.clone().map(PathBuf::into_os_string);
+            match checker.check(exec, &cwd, &[PathBuf::from("/")], &context.writable_roots) {
+                Ok(_) => Outcome::Allow,
+                Err(_) => Outcome::Heuristics,
+            }
+        }
+        Ok(MatchedExec::Forbidden { .. }) => Outcome::Forbidden,
+        Err(_) => Outcome::Heuristics,
+    }
+}
+
+fn migrated_outcome(policy: &Policy, command: &[String], context: &MatchContext) -> Outcome {
+    policy
+        .matches_for_command_in_context(command, context, None)
+        .iter()
+        .map(RuleMatch::decision)
+        .max()
+        .map_or(Outcome::Heuristics, Outcome::from)
+}
+
+/// One `define_program` call rendered as a rule, or as a commented stub when the legacy spec
+/// relies on a check the new format has no counterpart for.
+struct SpecTranslation {
+    program: String,
+    function: &'static str,
+    fields: Vec<(&'static str, String)>,
+    todos: Vec<String>,
+    stub_reason: Option<String>,
+    /// The legacy spec allowed the command but the rule is not equivalent to it.
+    prompted: bool,
+}
+
+impl SpecTranslation {
+    fn render(&self) -> String {
+        let mut out = format!("# Legacy spec for `{}`.\n", self.program);
+        if let Some(reason) = &self.stub_reason {
+            out.push_str(&format!("# TODO: not migrated: {reason}.\n"));
+        }
+        for todo in &self.todos {
+            out.push_str(&format!("# TODO: {todo}.\n"));
+        }
+        let comment = if self.stub_reason.is_some() { "# " } else { "" };
+        out.push_str(&format!("{comment}{}(\n", self.function));
+        for (name, value) in &self.fields {
+            out.push_str(&format!("{comment}    {name} = {value},\n"));
+        }
+        out.push_str(&format!("{comment})\n"));
+        out
+    }
+}
+
+fn translate_spec(spec: &ProgramSpec) -> SpecTranslation {
+    let mut todos = Vec::new();
+    let mut stub_reason = None;
+
+    // Leading literal arguments become part of the prefix; the rest only constrain arity.
+    let mut prefix = vec![spec.program.clone()];
+    let mut rest = spec.arg_patterns.as_slice();
+    while let Some((ArgMatcher::Literal(literal), tail)) = rest.split_first() {
+        prefix.push(literal.clone());
+        rest = tail;
+    }
+
+    let mut min_args = 0;
+    let mut max_args = Some(0);
+    let mut writes_files = false;
+    for matcher in rest {
+        match matcher {
+            ArgMatcher::Literal(literal) => {
+                todos.push(format!("a positional argument must be `{literal}`"));
+            }
+            ArgMatcher::PositiveInteger => {
+                todos.push("a positional argument must be a positive integer".to_string());
+            }
+            ArgMatcher::SedCommand => {
+                stub_reason = Some(
+                    "sed commands are only safe after the legacy engine vets them".to_string(),
+                );
+            }
+            ArgMatcher::WriteableFile => writes_files = true,
+            ArgMatcher::OpaqueNonFile
+            | ArgMatcher::ReadableFile
+            | ArgMatcher::ReadableFiles
+            | ArgMatcher::ReadableFilesOrCwd
+            | ArgMatcher::UnverifiedVarargs => {}
+        }
+        match matcher.cardinality().is_exact() {
+            Some(count) => {
+                min_args += count;
+                max_args = max_args.map(|max| max + count);
+            }
+            None => {
+                if matches!(matcher, ArgMatcher::ReadableFiles) {
+                    min_args += 1;
+                }
+                max_args = None;
+            }
+        }
+    }
+    if min_args > 1 {
+        todos.push(format!(
+            "legacy requires at least {min_args} positional arguments"
+        ));
+    }
+    match max_args {
+        Some(0) => todos.push("legacy accepts no positional arguments".to_string()),
+        Some(1) => todos.push("legacy accepts at most 1 positional argument".to_string()),
+        Some(max) => todos.push(format!("legacy accepts at most {max} positional arguments")),
+        None => {}
+    }
+
+    let mut options: Vec<&Opt> = spec.allowed_options.values().collect();
+    options.sort_by(|a, b| a.name().cmp(b.name()));
+    if options.is_empty() {
+        todos.push("legacy rejects every option".to_string());
+    } else {
+        let names: Vec<&str> = options.iter().map(|opt| opt.name()).collect();
+        todos.push(format!(
+            "legacy rejects options other than {}",
+            names.join(", ")
+        ));
+    }
+    for opt in &options {
+        match &opt.meta {
+            OptMeta::Value(ArgType::PositiveInteger) => {
+                todos.push(format!(
+                    "the value of {} must be a positive integer",
+                    opt.name()
+                ));
+            }
+            OptMeta::Value(ArgType::SedCommand) => {
+                stub_reason = Some(
+                    "sed commands are only safe after the legacy engine vets them".to_string(),
+                );
+            }
+            OptMeta::Value(ArgType::WriteableFile) => writes_files = true,
+            OptMeta::Flag | OptMeta::Value(_) => {}
+        }
+    }
+    let required: Vec<&str> = options
+        .iter()
+        .filter(|opt| opt.required)
+        .map(|opt| opt.name())
+        .collect();
+    let flags_present = if required.len() == 1 {
+        required
+    } else {
+        if !required.is_empty() {
+            todos.push(format!("legacy requires all of {}", required.join(", ")));
+        }
+        Vec::new()
+    };
+
+    let (decision, justification) = match spec.forbidden() {
+        Some(reason) => (Decision::Forbidden, Some(reason.to_string())),
+        None => (Decision::Allow, None),
+    };
+    let any_arg: Vec<&str> = if min_args > 0 { vec!["*"] } else { Vec::new() };
+    let paths = writes_files.then_some(PathConstraint::Within(PathScope::WritableRoots));
+
+    let pattern = PrefixPattern {
+        first: Arc::from(spec.program.as_str()),
+        rest: prefix[1..]
+            .iter()
+            .map(|token| PatternToken::Single(token.clone()))
+            .collect(),
+    };
+    let plain_prefix = any_arg.is_empty() && flags_present.is_empty() && paths.is_none();
+    let rule: RuleRef = if plain_prefix {
+        Arc::new(PrefixRule {
+            pattern,
+            decision,
+            justification: justification.clone(),
+        })
+    } else {
+        Arc::new(ArgRule {
+            pattern,
+            all_args: Vec::new(),
+            any_arg: any_arg
+                .iter()
+                .map(|glob| ArgPattern::Glob(glob.to_string()))
+                .collect(),
+            flags_present: flags_present.iter().map(ToString::to_string).collect(),
+            flags_absent: Vec::new(),
+            paths,
+            decision,
+            justification: justification.clone(),
+        })
+    };
+    // Keep the examples this rule agrees with; the others are a TODO for the reviewer.
+    let context = MatchContext::for_examples();
+    let mut matches = Vec::new();
+    let mut not_matches = Vec::new();
+    for args in spec.should_match() {
+        let command = with_program(&spec.program, args);
+        if rule.matches_in_context(&command, &context).is_some() {
+            matches.push(render_command(&command));
+        } else {
+            todos.push(format!(
+                "legacy accepts `{}` but this rule does not match it",
+                render_command(&command)
+            ));
+        }
+    }
+    for args in spec.should_not_match() {
+        let command = with_program(&spec.program, args);
+        if rule.matches_in_context(&command, &context).is_none() {
+            not_matches.push(render_command(&command));
+        } else {
+            todos.push(format!(
+                "legacy rejects `{}` but this rule matches it",
+                render_command(&command)
+            ));
+        }
+    }
+
+    // Only a rule that makes every check the legacy spec made may allow on its own; anything
+    // looser would allow commands the legacy engine rejected, e.g. an option outside the
+    // allowlist.
+    let prompted = decision == Decision::Allow && stub_reason.is_none() && !todos.is_empty();
+    let decision = if prompted {
+        todos.push("rule prompts instead of allowing until the checks above are added".to_string());
+        Decision::Prompt
+    } else {
+        decision
+    };
+
+    let mut fields = vec![
+        ("pattern", quote_list(&prefix)),
+        ("decision", quote(decision.as_str())),
+    ];
+    if !any_arg.is_empty() {
+        fields.push(("any_arg", quote_list(&any_arg)));
+    }
+    if !flags_present.is_empty() {
+        fields.push(("flags_present", quote_list(&flags_present)));
+    }
+    if paths.is_some() {
+        fields.push(("paths", quote("within_writable_roots")));
+    }
+    if !matches.is_empty() {
+        fields.push(("match", quote_list(&matches)));
+    }
+    if !not_matches.is_empty() {
+        fields.push(("not_match", quote_list(&not_matches)));
+    }
+    if let Some(justification) = &justification {
+        fields.push(("justification", quote(justification)));
+    }
+
+    SpecTranslation {
+        program: spec.program.clone(),
+        function: if plain_prefix {
+            "prefix_rule"
+        } else {
+            "arg_rule"
+        },
+        fields,
+        todos,
+        stub_reason,
+        prompted,
+    }
+}
+
+fn with_program(program: &str, args: &[String]) -> Vec<String> {
+    std::iter::once(program.to_string())
+        .chain(args.iter().cloned())
+        .collect()
+}
+
+fn quote(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn quote_list<S: AsRef<str>>(values: &[S]) -> String {
+    let quoted: Vec<String> = values.iter().map(|value| quote(value.as_ref())).collect();
+    format!("[{}]", quoted.join(", "))
+}
@@ -18,6 +18,9 @@ use std::sync::Arc;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::ArgPattern;
+use crate::rule::ArgRule;
+use crate::rule::PathConstraint;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -138,6 +141,86 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     }
 }
 
+fn parse_strings<'v>(field: &str, values: UnpackList<Value<'v>>) -> Result<Vec<String>> {
+    values
+        .items
+        .into_iter()
+        .map(|value| {
+            value.unpack_str().map(str::to_string).ok_or_else(|| {
+                Error::InvalidRule(format!(
+                    "{field} entries must be strings (got {})",
+                    value.get_type()
+                ))
+            })
+        })
+        .collect()
+}
+
+fn parse_arg_patterns<'v>(
+    field: &str,
+    values: Option<UnpackList<Value<'v>>>,
+) -> Result<Vec<ArgPattern>> {
+    values
+        .map(|values| parse_strings(field, values))
+        .transpose()?
+        .unwrap_or_default()
+        .iter()
+        .map(|raw| ArgPattern::parse(raw))
+        .collect()
+}
+
+fn parse_flags<'v>(field: &str, values: Option<UnpackList<Value<'v>>>) -> Result<Vec<String>> {
+    let flags = values
+        .map(|values| parse_strings(field, values))
+        .transpose()?
+        .unwrap_or_default();
+    match flags
+        .iter()
+        .find(|flag| !flag.starts_with('-') || flag.len() < 2)
+    {
+        Some(flag) => Err(Error::InvalidRule(format!(
+            "{field} entries must be flags starting with `-` (got {flag})"
+        ))),
+        None => Ok(flags),
+    }
+}
+
+fn parse_decision(decision: Option<&str>) -> Result<Decision> {
+    match decision {
+        Some(raw) => Decision::parse(raw),
+        None => Ok(Decision::Allow),
+    }
+}
+
+fn parse_justification(justification: Option<&str>) -> Result<Option<String>> {
+    match justification {
+        Some(raw) if raw.trim().is_empty() => Err(Error::InvalidRule(
+            "justification cannot be empty".to_string(),
+        )),
+        Some(raw) => Ok(Some(raw.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Splits a pattern into one `PrefixPattern` per alternative of its first token, since
+/// rules are keyed by program.
+fn prefix_patterns(pattern_tokens: &[PatternToken]) -> Result<Vec<PrefixPattern>> {
+    let (first_token, remaining_tokens) = pattern_tokens
+        .split_first()
+        .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+
+    let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
+
+    Ok(first_token
+        .alternatives()
+        .iter()
+        .map(|head| PrefixPattern {
+            first: Arc::from(head.as_str()),
+            rest: rest.clone(),
+        })
+        .collect())
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -215,18 +298,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         justification: Option<&'v str>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
-        let decision = match decision {
-            Some(raw) => Decision::parse(raw)?,
-            None => Decision::Allow,
-        };
-
-        let justification = match justification {
-            Some(raw) if raw.trim().is_empty() => {
-                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
-            }
-            Some(raw) => Some(raw.to_string()),
-            None => None,
-        };
+        let decision = parse_decision(decision)?;
+        let justification = parse_justification(justification)?;
 
         let pattern_tokens = parse_pattern(pattern)?;
 
@@ -239,21 +312,80 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
 
         let mut builder = policy_builder(eval);
 
-        let (first_token, remaining_tokens) = pattern_tokens
-            .split_first()
-            .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+        let rules: Vec<RuleRef> = prefix_patterns(&pattern_tokens)?
+            .into_iter()
+            .map(|pattern| {
+                Arc::new(PrefixRule {
+                    pattern,
+                    decision,
+                    justification: justification.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        validate_not_match_examples(&rules, &not_matches)?;
+        validate_match_examples(&rules, &matches)?;
 
-        let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
+        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+        Ok(NoneType)
+    }
 
-        let rules: Vec<RuleRef> = first_token
-            .alternatives()
-            .iter()
-            .map(|head| {
-                Arc::new(PrefixRule {
-                    pattern: PrefixPattern {
-                        first: Arc::from(head.as_str()),
-                        rest: rest.clone(),
-                    },
+    fn arg_rule<'v>(
+        pattern: UnpackList<Value<'v>>,
+        decision: Option<&'v str>,
+        all_args: Option<UnpackList<Value<'v>>>,
+        any_arg: Option<UnpackList<Value<'v>>>,
+        flags_present: Option<UnpackList<Value<'v>>>,
+        flags_absent: Option<UnpackList<Value<'v>>>,
+        paths: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = parse_decision(decision)?;
+        let justification = parse_justification(justification)?;
+
+        let pattern_tokens = parse_pattern(pattern)?;
+        let all_args = parse_arg_patterns("all_args", all_args)?;
+        let any_arg = parse_arg_patterns("any_arg", any_arg)?;
+        let flags_present = parse_flags("flags_present", flags_present)?;
+        let flags_absent = parse_flags("flags_absent", flags_absent)?;
+        let paths = paths.map(PathConstraint::parse).transpose()?;
+
+        if all_args.is_empty()
+            && any_arg.is_empty()
+            && flags_present.is_empty()
+            && flags_absent.is_empty()
+            && paths.is_none()
+        {
+            return Err(Error::InvalidRule(
+                "arg_rule needs at least one of all_args, any_arg, flags_present, \
+                 flags_absent or paths; use prefix_rule otherwise"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let matches: Vec<Vec<String>> =
+            r#match.map(parse_examples).transpose()?.unwrap_or_default();
+        let not_matches: Vec<Vec<String>> = not_match
+            .map(parse_examples)
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut builder = policy_builder(eval);
+
+        let rules: Vec<RuleRef> = prefix_patterns(&pattern_tokens)?
+            .into_iter()
+            .map(|pattern| {
+                Arc::new(ArgRule {
+                    pattern,
+                    all_args: all_args.clone(),
+                    any_arg: any_arg.clone(),
+                    flags_present: flags_present.clone(),
+                    flags_absent: flags_absent.clone(),
+                    paths,
                     decision,
                     justification: justification.clone(),
                 }) as RuleRef
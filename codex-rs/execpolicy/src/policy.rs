@@ -1,6 +1,7 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::MatchContext;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -66,6 +67,21 @@ impl Policy {
         commands: Commands,
         heuristics_fallback: &F,
     ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        self.check_multiple_in_context(commands, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Like [`Policy::check_multiple`], resolving path-constrained rules against `context`.
+    pub fn check_multiple_in_context<Commands, F>(
+        &self,
+        commands: Commands,
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
     where
         Commands: IntoIterator,
         Commands::Item: AsRef<[String]>,
@@ -74,7 +90,11 @@ impl Policy {
         let matched_rules: Vec<RuleMatch> = commands
             .into_iter()
             .flat_map(|command| {
-                self.matches_for_command(command.as_ref(), Some(heuristics_fallback))
+                self.matches_for_command_in_context(
+                    command.as_ref(),
+                    context,
+                    Some(heuristics_fallback),
+                )
             })
             .collect();
 
@@ -85,12 +105,26 @@ impl Policy {
         &self,
         cmd: &[String],
         heuristics_fallback: HeuristicsFallback<'_>,
+    ) -> Vec<RuleMatch> {
+        self.matches_for_command_in_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    pub fn matches_for_command_in_context(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: HeuristicsFallback<'_>,
     ) -> Vec<RuleMatch> {
         let mut matched_rules: Vec<RuleMatch> = match cmd.first() {
             Some(first) => self
                 .rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches_in_context(cmd, context))
+                        .collect()
+                })
                 .unwrap_or_default(),
             None => Vec::new(),
         };
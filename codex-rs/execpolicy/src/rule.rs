@@ -1,12 +1,17 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use regex_lite::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
 use std::any::Any;
 use std::fmt::Debug;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use wildmatch::WildMatch;
 
 /// Matches a single command token, either a fixed string or one of several allowed alternatives.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -54,6 +59,38 @@ impl PrefixPattern {
 
         Some(cmd[..pattern_length].to_vec())
     }
+
+    /// Whether every command matching `other` also matches `self`.
+    pub fn covers(&self, other: &PrefixPattern) -> bool {
+        self.first == other.first
+            && self.rest.len() <= other.rest.len()
+            && self
+                .rest
+                .iter()
+                .zip(other.rest.iter())
+                .all(|(mine, theirs)| {
+                    theirs
+                        .alternatives()
+                        .iter()
+                        .all(|alt| mine.alternatives().contains(alt))
+                })
+    }
+
+    /// Renders the pattern as a Starlark list, e.g. `["git", ["push", "fetch"]]`.
+    pub fn to_starlark(&self) -> String {
+        let quote = |token: &str| serde_json::Value::String(token.to_string()).to_string();
+        let tokens: Vec<String> = std::iter::once(quote(&self.first))
+            .chain(self.rest.iter().map(|token| match token {
+                PatternToken::Single(single) => quote(single),
+                PatternToken::Alts(alternatives) => {
+                    let alternatives: Vec<String> =
+                        alternatives.iter().map(|alt| quote(alt)).collect();
+                    format!("[{}]", alternatives.join(", "))
+                }
+            }))
+            .collect();
+        format!("[{}]", tokens.join(", "))
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -96,6 +133,12 @@ pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+
+    /// Like [`Rule::matches`], but with the facts about where the command runs. Rules that
+    /// only look at tokens can rely on the default implementation.
+    fn matches_in_context(&self, cmd: &[String], _context: &MatchContext) -> Option<RuleMatch> {
+        self.matches(cmd)
+    }
 }
 
 pub type RuleRef = Arc<dyn Rule>;
@@ -116,12 +159,278 @@ impl Rule for PrefixRule {
     }
 }
 
+/// Where a command runs, used by rules that constrain path arguments.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatchContext {
+    /// Directory that relative path arguments are resolved against.
+    pub cwd: Option<PathBuf>,
+    /// Absolute roots the command is allowed to write to.
+    pub writable_roots: Vec<PathBuf>,
+    /// Subpaths of `writable_roots` that must stay read-only.
+    pub read_only_paths: Vec<PathBuf>,
+}
+
+impl MatchContext {
+    /// Context used to validate `match` / `not_match` examples: the command runs in
+    /// `/workspace`, which is also the only writable root.
+    pub fn for_examples() -> Self {
+        let workspace = PathBuf::from("/workspace");
+        Self {
+            cwd: Some(workspace.clone()),
+            writable_roots: vec![workspace],
+            read_only_paths: Vec::new(),
+        }
+    }
+
+    /// Resolves `arg` against the cwd and normalizes `.` and `..` lexically. Returns `None`
+    /// for arguments whose location depends on the shell (`~`, `$VAR`) or when a relative
+    /// path has no cwd to resolve against.
+    fn resolve(&self, arg: &str) -> Option<PathBuf> {
+        if arg.starts_with('~') || arg.contains('$') {
+            return None;
+        }
+        let path = Path::new(arg);
+        let joined = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.cwd.as_ref()?.join(path)
+        };
+
+        let mut normalized = PathBuf::new();
+        for component in joined.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                other => normalized.push(other.as_os_str()),
+            }
+        }
+        Some(normalized)
+    }
+
+    fn is_within(&self, path: &Path, scope: PathScope) -> bool {
+        match scope {
+            PathScope::Cwd => self.cwd.as_ref().is_some_and(|cwd| path.starts_with(cwd)),
+            PathScope::WritableRoots => {
+                self.writable_roots
+                    .iter()
+                    .any(|root| path.starts_with(root))
+                    && !self.read_only_paths.iter().any(|ro| path.starts_with(ro))
+            }
+        }
+    }
+}
+
+/// Matches a single argument against a glob, or a regex when written as `re:<regex>`.
+#[derive(Clone, Debug)]
+pub enum ArgPattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl ArgPattern {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.strip_prefix("re:") {
+            Some(regex) => Regex::new(&format!("^(?:{regex})$"))
+                .map(Self::Regex)
+                .map_err(|err| Error::InvalidPattern(format!("invalid regex `{regex}`: {err}"))),
+            None if raw.is_empty() => Err(Error::InvalidPattern(
+                "argument glob cannot be empty".to_string(),
+            )),
+            None => Ok(Self::Glob(raw.to_string())),
+        }
+    }
+
+    fn matches(&self, arg: &str) -> bool {
+        match self {
+            Self::Glob(glob) => WildMatch::new(glob).matches(arg),
+            Self::Regex(regex) => regex.is_match(arg),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathScope {
+    Cwd,
+    WritableRoots,
+}
+
+/// Constraint on the path arguments of a command, resolved against a [`MatchContext`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathConstraint {
+    /// Every path argument lies inside the scope.
+    Within(PathScope),
+    /// At least one path argument lies outside the scope (or cannot be resolved).
+    Outside(PathScope),
+}
+
+impl PathConstraint {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "within_cwd" => Ok(Self::Within(PathScope::Cwd)),
+            "within_writable_roots" => Ok(Self::Within(PathScope::WritableRoots)),
+            "outside_cwd" => Ok(Self::Outside(PathScope::Cwd)),
+            "outside_writable_roots" => Ok(Self::Outside(PathScope::WritableRoots)),
+            other => Err(Error::InvalidRule(format!(
+                "paths must be one of within_cwd, within_writable_roots, outside_cwd, \
+                 outside_writable_roots (got {other})"
+            ))),
+        }
+    }
+
+    fn holds(&self, args: &[&str], context: &MatchContext) -> bool {
+        let inside = |arg: &&str| {
+            context
+                .resolve(arg)
+                .is_some_and(|path| context.is_within(&path, self.scope()))
+        };
+        match self {
+            Self::Within(_) => args.iter().all(inside),
+            Self::Outside(_) => !args.iter().all(inside),
+        }
+    }
+
+    fn scope(&self) -> PathScope {
+        match self {
+            Self::Within(scope) | Self::Outside(scope) => *scope,
+        }
+    }
+}
+
+/// A prefix rule with extra conditions on the rest of argv. Every condition that is set
+/// must hold for the rule to match.
+#[derive(Clone, Debug)]
+pub struct ArgRule {
+    pub pattern: PrefixPattern,
+    /// Every positional argument after the prefix must match one of these.
+    pub all_args: Vec<ArgPattern>,
+    /// At least one positional argument after the prefix must match one of these.
+    pub any_arg: Vec<ArgPattern>,
+    /// At least one of these flags must appear anywhere in argv.
+    pub flags_present: Vec<String>,
+    /// None of these flags may appear anywhere in argv.
+    pub flags_absent: Vec<String>,
+    pub paths: Option<PathConstraint>,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
+impl Rule for ArgRule {
+    fn program(&self) -> &str {
+        self.pattern.first.as_ref()
+    }
+
+    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+        self.matches_in_context(cmd, &MatchContext::default())
+    }
+
+    fn matches_in_context(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch> {
+        let matched_prefix = self.pattern.matches_prefix(cmd)?;
+        let flags = flag_tokens(&cmd[1..]);
+        let rest = &cmd[matched_prefix.len()..];
+        let positional = positional_args(rest);
+
+        let has_flag = |flag: &String| flags.iter().any(|token| token_has_flag(token, flag));
+        let any_pattern = |patterns: &[ArgPattern], arg: &str| {
+            patterns.iter().any(|pattern| pattern.matches(arg))
+        };
+
+        let conditions_hold = (self.all_args.is_empty()
+            || positional
+                .iter()
+                .all(|arg| any_pattern(&self.all_args, arg)))
+            && (self.any_arg.is_empty()
+                || positional.iter().any(|arg| any_pattern(&self.any_arg, arg)))
+            && (self.flags_present.is_empty() || self.flags_present.iter().any(has_flag))
+            && !self.flags_absent.iter().any(has_flag)
+            && self
+                .paths
+                .is_none_or(|constraint| constraint.holds(&path_candidates(rest), context));
+
+        conditions_hold.then(|| RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            decision: self.decision,
+            justification: self.justification.clone(),
+        })
+    }
+}
+
+/// Tokens that look like flags, up to a `--` separator.
+fn flag_tokens(args: &[String]) -> Vec<&str> {
+    args.iter()
+        .map(String::as_str)
+        .take_while(|token| *token != "--")
+        .filter(|token| token.starts_with('-') && *token != "-")
+        .collect()
+}
+
+/// Tokens that are not flags. Everything after `--` is positional; option values such as
+/// the `out.txt` in `-o out.txt` are treated as positional too.
+fn positional_args(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut after_separator = false;
+    for token in args {
+        if after_separator || token == "-" || !token.starts_with('-') {
+            positional.push(token.as_str());
+        } else if token == "--" {
+            after_separator = true;
+        }
+    }
+    positional
+}
+
+/// Arguments that may name a path: the positional ones (which include separate option
+/// values such as `-t /etc`) plus values attached to an option, as in
+/// `--target-directory=/etc` or `-t/etc`.
+fn path_candidates(args: &[String]) -> Vec<&str> {
+    let mut candidates = positional_args(args);
+    for token in flag_tokens(args) {
+        if let Some(long) = token.strip_prefix("--") {
+            if let Some((_, value)) = long.split_once('=') {
+                candidates.push(value);
+            }
+        } else if let Some(value) = token.get(2..)
+            && (value.contains('/') || value.starts_with('.') || value.starts_with('~'))
+        {
+            // A bundle of short flags never contains a path separator.
+            candidates.push(value);
+        }
+    }
+    candidates
+}
+
+/// Whether `token` sets `flag`: an exact match, `--flag=value`, or a short flag bundled with
+/// others (`-f` in `-fu`). Bundling errs on the side of reporting the flag as present.
+fn token_has_flag(token: &str, flag: &str) -> bool {
+    if token == flag {
+        return true;
+    }
+    if let Some(long) = flag.strip_prefix("--") {
+        return token
+            .strip_prefix("--")
+            .and_then(|rest| rest.strip_prefix(long))
+            .is_some_and(|rest| rest.starts_with('='));
+    }
+    let mut chars = flag.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('-'), Some(short), None) if short != '-' => {
+            token.starts_with('-') && !token.starts_with("--") && token[1..].contains(short)
+        }
+        _ => false,
+    }
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(rules: &[RuleRef], matches: &[Vec<String>]) -> Result<()> {
     let mut unmatched_examples = Vec::new();
 
+    let context = MatchContext::for_examples();
     for example in matches {
-        if rules.iter().any(|rule| rule.matches(example).is_some()) {
+        if rules
+            .iter()
+            .any(|rule| rule.matches_in_context(example, &context).is_some())
+        {
             continue;
         }
 
@@ -146,8 +455,12 @@ pub(crate) fn validate_not_match_examples(
     rules: &[RuleRef],
     not_matches: &[Vec<String>],
 ) -> Result<()> {
+    let context = MatchContext::for_examples();
     for example in not_matches {
-        if let Some(rule) = rules.iter().find(|rule| rule.matches(example).is_some()) {
+        if let Some(rule) = rules
+            .iter()
+            .find(|rule| rule.matches_in_context(example, &context).is_some())
+        {
             return Err(Error::ExampleDidMatch {
                 rule: format!("{rule:?}"),
                 example: try_join(example.iter().map(String::as_str))
@@ -0,0 +1,535 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use shlex::try_join;
+
+use crate::Decision;
+use crate::Policy;
+use crate::amend::blocking_append_rule;
+use crate::execpolicycheck::load_policies;
+use crate::rule::PatternToken;
+use crate::rule::PrefixPattern;
+use crate::rule::PrefixRule;
+use crate::rule::Rule;
+
+/// File inside `CODEX_HOME` where core records approval decisions.
+pub const APPROVAL_LOG_FILENAME: &str = "approvals.jsonl";
+
+/// Longest shape recorded for a command: the program plus up to two subcommand words.
+const MAX_SHAPE_TOKENS: usize = 3;
+
+const MAX_EXAMPLES: usize = 3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approved,
+    ApprovedForSession,
+    ApprovedWithAmendment,
+    Denied,
+    Abort,
+}
+
+impl ApprovalDecision {
+    pub fn is_approved(self) -> bool {
+        matches!(
+            self,
+            Self::Approved | Self::ApprovedForSession | Self::ApprovedWithAmendment
+        )
+    }
+}
+
+/// One line of the approval log. Scripts such as `bash -lc "a && b"` are logged as one
+/// record per plain command, all sharing the decision.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub ts: u64,
+    pub command: Vec<String>,
+    pub shape: Vec<String>,
+    pub cwd: PathBuf,
+    pub decision: ApprovalDecision,
+}
+
+/// The program followed by its leading subcommand-like words, e.g. `git commit -m wip` has
+/// the shape `git commit`. Flags, paths and other values end the shape.
+pub fn command_shape(command: &[String]) -> Vec<String> {
+    let Some((program, args)) = command.split_first() else {
+        return Vec::new();
+    };
+    std::iter::once(program.clone())
+        .chain(
+            args.iter()
+                .take_while(|token| is_subcommand_word(token))
+                .take(MAX_SHAPE_TOKENS - 1)
+                .cloned(),
+        )
+        .collect()
+}
+
+fn is_subcommand_word(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+}
+
+/// Reads the approval log, skipping lines that don't parse.
+pub fn read_approval_log(path: &Path) -> Result<Vec<ApprovalRecord>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", path.display()));
+        }
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Commands the agent ran in past sessions, recovered from shell tool calls in rollout
+/// files under `sessions_dir`. Shell scripts are split on `&&`, `||`, `;` and `|`.
+pub fn read_rollout_commands(sessions_dir: &Path) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut pending = vec![sessions_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "jsonl")
+                && let Ok(contents) = fs::read_to_string(&path)
+            {
+                commands.extend(
+                    contents
+                        .lines()
+                        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                        .flat_map(|line| rollout_line_commands(&line)),
+                );
+            }
+        }
+    }
+    commands
+}
+
+fn rollout_line_commands(line: &Value) -> Vec<Vec<String>> {
+    if line["type"] != "response_item" {
+        return Vec::new();
+    }
+    let payload = &line["payload"];
+    let command = match payload["type"].as_str() {
+        Some("local_shell_call") => string_array(&payload["action"]["command"]),
+        Some("function_call") => {
+            let arguments: Value = payload["arguments"]
+                .as_str()
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .unwrap_or_default();
+            match payload["name"].as_str() {
+                Some("shell" | "container.exec") => string_array(&arguments["command"]),
+                Some("shell_command") => arguments["command"].as_str().map(script_tokens),
+                Some("exec_command") => arguments["cmd"].as_str().map(script_tokens),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    command.map(split_plain_commands).unwrap_or_default()
+}
+
+fn string_array(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|token| token.as_str().map(str::to_string))
+        .collect()
+}
+
+fn script_tokens(script: &str) -> Vec<String> {
+    shlex::split(script).unwrap_or_default()
+}
+
+/// Unwraps `bash -lc <script>` and splits the tokens on shell control operators.
+fn split_plain_commands(command: Vec<String>) -> Vec<Vec<String>> {
+    let tokens = match command.as_slice() {
+        [shell, flag, script]
+            if matches!(shell.as_str(), "bash" | "zsh" | "sh")
+                && matches!(flag.as_str(), "-lc" | "-c") =>
+        {
+            script_tokens(script)
+        }
+        _ => command,
+    };
+    tokens
+        .split(|token| matches!(token.as_str(), "&&" | "||" | ";" | "|"))
+        .filter(|part| !part.is_empty())
+        .map(<[String]>::to_vec)
+        .collect()
+}
+
+/// A generalized prefix rule mined from past approvals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    pub pattern: PrefixPattern,
+    /// Approved prompts whose command this rule matches.
+    pub prompts_avoided: usize,
+    /// Commands from past sessions this rule matches.
+    pub rollout_matches: usize,
+    pub examples: Vec<Vec<String>>,
+}
+
+impl Suggestion {
+    /// Renders the suggestion as an `allow` prefix rule with its examples as `match`.
+    pub fn to_rule(&self) -> String {
+        let mut rule = format!(
+            "# Would have avoided {} prompt(s); matches {} command(s) from past sessions.\n",
+            self.prompts_avoided, self.rollout_matches
+        );
+        rule.push_str("prefix_rule(\n");
+        rule.push_str(&format!("    pattern = {},\n", self.pattern.to_starlark()));
+        rule.push_str("    decision = \"allow\",\n");
+        rule.push_str("    match = [\n");
+        for example in &self.examples {
+            let rendered =
+                try_join(example.iter().map(String::as_str)).unwrap_or_else(|_| example.join(" "));
+            rule.push_str(&format!("        {},\n", Value::String(rendered)));
+        }
+        rule.push_str("    ],\n)");
+        rule
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SuggestReport {
+    pub suggestions: Vec<Suggestion>,
+    /// Candidate patterns dropped because they would also allow a command the user denied.
+    pub rejected: Vec<String>,
+}
+
+/// Groups approved commands that no existing rule matches by shape, merges shapes that
+/// differ only in their last word into alternatives, and keeps candidates that match at
+/// least `min_prompts` approvals and no denials.
+pub fn suggest_rules(
+    policy: &Policy,
+    records: &[ApprovalRecord],
+    rollout_commands: &[Vec<String>],
+    min_prompts: usize,
+) -> SuggestReport {
+    let uncovered = |command: &Vec<String>| policy.matches_for_command(command, None).is_empty();
+    let approved: Vec<&ApprovalRecord> = records
+        .iter()
+        .filter(|record| record.decision.is_approved() && uncovered(&record.command))
+        .collect();
+    let denied: Vec<&Vec<String>> = records
+        .iter()
+        .filter(|record| !record.decision.is_approved())
+        .map(|record| &record.command)
+        .collect();
+
+    // (shape without its last word) -> alternatives for the last word.
+    let mut merged: BTreeMap<Vec<String>, BTreeSet<String>> = BTreeMap::new();
+    for record in &approved {
+        let shape = if record.shape.is_empty() {
+            command_shape(&record.command)
+        } else {
+            record.shape.clone()
+        };
+        if let Some((last, parent)) = shape.split_last() {
+            merged
+                .entry(parent.to_vec())
+                .or_default()
+                .insert(last.clone());
+        }
+    }
+
+    let candidates: Vec<PrefixPattern> = merged
+        .into_iter()
+        .flat_map(|(parent, last)| patterns_from_shapes(parent, last))
+        .collect();
+    // Drop candidates a broader candidate already covers.
+    let candidates: Vec<&PrefixPattern> = candidates
+        .iter()
+        .filter(|pattern| {
+            !candidates
+                .iter()
+                .any(|other| other != *pattern && other.covers(pattern))
+        })
+        .collect();
+
+    let mut report = SuggestReport::default();
+    for pattern in candidates {
+        let rule = PrefixRule {
+            pattern: pattern.clone(),
+            decision: Decision::Allow,
+            justification: None,
+        };
+        if denied.iter().any(|command| rule.matches(command).is_some()) {
+            report.rejected.push(pattern.to_starlark());
+            continue;
+        }
+
+        let matching: Vec<&Vec<String>> = approved
+            .iter()
+            .map(|record| &record.command)
+            .filter(|command| rule.matches(command).is_some())
+            .collect();
+        if matching.len() < min_prompts.max(1) {
+            continue;
+        }
+        let mut examples: Vec<Vec<String>> = Vec::new();
+        for command in &matching {
+            if examples.len() == MAX_EXAMPLES {
+                break;
+            }
+            if !examples.contains(command) {
+                examples.push((*command).clone());
+            }
+        }
+        report.suggestions.push(Suggestion {
+            pattern: pattern.clone(),
+            prompts_avoided: matching.len(),
+            rollout_matches: rollout_commands
+                .iter()
+                .filter(|command| rule.matches(command).is_some())
+                .count(),
+            examples,
+        });
+    }
+
+    report.suggestions.sort_by(|a, b| {
+        b.prompts_avoided
+            .cmp(&a.prompts_avoided)
+            .then_with(|| a.pattern.to_starlark().cmp(&b.pattern.to_starlark()))
+    });
+    report
+}
+
+/// One pattern for `parent` followed by any of `last`. One-word shapes become one pattern
+/// per program, since rules are keyed by their first token.
+fn patterns_from_shapes(parent: Vec<String>, last: BTreeSet<String>) -> Vec<PrefixPattern> {
+    let Some((first, rest)) = parent.split_first() else {
+        return last
+            .iter()
+            .map(|program| PrefixPattern {
+                first: Arc::from(program.as_str()),
+                rest: Vec::new().into(),
+            })
+            .collect();
+    };
+    let last: Vec<String> = last.into_iter().collect();
+    let last = match last.as_slice() {
+        [single] => PatternToken::Single(single.clone()),
+        _ => PatternToken::Alts(last),
+    };
+    vec![PrefixPattern {
+        first: Arc::from(first.as_str()),
+        rest: rest
+            .iter()
+            .cloned()
+            .map(PatternToken::Single)
+            .chain(std::iter::once(last))
+            .collect::<Vec<_>>()
+            .into(),
+    }]
+}
+
+/// Arguments for proposing prefix rules from past approval decisions.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicySuggestCommand {
+    /// Policy files already in effect; commands they match are ignored. Defaults to every
+    /// `*.rules` file in `$CODEX_HOME/rules`.
+    #[arg(short = 'r', long = "rules", value_name = "PATH")]
+    pub rules: Vec<PathBuf>,
+
+    /// Approval log to mine. Defaults to `$CODEX_HOME/approvals.jsonl`.
+    #[arg(long, value_name = "PATH")]
+    pub log: Option<PathBuf>,
+
+    /// Skip scanning rollout files in `$CODEX_HOME/sessions`.
+    #[arg(long)]
+    pub no_rollouts: bool,
+
+    /// Only suggest rules that would have avoided at least this many prompts.
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    pub min_prompts: usize,
+
+    /// Review each suggestion and append the accepted ones to this policy file.
+    #[arg(long, value_name = "PATH")]
+    pub write: Option<PathBuf>,
+
+    /// With `--write`, accept every suggestion without asking.
+    #[arg(long, requires = "write")]
+    pub yes: bool,
+}
+
+impl ExecPolicySuggestCommand {
+    pub fn run(&self, codex_home: &Path) -> Result<()> {
+        let rule_paths = if self.rules.is_empty() {
+            default_rule_paths(codex_home)
+        } else {
+            self.rules.clone()
+        };
+        let policy = load_policies(&rule_paths)?;
+        let log_path = self
+            .log
+            .clone()
+            .unwrap_or_else(|| codex_home.join(APPROVAL_LOG_FILENAME));
+        let records = read_approval_log(&log_path)?;
+        let rollout_commands = if self.no_rollouts {
+            Vec::new()
+        } else {
+            read_rollout_commands(&codex_home.join("sessions"))
+        };
+
+        let report = suggest_rules(&policy, &records, &rollout_commands, self.min_prompts);
+        for pattern in &report.rejected {
+            println!("# Skipped {pattern}: it would also allow a command you denied.");
+        }
+        if report.suggestions.is_empty() {
+            println!(
+                "No suggestions from {} logged approval(s) in {}.",
+                records.len(),
+                log_path.display()
+            );
+            return Ok(());
+        }
+
+        let Some(policy_path) = &self.write else {
+            for suggestion in &report.suggestions {
+                println!("{}\n", suggestion.to_rule());
+            }
+            return Ok(());
+        };
+
+        let stdin = std::io::stdin();
+        let mut lines = stdin.lock().lines();
+        let mut written = 0;
+        for suggestion in &report.suggestions {
+            let rule = suggestion.to_rule();
+            println!("{rule}\n");
+            if !self.yes {
+                print!("Add this rule to {}? [y/N/q] ", policy_path.display());
+                std::io::stdout().flush()?;
+                let answer = lines.next().transpose()?.unwrap_or_default();
+                match answer.trim() {
+                    "y" | "Y" | "yes" => {}
+                    "q" | "Q" => break,
+                    _ => continue,
+                }
+            }
+            blocking_append_rule(policy_path, &rule)?;
+            written += 1;
+        }
+        println!("Wrote {written} rule(s) to {}.", policy_path.display());
+        Ok(())
+    }
+}
+
+fn default_rule_paths(codex_home: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(codex_home.join("rules")) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rules"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolicyParser;
+    use pretty_assertions::assert_eq;
+
+    fn tokens(command: &str) -> Vec<String> {
+        shlex::split(command).unwrap_or_default()
+    }
+
+    fn record(command: &str, decision: ApprovalDecision) -> ApprovalRecord {
+        let command = tokens(command);
+        ApprovalRecord {
+            ts: 0,
+            shape: command_shape(&command),
+            command,
+            cwd: PathBuf::from("/workspace"),
+            decision,
+        }
+    }
+
+    #[test]
+    fn shapes_stop_at_flags_and_values() {
+        assert_eq!(
+            command_shape(&tokens("git commit -m wip")),
+            tokens("git commit")
+        );
+        assert_eq!(
+            command_shape(&tokens("npm run build --prod")),
+            tokens("npm run build")
+        );
+        assert_eq!(command_shape(&tokens("cat README.md")), tokens("cat"));
+    }
+
+    #[test]
+    fn merges_alternatives_and_skips_denied_and_covered_commands() {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", r#"prefix_rule(pattern = ["cargo", "fmt"])"#)
+            .expect("parse policy");
+        let policy = parser.build();
+
+        let records = vec![
+            record("git add -A", ApprovalDecision::Approved),
+            record("git commit -m wip", ApprovalDecision::Approved),
+            record("git add src", ApprovalDecision::ApprovedForSession),
+            record("cargo fmt --all", ApprovalDecision::Approved),
+            record("cargo fmt", ApprovalDecision::Approved),
+            record("rm -rf target", ApprovalDecision::Approved),
+            record("rm -rf target", ApprovalDecision::Approved),
+            record("rm -rf /", ApprovalDecision::Denied),
+        ];
+        let rollouts = vec![tokens("git add ."), tokens("git status")];
+
+        let report = suggest_rules(&policy, &records, &rollouts, 2);
+
+        assert_eq!(report.rejected, vec![r#"["rm"]"#.to_string()]);
+        assert_eq!(
+            report.suggestions,
+            vec![Suggestion {
+                pattern: PrefixPattern {
+                    first: Arc::from("git"),
+                    rest: vec![PatternToken::Alts(tokens("add commit"))].into(),
+                },
+                prompts_avoided: 3,
+                rollout_matches: 1,
+                examples: vec![
+                    tokens("git add -A"),
+                    tokens("git commit -m wip"),
+                    tokens("git add src")
+                ],
+            }]
+        );
+
+        // The rendered rule must load, which also validates its `match` examples.
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("suggested.rules", &report.suggestions[0].to_rule())
+            .expect("suggested rule should parse");
+    }
+}
@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -6,10 +7,22 @@ use anyhow::Result;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::execpolicytest::CaseMismatch;
+use codex_execpolicy::execpolicytest::CaseRef;
+use codex_execpolicy::execpolicytest::Outcome;
+use codex_execpolicy::execpolicytest::PolicyTestReport;
+use codex_execpolicy::execpolicytest::RuleOverlap;
+use codex_execpolicy::execpolicytest::parse_cases;
+use codex_execpolicy::execpolicytest::run_cases;
+use codex_execpolicy::execpolicytest::sourced_rules;
+use codex_execpolicy::migrate::Divergence;
+use codex_execpolicy::migrate::MigrationReport;
+use codex_execpolicy::migrate::migrate;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
 use codex_execpolicy::rule::PrefixRule;
@@ -551,3 +564,337 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn arg_rule_flags_are_found_anywhere_in_argv() -> Result<()> {
+    let policy_src = r#"
+arg_rule(
+    pattern = ["git", "push"],
+    flags_present = ["--force", "-f"],
+    decision = "forbidden",
+    match = ["git push origin main --force", "git push -uf origin main", "git push --force=yes"],
+    not_match = ["git push origin main", "git push -- --force", "git push --force-with-lease"],
+)
+arg_rule(
+    pattern = ["git", "push"],
+    flags_absent = ["--force", "-f"],
+    match = ["git push origin main"],
+    not_match = ["git push -f"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let forced = policy.check(&tokens(&["git", "push", "origin", "-f"]), &allow_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Forbidden,
+                justification: None,
+            }],
+        },
+        forced
+    );
+    Ok(())
+}
+
+#[test]
+fn arg_rule_matches_args_against_globs_and_regexes() -> Result<()> {
+    let policy_src = r#"
+arg_rule(
+    pattern = ["curl"],
+    all_args = ["http://localhost*", "re:https?://127\\.0\\.0\\.1(:\\d+)?(/.*)?"],
+    match = ["curl -s http://localhost:8080/health", "curl https://127.0.0.1:3000/api"],
+    not_match = ["curl https://example.com", "curl http://localhost https://example.com"],
+)
+arg_rule(
+    pattern = ["curl"],
+    any_arg = ["*.example.com*"],
+    decision = "prompt",
+    match = ["curl -s https://api.example.com/v1"],
+    not_match = ["curl http://localhost"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let remote = policy.check(&tokens(&["curl", "https://example.org"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                command: tokens(&["curl", "https://example.org"]),
+                decision: Decision::Prompt,
+            }],
+        },
+        remote
+    );
+    Ok(())
+}
+
+#[test]
+fn arg_rule_resolves_path_args_against_context() -> Result<()> {
+    let policy_src = r#"
+arg_rule(
+    pattern = ["rm"],
+    paths = "within_writable_roots",
+    match = ["rm -rf build", "rm /workspace/target/debug/app"],
+    not_match = ["rm ../secrets", "rm -rf ~", "rm $HOME/file", "rm /etc/passwd"],
+)
+arg_rule(
+    pattern = ["rm"],
+    paths = "outside_writable_roots",
+    decision = "forbidden",
+    match = ["rm -rf /"],
+    not_match = ["rm src/lib.rs"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let context = MatchContext {
+        cwd: Some(PathBuf::from("/repo/crate")),
+        writable_roots: vec![PathBuf::from("/repo")],
+        read_only_paths: vec![PathBuf::from("/repo/.git")],
+    };
+    let inside = policy.check_multiple_in_context(
+        [tokens(&["rm", "-r", "../docs/old"])],
+        &context,
+        &prompt_all,
+    );
+    assert_eq!(Decision::Allow, inside.decision);
+
+    let read_only =
+        policy.check_multiple_in_context([tokens(&["rm", "-rf", "../.git"])], &context, &allow_all);
+    assert_eq!(Decision::Forbidden, read_only.decision);
+
+    // Without a cwd, relative paths cannot be resolved and count as outside.
+    let no_context = policy.check(&tokens(&["rm", "notes.txt"]), &allow_all);
+    assert_eq!(Decision::Forbidden, no_context.decision);
+    Ok(())
+}
+
+#[test]
+fn arg_rule_checks_option_values_as_paths() -> Result<()> {
+    let policy_src = r#"
+arg_rule(
+    pattern = ["cp"],
+    paths = "within_writable_roots",
+    match = ["cp -r src /workspace/backup"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let context = MatchContext {
+        cwd: Some(PathBuf::from("/repo")),
+        writable_roots: vec![PathBuf::from("/repo")],
+        read_only_paths: Vec::new(),
+    };
+    let check = |command: &[&str]| {
+        policy
+            .check_multiple_in_context([tokens(command)], &context, &prompt_all)
+            .decision
+    };
+
+    assert_eq!(
+        Decision::Allow,
+        check(&["cp", "--target-directory=out", "a.txt"])
+    );
+    assert_eq!(
+        Decision::Prompt,
+        check(&["cp", "--target-directory=/etc", "a.txt"])
+    );
+    assert_eq!(Decision::Prompt, check(&["cp", "-t", "/etc", "a.txt"]));
+    assert_eq!(Decision::Prompt, check(&["cp", "-t/etc", "a.txt"]));
+    assert_eq!(Decision::Allow, check(&["cp", "-rf", "a", "b"]));
+    Ok(())
+}
+
+#[test]
+fn arg_rule_requires_a_constraint() {
+    let policy_src = r#"
+arg_rule(
+    pattern = ["rm"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("invalid rule: arg_rule needs at least one of")
+    );
+}
+
+#[test]
+fn policy_test_reports_mismatches_coverage_and_overlaps() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git"],
+    decision = "prompt",
+)
+prefix_rule(
+    pattern = ["git", "status"],
+)
+prefix_rule(
+    pattern = ["rm"],
+    decision = "forbidden",
+)
+prefix_rule(
+    pattern = ["rm"],
+    decision = "prompt",
+)
+prefix_rule(
+    pattern = ["cargo", "test"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let rules = sourced_rules("test.rules", &parser.build());
+
+    let cases = parse_cases(
+        r#"
+{"command": "git status", "decision": "prompt"}
+{"command": ["rm", "-rf", "target"], "decision": "allow"}
+# comments and blank lines are skipped
+{"command": "python script.py", "decision": "heuristics"}
+"#,
+    )?;
+    let report = run_cases(&rules, &cases);
+
+    assert_eq!(
+        PolicyTestReport {
+            cases: 3,
+            mismatches: vec![CaseMismatch {
+                line: 3,
+                command: "rm -rf target".to_string(),
+                expected: Outcome::Allow,
+                actual: Outcome::Forbidden,
+            }],
+            heuristics: vec![CaseRef {
+                line: 5,
+                command: "python script.py".to_string(),
+            }],
+            unused_rules: vec![r#"test.rules: prefix_rule ["cargo", "test"] -> allow"#.to_string()],
+            conflicts: vec![RuleOverlap {
+                rule: r#"test.rules: prefix_rule ["rm"] -> prompt"#.to_string(),
+                by: r#"test.rules: prefix_rule ["rm"] -> forbidden"#.to_string(),
+            }],
+            shadowed: vec![RuleOverlap {
+                rule: r#"test.rules: prefix_rule ["git", "status"] -> allow"#.to_string(),
+                by: r#"test.rules: prefix_rule ["git"] -> prompt"#.to_string(),
+            }],
+        },
+        report
+    );
+    Ok(())
+}
+
+#[test]
+fn migrate_translates_legacy_specs_and_reports_divergences() -> Result<()> {
+    let legacy_src = r#"
+define_program(
+    program="cp",
+    options=[flag("-r")],
+    args=[ARG_RFILES, ARG_WFILE],
+    should_match=[["foo", "bar"]],
+    should_not_match=[["foo"]],
+)
+
+define_program(
+    program="sed",
+    args=[ARG_SED_COMMAND, ARG_RFILES],
+)
+
+forbid_program_regex("^rm$", "no rm")
+"#;
+    let legacy = codex_execpolicy_legacy::PolicyParser::new("test.policy", legacy_src)
+        .parse()
+        .map_err(starlark::Error::into_anyhow)?;
+    let cases = parse_cases(
+        r#"
+{"command": "cp /etc/passwd out"}
+{"command": "cp -f foo bar"}
+{"command": "rm -rf build"}
+"#,
+    )?;
+
+    let migration = migrate("test.policy", legacy, &cases)?;
+
+    assert_eq!(
+        r#"# Migrated from test.policy by `codex execpolicy migrate`.
+# TODO lines list checks the legacy policy made that these rules do not.
+
+# Legacy spec for `cp`.
+# TODO: legacy requires at least 2 positional arguments.
+# TODO: legacy rejects options other than -r.
+# TODO: legacy rejects `cp foo` but this rule matches it.
+# TODO: rule prompts instead of allowing until the checks above are added.
+arg_rule(
+    pattern = ["cp"],
+    decision = "prompt",
+    any_arg = ["*"],
+    paths = "within_writable_roots",
+    match = ["cp foo bar"],
+)
+
+# Legacy spec for `sed`.
+# TODO: not migrated: sed commands are only safe after the legacy engine vets them.
+# TODO: legacy requires at least 2 positional arguments.
+# TODO: legacy rejects every option.
+# arg_rule(
+#     pattern = ["sed"],
+#     decision = "allow",
+#     any_arg = ["*"],
+# )
+
+# TODO: forbid_program_regex("^rm$", "no rm") has no equivalent; add forbidden prefix_rule()s for the programs it should catch.
+"#,
+        migration.rules
+    );
+    assert_eq!(
+        MigrationReport {
+            migrated: 1,
+            prompted: 1,
+            stubs: 2,
+            commands: 5,
+            divergences: vec![
+                Divergence {
+                    command: "cp foo bar".to_string(),
+                    legacy: Outcome::Allow,
+                    migrated: Outcome::Prompt,
+                },
+                Divergence {
+                    command: "cp foo".to_string(),
+                    legacy: Outcome::Heuristics,
+                    migrated: Outcome::Prompt,
+                },
+                Divergence {
+                    command: "cp /etc/passwd out".to_string(),
+                    legacy: Outcome::Allow,
+                    migrated: Outcome::Heuristics,
+                },
+                Divergence {
+                    command: "cp -f foo bar".to_string(),
+                    legacy: Outcome::Heuristics,
+                    migrated: Outcome::Prompt,
+                },
+                Divergence {
+                    command: "rm -rf build".to_string(),
+                    legacy: Outcome::Forbidden,
+                    migrated: Outcome::Heuristics,
+                },
+            ],
+        },
+        migration.report
+    );
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Read-only and denied paths inside the writable workspace.
+//!
+//! Landlock can only grant access, so it cannot take writes away from a path beneath a writable
+//! root without also taking them from the directories around it. Carve-outs are enforced with
+//! mounts in a private mount namespace instead: read-only paths are bind-mounted read-only over
+//! themselves, denied directories are covered by an empty read-only tmpfs, and denied files by
+//! an empty unreadable file.
+//!
+//! The mounts are owned by a user namespace in which the command, once exec'd under its own
+//! uid, holds no capabilities, so it cannot unmount them; Landlock additionally forbids mount
+//! changes to sandboxed threads.
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fs::OpenOptions;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::user_namespace::unshare_as_current_user;
+
+/// Not exported by libc for every target.
+const ST_RELATIME: libc::c_ulong = 4096;
+
+/// Moves this process into a private mount namespace with `read_only_subpaths` mounted
+/// read-only and `deny_read_paths` hidden. Does nothing when none of them exist. Must be called
+/// while the process is still single-threaded, before Landlock is applied.
+pub(crate) fn mount_carve_outs(
+    read_only_subpaths: &[AbsolutePathBuf],
+    deny_read_paths: &[AbsolutePathBuf],
+) -> io::Result<()> {
+    let read_only: Vec<PathBuf> = read_only_subpaths
+        .iter()
+        .filter_map(existing_canonical)
+        .collect();
+    let (deny_dirs, deny_files): (Vec<PathBuf>, Vec<PathBuf>) = deny_read_paths
+        .iter()
+        .filter_map(existing_canonical)
+        .partition(|path| path.is_dir());
+    if read_only.is_empty() && deny_dirs.is_empty() && deny_files.is_empty() {
+        return Ok(());
+    }
+
+    unshare_as_current_user(libc::CLONE_NEWNS)?;
+    // Keep the mounts below from propagating back to the host.
+    mount(None, c"/", None, libc::MS_REC | libc::MS_PRIVATE, None)?;
+
+    // Files first: covering them needs a scratch directory, which may sit below another
+    // carve-out.
+    if !deny_files.is_empty() {
+        cover_files(&deny_files)?;
+    }
+    for path in &read_only {
+        let path = c_path(path)?;
+        bind_read_only(&path, &path)?;
+    }
+    for dir in &deny_dirs {
+        mount(
+            Some(c"tmpfs"),
+            &c_path(dir)?,
+            Some(c"tmpfs"),
+            libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+            Some(c"mode=000,size=4k"),
+        )?;
+    }
+    Ok(())
+}
+
+/// Only a file can be mounted over a file. An unreadable empty file is created on a scratch
+/// tmpfs, bound over each of `files`, and the scratch tmpfs is detached again; the binds keep
+/// it alive.
+fn cover_files(files: &[PathBuf]) -> io::Result<()> {
+    let scratch = std::env::temp_dir().join(format!("codex-linux-sandbox-{}", std::process::id()));
+    std::fs::create_dir(&scratch)?;
+    let result = bind_empty_file_over(&scratch, files);
+    let _ = std::fs::remove_dir(&scratch);
+    result
+}
+
+fn bind_empty_file_over(scratch: &Path, files: &[PathBuf]) -> io::Result<()> {
+    let scratch_c = c_path(scratch)?;
+    mount(
+        Some(c"tmpfs"),
+        &scratch_c,
+        Some(c"tmpfs"),
+        libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+        Some(c"size=4k"),
+    )?;
+    let empty = scratch.join("empty");
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o000)
+        .open(&empty)?;
+    let empty = c_path(&empty)?;
+    for file in files {
+        bind_read_only(&empty, &c_path(file)?)?;
+    }
+    // SAFETY: `scratch_c` is a valid NUL-terminated path.
+    if unsafe { libc::umount2(scratch_c.as_ptr(), libc::MNT_DETACH) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Bind-mounts `source` over `target` and makes the new mount read-only.
+fn bind_read_only(source: &CStr, target: &CStr) -> io::Result<()> {
+    mount(
+        Some(source),
+        target,
+        None,
+        libc::MS_BIND | libc::MS_REC,
+        None,
+    )?;
+    // Inside a user namespace, the remount must keep the flags the mount is locked with.
+    let locked = locked_mount_flags(target)?;
+    mount(
+        None,
+        target,
+        None,
+        libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY | locked,
+        None,
+    )
+}
+
+fn locked_mount_flags(path: &CStr) -> io::Result<libc::c_ulong> {
+    // SAFETY: `statvfs` is plain data, for which all zeroes is a valid value.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid NUL-terminated path and `stat` a valid out pointer.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let flags = [
+        (libc::ST_NOSUID, libc::MS_NOSUID),
+        (libc::ST_NODEV, libc::MS_NODEV),
+        (libc::ST_NOEXEC, libc::MS_NOEXEC),
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (ST_RELATIME, libc::MS_RELATIME),
+    ];
+    Ok(flags
+        .into_iter()
+        .filter(|(st_flag, _)| stat.f_flag & st_flag != 0)
+        .fold(0, |mount_flags, (_, ms_flag)| mount_flags | ms_flag))
+}
+
+fn mount(
+    source: Option<&CStr>,
+    target: &CStr,
+    fstype: Option<&CStr>,
+    flags: libc::c_ulong,
+    data: Option<&CStr>,
+) -> io::Result<()> {
+    let ptr = |value: Option<&CStr>| value.map_or(std::ptr::null(), CStr::as_ptr);
+    // SAFETY: every pointer is either null or a valid NUL-terminated string.
+    let rc = unsafe {
+        libc::mount(
+            ptr(source),
+            target.as_ptr(),
+            ptr(fstype),
+            flags,
+            ptr(data).cast(),
+        )
+    };
+    if rc != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("failed to mount over {}: {err}", target.to_string_lossy()),
+        ));
+    }
+    Ok(())
+}
+
+fn c_path(path: &Path) -> io::Result<CString> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+/// Mounts work on resolved paths. Carve-outs that do not exist yet are skipped rather than
+/// locking down the directory they would be created in.
+fn existing_canonical(path: &AbsolutePathBuf) -> Option<PathBuf> {
+    path.as_path().canonicalize().ok()
+}
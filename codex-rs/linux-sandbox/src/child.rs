@@ -0,0 +1,32 @@
+//! Waiting for the command from a process that forked it and stays behind.
+
+use std::io;
+
+/// Waits for `child` and returns its wait status and resource usage.
+pub(crate) fn wait_for_child(child: libc::pid_t) -> Option<(libc::c_int, libc::rusage)> {
+    let mut status = 0;
+    // SAFETY: `rusage` is plain data, for which all zeroes is a valid value.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: `status` and `usage` are valid out pointers.
+        let rc = unsafe { libc::wait4(child, &mut status, 0, &mut usage) };
+        if rc == child {
+            return Some((status, usage));
+        }
+        if rc < 0 && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            return None;
+        }
+    }
+}
+
+/// Converts a wait status into an exit code, using the shell's `128 + signal` convention for
+/// signals.
+pub(crate) fn exit_code(status: libc::c_int) -> i32 {
+    if libc::WIFEXITED(status) {
+        libc::WEXITSTATUS(status)
+    } else if libc::WIFSIGNALED(status) {
+        128 + libc::WTERMSIG(status)
+    } else {
+        1
+    }
+}
@@ -25,17 +25,28 @@ use seccompiler::SeccompRule;
 use seccompiler::TargetArch;
 use seccompiler::apply_filter;
 
+use crate::carve_outs::mount_carve_outs;
+
 /// Apply sandbox policies inside this thread so only the child inherits
 /// them, not the entire CLI process.
+///
+/// `proxied_network` is set when the thread is already confined to a network
+/// namespace whose only way out is the Codex network proxy.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    proxied_network: bool,
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        install_network_seccomp_filter_on_current_thread(proxied_network)?;
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
+        // Mounts must be in place before Landlock forbids changing them.
+        mount_carve_outs(
+            sandbox_policy.get_read_only_subpaths(),
+            sandbox_policy.get_deny_read_paths(),
+        )?;
         let writable_roots = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
@@ -44,9 +55,6 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
         install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
@@ -86,7 +94,17 @@ fn install_filesystem_landlock_rules_on_current_thread(
 
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+///
+/// With `proxied_network`, IPv4/IPv6 sockets may be created and connected
+/// instead, so the command can reach the proxy relay on loopback; the network
+/// namespace keeps them from going anywhere else. Unix sockets live on the
+/// host filesystem, which the namespace does not hide, and seccomp cannot see
+/// the address passed to `connect`, so `socket(AF_UNIX)` is denied in this
+/// mode. `socketpair` still works. Binding, listening and accepting stay
+/// blocked either way.
+fn install_network_seccomp_filter_on_current_thread(
+    proxied_network: bool,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -95,34 +113,58 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
+    if !proxied_network {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+    }
     deny_syscall(libc::SYS_accept);
     deny_syscall(libc::SYS_accept4);
     deny_syscall(libc::SYS_bind);
     deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmmsg);
     // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
     // with their socketpair + child processes for sub-proc management
     // deny_syscall(libc::SYS_recvfrom);
     deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
 
-    // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
-    let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
-        0, // first argument (domain)
-        SeccompCmpArgLen::Dword,
-        SeccompCmpOp::Ne,
-        libc::AF_UNIX as u64,
-    )?])?;
+    // For `socket` we allow the domains below and deny everything else. A rule
+    // matches (and denies) only when all of its conditions hold.
+    let allowed_domains: &[i32] = if proxied_network {
+        &[libc::AF_INET, libc::AF_INET6]
+    } else {
+        &[libc::AF_UNIX]
+    };
+    let domain_rule = SeccompRule::new(
+        allowed_domains
+            .iter()
+            .map(|domain| {
+                SeccompCondition::new(
+                    0, // first argument (domain)
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::Ne,
+                    *domain as u64,
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+    )?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
-    rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
+    rules.insert(libc::SYS_socket, vec![domain_rule]);
+    // socketpair only ever creates local sockets; keep it to AF_UNIX.
+    rules.insert(
+        libc::SYS_socketpair,
+        vec![SeccompRule::new(vec![SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?])?],
+    );
 
     let filter = SeccompFilter::new(
         rules,
@@ -1,7 +1,17 @@
 #[cfg(target_os = "linux")]
+mod carve_outs;
+#[cfg(target_os = "linux")]
+mod child;
+#[cfg(target_os = "linux")]
 mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
+#[cfg(target_os = "linux")]
+mod proxy_bridge;
+#[cfg(target_os = "linux")]
+mod resource_limits;
+#[cfg(target_os = "linux")]
+mod user_namespace;
 
 #[cfg(target_os = "linux")]
 pub fn run_main() -> ! {
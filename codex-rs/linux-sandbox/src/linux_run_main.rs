@@ -3,6 +3,9 @@ use std::ffi::CString;
 use std::path::PathBuf;
 
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::proxy_bridge::enter_proxied_network;
+use crate::resource_limits::ResourceLimitArgs;
+use crate::resource_limits::enforce_resource_limits;
 
 #[derive(Debug, Parser)]
 pub struct LandlockCommand {
@@ -14,6 +17,14 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Unix socket of the Codex network proxy. When set, the command gets
+    /// network access only through that proxy.
+    #[arg(long = "network-proxy-socket")]
+    pub network_proxy_socket: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub resource_limits: ResourceLimitArgs,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,10 +34,32 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        network_proxy_socket,
+        resource_limits,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
+    if !resource_limits.is_empty()
+        && let Err(e) = enforce_resource_limits(&resource_limits)
+    {
+        panic!("error applying resource limits: {e:?}");
+    }
+
+    let proxied_network = match network_proxy_socket {
+        Some(socket) if !sandbox_policy.has_full_network_access() => {
+            if let Err(e) = enter_proxied_network(&socket) {
+                panic!("error setting up proxied network: {e:?}");
+            }
+            true
+        }
+        _ => false,
+    };
+
+    if let Err(e) = apply_sandbox_policy_to_current_thread(
+        &sandbox_policy,
+        &sandbox_policy_cwd,
+        proxied_network,
+    ) {
         panic!("error running landlock: {e:?}");
     }
 
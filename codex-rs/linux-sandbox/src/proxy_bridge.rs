@@ -0,0 +1,145 @@
+//! Network access through the Codex network proxy.
+//!
+//! The command runs in a fresh network namespace whose only interface is loopback, so it cannot
+//! reach anything directly. This process listens on a loopback port inside that namespace and
+//! relays each connection to the proxy's Unix socket, which is a filesystem object and therefore
+//! reachable from any network namespace. The command finds the relay through the usual
+//! `HTTP_PROXY`-style environment variables.
+
+use std::io;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::child::exit_code;
+use crate::child::wait_for_child;
+use crate::user_namespace::unshare_as_current_user;
+
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+
+/// Moves this process into a private network namespace and forks.
+///
+/// Returns in the child, which should go on to sandbox itself and exec the command. The parent
+/// relays connections to `proxy_socket` until the child exits, then exits with the child's
+/// status. Must be called while the process is still single-threaded.
+pub(crate) fn enter_proxied_network(proxy_socket: &Path) -> io::Result<()> {
+    // SAFETY: plain syscall.
+    let parent_pid = unsafe { libc::getpid() };
+
+    // A new user namespace gives us the privileges to configure the new network namespace
+    // without being root.
+    unshare_as_current_user(libc::CLONE_NEWNET)?;
+    bring_up_loopback()?;
+
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    let proxy_url = format!("http://{}", listener.local_addr()?);
+
+    // SAFETY: the process is single-threaded, so the child gets a consistent copy of it.
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            drop(listener);
+            // SAFETY: plain syscalls. If the relay is already gone, so is our network access.
+            unsafe {
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+                if libc::getppid() != parent_pid {
+                    libc::_exit(1);
+                }
+            }
+            for var in PROXY_ENV_VARS {
+                // SAFETY: the child is single-threaded.
+                unsafe { std::env::set_var(var, &proxy_url) };
+            }
+            for var in ["NO_PROXY", "no_proxy"] {
+                // SAFETY: the child is single-threaded.
+                unsafe { std::env::remove_var(var) };
+            }
+            Ok(())
+        }
+        child => {
+            let proxy_socket = proxy_socket.to_path_buf();
+            std::thread::spawn(move || relay_connections(listener, proxy_socket));
+            std::process::exit(wait_for_child(child).map_or(1, |(status, _)| exit_code(status)));
+        }
+    }
+}
+
+fn bring_up_loopback() -> io::Result<()> {
+    // SAFETY: plain syscall; the result is checked before use.
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fd` is a freshly opened descriptor that nothing else owns.
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // SAFETY: `ifreq` is plain old data, and all zeroes is a valid value.
+    let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in request.ifr_name.iter_mut().zip(b"lo") {
+        *dst = *src as libc::c_char;
+    }
+    // SAFETY: `request` is a valid `ifreq` for both ioctls, and `ifru_flags` is the member
+    // SIOCGIFFLAGS fills in.
+    unsafe {
+        if libc::ioctl(
+            socket.as_raw_fd(),
+            libc::SIOCGIFFLAGS as libc::Ioctl,
+            &mut request,
+        ) < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+        request.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+        if libc::ioctl(
+            socket.as_raw_fd(),
+            libc::SIOCSIFFLAGS as libc::Ioctl,
+            &request,
+        ) < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn relay_connections(listener: TcpListener, proxy_socket: PathBuf) {
+    for client in listener.incoming() {
+        let Ok(client) = client else {
+            continue;
+        };
+        let proxy_socket = proxy_socket.clone();
+        std::thread::spawn(move || {
+            // Dropping the client without a response makes the command see a reset connection.
+            if let Ok(proxy) = UnixStream::connect(&proxy_socket) {
+                let _ = relay(client, proxy);
+            }
+        });
+    }
+}
+
+fn relay(client: TcpStream, proxy: UnixStream) -> io::Result<()> {
+    let mut client_reader = client.try_clone()?;
+    let mut proxy_writer = proxy.try_clone()?;
+    let upload = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_reader, &mut proxy_writer);
+        let _ = proxy_writer.shutdown(Shutdown::Write);
+    });
+    let (mut proxy_reader, mut client_writer) = (proxy, client);
+    let _ = io::copy(&mut proxy_reader, &mut client_writer);
+    let _ = client_writer.shutdown(Shutdown::Write);
+    let _ = upload.join();
+    Ok(())
+}
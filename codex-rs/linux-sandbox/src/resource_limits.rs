@@ -0,0 +1,338 @@
+//! Per-command resource limits.
+//!
+//! CPU time, open files and file size are plain rlimits. Memory and process count apply to the
+//! whole command tree when the helper can create a cgroup v2 child of the cgroup it runs in with
+//! the `memory` and `pids` controllers enabled; otherwise they fall back to `RLIMIT_DATA` (per
+//! process) and `RLIMIT_NPROC` (per user). This process forks, stays behind to see how the
+//! command ended, and reports the limit that stopped it on stderr.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::protocol::ResourceLimitKind;
+use codex_core::resource_limits::RESOURCE_LIMIT_EXCEEDED_PREFIX;
+
+use crate::child::exit_code;
+use crate::child::wait_for_child;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+/// Prefix of the cgroups this helper creates.
+const CGROUP_PREFIX: &str = "codex-sandbox-";
+const MIB: libc::rlim_t = 1024 * 1024;
+
+#[cfg(target_env = "gnu")]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(not(target_env = "gnu"))]
+type RlimitResource = libc::c_int;
+
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub struct ResourceLimitArgs {
+    /// Memory the command and its children may use together, in MiB.
+    #[arg(long = "max-memory-mb")]
+    pub max_memory_mb: Option<libc::rlim_t>,
+
+    /// CPU time each process may consume, in seconds.
+    #[arg(long = "max-cpu-seconds")]
+    pub max_cpu_seconds: Option<libc::rlim_t>,
+
+    /// Processes (and threads) the command may have running at once.
+    #[arg(long = "max-processes")]
+    pub max_processes: Option<libc::rlim_t>,
+
+    /// File descriptors each process may have open.
+    #[arg(long = "max-open-files")]
+    pub max_open_files: Option<libc::rlim_t>,
+
+    /// Largest file a process may write, in MiB.
+    #[arg(long = "max-file-size-mb")]
+    pub max_file_size_mb: Option<libc::rlim_t>,
+}
+
+impl ResourceLimitArgs {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.max_memory_mb.is_none()
+            && self.max_cpu_seconds.is_none()
+            && self.max_processes.is_none()
+            && self.max_open_files.is_none()
+            && self.max_file_size_mb.is_none()
+    }
+}
+
+/// Applies `limits` and forks.
+///
+/// Returns in the child, which should go on to sandbox itself and exec the command. The parent
+/// waits for the child, reports the limit that stopped it (if any), removes the cgroup and exits
+/// with the child's status. Must be called while the process is still single-threaded.
+pub(crate) fn enforce_resource_limits(limits: &ResourceLimitArgs) -> io::Result<()> {
+    let cgroup = if limits.max_memory_mb.is_some() || limits.max_processes.is_some() {
+        CommandCgroup::create(limits).ok()
+    } else {
+        None
+    };
+
+    // SAFETY: plain syscall.
+    let parent_pid = unsafe { libc::getpid() };
+    // SAFETY: the process is single-threaded, so the child gets a consistent copy of it.
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            // SAFETY: plain syscalls. Without the parent, nobody would clean up after us.
+            unsafe {
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+                if libc::getppid() != parent_pid {
+                    libc::_exit(1);
+                }
+            }
+            let in_cgroup = cgroup.as_ref().is_some_and(|cgroup| cgroup.join().is_ok());
+            // The parent owns the cgroup; the child must not remove it.
+            std::mem::forget(cgroup);
+            apply_rlimits(limits, in_cgroup)
+        }
+        child => {
+            let Some((status, usage)) = wait_for_child(child) else {
+                std::process::exit(1);
+            };
+            if let Some((kind, setting)) = exceeded_limit(limits, cgroup.as_ref(), status, &usage) {
+                eprintln!("{RESOURCE_LIMIT_EXCEEDED_PREFIX}{kind} ({setting})");
+            }
+            drop(cgroup);
+            std::process::exit(exit_code(status));
+        }
+    }
+}
+
+fn apply_rlimits(limits: &ResourceLimitArgs, in_cgroup: bool) -> io::Result<()> {
+    if let Some(seconds) = limits.max_cpu_seconds {
+        // SIGXCPU at the soft limit tells us which limit it was; SIGKILL a second later if the
+        // command ignores it.
+        set_rlimit(libc::RLIMIT_CPU, seconds, seconds.saturating_add(1))?;
+    }
+    if let Some(files) = limits.max_open_files {
+        set_rlimit(libc::RLIMIT_NOFILE, files, files)?;
+    }
+    if let Some(mb) = limits.max_file_size_mb {
+        let bytes = mb.saturating_mul(MIB);
+        set_rlimit(libc::RLIMIT_FSIZE, bytes, bytes)?;
+    }
+    if !in_cgroup {
+        if let Some(mb) = limits.max_memory_mb {
+            let bytes = mb.saturating_mul(MIB);
+            set_rlimit(libc::RLIMIT_DATA, bytes, bytes)?;
+        }
+        if let Some(processes) = limits.max_processes {
+            set_rlimit(libc::RLIMIT_NPROC, processes, processes)?;
+        }
+    }
+    Ok(())
+}
+
+/// Lowers a limit. Values above the current hard limit are clamped to it, since only a
+/// privileged process may raise it.
+fn set_rlimit(resource: RlimitResource, soft: libc::rlim_t, hard: libc::rlim_t) -> io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `current` is a valid out pointer.
+    if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let hard = hard.min(current.rlim_max);
+    let limit = libc::rlimit {
+        rlim_cur: soft.min(hard),
+        rlim_max: hard,
+    };
+    // SAFETY: `limit` is a valid pointer.
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Works out which configured limit stopped the command, along with the setting to mention.
+fn exceeded_limit(
+    limits: &ResourceLimitArgs,
+    cgroup: Option<&CommandCgroup>,
+    status: libc::c_int,
+    usage: &libc::rusage,
+) -> Option<(ResourceLimitKind, String)> {
+    if libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0 {
+        return None;
+    }
+    // A shell running the command reports a fatal signal as `128 + signal`.
+    let signal = if libc::WIFSIGNALED(status) {
+        libc::WTERMSIG(status)
+    } else {
+        exit_code(status) - 128
+    };
+
+    if let Some(cgroup) = cgroup {
+        // `max` counts allocations that ran into the limit, which fail the command without
+        // an OOM kill when it handles the error.
+        if let Some(mb) = limits.max_memory_mb
+            && (cgroup.event_count("memory.events", "oom_kill") > 0
+                || cgroup.event_count("memory.events", "max") > 0)
+        {
+            return Some((ResourceLimitKind::Memory, format!("max_memory_mb = {mb}")));
+        }
+        if let Some(processes) = limits.max_processes
+            && cgroup.event_count("pids.events", "max") > 0
+        {
+            return Some((
+                ResourceLimitKind::Processes,
+                format!("max_processes = {processes}"),
+            ));
+        }
+    }
+    if let Some(seconds) = limits.max_cpu_seconds {
+        let cpu_seconds = usage.ru_utime.tv_sec + usage.ru_stime.tv_sec;
+        let killed_at_hard_limit = libc::WIFSIGNALED(status)
+            && signal == libc::SIGKILL
+            && libc::rlim_t::try_from(cpu_seconds).is_ok_and(|used| used >= seconds);
+        if signal == libc::SIGXCPU || killed_at_hard_limit {
+            return Some((
+                ResourceLimitKind::CpuTime,
+                format!("max_cpu_seconds = {seconds}"),
+            ));
+        }
+    }
+    if let Some(mb) = limits.max_file_size_mb
+        && signal == libc::SIGXFSZ
+    {
+        return Some((
+            ResourceLimitKind::FileSize,
+            format!("max_file_size_mb = {mb}"),
+        ));
+    }
+    None
+}
+
+/// A cgroup v2 child of the cgroup this process runs in, removed on drop.
+struct CommandCgroup {
+    path: PathBuf,
+}
+
+impl CommandCgroup {
+    fn create(limits: &ResourceLimitArgs) -> io::Result<Self> {
+        let parent = current_cgroup()?;
+        remove_stale_cgroups(&parent);
+        // SAFETY: plain syscall.
+        let pid = unsafe { libc::getpid() };
+
+        let mut controllers = Vec::new();
+        if limits.max_memory_mb.is_some() {
+            controllers.push("memory");
+        }
+        if limits.max_processes.is_some() {
+            controllers.push("pids");
+        }
+        let enabled = fs::read_to_string(parent.join("cgroup.subtree_control"))?;
+        let missing: Vec<String> = controllers
+            .into_iter()
+            .filter(|controller| !enabled.split_whitespace().any(|e| e == *controller))
+            .map(|controller| format!("+{controller}"))
+            .collect();
+        if !missing.is_empty() {
+            enable_controllers(&parent, &missing.join(" "), pid)?;
+        }
+
+        let path = parent.join(format!("{CGROUP_PREFIX}{pid}"));
+        fs::create_dir(&path)?;
+        let cgroup = Self { path };
+        if let Some(mb) = limits.max_memory_mb {
+            let bytes = mb.saturating_mul(MIB);
+            fs::write(cgroup.path.join("memory.max"), bytes.to_string())?;
+            // Swapping would let the command exceed the limit.
+            let _ = fs::write(cgroup.path.join("memory.swap.max"), "0");
+        }
+        if let Some(processes) = limits.max_processes {
+            fs::write(cgroup.path.join("pids.max"), processes.to_string())?;
+        }
+        Ok(cgroup)
+    }
+
+    /// Moves the calling process into the cgroup.
+    fn join(&self) -> io::Result<()> {
+        fs::write(self.path.join("cgroup.procs"), "0")
+    }
+
+    /// Reads a counter such as `oom_kill` from an events file like `memory.events`.
+    fn event_count(&self, file: &str, key: &str) -> u64 {
+        fs::read_to_string(self.path.join(file))
+            .ok()
+            .and_then(|events| {
+                events.lines().find_map(|line| {
+                    line.strip_prefix(key)?
+                        .strip_prefix(' ')?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for CommandCgroup {
+    fn drop(&mut self) {
+        // Background processes the command left behind would keep the cgroup busy.
+        let _ = fs::write(self.path.join("cgroup.kill"), "1");
+        for _ in 0..50 {
+            if fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+}
+
+/// Enables `controllers` for the children of `parent`, the cgroup this process runs in.
+///
+/// A cgroup with processes of its own cannot enable controllers for its children, so this
+/// process first moves into a leaf of its own. That is enough when it was the only process in
+/// its cgroup; otherwise enabling still fails, and the process moves back.
+fn enable_controllers(parent: &Path, controllers: &str, pid: libc::pid_t) -> io::Result<()> {
+    let leaf = parent.join(format!("{CGROUP_PREFIX}{pid}-helper"));
+    fs::create_dir(&leaf)?;
+    let enabled = fs::write(leaf.join("cgroup.procs"), pid.to_string())
+        .and_then(|()| fs::write(parent.join("cgroup.subtree_control"), controllers));
+    if enabled.is_err() {
+        let _ = fs::write(parent.join("cgroup.procs"), pid.to_string());
+        let _ = fs::remove_dir(&leaf);
+    }
+    enabled
+}
+
+/// Removes the empty cgroups earlier runs left behind. A helper cannot remove its own leaf:
+/// once controllers are enabled above it, there is nowhere for the helper to move to.
+fn remove_stale_cgroups(parent: &Path) {
+    let Ok(entries) = fs::read_dir(parent) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(CGROUP_PREFIX)
+        {
+            continue;
+        }
+        let empty = fs::read_to_string(entry.path().join("cgroup.events"))
+            .is_ok_and(|events| events.lines().any(|line| line == "populated 0"));
+        if empty {
+            let _ = fs::remove_dir(entry.path());
+        }
+    }
+}
+
+/// Path of the cgroup v2 this process runs in.
+fn current_cgroup() -> io::Result<PathBuf> {
+    let membership = fs::read_to_string("/proc/self/cgroup")?;
+    let relative = membership
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cgroup v2 membership"))?;
+    Ok(Path::new(CGROUP_ROOT).join(relative.trim().trim_start_matches('/')))
+}
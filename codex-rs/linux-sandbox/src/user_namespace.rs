@@ -0,0 +1,21 @@
+//! Unprivileged namespaces for the command.
+
+use std::io;
+
+/// Moves this process into a new user namespace together with the other `namespaces`, which
+/// it needs the namespace's privileges to set up. Our ids are mapped onto themselves so files
+/// keep their owners. Must be called while the process is still single-threaded.
+pub(crate) fn unshare_as_current_user(namespaces: libc::c_int) -> io::Result<()> {
+    // SAFETY: these calls have no preconditions.
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+
+    // SAFETY: plain syscall; `unshare(CLONE_NEWUSER)` fails rather than misbehaving when the
+    // process has more than one thread.
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | namespaces) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    std::fs::write("/proc/self/setgroups", "deny")?;
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+    Ok(())
+}
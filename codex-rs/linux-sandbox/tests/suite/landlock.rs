@@ -34,20 +34,8 @@ fn create_env_from_core_vars() -> HashMap<String, String> {
     create_env(&policy)
 }
 
-#[expect(clippy::print_stdout, clippy::expect_used, clippy::unwrap_used)]
+#[expect(clippy::unwrap_used)]
 async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
-    let cwd = std::env::current_dir().expect("cwd should exist");
-    let sandbox_cwd = cwd.clone();
-    let params = ExecParams {
-        command: cmd.iter().copied().map(str::to_owned).collect(),
-        cwd,
-        expiration: timeout_ms.into(),
-        env: create_env_from_core_vars(),
-        sandbox_permissions: SandboxPermissions::UseDefault,
-        justification: None,
-        arg0: None,
-    };
-
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: writable_roots
             .iter()
@@ -59,12 +47,31 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         // writing to in the sandbox.
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
+    run_cmd_with_policy(cmd, &sandbox_policy, timeout_ms).await;
+}
+
+#[expect(clippy::print_stdout, clippy::expect_used, clippy::unwrap_used)]
+async fn run_cmd_with_policy(cmd: &[&str], sandbox_policy: &SandboxPolicy, timeout_ms: u64) {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_cwd = cwd.clone();
+    let params = ExecParams {
+        command: cmd.iter().copied().map(str::to_owned).collect(),
+        cwd,
+        expiration: timeout_ms.into(),
+        env: create_env_from_core_vars(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        arg0: None,
+    };
+
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
     let res = process_exec_tool_call(
         params,
-        &sandbox_policy,
+        sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         None,
@@ -127,6 +134,119 @@ async fn test_writable_root() {
     .await;
 }
 
+#[expect(clippy::unwrap_used)]
+fn carve_out_policy(
+    writable_root: &std::path::Path,
+    read_only_subpaths: Vec<PathBuf>,
+    deny_read_paths: Vec<PathBuf>,
+) -> SandboxPolicy {
+    let absolute = |paths: Vec<PathBuf>| {
+        paths
+            .into_iter()
+            .map(|p| AbsolutePathBuf::try_from(p).unwrap())
+            .collect()
+    };
+    SandboxPolicy::WorkspaceWrite {
+        writable_roots: absolute(vec![writable_root.to_path_buf()]),
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        read_only_subpaths: absolute(read_only_subpaths),
+        deny_read_paths: absolute(deny_read_paths),
+    }
+}
+
+#[tokio::test]
+async fn test_writable_root_beside_read_only_subpath() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let hooks = tmpdir.path().join("hooks");
+    std::fs::create_dir(&hooks).unwrap();
+    let src = tmpdir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    let policy = carve_out_policy(tmpdir.path(), vec![hooks], vec![]);
+    run_cmd_with_policy(
+        &[
+            "bash",
+            "-lc",
+            &format!("echo blah > {}", src.join("main.rs").to_string_lossy()),
+        ],
+        &policy,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_carve_outs_keep_parent_directory_editable() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secret = tmpdir.path().join(".env");
+    std::fs::write(&secret, "TOKEN=1").unwrap();
+    let hooks = tmpdir.path().join("hooks");
+    std::fs::create_dir(&hooks).unwrap();
+    let policy = carve_out_policy(tmpdir.path(), vec![hooks], vec![secret]);
+    let root = tmpdir.path().to_string_lossy();
+    run_cmd_with_policy(
+        &[
+            "bash",
+            "-lc",
+            &format!("echo blah > {root}/new && mv {root}/new {root}/renamed && rm {root}/renamed"),
+        ],
+        &policy,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_read_only_subpath_write() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let hooks = tmpdir.path().join("hooks");
+    std::fs::create_dir(&hooks).unwrap();
+    let policy = carve_out_policy(tmpdir.path(), vec![hooks.clone()], vec![]);
+    run_cmd_with_policy(
+        &[
+            "bash",
+            "-lc",
+            &format!("echo blah > {}", hooks.join("pre-commit").to_string_lossy()),
+        ],
+        &policy,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_deny_read_path_read() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secret = tmpdir.path().join(".env");
+    std::fs::write(&secret, "TOKEN=1").unwrap();
+    let policy = carve_out_policy(tmpdir.path(), vec![], vec![secret.clone()]);
+    run_cmd_with_policy(
+        &["cat", &secret.to_string_lossy()],
+        &policy,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_deny_read_path_directory_listing() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secrets = tmpdir.path().join("secrets");
+    std::fs::create_dir(&secrets).unwrap();
+    std::fs::write(secrets.join("key"), "TOKEN=1").unwrap();
+    let policy = carve_out_policy(tmpdir.path(), vec![], vec![secrets.clone()]);
+    run_cmd_with_policy(
+        &["ls", &secrets.to_string_lossy()],
+        &policy,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
 #[tokio::test]
 #[should_panic(expected = "Sandbox(Timeout")]
 async fn test_timeout() {
@@ -238,3 +358,41 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Under the network proxy, IP sockets are allowed so the command can reach the
+/// loopback relay, but Unix sockets on the host must stay out of reach.
+#[tokio::test]
+async fn proxied_network_blocks_host_unix_sockets() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let host_socket = dir.path().join("host.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&host_socket).expect("bind host socket");
+    listener
+        .set_nonblocking(true)
+        .expect("nonblocking listener");
+
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let status = tokio::process::Command::new(sandbox_program)
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .args(["--sandbox-policy", r#"{"type":"read-only"}"#])
+        .arg("--network-proxy-socket")
+        .arg(dir.path().join("proxy.sock"))
+        .args([
+            "--",
+            "python3",
+            "-c",
+            "import socket, sys; socket.socket(socket.AF_UNIX).connect(sys.argv[1])",
+        ])
+        .arg(&host_socket)
+        .status()
+        .await
+        .expect("run sandbox");
+
+    assert!(!status.success(), "connect to a host Unix socket succeeded");
+    assert_eq!(
+        listener.accept().map(|_| ()).map_err(|err| err.kind()),
+        Err(std::io::ErrorKind::WouldBlock),
+        "host Unix socket received a connection from the sandbox"
+    );
+}
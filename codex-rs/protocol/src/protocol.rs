@@ -25,6 +25,7 @@ use crate::plan_tool::UpdatePlanArgs;
 use crate::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::CallToolResult;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::RequestId;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
@@ -186,9 +187,31 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Request the prompts published by the configured MCP servers.
+    /// Reply is delivered via `EventMsg::ListMcpPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Fetch a prompt published by an MCP server and run its messages as the
+    /// input of a new turn. While a turn is running, a prompt made only of user
+    /// messages is added to it; one with assistant messages is rejected with
+    /// an `EventMsg::Error`.
+    RunMcpPrompt {
+        /// Name of the MCP server that publishes the prompt.
+        server: String,
+        /// Prompt name as reported by `prompts/list`.
+        prompt: String,
+        /// Values for the prompt's declared arguments.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        arguments: HashMap<String, String>,
+    },
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request the background processes started with `exec_command` that are still tracked.
+    /// Reply is delivered via `EventMsg::ListProcessesResponse`.
+    ListProcesses,
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -210,6 +233,26 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Request the ghost snapshots taken in this session.
+    /// Reply is delivered via `EventMsg::UndoTimeline`.
+    ListUndoSnapshots,
+
+    /// Request the diff from a ghost snapshot to the current working tree.
+    /// Reply is delivered via `EventMsg::UndoSnapshotDiff`.
+    GetUndoSnapshotDiff { commit_id: String },
+
+    /// Restore the working tree to a ghost snapshot. When `paths` is not
+    /// empty, only those files (relative to the session cwd) are restored.
+    /// The replaced state can be brought back with `Op::Redo`.
+    RestoreUndoSnapshot {
+        commit_id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Bring back the working tree replaced by the last undo or restore.
+    Redo,
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
@@ -341,6 +384,16 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// Paths under the writable roots that stay read-only, e.g.
+        /// `.git/hooks` or `.env`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        read_only_subpaths: Vec<AbsolutePathBuf>,
+
+        /// Paths the sandboxed command can neither read nor write, e.g.
+        /// `~/.ssh`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read_paths: Vec<AbsolutePathBuf>,
     },
 }
 
@@ -398,12 +451,34 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         }
     }
 
-    /// Always returns `true`; restricting read access is not supported.
+    /// Returns `false` only when some paths are denied with `deny_read_paths`.
     pub fn has_full_disk_read_access(&self) -> bool {
-        true
+        self.get_deny_read_paths().is_empty()
+    }
+
+    /// Paths configured to stay read-only under the writable roots.
+    pub fn get_read_only_subpaths(&self) -> &[AbsolutePathBuf] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                read_only_subpaths, ..
+            } => read_only_subpaths,
+            _ => &[],
+        }
+    }
+
+    /// Paths the sandboxed command may not read or write at all.
+    pub fn get_deny_read_paths(&self) -> &[AbsolutePathBuf] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                deny_read_paths, ..
+            } => deny_read_paths,
+            _ => &[],
+        }
     }
 
     pub fn has_full_disk_write_access(&self) -> bool {
@@ -437,6 +512,8 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                read_only_subpaths,
+                deny_read_paths,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<AbsolutePathBuf> = writable_roots.clone();
@@ -510,6 +587,15 @@ impl SandboxPolicy {
                         if top_level_codex.as_path().is_dir() {
                             subpaths.push(top_level_codex);
                         }
+                        // Configured carve-outs, including denied paths, which
+                        // are not writable either.
+                        for carve_out in read_only_subpaths.iter().chain(deny_read_paths) {
+                            if carve_out.as_path().starts_with(writable_root.as_path())
+                                && !subpaths.contains(carve_out)
+                            {
+                                subpaths.push(carve_out.clone());
+                            }
+                        }
                         WritableRoot {
                             root: writable_root,
                             read_only_subpaths: subpaths,
@@ -596,6 +682,9 @@ pub enum EventMsg {
 
     McpToolCallEnd(McpToolCallEndEvent),
 
+    /// Audit record of an MCP server's `sampling/createMessage` request.
+    McpSampling(McpSamplingEvent),
+
     WebSearchBegin(WebSearchBeginEvent),
 
     WebSearchEnd(WebSearchEndEvent),
@@ -630,6 +719,13 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// Ghost snapshots of the session, in response to `Op::ListUndoSnapshots`.
+    UndoTimeline(UndoTimelineEvent),
+
+    /// Diff from a ghost snapshot to the working tree, in response to
+    /// `Op::GetUndoSnapshotDiff`.
+    UndoSnapshotDiff(UndoSnapshotDiffEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -652,6 +748,12 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// Prompts published by MCP servers.
+    ListMcpPromptsResponse(ListMcpPromptsResponseEvent),
+
+    /// Background processes started with `exec_command`.
+    ListProcessesResponse(ListProcessesResponseEvent),
+
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
@@ -728,6 +830,8 @@ pub enum CodexErrorInfo {
         http_status_code: Option<u16>,
     },
     ThreadRollbackFailed,
+    /// An undo timeline request could not be served.
+    UndoFailed,
     Other,
 }
 
@@ -1501,6 +1605,18 @@ impl Default for ExecCommandSource {
     }
 }
 
+/// Per-command resource limit that stopped a command.
+#[derive(Debug, Clone, Copy, Display, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ResourceLimitKind {
+    Memory,
+    CpuTime,
+    Processes,
+    OpenFiles,
+    FileSize,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecCommandBeginEvent {
     /// Identifier so this can be paired with the ExecCommandEnd event.
@@ -1562,6 +1678,14 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Set when the command was stopped by one of the configured resource limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub resource_limit_exceeded: Option<ResourceLimitKind>,
+    /// File holding the command's full, untruncated output, when it was spooled to disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub output_log: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1631,6 +1755,44 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+/// Response payload for `Op::ListUndoSnapshots`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UndoTimelineEvent {
+    /// Snapshots from oldest to newest.
+    pub snapshots: Vec<UndoSnapshot>,
+    /// Whether `Op::Redo` has a state to bring back.
+    pub redo_available: bool,
+}
+
+/// A ghost snapshot, taken at the start of a turn.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UndoSnapshot {
+    pub commit_id: String,
+    /// The user message of the turn the snapshot was taken for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Files the turn changed, up to the next snapshot or the current working tree.
+    pub files: Vec<UndoSnapshotFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UndoSnapshotFile {
+    /// Path relative to the session cwd.
+    pub path: PathBuf,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+/// Response payload for `Op::GetUndoSnapshotDiff`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UndoSnapshotDiffEvent {
+    pub commit_id: String,
+    /// Unified diff from the snapshot to the current working tree.
+    pub diff: String,
+    /// Files that differ, for restoring a selection of them.
+    pub files: Vec<UndoSnapshotFile>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
@@ -1709,6 +1871,9 @@ pub struct McpListToolsResponseEvent {
     pub resources: std::collections::HashMap<String, Vec<McpResource>>,
     /// Known resource templates grouped by server name.
     pub resource_templates: std::collections::HashMap<String, Vec<McpResourceTemplate>>,
+    /// Known prompts grouped by server name.
+    #[serde(default)]
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
     /// Authentication status for each configured MCP server.
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
@@ -1744,6 +1909,29 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpSamplingEvent {
+    /// Server that asked for the completion.
+    pub server: String,
+    pub status: McpSamplingStatus,
+    /// Model that produced the completion, when one ran.
+    pub model: Option<String>,
+    /// Tokens the request spent against the server's budget.
+    pub tokens_used: u64,
+    /// Tokens left in the server's budget afterwards.
+    pub budget_remaining: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case", tag = "state")]
+#[ts(rename_all = "snake_case", tag = "state")]
+pub enum McpSamplingStatus {
+    Completed,
+    Declined,
+    BudgetExhausted,
+    Failed { error: String },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -1772,6 +1960,39 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListMcpPromptsResponseEvent {
+    /// Prompts grouped by server name.
+    pub prompts: HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Response payload for `Op::ListProcesses`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListProcessesResponseEvent {
+    pub processes: Vec<ProcessSummary>,
+}
+
+/// A process started with `exec_command` that has not been cleaned up yet.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProcessSummary {
+    pub process_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    /// Label of a long-running service, which keeps running after the turn that started it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// Seconds since the process started.
+    pub age_secs: u64,
+    /// Exit code once the process has exited; `None` while it is running.
+    pub exit_code: Option<i32>,
+    /// Last lines of output.
+    pub output_tail: String,
+    /// TCP ports the process or its children listen on (Linux only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub listening_ports: Vec<u16>,
+}
+
 /// Response payload for `Op::ListSkills`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListSkillsResponseEvent {
@@ -1892,6 +2113,28 @@ pub enum FileChange {
         unified_diff: String,
         move_path: Option<PathBuf>,
     },
+    /// The file is created as a copy of `source`, with `unified_diff` (empty
+    /// for a plain copy) applied to it.
+    Copy {
+        source: PathBuf,
+        unified_diff: String,
+    },
+    /// Only the permission bits of the file change. `old_mode` is `None`
+    /// where it could not be read.
+    Mode {
+        old_mode: Option<u32>,
+        new_mode: u32,
+    },
+    /// The file is created (or replaced) as a symbolic link to `target`.
+    Symlink {
+        target: PathBuf,
+    },
+    /// The file is written with `size` bytes of binary contents, replacing
+    /// a file of `previous_size` bytes if there was one.
+    Binary {
+        size: u64,
+        previous_size: Option<u64>,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1940,6 +2183,37 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn workspace_write_carve_outs_are_read_only_under_their_root() -> Result<()> {
+        let workspace = tempfile::tempdir()?;
+        let env_file = AbsolutePathBuf::try_from(workspace.path().join(".env"))?;
+        let secrets = AbsolutePathBuf::try_from(workspace.path().join("secrets"))?;
+        let home = tempfile::tempdir()?;
+        let ssh = AbsolutePathBuf::try_from(home.path().join(".ssh"))?;
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            read_only_subpaths: vec![env_file.clone()],
+            deny_read_paths: vec![secrets.clone(), ssh.clone()],
+        };
+
+        let roots = policy.get_writable_roots_with_cwd(workspace.path());
+        assert_eq!(
+            vec![WritableRoot {
+                root: AbsolutePathBuf::try_from(workspace.path())?,
+                read_only_subpaths: vec![env_file.clone(), secrets.clone()],
+            }],
+            roots
+        );
+        assert!(!roots[0].is_path_writable(&secrets.as_path().join("key")));
+        assert!(roots[0].is_path_writable(&workspace.path().join("src")));
+        assert!(!policy.has_full_disk_read_access());
+        assert_eq!(vec![secrets, ssh], policy.get_deny_read_paths());
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
@@ -1,4 +1,8 @@
 use std::sync::Arc;
+use std::sync::RwLock;
+
+use mcp_types::ListRootsResult;
+use mcp_types::Root;
 
 use rmcp::ClientHandler;
 use rmcp::RoleClient;
@@ -6,6 +10,9 @@ use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParam;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ListRootsResult as RmcpListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
@@ -18,35 +25,94 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::rmcp_client::OnServerChange;
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
+use crate::rmcp_client::ServerChange;
+use crate::utils::convert_to_mcp;
+use crate::utils::convert_to_rmcp;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    on_change: Option<Arc<OnServerChange>>,
+    roots: Arc<RwLock<Vec<Root>>>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        on_change: Option<OnServerChange>,
+        roots: Arc<RwLock<Vec<Root>>>,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            send_sampling: send_sampling.map(Arc::new),
+            on_change: on_change.map(Arc::new),
+            roots,
+        }
+    }
+
+    fn notify_change(&self, change: ServerChange) {
+        if let Some(on_change) = &self.on_change {
+            on_change(change);
         }
     }
 }
 
+fn convert_request_id(id: RequestId) -> mcp_types::RequestId {
+    match id {
+        RequestId::String(id) => mcp_types::RequestId::String(id.to_string()),
+        RequestId::Number(id) => mcp_types::RequestId::Integer(id),
+    }
+}
+
 impl ClientHandler for LoggingClientHandler {
     async fn create_elicitation(
         &self,
         request: CreateElicitationRequestParam,
         context: RequestContext<RoleClient>,
     ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
-        let id = match context.id {
-            RequestId::String(id) => mcp_types::RequestId::String(id.to_string()),
-            RequestId::Number(id) => mcp_types::RequestId::Integer(id),
+        (self.send_elicitation)(convert_request_id(context.id), request)
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        let Some(send_sampling) = &self.send_sampling else {
+            return Err(rmcp::ErrorData::invalid_request(
+                "sampling is not supported by this client",
+                None,
+            ));
         };
-        (self.send_elicitation)(id, request)
+        let params = convert_to_mcp(params)
+            .map_err(|err| rmcp::ErrorData::invalid_params(err.to_string(), None))?;
+        let result = send_sampling(convert_request_id(context.id), params)
             .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))?;
+        convert_to_rmcp(result)
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<RmcpListRootsResult, rmcp::ErrorData> {
+        let roots = self
+            .roots
+            .read()
+            .map_err(|_| rmcp::ErrorData::internal_error("roots lock poisoned", None))?
+            .clone();
+        convert_to_rmcp(ListRootsResult { roots })
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
@@ -78,14 +144,17 @@ impl ClientHandler for LoggingClientHandler {
         _context: NotificationContext<RoleClient>,
     ) {
         info!("MCP server resource updated (uri: {})", params.uri);
+        self.notify_change(ServerChange::ResourceUpdated { uri: params.uri });
     }
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.notify_change(ServerChange::ResourceListChanged);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.notify_change(ServerChange::ToolListChanged);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
@@ -12,8 +12,14 @@ use futures::FutureExt;
 use futures::future::BoxFuture;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -23,6 +29,8 @@ use mcp_types::ListToolsResult;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
+use mcp_types::Root;
+use mcp_types::SubscribeRequestParams;
 use reqwest::header::HeaderMap;
 use rmcp::model::CallToolRequestParam;
 use rmcp::model::ClientNotification;
@@ -32,10 +40,12 @@ use rmcp::model::CreateElicitationResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
 use rmcp::model::ReadResourceRequestParam;
 use rmcp::model::ServerResult;
+use rmcp::model::SubscribeRequestParam;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
 use rmcp::service::{self};
@@ -95,10 +105,30 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+pub type Sampling = CreateMessageRequestParams;
+pub type SamplingResponse = CreateMessageResult;
+
+/// Interface for running `sampling/createMessage` requests from the server.
+pub type SendSampling =
+    Box<dyn Fn(RequestId, Sampling) -> BoxFuture<'static, Result<SamplingResponse>> + Send + Sync>;
+
+/// Change notifications from the server that the client's owner acts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerChange {
+    ToolListChanged,
+    ResourceListChanged,
+    ResourceUpdated { uri: String },
+}
+
+/// Callback for [`ServerChange`] notifications.
+pub type OnServerChange = Box<dyn Fn(ServerChange) + Send + Sync>;
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    /// Roots answered to `roots/list`, shared with the client handler.
+    roots: Arc<std::sync::RwLock<Vec<Root>>>,
 }
 
 impl RmcpClient {
@@ -155,6 +185,7 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            roots: Arc::default(),
         })
     }
 
@@ -209,6 +240,7 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
+            roots: Arc::default(),
         })
     }
 
@@ -219,9 +251,17 @@ impl RmcpClient {
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        on_change: Option<OnServerChange>,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params, send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            rmcp_params,
+            send_elicitation,
+            send_sampling,
+            on_change,
+            Arc::clone(&self.roots),
+        );
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -350,6 +390,81 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    /// Asks the server to send `notifications/resources/updated` when the
+    /// resource changes.
+    pub async fn subscribe_resource(
+        &self,
+        params: SubscribeRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params: SubscribeRequestParam = convert_to_rmcp(params)?;
+        let fut = service.subscribe(rmcp_params);
+        run_with_timeout(fut, timeout, "resources/subscribe").await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
+    /// Replaces the roots answered to `roots/list`. Once initialized, the
+    /// server is told when they change.
+    pub async fn set_roots(&self, roots: Vec<Root>) -> Result<()> {
+        {
+            let mut current = self
+                .roots
+                .write()
+                .map_err(|_| anyhow!("roots lock poisoned"))?;
+            if *current == roots {
+                return Ok(());
+            }
+            *current = roots;
+        }
+
+        let service = {
+            let guard = self.state.lock().await;
+            match &*guard {
+                ClientState::Ready { service, .. } => Arc::clone(service),
+                ClientState::Connecting { .. } => return Ok(()),
+            }
+        };
+        service
+            .notify_roots_list_changed()
+            .await
+            .map_err(|err| anyhow!("failed to send roots/list_changed: {err}"))
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -538,9 +538,10 @@ impl App {
 
         // Cleanup old council jobs
         let cleanup_root = app.config.cwd.clone();
+        let retention = app.config.council.retention.clone();
 
         tokio::spawn(async move {
-            if let Err(e) = codex_council::cleanup_old_jobs(cleanup_root).await {
+            if let Err(e) = codex_council::cleanup_old_jobs(cleanup_root, retention).await {
                 tracing::warn!("Failed to cleanup old council jobs: {}", e);
             }
         });
@@ -1385,16 +1386,23 @@ impl App {
                     critic_gpt_model: self.config.council_critic_gpt_model.clone(),
                     critic_gemini_model: self.config.council_critic_gemini_model.clone(),
                     implementer_model: self.config.council_implementer_model.clone(),
+                    settings: self.config.council.clone(),
                 };
                 match self
                     .council_job_manager
                     .spawn_job(mode, target, config, self.app_event_tx.clone().app_event_tx)
                     .await
                 {
-                    Ok(job_id) => {
+                    Ok(crate::council_job::SpawnedJob::Started(job_id)) => {
                         self.chat_widget
                             .add_info_message(format!("Started Council job {job_id}"), None);
                     }
+                    Ok(crate::council_job::SpawnedJob::Queued { job_id, position }) => {
+                        self.chat_widget.add_info_message(
+                            format!("Queued Council job {job_id} (position {position})"),
+                            Some("See /thinthread jobs for progress.".to_string()),
+                        );
+                    }
                     Err(e) => {
                         self.chat_widget
                             .add_error_message(format!("Failed to start job: {e}"));
@@ -1402,17 +1410,36 @@ impl App {
                 }
             }
             AppEvent::CancelCouncilJob(job_id) => {
-                if self.council_job_manager.active_job_id().as_deref() == Some(&job_id) {
-                    self.council_job_manager.cancel_active_job();
-                    self.chat_widget
-                        .add_info_message("Job cancellation requested.".to_string(), None);
-                } else {
-                    self.chat_widget
-                        .add_error_message("Job not running or ID mismatch.".to_string());
+                match self.council_job_manager.cancel_job(&job_id) {
+                    Ok(()) => {
+                        self.chat_widget.add_info_message(
+                            format!("Cancellation requested for job {job_id}."),
+                            None,
+                        );
+                    }
+                    Err(e) => {
+                        self.chat_widget.add_error_message(e.to_string());
+                    }
                 }
             }
+            AppEvent::ShowCouncilJobs => {
+                self.chat_widget
+                    .add_plain_history_lines(self.council_job_manager.jobs_lines());
+            }
+            AppEvent::OpenCouncilRuns => {
+                let repo_root =
+                    find_git_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone());
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_council_runs(
+                    repo_root,
+                    self.app_event_tx.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::ApplyCouncilJob(job_id) => {
-                let repo_root = self.config.cwd.clone();
+                // The run's patch is relative to the repository root it reviewed.
+                let repo_root =
+                    find_git_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone());
                 match self
                     .council_job_manager
                     .apply_job(&job_id, repo_root.as_path())
@@ -1459,8 +1486,21 @@ impl App {
                             break;
                         }
                     }
-                    if let codex_council::CouncilEvent::JobFinished { .. } = &event {
-                        self.council_job_manager.on_job_finished(&job_id);
+                    if let codex_council::CouncilEvent::JobFinished {
+                        outcome,
+                        summary_line,
+                        ..
+                    } = &event
+                        && let Some(next) = self.council_job_manager.on_job_finished(
+                            &job_id,
+                            outcome.clone(),
+                            summary_line.clone(),
+                        )
+                    {
+                        self.chat_widget.add_info_message(
+                            format!("Started queued Council job {next}"),
+                            None,
+                        );
                     }
                 }
             }
@@ -1924,6 +1964,16 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::OpenUndoSnapshotActions(snapshot) => {
+                self.chat_widget.show_undo_snapshot_actions(snapshot);
+            }
+            AppEvent::RequestUndoSnapshotDiff {
+                commit_id,
+                pick_files,
+            } => {
+                self.chat_widget
+                    .request_undo_snapshot_diff(commit_id, pick_files);
+            }
             AppEvent::FullScreenApprovalRequest(request) => match request {
                 ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
                     let _ = tui.enter_alt_screen();
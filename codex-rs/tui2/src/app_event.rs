@@ -4,6 +4,7 @@ use codex_common::approval_presets::ApprovalPreset;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::UndoSnapshot;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::ModelPreset;
 
@@ -27,6 +28,10 @@ pub(crate) enum AppEvent {
     },
     CancelCouncilJob(String),
     ApplyCouncilJob(String),
+    /// Show queued, running and finished Council jobs.
+    ShowCouncilJobs,
+    /// Open the full-screen browser over past Council runs.
+    OpenCouncilRuns,
 
     /// Start a new session.
     NewSession,
@@ -171,6 +176,16 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open the actions for a snapshot picked in the undo timeline.
+    OpenUndoSnapshotActions(UndoSnapshot),
+
+    /// Request the diff since an undo snapshot, either to preview it or to
+    /// pick the files to restore.
+    RequestUndoSnapshotDiff {
+        commit_id: String,
+        pick_files: bool,
+    },
+
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
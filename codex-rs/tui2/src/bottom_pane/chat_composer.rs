@@ -34,8 +34,11 @@ use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::SkillPopup;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptCommand;
+use crate::bottom_pane::prompt_args::McpPromptInvocation;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -77,6 +80,7 @@ pub enum InputResult {
     Submitted(String),
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String),
+    McpPrompt(McpPromptInvocation),
     None,
 }
 
@@ -121,6 +125,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
@@ -176,6 +181,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
@@ -523,6 +529,13 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = command.command_with_arg_placeholders();
+                                self.textarea.set_text(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -576,6 +589,29 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx) {
+                                if command.has_arguments() {
+                                    let (text, cursor) = command.command_with_arg_placeholders();
+                                    self.textarea.set_text(&text);
+                                    self.textarea.set_cursor(cursor);
+                                    return (InputResult::None, true);
+                                }
+                                let text = format!("/{}", command.command());
+                                self.textarea.set_text("");
+                                self.history.record_local_submission(&text);
+                                return (
+                                    InputResult::McpPrompt(McpPromptInvocation {
+                                        command: text,
+                                        server: command.server.clone(),
+                                        prompt: command.prompt.name.clone(),
+                                        arguments: HashMap::new(),
+                                    }),
+                                    true,
+                                );
+                            }
+                            return (InputResult::None, true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -1187,7 +1223,8 @@ impl ChatComposer {
                                     .any(|prompt| prompt.name == prompt_name)
                             })
                             .unwrap_or(false);
-                        if !is_builtin && !is_known_prompt {
+                        let is_mcp_prompt = self.mcp_prompts.iter().any(|p| p.command() == name);
+                        if !is_builtin && !is_known_prompt && !is_mcp_prompt {
                             let message = format!(
                                 r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
                             );
@@ -1213,6 +1250,22 @@ impl ChatComposer {
                     return (InputResult::CommandWithArgs(cmd, rest.to_string()), true);
                 }
 
+                match parse_mcp_prompt(&text, &self.mcp_prompts) {
+                    Ok(Some(invocation)) => {
+                        self.history.record_local_submission(&text);
+                        return (InputResult::McpPrompt(invocation), true);
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            history_cell::new_error_event(err.user_message()),
+                        )));
+                        self.textarea.set_text(&original_input);
+                        self.textarea.set_cursor(original_input.len());
+                        return (InputResult::None, true);
+                    }
+                }
+
                 let expanded_prompt = match expand_custom_prompt(&text, &self.custom_prompts) {
                     Ok(expanded) => expanded,
                     Err(err) => {
@@ -1703,6 +1756,10 @@ impl ChatComposer {
         self.custom_prompts
             .iter()
             .any(|p| fuzzy_match(&format!("{prompt_prefix}{}", p.name), name).is_some())
+            || self
+                .mcp_prompts
+                .iter()
+                .any(|p| fuzzy_match(&p.command(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -1748,6 +1805,7 @@ impl ChatComposer {
                     let skills_enabled = self.skills_enabled();
                     let mut command_popup =
                         CommandPopup::new(self.custom_prompts.clone(), skills_enabled);
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1762,6 +1820,13 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -2698,7 +2763,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -2754,7 +2819,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -3522,6 +3587,82 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    fn docs_mcp_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: mcp_types::Prompt {
+                arguments: Some(vec![mcp_types::PromptArgument {
+                    description: None,
+                    name: "topic".to_string(),
+                    required: Some(true),
+                    title: None,
+                }]),
+                description: Some("Explain a topic".to_string()),
+                name: "explain".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn selecting_mcp_prompt_inserts_argument_form() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_mcp_prompts(vec![docs_mcp_prompt()]);
+
+        type_chars_humanlike(
+            &mut composer,
+            &['/', 'm', 'c', 'p', ':', 'd', 'o', 'c', 's'],
+        );
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(InputResult::None, result);
+        assert_eq!(composer.textarea.text(), "/mcp:docs:explain topic=\"\"");
+        assert_eq!(
+            composer.textarea.cursor(),
+            "/mcp:docs:explain topic=\"".len()
+        );
+    }
+
+    #[test]
+    fn mcp_prompt_submission_parses_arguments() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_mcp_prompts(vec![docs_mcp_prompt()]);
+
+        composer
+            .textarea
+            .set_text("/mcp:docs:explain topic=\"lifetimes\"");
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            InputResult::McpPrompt(McpPromptInvocation {
+                command: "/mcp:docs:explain topic=\"lifetimes\"".to_string(),
+                server: "docs".to_string(),
+                prompt: "explain".to_string(),
+                arguments: HashMap::from([("topic".to_string(), "lifetimes".to_string())]),
+            }),
+            result
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
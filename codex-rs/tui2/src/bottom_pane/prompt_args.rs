@@ -1,11 +1,15 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use lazy_static::lazy_static;
+use mcp_types::Prompt;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+/// Prefix of slash commands that run MCP server prompts: `/mcp:<server>:<prompt>`.
+pub const MCP_PROMPT_CMD_PREFIX: &str = "mcp";
+
 lazy_static! {
     static ref PROMPT_ARG_REGEX: Regex =
         Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
@@ -299,7 +303,10 @@ pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(format!("/{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+fn command_with_arg_placeholders(mut text: String, args: &[String]) -> (String, usize) {
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -310,6 +317,98 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
     (text, cursor)
 }
 
+/// A prompt published by an MCP server, offered as `/mcp:<server>:<prompt>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpPromptCommand {
+    pub server: String,
+    pub prompt: Prompt,
+}
+
+impl McpPromptCommand {
+    /// Command name without the leading slash.
+    pub fn command(&self) -> String {
+        format!(
+            "{MCP_PROMPT_CMD_PREFIX}:{}:{}",
+            self.server, self.prompt.name
+        )
+    }
+
+    pub fn has_arguments(&self) -> bool {
+        self.prompt
+            .arguments
+            .as_ref()
+            .is_some_and(|args| !args.is_empty())
+    }
+
+    fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|arg| arg.name.clone())
+            .collect()
+    }
+
+    /// Command text with a `name=""` placeholder for every declared argument,
+    /// and the cursor position inside the first placeholder.
+    pub fn command_with_arg_placeholders(&self) -> (String, usize) {
+        command_with_arg_placeholders(format!("/{}", self.command()), &self.argument_names())
+    }
+}
+
+/// A fully parsed `/mcp:<server>:<prompt> key=value …` submission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpPromptInvocation {
+    /// The text the user submitted, shown in the transcript.
+    pub command: String,
+    pub server: String,
+    pub prompt: String,
+    pub arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/mcp:<server>:<prompt> [key=value] …` for a known MCP prompt.
+///
+/// Returns `Ok(None)` when the text does not name one of `mcp_prompts`, and an
+/// error when an argument is malformed or a required argument is missing.
+pub fn parse_mcp_prompt(
+    text: &str,
+    mcp_prompts: &[McpPromptCommand],
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((name, rest)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some(command) = mcp_prompts.iter().find(|p| p.command() == name) else {
+        return Ok(None);
+    };
+
+    let arguments = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{name}"),
+        error,
+    })?;
+    let missing: Vec<String> = command
+        .prompt
+        .arguments
+        .iter()
+        .flatten()
+        .filter(|arg| arg.required == Some(true))
+        .filter(|arg| arguments.get(&arg.name).is_none_or(String::is_empty))
+        .map(|arg| arg.name.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{name}"),
+            missing,
+        });
+    }
+
+    Ok(Some(McpPromptInvocation {
+        command: text.to_string(),
+        server: command.server.clone(),
+        prompt: command.prompt.name.clone(),
+        arguments,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -403,4 +502,65 @@ mod tests {
         let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    fn mcp_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: Prompt {
+                arguments: Some(vec![
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "topic".to_string(),
+                        required: Some(true),
+                        title: None,
+                    },
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "tone".to_string(),
+                        required: None,
+                        title: None,
+                    },
+                ]),
+                description: None,
+                name: "explain".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompt_placeholders_follow_argument_schema() {
+        assert_eq!(
+            mcp_prompt().command_with_arg_placeholders(),
+            (
+                "/mcp:docs:explain topic=\"\" tone=\"\"".to_string(),
+                "/mcp:docs:explain topic=\"".len()
+            )
+        );
+    }
+
+    #[test]
+    fn parses_mcp_prompt_arguments() {
+        let prompts = vec![mcp_prompt()];
+        let text = "/mcp:docs:explain topic=\"borrow checker\" tone=";
+
+        assert_eq!(
+            parse_mcp_prompt(text, &prompts).unwrap(),
+            Some(McpPromptInvocation {
+                command: text.to_string(),
+                server: "docs".to_string(),
+                prompt: "explain".to_string(),
+                arguments: HashMap::from([
+                    ("topic".to_string(), "borrow checker".to_string()),
+                    ("tone".to_string(), String::new()),
+                ]),
+            })
+        );
+        assert_eq!(parse_mcp_prompt("/mcp:docs:other", &prompts).unwrap(), None);
+
+        let err = parse_mcp_prompt("/mcp:docs:explain topic=\"\"", &prompts)
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("Missing required args for /mcp:docs:explain: topic"));
+    }
 }
@@ -0,0 +1,300 @@
+use std::path::PathBuf;
+
+use codex_core::protocol::Op;
+use codex_core::protocol::UndoSnapshotFile;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+/// Picks the files to restore from an undo snapshot.
+pub(crate) struct UndoFilesView {
+    commit_id: String,
+    files: Vec<UndoSnapshotFile>,
+    checked: Vec<bool>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl UndoFilesView {
+    pub(crate) fn new(
+        commit_id: String,
+        files: Vec<UndoSnapshotFile>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let mut state = ScrollState::new();
+        state.clamp_selection(files.len());
+        Self {
+            commit_id,
+            checked: vec![false; files.len()],
+            files,
+            state,
+            complete: false,
+            app_event_tx,
+        }
+    }
+
+    fn toggle(&mut self) {
+        if let Some(checked) = self
+            .state
+            .selected_idx
+            .and_then(|idx| self.checked.get_mut(idx))
+        {
+            *checked = !*checked;
+        }
+    }
+
+    fn selected_paths(&self) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(file, _)| file.path.clone())
+            .collect()
+    }
+
+    fn restore(&mut self) {
+        let paths = self.selected_paths();
+        if paths.is_empty() {
+            return;
+        }
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::RestoreUndoSnapshot {
+                commit_id: self.commit_id.clone(),
+                paths,
+            }));
+        self.complete = true;
+    }
+
+    fn move_up(&mut self) {
+        let len = self.files.len();
+        self.state.move_up_wrap(len);
+        self.state
+            .ensure_visible(len, MAX_POPUP_ROWS.min(len.max(1)));
+    }
+
+    fn move_down(&mut self) {
+        let len = self.files.len();
+        self.state.move_down_wrap(len);
+        self.state
+            .ensure_visible(len, MAX_POPUP_ROWS.min(len.max(1)));
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .enumerate()
+            .map(|(idx, (file, checked))| {
+                let prefix = if self.state.selected_idx == Some(idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let mark = if *checked { 'x' } else { ' ' };
+                GenericDisplayRow {
+                    name: format!("{prefix} [{mark}] {}", file.path.display()),
+                    display_shortcut: None,
+                    match_indices: None,
+                    description: Some(format!("+{} -{}", file.insertions, file.deletions)),
+                    wrap_indent: None,
+                }
+            })
+            .collect()
+    }
+
+    fn header_lines(&self) -> Vec<Line<'static>> {
+        let short_id: String = self.commit_id.chars().take(7).collect();
+        let selected = self.checked.iter().filter(|checked| **checked).count();
+        vec![
+            Line::from(format!("Restore files from snapshot {short_id}").bold()),
+            Line::from(format!("{selected} of {} selected", self.files.len()).dim()),
+        ]
+    }
+}
+
+impl BottomPaneView for UndoFilesView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('k'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_down(),
+            KeyEvent {
+                code: KeyCode::Char(' '),
+                ..
+            } => self.toggle(),
+            KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            } => self.restore(),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for UndoFilesView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_height = measure_rows_height(&rows, &self.state, MAX_POPUP_ROWS, width);
+        // Header, a blank line, the rows, and the hint, inside one line of padding.
+        rows_height + 6
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, hint_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let [header_area, _, list_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(content_area.inset(Insets::vh(1, 2)));
+        for (line, y) in self.header_lines().into_iter().zip(header_area.y..) {
+            if y >= header_area.bottom() {
+                break;
+            }
+            line.render(
+                Rect {
+                    y,
+                    height: 1,
+                    ..header_area
+                },
+                buf,
+            );
+        }
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                width: list_area.width.saturating_add(2),
+                ..list_area
+            };
+            render_rows(
+                render_area,
+                buf,
+                &self.build_rows(),
+                &self.state,
+                render_area.height as usize,
+                "no changed files",
+            );
+        }
+
+        Line::from("space to select · enter to restore · esc to cancel")
+            .dim()
+            .render(
+                Rect {
+                    x: hint_area.x + 2,
+                    width: hint_area.width.saturating_sub(2),
+                    ..hint_area
+                },
+                buf,
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn file(path: &str) -> UndoSnapshotFile {
+        UndoSnapshotFile {
+            path: PathBuf::from(path),
+            insertions: 1,
+            deletions: 0,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn restores_only_checked_files() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = UndoFilesView::new(
+            "abc1234def".to_string(),
+            vec![file("a.txt"), file("b.txt"), file("c.txt")],
+            AppEventSender::new(tx),
+        );
+
+        // Nothing happens until a file is checked.
+        view.handle_key_event(key(KeyCode::Enter));
+        assert!(!view.is_complete());
+
+        view.handle_key_event(key(KeyCode::Char(' ')));
+        view.handle_key_event(key(KeyCode::Down));
+        view.handle_key_event(key(KeyCode::Down));
+        view.handle_key_event(key(KeyCode::Char(' ')));
+        view.handle_key_event(key(KeyCode::Enter));
+
+        assert!(view.is_complete());
+        match rx.try_recv() {
+            Ok(AppEvent::CodexOp(Op::RestoreUndoSnapshot { commit_id, paths })) => {
+                assert_eq!(commit_id, "abc1234def");
+                assert_eq!(paths, vec![PathBuf::from("a.txt"), PathBuf::from("c.txt")]);
+            }
+            other => panic!("expected a restore op, got {other:?}"),
+        }
+    }
+}
@@ -33,8 +33,12 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::ListMcpPromptsResponseEvent;
+use codex_core::protocol::ListProcessesResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpSamplingEvent;
+use codex_core::protocol::McpSamplingStatus;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
@@ -54,7 +58,11 @@ use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
+use codex_core::protocol::UndoSnapshot;
+use codex_core::protocol::UndoSnapshotDiffEvent;
+use codex_core::protocol::UndoSnapshotFile;
 use codex_core::protocol::UndoStartedEvent;
+use codex_core::protocol::UndoTimelineEvent;
 use codex_core::protocol::UserMessageEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
@@ -89,9 +97,12 @@ use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::McpPromptCommand;
+use crate::bottom_pane::McpPromptInvocation;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::UndoFilesView;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
@@ -322,6 +333,8 @@ pub(crate) struct ChatWidget {
     queued_user_messages: VecDeque<UserMessage>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
+    // Undo snapshot whose diff was requested to pick files to restore
+    undo_file_picker: Option<String>,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
@@ -754,6 +767,10 @@ impl ChatWidget {
             self.on_warning(format!("MCP startup incomplete ({})", parts.join("; ")));
         }
 
+        if !ev.ready.is_empty() {
+            self.submit_op(Op::ListMcpPrompts);
+        }
+
         self.mcp_startup_status = None;
         self.bottom_pane.set_task_running(false);
         self.maybe_send_next_queued_input();
@@ -927,6 +944,32 @@ impl ChatWidget {
         self.set_status_header(message);
     }
 
+    fn on_mcp_sampling(&mut self, event: McpSamplingEvent) {
+        let McpSamplingEvent {
+            server,
+            status,
+            model,
+            tokens_used,
+            budget_remaining,
+        } = event;
+        match status {
+            McpSamplingStatus::Completed => {
+                let model = model.unwrap_or_else(|| "the model".to_string());
+                self.add_info_message(
+                    format!("MCP server `{server}` sampled {model} ({tokens_used} tokens)"),
+                    Some(format!("{budget_remaining} tokens left in its budget")),
+                );
+            }
+            McpSamplingStatus::Declined => {}
+            McpSamplingStatus::BudgetExhausted => self.add_error_message(format!(
+                "MCP server `{server}` has used its sampling token budget"
+            )),
+            McpSamplingStatus::Failed { error } => self.add_error_message(format!(
+                "Sampling request from MCP server `{server}` failed: {error}"
+            )),
+        }
+    }
+
     fn on_undo_started(&mut self, event: UndoStartedEvent) {
         self.bottom_pane.ensure_status_indicator();
         self.bottom_pane.set_interrupt_hint_visible(false);
@@ -1330,6 +1373,7 @@ impl ChatWidget {
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            undo_file_picker: None,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
@@ -1414,6 +1458,7 @@ impl ChatWidget {
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
+            undo_file_picker: None,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
@@ -1500,6 +1545,9 @@ impl ChatWidget {
                     InputResult::CommandWithArgs(cmd, args) => {
                         self.dispatch_command_with_args(cmd, args);
                     }
+                    InputResult::McpPrompt(invocation) => {
+                        self.submit_mcp_prompt(invocation);
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1582,9 +1630,9 @@ impl ChatWidget {
                 }
                 self.request_exit();
             }
-            // SlashCommand::Undo => {
-            //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
-            // }
+            SlashCommand::Undo => {
+                self.submit_op(Op::ListUndoSnapshots);
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -1614,6 +1662,9 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Ps => {
+                self.submit_op(Op::ListProcesses);
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -1626,7 +1677,7 @@ impl ChatWidget {
             }
             SlashCommand::ThinThread => {
                 self.add_info_message(
-                    "Usage: /thinthread <fix|review> <file>\n       /thinthread apply <job_id>\n       /thinthread cancel <job_id>".to_string(),
+                    "Usage: /thinthread <fix|review> <file>...\n       /thinthread apply <job_id>\n       /thinthread cancel <job_id>\n       /thinthread jobs\n       /thinthread runs".to_string(),
                     None,
                 );
             }
@@ -1700,16 +1751,24 @@ impl ChatWidget {
                         };
                         if args_vec.len() < 2 {
                             self.add_error_message(format!(
-                                "Usage: /thinthread {} <file>",
+                                "Usage: /thinthread {} <file>...",
                                 args_vec[0]
                             ));
                             return;
                         }
-                        let target_str = args_vec[1];
-                        // Basic resolution relative to CWD
-                        let target = self.config.cwd.join(target_str);
-                        self.app_event_tx
-                            .send(AppEvent::StartCouncilJob { mode, target });
+                        // One job per file; the job manager queues beyond its concurrency limit.
+                        for target_str in &args_vec[1..] {
+                            // Basic resolution relative to CWD
+                            let target = self.config.cwd.join(target_str);
+                            self.app_event_tx
+                                .send(AppEvent::StartCouncilJob { mode, target });
+                        }
+                    }
+                    "jobs" => {
+                        self.app_event_tx.send(AppEvent::ShowCouncilJobs);
+                    }
+                    "runs" => {
+                        self.app_event_tx.send(AppEvent::OpenCouncilRuns);
                     }
                     "apply" => {
                         if args_vec.len() < 2 {
@@ -1868,6 +1927,22 @@ impl ChatWidget {
         self.needs_final_message_separator = false;
     }
 
+    fn submit_mcp_prompt(&mut self, invocation: McpPromptInvocation) {
+        let McpPromptInvocation {
+            command,
+            server,
+            prompt,
+            arguments,
+        } = invocation;
+        self.submit_op(Op::RunMcpPrompt {
+            server,
+            prompt,
+            arguments,
+        });
+        self.add_to_history(history_cell::new_user_prompt(command));
+        self.needs_final_message_separator = false;
+    }
+
     /// Replay a subset of initial events into the UI to seed the transcript when
     /// resuming an existing session. This approximates the live event flow and
     /// is intentionally conservative: only safe-to-replay items are rendered to
@@ -1968,11 +2043,14 @@ impl ChatWidget {
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
+            EventMsg::McpSampling(ev) => self.on_mcp_sampling(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ListMcpPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::ListProcessesResponse(ev) => self.on_list_processes(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
@@ -1988,6 +2066,8 @@ impl ChatWidget {
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::UndoTimeline(ev) => self.on_undo_timeline(ev),
+            EventMsg::UndoSnapshotDiff(ev) => self.on_undo_snapshot_diff(ev),
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
@@ -3214,10 +3294,138 @@ impl ChatWidget {
             ev.tools,
             ev.resources,
             ev.resource_templates,
+            ev.prompts,
             &ev.auth_statuses,
         ));
     }
 
+    fn on_list_processes(&mut self, ev: ListProcessesResponseEvent) {
+        self.add_to_history(history_cell::new_processes_output(&ev.processes));
+    }
+
+    fn on_undo_timeline(&mut self, ev: UndoTimelineEvent) {
+        let UndoTimelineEvent {
+            snapshots,
+            redo_available,
+        } = ev;
+        if snapshots.is_empty() && !redo_available {
+            self.add_info_message(
+                "No snapshots to undo yet.".to_string(),
+                Some("Snapshots are taken at the start of each turn in a git repository.".into()),
+            );
+            return;
+        }
+
+        let mut items: Vec<SelectionItem> = Vec::with_capacity(snapshots.len() + 1);
+        if redo_available {
+            items.push(SelectionItem {
+                name: "Redo last undo".to_string(),
+                description: Some("bring back the changes the last undo replaced".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::Redo));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        // Newest first, since that is what undo usually goes back to.
+        for snapshot in snapshots.into_iter().rev() {
+            let short_id: String = snapshot.commit_id.chars().take(7).collect();
+            let summary = snapshot.summary.as_deref().unwrap_or("(no message)");
+            items.push(SelectionItem {
+                name: format!("{short_id}  {summary}"),
+                description: Some(undo_snapshot_stats(&snapshot.files)),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenUndoSnapshotActions(snapshot.clone()));
+                })],
+                dismiss_on_select: false,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Undo timeline".to_string()),
+            subtitle: Some("Go back to the start of a turn.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_undo_snapshot_actions(&mut self, snapshot: UndoSnapshot) {
+        let short_id: String = snapshot.commit_id.chars().take(7).collect();
+        let commit_id = snapshot.commit_id;
+        let items = vec![
+            SelectionItem {
+                name: "Preview changes since this point".to_string(),
+                actions: vec![Box::new({
+                    let commit_id = commit_id.clone();
+                    move |tx: &AppEventSender| {
+                        tx.send(AppEvent::RequestUndoSnapshotDiff {
+                            commit_id: commit_id.clone(),
+                            pick_files: false,
+                        });
+                    }
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Restore to this point".to_string(),
+                description: Some("undo this turn and every later one".to_string()),
+                actions: vec![Box::new({
+                    let commit_id = commit_id.clone();
+                    move |tx: &AppEventSender| {
+                        tx.send(AppEvent::CodexOp(Op::RestoreUndoSnapshot {
+                            commit_id: commit_id.clone(),
+                            paths: Vec::new(),
+                        }));
+                    }
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Restore selected files…".to_string(),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::RequestUndoSnapshotDiff {
+                        commit_id: commit_id.clone(),
+                        pick_files: true,
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("Snapshot {short_id}")),
+            subtitle: snapshot.summary,
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn request_undo_snapshot_diff(&mut self, commit_id: String, pick_files: bool) {
+        self.undo_file_picker = pick_files.then(|| commit_id.clone());
+        self.submit_op(Op::GetUndoSnapshotDiff { commit_id });
+    }
+
+    fn on_undo_snapshot_diff(&mut self, ev: UndoSnapshotDiffEvent) {
+        if self.undo_file_picker.take().as_ref() != Some(&ev.commit_id) {
+            self.app_event_tx.send(AppEvent::DiffResult(ev.diff));
+            return;
+        }
+        if ev.files.is_empty() {
+            self.add_info_message("No files changed since this snapshot.".to_string(), None);
+            return;
+        }
+        let view = UndoFilesView::new(ev.commit_id, ev.files, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
         debug!("received {len} custom prompts");
@@ -3225,6 +3433,21 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: ListMcpPromptsResponseEvent) {
+        let prompts: Vec<McpPromptCommand> = ev
+            .prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
         self.set_skills_from_response(&ev);
     }
@@ -3613,6 +3836,16 @@ pub(crate) fn show_review_commit_picker_with_entries(
     });
 }
 
+fn undo_snapshot_stats(files: &[UndoSnapshotFile]) -> String {
+    if files.is_empty() {
+        return "no file changes".to_string();
+    }
+    let insertions: u64 = files.iter().map(|file| file.insertions).sum();
+    let deletions: u64 = files.iter().map(|file| file.deletions).sum();
+    let noun = if files.len() == 1 { "file" } else { "files" };
+    format!("{} {noun} changed, +{insertions} -{deletions}", files.len())
+}
+
 fn find_skill_mentions(text: &str, skills: &[SkillMetadata]) -> Vec<SkillMetadata> {
     let mut seen: HashSet<String> = HashSet::new();
     let mut matches: Vec<SkillMetadata> = Vec::new();
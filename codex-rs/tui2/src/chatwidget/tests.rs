@@ -398,6 +398,7 @@ async fn make_chatwidget_manual(
         queued_user_messages: VecDeque::new(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
+        undo_file_picker: None,
         is_review_mode: false,
         pre_review_token_info: None,
         needs_final_message_separator: false,
@@ -910,6 +911,8 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     });
 }
@@ -1165,6 +1168,8 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     });
 
@@ -1797,6 +1802,8 @@ async fn preset_matching_ignores_extra_writable_roots() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        read_only_subpaths: vec![],
+        deny_read_paths: vec![],
     };
 
     assert!(
@@ -3226,6 +3233,8 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            resource_limit_exceeded: None,
+            output_log: None,
         }),
     });
     chat.handle_codex_event(Event {
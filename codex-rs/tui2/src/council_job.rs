@@ -5,8 +5,13 @@ use anyhow::anyhow;
 use codex_council::CouncilConfig;
 use codex_council::CouncilMode;
 use codex_council::CouncilRunner;
+use codex_council::JobOutcome;
 use codex_council::cleanup_old_jobs;
+use codex_council::create_run_dir;
 use codex_council::parsing;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use std::collections::VecDeque;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::sync::mpsc::UnboundedSender;
@@ -14,10 +19,50 @@ use tokio_util::sync::CancellationToken;
 use tracing::error;
 use tracing::info;
 
+/// Number of finished jobs kept for the `/thinthread jobs` view.
+const MAX_FINISHED_JOBS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CouncilJobStatus {
+    Queued,
+    Running,
+    Finished(JobOutcome),
+}
+
+#[derive(Clone)]
+pub(crate) struct CouncilJob {
+    pub(crate) id: String,
+    pub(crate) mode: CouncilMode,
+    pub(crate) target: PathBuf,
+    pub(crate) status: CouncilJobStatus,
+    pub(crate) summary: Option<String>,
+    cancel_token: CancellationToken,
+}
+
+/// A job waiting for a free slot, together with everything needed to start it.
+#[derive(Clone)]
+struct PendingJob {
+    job_id: String,
+    job_dir: PathBuf,
+    config: CouncilConfig,
+    event_tx: UnboundedSender<AppEvent>,
+}
+
+/// Result of submitting a job to the manager.
+#[derive(Debug, PartialEq)]
+pub(crate) enum SpawnedJob {
+    Started(String),
+    Queued { job_id: String, position: usize },
+}
+
+/// Runs Council jobs in parallel up to `max_concurrent_jobs`, queueing the rest.
+///
+/// Every job gets its own run directory, worktree (keyed by run id), progress cell and
+/// cancellation token.
 #[derive(Default, Clone)]
 pub(crate) struct CouncilJobManager {
-    active_job_id: Option<String>,
-    cancel_token: Option<CancellationToken>,
+    jobs: Vec<CouncilJob>,
+    queue: VecDeque<PendingJob>,
 }
 
 impl CouncilJobManager {
@@ -25,13 +70,11 @@ impl CouncilJobManager {
         Self::default()
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn is_running(&self) -> bool {
-        self.active_job_id.is_some()
-    }
-
-    pub(crate) fn active_job_id(&self) -> Option<String> {
-        self.active_job_id.clone()
+    pub(crate) fn running_count(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.status == CouncilJobStatus::Running)
+            .count()
     }
 
     pub(crate) async fn spawn_job(
@@ -40,34 +83,63 @@ impl CouncilJobManager {
         target: PathBuf,
         config: CouncilConfig,
         event_tx: UnboundedSender<AppEvent>,
-    ) -> Result<String> {
-        if let Some(id) = &self.active_job_id {
-            return Err(anyhow!("A Council job is already running (id={id})."));
-        }
-
+    ) -> Result<SpawnedJob> {
         // Cleanup old jobs
         let repo_root = config.repo_root.clone();
+        let retention = config.settings.retention.clone();
         tokio::spawn(async move {
-            if let Err(e) = cleanup_old_jobs(repo_root).await {
+            if let Err(e) = cleanup_old_jobs(repo_root, retention).await {
                 error!("Failed to cleanup old jobs: {}", e);
             }
         });
 
-        // Setup job
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)?
-            .as_secs();
-        let run_id = format!("run-{timestamp}");
-        let job_dir = config.repo_root.join(".council").join("runs").join(&run_id);
-        tokio::fs::create_dir_all(&job_dir).await?;
+        let (job_id, job_dir) = create_run_dir(&config.repo_root).await?;
+        let max_concurrent = config.settings.max_concurrent_jobs.max(1);
 
-        let (council_tx, mut council_rx) = tokio::sync::mpsc::channel(100);
-        let cancel_token = CancellationToken::new();
+        self.jobs.push(CouncilJob {
+            id: job_id.clone(),
+            mode,
+            target,
+            status: CouncilJobStatus::Queued,
+            summary: None,
+            cancel_token: CancellationToken::new(),
+        });
+        self.queue.push_back(PendingJob {
+            job_id: job_id.clone(),
+            job_dir,
+            config,
+            event_tx,
+        });
+
+        if self.running_count() < max_concurrent {
+            self.start_next();
+        }
+
+        match self.queue.iter().position(|p| p.job_id == job_id) {
+            Some(idx) => Ok(SpawnedJob::Queued {
+                job_id,
+                position: idx + 1,
+            }),
+            None => Ok(SpawnedJob::Started(job_id)),
+        }
+    }
 
-        self.active_job_id = Some(run_id.clone());
-        self.cancel_token = Some(cancel_token.clone());
+    /// Starts the oldest queued job, if any. Returns its id.
+    fn start_next(&mut self) -> Option<String> {
+        let pending = self.queue.pop_front()?;
+        let job = self.jobs.iter_mut().find(|job| job.id == pending.job_id)?;
+        job.status = CouncilJobStatus::Running;
 
-        let runner = CouncilRunner::new(config, council_tx, cancel_token, job_dir);
+        let PendingJob {
+            job_id,
+            job_dir,
+            config,
+            event_tx,
+        } = pending;
+        let (council_tx, mut council_rx) = tokio::sync::mpsc::channel(100);
+        let runner = CouncilRunner::new(config, council_tx, job.cancel_token.clone(), job_dir);
+        let mode = job.mode;
+        let target = job.target.clone();
 
         // Spawn runner
         tokio::spawn(async move {
@@ -77,29 +149,116 @@ impl CouncilJobManager {
         });
 
         // Spawn bridge
-        let bridge_tx = event_tx.clone();
-        let bridge_run_id = run_id.clone();
+        let bridge_run_id = job_id.clone();
         tokio::spawn(async move {
             while let Some(event) = council_rx.recv().await {
                 // Bridge to TUI
-                let _ = bridge_tx.send(AppEvent::CouncilJobEvent(bridge_run_id.clone(), event));
+                let _ = event_tx.send(AppEvent::CouncilJobEvent(bridge_run_id.clone(), event));
             }
         });
 
-        Ok(run_id)
+        Some(job_id)
     }
 
-    pub(crate) fn cancel_active_job(&mut self) {
-        if let Some(token) = &self.cancel_token {
-            token.cancel();
+    /// Cancels a running job or drops a queued one.
+    pub(crate) fn cancel_job(&mut self, job_id: &str) -> Result<()> {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == job_id) else {
+            return Err(anyhow!("No Council job with id {job_id}."));
+        };
+        match job.status {
+            CouncilJobStatus::Running => {
+                job.cancel_token.cancel();
+                Ok(())
+            }
+            CouncilJobStatus::Queued => {
+                // The run directory of a job that never started is still empty.
+                if let Some(pending) = self.queue.iter().find(|pending| pending.job_id == job_id) {
+                    let _ = std::fs::remove_dir(&pending.job_dir);
+                }
+                self.queue.retain(|pending| pending.job_id != job_id);
+                job.status = CouncilJobStatus::Finished(JobOutcome::Cancelled);
+                job.summary = Some("Removed from queue.".to_string());
+                Ok(())
+            }
+            CouncilJobStatus::Finished(_) => {
+                Err(anyhow!("Council job {job_id} has already finished."))
+            }
+        }
+    }
+
+    /// Records the outcome of a finished job and starts the next queued one.
+    /// Returns the id of the job that was started, if any.
+    pub(crate) fn on_job_finished(
+        &mut self,
+        job_id: &str,
+        outcome: JobOutcome,
+        summary: String,
+    ) -> Option<String> {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == job_id) {
+            job.status = CouncilJobStatus::Finished(outcome);
+            job.summary = Some(summary);
         }
+        self.prune_finished();
+        self.start_next()
     }
 
-    pub(crate) fn on_job_finished(&mut self, job_id: &str) {
-        if self.active_job_id.as_deref() == Some(job_id) {
-            self.active_job_id = None;
-            self.cancel_token = None;
+    fn prune_finished(&mut self) {
+        let finished = self
+            .jobs
+            .iter()
+            .filter(|job| matches!(job.status, CouncilJobStatus::Finished(_)))
+            .count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && matches!(job.status, CouncilJobStatus::Finished(_)) {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Lines for the `/thinthread jobs` view: queued, running and finished runs.
+    pub(crate) fn jobs_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = vec!["🧵 ThinThread jobs".red().bold().into()];
+        if self.jobs.is_empty() {
+            lines.push("  No Council jobs in this session.".dim().into());
+            return lines;
         }
+
+        for job in &self.jobs {
+            let status = match &job.status {
+                CouncilJobStatus::Queued => {
+                    let position = self
+                        .queue
+                        .iter()
+                        .position(|p| p.job_id == job.id)
+                        .map(|idx| idx + 1)
+                        .unwrap_or_default();
+                    format!("queued #{position}").dim()
+                }
+                CouncilJobStatus::Running => "running".cyan(),
+                CouncilJobStatus::Finished(JobOutcome::Success) => "success".green(),
+                CouncilJobStatus::Finished(JobOutcome::Failure) => "failed".red(),
+                CouncilJobStatus::Finished(JobOutcome::Cancelled) => "cancelled".magenta(),
+            };
+            let mut spans = vec![
+                "  ".into(),
+                job.id.clone().into(),
+                "  ".into(),
+                status,
+                "  ".into(),
+                format!("{:?} ", job.mode).dim(),
+                job.target.display().to_string().into(),
+            ];
+            if let Some(summary) = &job.summary {
+                spans.push(" — ".dim());
+                spans.push(summary.clone().dim());
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
     }
 
     pub(crate) async fn apply_job(&self, job_id: &str, repo_root: &Path) -> Result<()> {
@@ -119,17 +278,7 @@ impl CouncilJobManager {
             .await
             .context("Failed to read patch file")?;
 
-        let patch_content = if let Some(p) = parsing::extract_patch(&patch_content_raw) {
-            p
-        } else if patch_content_raw.contains("```") {
-            patch_content_raw
-                .split("```")
-                .nth(1)
-                .unwrap_or(&patch_content_raw)
-                .to_string()
-        } else {
-            patch_content_raw
-        };
+        let patch_content = parsing::extract_patch_lenient(&patch_content_raw);
 
         if !parsing::looks_like_apply_patch(&patch_content) {
             return Err(anyhow!("Patch content failed validation."));
@@ -141,20 +290,9 @@ impl CouncilJobManager {
 
         info!("Applying patch for job {}...", job_id);
 
-        // 1. Dry Run Check (Strict Gate)
-        // We use git apply --check if possible, but our patch format is custom (apply_patch tool).
-        // Since we are applying to the REAL repo root, we must be careful.
-        // The spec says: "If git apply --check is not suitable... implement equivalent."
-        // codex_apply_patch doesn't have a dry-run mode exposed yet?
-        // Let's assume we proceed with caution or check if we can add dry-run to codex_apply_patch later.
-        // For now, we will RELY on the fact that we just ran this in a worktree.
-        // BUT the "Gate" requires a check against current state.
-
-        // TODO: Add dry-run to codex-apply-patch crate.
-        // For MVP without modifying apply-patch crate deeply:
-        // We can check if files exist and permissions are okay?
-        // Or we just proceed because the user explicitly typed "/thinthread apply".
-
+        // apply_patch verifies every hunk against the current tree before writing anything and
+        // restores the original files if a write fails, so a patch that no longer applies
+        // leaves the repo root untouched.
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
 
@@ -198,3 +336,95 @@ impl CouncilJobManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn queued(manager: &mut CouncilJobManager, job_id: &str) {
+        let (event_tx, _event_rx) = tokio::sync::mpsc::unbounded_channel();
+        manager.jobs.push(CouncilJob {
+            id: job_id.to_string(),
+            mode: CouncilMode::Review,
+            target: PathBuf::from("src/lib.rs"),
+            status: CouncilJobStatus::Queued,
+            summary: None,
+            cancel_token: CancellationToken::new(),
+        });
+        manager.queue.push_back(PendingJob {
+            job_id: job_id.to_string(),
+            job_dir: PathBuf::from("/tmp/does-not-matter"),
+            config: CouncilConfig {
+                repo_root: PathBuf::from("/tmp"),
+                prompt_version: "v2".to_string(),
+                chair_model: "chair".to_string(),
+                critic_gpt_model: "gpt".to_string(),
+                critic_gemini_model: "gemini".to_string(),
+                implementer_model: "implementer".to_string(),
+                settings: Default::default(),
+            },
+            event_tx,
+        });
+    }
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn cancelling_queued_job_removes_it_from_queue() {
+        let mut manager = CouncilJobManager::new();
+        queued(&mut manager, "run-1");
+        queued(&mut manager, "run-2");
+
+        manager
+            .cancel_job("run-1")
+            .expect("queued job can be cancelled");
+
+        assert_eq!(manager.queue.len(), 1);
+        assert_eq!(manager.queue[0].job_id, "run-2");
+        assert_eq!(
+            manager.jobs[0].status,
+            CouncilJobStatus::Finished(JobOutcome::Cancelled)
+        );
+        assert!(manager.cancel_job("run-1").is_err());
+        assert!(manager.cancel_job("run-404").is_err());
+    }
+
+    #[test]
+    fn cancelling_queued_job_removes_its_run_dir() {
+        let repo = tempfile::TempDir::new().expect("tempdir");
+        let job_dir = repo.path().join("run-1");
+        std::fs::create_dir(&job_dir).expect("create run dir");
+        let mut manager = CouncilJobManager::new();
+        queued(&mut manager, "run-1");
+        manager.queue[0].job_dir = job_dir.clone();
+
+        manager
+            .cancel_job("run-1")
+            .expect("queued job can be cancelled");
+
+        assert!(!job_dir.exists());
+    }
+
+    #[test]
+    fn jobs_view_lists_queue_positions_and_outcomes() {
+        let mut manager = CouncilJobManager::new();
+        queued(&mut manager, "run-1");
+        queued(&mut manager, "run-2");
+        manager
+            .cancel_job("run-1")
+            .expect("queued job can be cancelled");
+
+        let lines: Vec<String> = manager.jobs_lines().iter().map(line_text).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "🧵 ThinThread jobs".to_string(),
+                "  run-1  cancelled  Review src/lib.rs — Removed from queue.".to_string(),
+                "  run-2  queued #1  Review src/lib.rs".to_string(),
+            ]
+        );
+    }
+}
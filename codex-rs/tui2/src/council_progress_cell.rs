@@ -2,6 +2,7 @@ use crate::history_cell::HistoryCell;
 use codex_council::CouncilEvent;
 use codex_council::CouncilMode;
 use codex_council::JobOutcome;
+use codex_council::RunUsage;
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
 use std::path::PathBuf;
@@ -13,6 +14,7 @@ pub struct CouncilProgressState {
     pub phases: Vec<PhaseState>,
     pub outcome: Option<JobOutcome>,
     pub summary: Option<String>,
+    pub usage: Option<RunUsage>,
     pub dry_run_failed: bool,
 }
 
@@ -69,6 +71,7 @@ impl CouncilProgressCell {
             phases,
             outcome: None,
             summary: None,
+            usage: None,
             dry_run_failed: false,
         };
 
@@ -115,9 +118,11 @@ impl CouncilProgressCell {
             CouncilEvent::JobFinished {
                 outcome,
                 summary_line,
+                usage,
             } => {
                 state.outcome = Some(outcome.clone());
                 state.summary = Some(summary_line);
+                state.usage = usage;
                 for p in state.phases.iter_mut() {
                     if p.status == PhaseStatus::Running {
                         p.status = if outcome == JobOutcome::Success {
@@ -209,6 +214,15 @@ impl HistoryCell for CouncilProgressCell {
                 state.summary.clone().unwrap_or_default().into(),
             ]));
 
+            if let Some(usage) = &state.usage
+                && usage.total.calls > 0
+            {
+                lines.push(Line::from(vec![
+                    "  Usage: ".dim(),
+                    usage.summary_line().dim(),
+                ]));
+            }
+
             if self.mode == CouncilMode::Fix && *outcome == JobOutcome::Success {
                 lines.push(Line::from("")); // Spacer
                 lines.push(Line::from(vec![
@@ -102,7 +102,12 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
         let (added, removed) = match change {
             FileChange::Add { content } => (content.lines().count(), 0),
             FileChange::Delete { content } => (0, content.lines().count()),
-            FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+            FileChange::Update { unified_diff, .. } | FileChange::Copy { unified_diff, .. } => {
+                calculate_add_remove_from_diff(unified_diff)
+            }
+            FileChange::Mode { .. } | FileChange::Symlink { .. } | FileChange::Binary { .. } => {
+                (0, 0)
+            }
         };
         let move_path = match change {
             FileChange::Update {
@@ -142,6 +147,9 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         if let Some(move_path) = &row.move_path {
             spans.push(format!(" → {}", display_path_for(move_path, cwd)).into());
         }
+        if let FileChange::Copy { source, .. } = &row.change {
+            spans.push(format!(" (copy of {})", display_path_for(source, cwd)).into());
+        }
         spans
     };
 
@@ -153,8 +161,14 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. }
+            | FileChange::Symlink { .. }
+            | FileChange::Binary {
+                previous_size: None,
+                ..
+            } => "Added",
             FileChange::Delete { .. } => "Deleted",
+            FileChange::Copy { .. } => "Copied",
             _ => "Edited",
         };
         header_spans.push(verb.bold());
@@ -219,79 +233,107 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                 ));
             }
         }
-        FileChange::Update { unified_diff, .. } => {
-            if let Ok(patch) = diffy::Patch::from_str(unified_diff) {
-                let mut max_line_number = 0;
-                for h in patch.hunks() {
-                    let mut old_ln = h.old_range().start();
-                    let mut new_ln = h.new_range().start();
-                    for l in h.lines() {
-                        match l {
-                            diffy::Line::Insert(_) => {
-                                max_line_number = max_line_number.max(new_ln);
-                                new_ln += 1;
-                            }
-                            diffy::Line::Delete(_) => {
-                                max_line_number = max_line_number.max(old_ln);
-                                old_ln += 1;
-                            }
-                            diffy::Line::Context(_) => {
-                                max_line_number = max_line_number.max(new_ln);
-                                old_ln += 1;
-                                new_ln += 1;
-                            }
-                        }
+        FileChange::Update { unified_diff, .. } | FileChange::Copy { unified_diff, .. } => {
+            render_unified_diff(unified_diff, out, width);
+        }
+        FileChange::Mode { old_mode, new_mode } => {
+            let description = match old_mode {
+                Some(old_mode) => format!("mode {old_mode:o} → {new_mode:o}"),
+                None => format!("mode {new_mode:o}"),
+            };
+            out.push(RtLine::from(description.dim()));
+        }
+        FileChange::Symlink { target } => {
+            out.push(RtLine::from(
+                format!("symlink to {}", target.display()).dim(),
+            ));
+        }
+        FileChange::Binary {
+            size,
+            previous_size,
+        } => {
+            let description = match previous_size {
+                Some(previous_size) => {
+                    format!("binary file, {previous_size} → {size} bytes")
+                }
+                None => format!("binary file, {size} bytes"),
+            };
+            out.push(RtLine::from(description.dim()));
+        }
+    }
+}
+
+fn render_unified_diff(unified_diff: &str, out: &mut Vec<RtLine<'static>>, width: usize) {
+    if let Ok(patch) = diffy::Patch::from_str(unified_diff) {
+        let mut max_line_number = 0;
+        for h in patch.hunks() {
+            let mut old_ln = h.old_range().start();
+            let mut new_ln = h.new_range().start();
+            for l in h.lines() {
+                match l {
+                    diffy::Line::Insert(_) => {
+                        max_line_number = max_line_number.max(new_ln);
+                        new_ln += 1;
+                    }
+                    diffy::Line::Delete(_) => {
+                        max_line_number = max_line_number.max(old_ln);
+                        old_ln += 1;
+                    }
+                    diffy::Line::Context(_) => {
+                        max_line_number = max_line_number.max(new_ln);
+                        old_ln += 1;
+                        new_ln += 1;
                     }
                 }
-                let line_number_width = line_number_width(max_line_number);
-                let mut is_first_hunk = true;
-                for h in patch.hunks() {
-                    if !is_first_hunk {
-                        let spacer = format!("{:width$} ", "", width = line_number_width.max(1));
-                        let spacer_span = RtSpan::styled(spacer, style_gutter());
-                        out.push(RtLine::from(vec![spacer_span, "⋮".dim()]));
+            }
+        }
+        let line_number_width = line_number_width(max_line_number);
+        let mut is_first_hunk = true;
+        for h in patch.hunks() {
+            if !is_first_hunk {
+                let spacer = format!("{:width$} ", "", width = line_number_width.max(1));
+                let spacer_span = RtSpan::styled(spacer, style_gutter());
+                out.push(RtLine::from(vec![spacer_span, "⋮".dim()]));
+            }
+            is_first_hunk = false;
+
+            let mut old_ln = h.old_range().start();
+            let mut new_ln = h.new_range().start();
+            for l in h.lines() {
+                match l {
+                    diffy::Line::Insert(text) => {
+                        let s = text.trim_end_matches('\n');
+                        out.extend(push_wrapped_diff_line(
+                            new_ln,
+                            DiffLineType::Insert,
+                            s,
+                            width,
+                            line_number_width,
+                        ));
+                        new_ln += 1;
+                    }
+                    diffy::Line::Delete(text) => {
+                        let s = text.trim_end_matches('\n');
+                        out.extend(push_wrapped_diff_line(
+                            old_ln,
+                            DiffLineType::Delete,
+                            s,
+                            width,
+                            line_number_width,
+                        ));
+                        old_ln += 1;
                     }
-                    is_first_hunk = false;
-
-                    let mut old_ln = h.old_range().start();
-                    let mut new_ln = h.new_range().start();
-                    for l in h.lines() {
-                        match l {
-                            diffy::Line::Insert(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    new_ln,
-                                    DiffLineType::Insert,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                new_ln += 1;
-                            }
-                            diffy::Line::Delete(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    old_ln,
-                                    DiffLineType::Delete,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                old_ln += 1;
-                            }
-                            diffy::Line::Context(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    new_ln,
-                                    DiffLineType::Context,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                old_ln += 1;
-                                new_ln += 1;
-                            }
-                        }
+                    diffy::Line::Context(text) => {
+                        let s = text.trim_end_matches('\n');
+                        out.extend(push_wrapped_diff_line(
+                            new_ln,
+                            DiffLineType::Context,
+                            s,
+                            width,
+                            line_number_width,
+                        ));
+                        old_ln += 1;
+                        new_ln += 1;
                     }
                 }
             }
@@ -32,6 +32,7 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::NetworkAccess;
+use codex_core::protocol::ProcessSummary;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -1469,6 +1470,7 @@ pub(crate) fn new_mcp_tools_output(
     tools: HashMap<String, mcp_types::Tool>,
     resources: HashMap<String, Vec<Resource>>,
     resource_templates: HashMap<String, Vec<ResourceTemplate>>,
+    prompts: HashMap<String, Vec<mcp_types::Prompt>>,
     auth_statuses: &HashMap<String, McpAuthStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
@@ -1621,11 +1623,87 @@ pub(crate) fn new_mcp_tools_output(
             lines.push(spans.into());
         }
 
+        // Only servers with the prompts capability report any, so skip the
+        // line instead of printing "(none)" for every other server.
+        if let Some(server_prompts) = prompts.get(server.as_str())
+            && !server_prompts.is_empty()
+        {
+            let commands: Vec<String> = server_prompts
+                .iter()
+                .map(|prompt| format!("/mcp:{server}:{}", prompt.name))
+                .collect();
+            lines.push(vec!["    • Prompts: ".into(), commands.join(", ").into()].into());
+        }
+
         lines.push(Line::from(""));
     }
 
     PlainHistoryCell { lines }
 }
+/// Output for the `/ps` command: background processes started by the agent.
+pub(crate) fn new_processes_output(processes: &[ProcessSummary]) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/ps".magenta().into(),
+        "".into(),
+        vec!["⚙️  ".into(), "Background Processes".bold()].into(),
+        "".into(),
+    ];
+
+    if processes.is_empty() {
+        lines.push("  • No background processes.".italic().into());
+        lines.push("".into());
+        return PlainHistoryCell { lines };
+    }
+
+    for process in processes {
+        let mut header: Vec<Span<'static>> = vec![
+            "  • ".into(),
+            strip_bash_lc_and_escape(&process.command).into(),
+        ];
+        if let Some(service) = &process.service {
+            header.push(" ".into());
+            header.push(format!("({service})").cyan());
+        }
+        lines.push(header.into());
+        lines.push(vec!["    • Session: ".into(), process.process_id.clone().into()].into());
+        let status = match process.exit_code {
+            Some(0) => "exited with code 0".green(),
+            Some(code) => format!("exited with code {code}").red(),
+            None => format!(
+                "running for {}",
+                crate::status_indicator_widget::fmt_elapsed_compact(process.age_secs)
+            )
+            .into(),
+        };
+        lines.push(vec!["    • Status: ".into(), status].into());
+        lines.push(
+            vec![
+                "    • Cwd: ".into(),
+                process.cwd.display().to_string().into(),
+            ]
+            .into(),
+        );
+        if !process.listening_ports.is_empty() {
+            let ports = process
+                .listening_ports
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(vec!["    • Ports: ".into(), ports.into()].into());
+        }
+        if !process.output_tail.is_empty() {
+            lines.push("    • Recent output:".into());
+            for line in process.output_tail.lines() {
+                lines.push(ansi_escape_line(&format!("      {line}")).dim());
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
     let mut line = vec!["• ".dim(), message.into()];
     if let Some(hint) = hint {
@@ -2034,6 +2112,7 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            sampling_token_budget: None,
         };
         config.mcp_servers.insert("docs".to_string(), stdio_config);
 
@@ -2053,6 +2132,7 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            sampling_token_budget: None,
         };
         config.mcp_servers.insert("http".to_string(), http_config);
 
@@ -2094,6 +2174,7 @@ mod tests {
             tools,
             HashMap::new(),
             HashMap::new(),
+            HashMap::new(),
             &auth_statuses,
         );
         let rendered = render_lines(&cell.display_lines(120)).join("\n");
@@ -2101,6 +2182,40 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn processes_output_lists_services_and_ports() {
+        let processes = vec![ProcessSummary {
+            process_id: "1000".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "npm run dev".into()],
+            cwd: PathBuf::from("/repo/web"),
+            service: Some("web".to_string()),
+            age_secs: 75,
+            exit_code: None,
+            output_tail: "ready on http://localhost:3000".to_string(),
+            listening_ports: vec![3000],
+        }];
+
+        let rendered = render_lines(&new_processes_output(&processes).display_lines(80));
+
+        assert_eq!(
+            rendered,
+            vec![
+                "/ps",
+                "",
+                "⚙️  Background Processes",
+                "",
+                "  • npm run dev (web)",
+                "    • Session: 1000",
+                "    • Status: running for 1m 15s",
+                "    • Cwd: /repo/web",
+                "    • Ports: 3000",
+                "    • Recent output:",
+                "      ready on http://localhost:3000",
+                "",
+            ]
+        );
+    }
+
     #[test]
     fn empty_agent_message_cell_transcript() {
         let cell = AgentMessageCell::new(vec![Line::default()], false);
@@ -29,9 +29,14 @@ use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 
+mod council_runs;
+
+pub(crate) use council_runs::CouncilRunsOverlay;
+
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    CouncilRuns(CouncilRunsOverlay),
 }
 
 impl Overlay {
@@ -50,10 +55,18 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_council_runs(
+        repo_root: std::path::PathBuf,
+        app_event_tx: crate::app_event_sender::AppEventSender,
+    ) -> Self {
+        Self::CouncilRuns(CouncilRunsOverlay::new(repo_root, app_event_tx))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::CouncilRuns(o) => o.handle_event(tui, event),
         }
     }
 
@@ -61,6 +74,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::CouncilRuns(o) => o.is_done(),
         }
     }
 }
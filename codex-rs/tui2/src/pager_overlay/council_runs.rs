@@ -0,0 +1,733 @@
+//! Full-screen browser for past Council runs (`/thinthread runs`).
+//!
+//! The list view shows every run under `.council/runs`; opening a run lets you tab
+//! through each critic's critique, the findings table, the plan, the patch and the
+//! before/after verification output.
+
+use std::collections::HashMap;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_core::protocol::FileChange;
+use codex_council::JobOutcome;
+use codex_council::artifacts;
+use codex_council::artifacts::RunArtifacts;
+use codex_council::artifacts::RunRecord;
+use codex_council::parsing::Severity;
+use codex_council::verify::VerifyResult;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+
+use super::CachedRenderable;
+use super::KEY_CTRL_C;
+use super::KEY_DOWN;
+use super::KEY_ENTER;
+use super::KEY_ESC;
+use super::KEY_J;
+use super::KEY_K;
+use super::KEY_Q;
+use super::KEY_UP;
+use super::PAGER_KEY_HINTS;
+use super::PagerView;
+use super::render_key_hints;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::diff_render::DiffSummary;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::render::renderable::Renderable;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_BACKTAB: KeyBinding = key_hint::shift(KeyCode::BackTab);
+const KEY_LEFT: KeyBinding = key_hint::plain(KeyCode::Left);
+const KEY_RIGHT: KeyBinding = key_hint::plain(KeyCode::Right);
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
+const KEY_A: KeyBinding = key_hint::plain(KeyCode::Char('a'));
+const KEY_E: KeyBinding = key_hint::plain(KeyCode::Char('e'));
+const KEY_R: KeyBinding = key_hint::plain(KeyCode::Char('r'));
+const KEY_Y: KeyBinding = key_hint::plain(KeyCode::Char('y'));
+
+/// Maximum lines of command output shown per verification step.
+const VERIFY_OUTPUT_MAX_LINES: usize = 200;
+
+pub(crate) struct CouncilRunsOverlay {
+    repo_root: PathBuf,
+    runs: Vec<RunRecord>,
+    selected: usize,
+    list: PagerView,
+    detail: Option<RunDetail>,
+    status: Option<String>,
+    /// Run whose patch waits for the user to confirm applying it.
+    pending_apply: Option<String>,
+    app_event_tx: AppEventSender,
+    is_done: bool,
+}
+
+struct RunDetail {
+    record: RunRecord,
+    artifacts: RunArtifacts,
+    tabs: Vec<(String, PagerView)>,
+    active: usize,
+}
+
+impl CouncilRunsOverlay {
+    pub(crate) fn new(repo_root: PathBuf, app_event_tx: AppEventSender) -> Self {
+        let runs = artifacts::list_runs(&repo_root);
+        let mut overlay = Self {
+            repo_root,
+            runs,
+            selected: 0,
+            list: PagerView::new(Vec::new(), String::new(), 0),
+            detail: None,
+            status: None,
+            pending_apply: None,
+            app_event_tx,
+            is_done: false,
+        };
+        overlay.rebuild_list();
+        overlay
+    }
+
+    fn rebuild_list(&mut self) {
+        let renderables: Vec<Box<dyn Renderable>> = if self.runs.is_empty() {
+            vec![Box::new(Line::from(
+                "No Council runs found under .council/runs.".italic(),
+            ))]
+        } else {
+            self.runs
+                .iter()
+                .enumerate()
+                .map(|(idx, run)| {
+                    Box::new(run_row(run, idx == self.selected)) as Box<dyn Renderable>
+                })
+                .collect()
+        };
+        let title = format!("C O U N C I L   R U N S ({})", self.runs.len());
+        self.list = PagerView::new(renderables, title, self.list.scroll_offset);
+        self.list.scroll_chunk_into_view(self.selected);
+    }
+
+    fn open_selected(&mut self) {
+        let Some(record) = self.runs.get(self.selected).cloned() else {
+            return;
+        };
+        let artifacts = RunArtifacts::load(&record.dir);
+        let tabs = build_tabs(&record, &artifacts, &self.repo_root);
+        self.detail = Some(RunDetail {
+            record,
+            artifacts,
+            tabs,
+            active: 0,
+        });
+        self.status = None;
+    }
+
+    fn selected_record(&self) -> Option<&RunRecord> {
+        match &self.detail {
+            Some(detail) => Some(&detail.record),
+            None => self.runs.get(self.selected),
+        }
+    }
+
+    fn request_apply(&mut self) {
+        let Some(run_id) = self.selected_record().map(|r| r.run_id.clone()) else {
+            return;
+        };
+        self.status = Some(format!(
+            "Apply the patch from {run_id}? Press y to apply, any other key to cancel."
+        ));
+        self.pending_apply = Some(run_id);
+    }
+
+    fn resolve_apply(&mut self, confirmed: bool) {
+        let Some(run_id) = self.pending_apply.take() else {
+            return;
+        };
+        if confirmed {
+            self.app_event_tx.send(AppEvent::ApplyCouncilJob(run_id));
+            self.is_done = true;
+        } else {
+            self.status = Some("Apply cancelled.".to_string());
+        }
+    }
+
+    fn rerun(&mut self) {
+        let Some(meta) = self.selected_record().and_then(|r| r.metadata.clone()) else {
+            self.status = Some("Run metadata is missing; cannot re-run.".to_string());
+            return;
+        };
+        let target = if meta.target.is_absolute() {
+            meta.target
+        } else {
+            self.repo_root.join(meta.target)
+        };
+        self.app_event_tx.send(AppEvent::StartCouncilJob {
+            mode: meta.mode,
+            target,
+        });
+        self.is_done = true;
+    }
+
+    fn export(&mut self) {
+        let Some(record) = self.selected_record().cloned() else {
+            return;
+        };
+        let artifacts = match &self.detail {
+            Some(detail) => detail.artifacts.clone(),
+            None => RunArtifacts::load(&record.dir),
+        };
+        let report = artifacts::export_markdown(&record, &artifacts);
+        let export_dir = self.repo_root.join(".council").join("exports");
+        let path = export_dir.join(format!("{}.md", record.run_id));
+        let result =
+            std::fs::create_dir_all(&export_dir).and_then(|()| std::fs::write(&path, report));
+        self.status = Some(match result {
+            Ok(()) => format!("Exported to {}", path.display()),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
+
+    fn switch_tab(&mut self, forward: bool) {
+        if let Some(detail) = &mut self.detail {
+            let len = detail.tabs.len();
+            if len == 0 {
+                return;
+            }
+            detail.active = if forward {
+                (detail.active + 1) % len
+            } else {
+                (detail.active + len - 1) % len
+            };
+        }
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if self.runs.is_empty() {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1).min(self.runs.len() - 1)
+        } else {
+            self.selected.saturating_sub(1)
+        };
+        self.rebuild_list();
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
+        match &self.detail {
+            Some(detail) => {
+                let mut spans: Vec<Span<'static>> = vec![" ".into()];
+                for (idx, (title, _)) in detail.tabs.iter().enumerate() {
+                    if idx > 0 {
+                        spans.push(" │ ".dim());
+                    }
+                    if idx == detail.active {
+                        spans.push(title.clone().cyan().bold());
+                    } else {
+                        spans.push(title.clone().dim());
+                    }
+                }
+                Paragraph::new(Line::from(spans)).render_ref(line1, buf);
+                render_key_hints(line2, buf, PAGER_KEY_HINTS);
+                let pairs: Vec<(&[KeyBinding], &str)> = vec![
+                    (&[KEY_TAB, KEY_BACKTAB], "to switch tab"),
+                    (&[KEY_A], "to apply"),
+                    (&[KEY_E], "to export"),
+                    (&[KEY_R], "to re-run"),
+                    (&[KEY_ESC], "to go back"),
+                    (&[KEY_Q], "to quit"),
+                ];
+                render_key_hints(line3, buf, &pairs);
+            }
+            None => {
+                let status = self.status.clone().unwrap_or_default();
+                Paragraph::new(Line::from(vec![" ".into(), status.dim()])).render_ref(line1, buf);
+                let pairs: Vec<(&[KeyBinding], &str)> = vec![
+                    (&[KEY_UP, KEY_DOWN], "to select"),
+                    (&[KEY_ENTER], "to open"),
+                ];
+                render_key_hints(line2, buf, &pairs);
+                let pairs: Vec<(&[KeyBinding], &str)> = vec![
+                    (&[KEY_A], "to apply"),
+                    (&[KEY_E], "to export"),
+                    (&[KEY_R], "to re-run"),
+                    (&[KEY_Q], "to quit"),
+                ];
+                render_key_hints(line3, buf, &pairs);
+            }
+        }
+        if let (Some(_), Some(status)) = (&self.detail, &self.status) {
+            // Surface export results on the tab line when a run is open.
+            let width = status.chars().count() as u16 + 2;
+            let x = line1.right().saturating_sub(width);
+            Paragraph::new(Line::from(format!(" {status} ").dim()))
+                .render_ref(Rect::new(x, line1.y, width.min(line1.width), 1), buf);
+        }
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        match &mut self.detail {
+            Some(detail) => {
+                if let Some((_, view)) = detail.tabs.get_mut(detail.active) {
+                    view.render(top, buf);
+                }
+            }
+            None => self.list.render(top, buf),
+        }
+        self.render_hints(bottom, buf);
+    }
+
+    fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        if self.pending_apply.is_some() {
+            if key_event.kind != KeyEventKind::Release {
+                self.resolve_apply(KEY_Y.is_press(key_event));
+                tui.frame_requester().schedule_frame();
+            }
+            return Ok(());
+        }
+        match key_event {
+            e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                self.is_done = true;
+            }
+            e if KEY_A.is_press(e) => self.request_apply(),
+            e if KEY_R.is_press(e) => self.rerun(),
+            e if KEY_E.is_press(e) => self.export(),
+            e if self.detail.is_some() => match e {
+                e if KEY_ESC.is_press(e) || KEY_BACKSPACE.is_press(e) => {
+                    self.detail = None;
+                    self.status = None;
+                }
+                e if KEY_TAB.is_press(e) || KEY_RIGHT.is_press(e) => self.switch_tab(true),
+                e if KEY_BACKTAB.is_press(e) || KEY_LEFT.is_press(e) => self.switch_tab(false),
+                other => {
+                    if let Some(detail) = &mut self.detail
+                        && let Some((_, view)) = detail.tabs.get_mut(detail.active)
+                    {
+                        return view.handle_key_event(tui, other);
+                    }
+                }
+            },
+            e if KEY_ESC.is_press(e) => {
+                self.is_done = true;
+            }
+            e if KEY_UP.is_press(e) || KEY_K.is_press(e) => self.move_selection(false),
+            e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => self.move_selection(true),
+            e if KEY_ENTER.is_press(e) => self.open_selected(),
+            other => return self.list.handle_key_event(tui, other),
+        }
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => self.handle_key_event(tui, key_event),
+            TuiEvent::Mouse(mouse_event) => match &mut self.detail {
+                Some(detail) => match detail.tabs.get_mut(detail.active) {
+                    Some((_, view)) => view.handle_mouse_scroll(tui, mouse_event),
+                    None => Ok(()),
+                },
+                None => self.list.handle_mouse_scroll(tui, mouse_event),
+            },
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+fn run_row(run: &RunRecord, selected: bool) -> Line<'static> {
+    let marker = if selected {
+        "› ".cyan().bold()
+    } else {
+        "  ".into()
+    };
+    let outcome = match run.status.as_ref().map(|s| &s.outcome) {
+        Some(JobOutcome::Success) => "success  ".green(),
+        Some(JobOutcome::Failure) => "failed   ".red(),
+        Some(JobOutcome::Cancelled) => "cancelled".magenta(),
+        None => "unknown  ".dim(),
+    };
+    let (mode, target) = match &run.metadata {
+        Some(meta) => (
+            format!("{:?}", meta.mode),
+            meta.target.display().to_string(),
+        ),
+        None => ("?".to_string(), String::new()),
+    };
+    let id = if selected {
+        run.run_id.clone().bold()
+    } else {
+        run.run_id.clone().into()
+    };
+    let mut spans = vec![
+        marker,
+        id,
+        "  ".into(),
+        outcome,
+        "  ".into(),
+        format!("{mode:<6} ").dim(),
+        target.into(),
+    ];
+    if run.pinned {
+        spans.push(" (pinned)".cyan());
+    }
+    if let Some(status) = &run.status {
+        spans.push(" — ".dim());
+        spans.push(status.summary_line.clone().dim());
+    }
+    Line::from(spans)
+}
+
+fn paragraph(lines: Vec<Line<'static>>) -> Box<dyn Renderable> {
+    Box::new(CachedRenderable::new(
+        Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }),
+    ))
+}
+
+fn text_view(text: &str, title: String) -> PagerView {
+    let lines = text.lines().map(|l| Line::from(l.to_string())).collect();
+    PagerView::new(vec![paragraph(lines)], title, 0)
+}
+
+fn markdown_view(text: &str, title: String) -> PagerView {
+    let mut lines = Vec::new();
+    crate::markdown::append_markdown(text, None, &mut lines);
+    PagerView::new(vec![paragraph(lines)], title, 0)
+}
+
+fn build_tabs(
+    record: &RunRecord,
+    artifacts: &RunArtifacts,
+    repo_root: &Path,
+) -> Vec<(String, PagerView)> {
+    let header = |tab: &str| format!("{} · {tab}", record.run_id);
+    let mut tabs = Vec::new();
+
+    for (critic, text) in &artifacts.critiques {
+        let name = format!("{critic} critique");
+        tabs.push((name.clone(), text_view(text, header(&name))));
+    }
+
+    tabs.push((
+        "Findings".to_string(),
+        findings_view(artifacts, header("Findings")),
+    ));
+
+    if let Some(plan) = &artifacts.plan {
+        tabs.push(("Plan".to_string(), markdown_view(plan, header("Plan"))));
+    }
+
+    if let Some(patch) = &artifacts.patch {
+        tabs.push((
+            "Patch".to_string(),
+            patch_view(patch, repo_root, header("Patch")),
+        ));
+    }
+
+    if artifacts.verify_baseline.is_some() || artifacts.verify_final.is_some() {
+        tabs.push((
+            "Verification".to_string(),
+            verification_view(artifacts, header("Verification")),
+        ));
+    }
+    tabs
+}
+
+fn findings_view(artifacts: &RunArtifacts, title: String) -> PagerView {
+    let findings = artifacts.findings();
+    let lines: Vec<Line<'static>> = if findings.is_empty() {
+        vec!["No structured findings in the critiques.".italic().into()]
+    } else {
+        let mut lines = vec![Line::from(
+            "SEV  CRITIC   LOCATION                        ISSUE".bold(),
+        )];
+        for f in findings {
+            let severity = f.finding.severity.label();
+            let severity = match f.finding.severity {
+                Severity::P0 => severity.red().bold(),
+                Severity::P1 => severity.magenta(),
+                _ => severity.dim(),
+            };
+            lines.push(Line::from(vec![
+                severity,
+                "   ".into(),
+                format!("{:<8} ", f.critic).dim(),
+                format!("{:<31} ", f.finding.field("location").unwrap_or_default()).cyan(),
+                f.finding.field("issue").unwrap_or_default().into(),
+            ]));
+        }
+        lines
+    };
+    PagerView::new(vec![paragraph(lines)], title, 0)
+}
+
+/// Renders the patch with `diff_render` when it parses against the current checkout,
+/// falling back to the raw patch text otherwise.
+fn patch_view(patch: &str, repo_root: &Path, title: String) -> PagerView {
+    let argv = vec!["apply_patch".to_string(), patch.to_string()];
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, repo_root) {
+        MaybeApplyPatchVerified::Body(action) => {
+            let changes: HashMap<PathBuf, FileChange> = action
+                .changes()
+                .iter()
+                .map(|(path, change)| {
+                    let change = match change {
+                        ApplyPatchFileChange::Add { content } => FileChange::Add {
+                            content: content.clone(),
+                        },
+                        ApplyPatchFileChange::Delete { content } => FileChange::Delete {
+                            content: content.clone(),
+                        },
+                        ApplyPatchFileChange::Update {
+                            unified_diff,
+                            move_path,
+                            ..
+                        } => FileChange::Update {
+                            unified_diff: unified_diff.clone(),
+                            move_path: move_path.clone(),
+                        },
+                        ApplyPatchFileChange::Copy {
+                            source,
+                            unified_diff,
+                        } => FileChange::Copy {
+                            source: source.clone(),
+                            unified_diff: unified_diff.clone(),
+                        },
+                        ApplyPatchFileChange::Mode { old_mode, new_mode } => FileChange::Mode {
+                            old_mode: *old_mode,
+                            new_mode: *new_mode,
+                        },
+                        ApplyPatchFileChange::Symlink { target } => FileChange::Symlink {
+                            target: target.clone(),
+                        },
+                        ApplyPatchFileChange::Binary {
+                            size,
+                            previous_size,
+                        } => FileChange::Binary {
+                            size: *size,
+                            previous_size: *previous_size,
+                        },
+                    };
+                    (path.clone(), change)
+                })
+                .collect();
+            let summary = DiffSummary::new(changes, repo_root.to_path_buf());
+            PagerView::new(vec![summary.into()], title, 0)
+        }
+        _ => text_view(patch, title),
+    }
+}
+
+fn verification_view(artifacts: &RunArtifacts, title: String) -> PagerView {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let empty = Vec::new();
+    let before = artifacts.verify_baseline.as_ref().unwrap_or(&empty);
+    let after = artifacts.verify_final.as_ref().unwrap_or(&empty);
+
+    lines.push(Line::from(
+        "COMMAND                                  BEFORE  AFTER".bold(),
+    ));
+    let mut commands: Vec<&str> = before.iter().map(|r| r.command.as_str()).collect();
+    for r in after {
+        if !commands.contains(&r.command.as_str()) {
+            commands.push(r.command.as_str());
+        }
+    }
+    let status = |results: &[VerifyResult], cmd: &str| -> Span<'static> {
+        match results.iter().find(|r| r.command == cmd) {
+            Some(r) if r.success => "pass    ".green(),
+            Some(_) => "fail    ".red(),
+            None => "—       ".dim(),
+        }
+    };
+    for cmd in &commands {
+        lines.push(Line::from(vec![
+            format!("{cmd:<41} ").into(),
+            status(before, cmd),
+            status(after, cmd),
+        ]));
+    }
+
+    for (label, results) in [("Before", before), ("After", after)] {
+        for r in results {
+            lines.push(Line::from(""));
+            let mark = if r.success {
+                "✓".green()
+            } else {
+                "✗".red()
+            };
+            lines.push(Line::from(vec![
+                mark,
+                " ".into(),
+                format!("{label}: ").bold(),
+                r.command.clone().into(),
+            ]));
+            let output = format!("{}{}", r.stdout, r.stderr);
+            let output_lines: Vec<&str> = output.lines().collect();
+            let skipped = output_lines.len().saturating_sub(VERIFY_OUTPUT_MAX_LINES);
+            if skipped > 0 {
+                lines.push(format!("  … {skipped} earlier lines omitted").dim().into());
+            }
+            for line in &output_lines[skipped..] {
+                lines.push(Line::from(format!("  {line}").dim()));
+            }
+        }
+    }
+    PagerView::new(vec![paragraph(lines)], title, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn write_run(repo: &Path, id: &str, ts: u64) -> PathBuf {
+        let dir = artifacts::runs_dir(repo).join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(artifacts::METADATA_FILE),
+            format!(r#"{{"job_id":"{id}","mode":"Fix","target":"src/lib.rs","timestamp":{ts}}}"#),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn buffer_text(buf: &Buffer, area: Rect) -> String {
+        let mut out = String::new();
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                out.push_str(buf[(x, y)].symbol());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn lists_runs_and_opens_tabs_in_order() {
+        let repo = TempDir::new().unwrap();
+        write_run(repo.path(), "run-1", 100);
+        let newest = write_run(repo.path(), "run-2", 200);
+        std::fs::write(
+            newest.join(artifacts::STATUS_FILE),
+            r#"{"outcome":"Success","summary_line":"Base failures: 1, Final failures: 0","finished_at":201}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            newest.join("critique_gpt.md"),
+            r#"<finding severity="P1"><location>src/lib.rs:3</location><issue>Off by one</issue></finding>"#,
+        )
+        .unwrap();
+        std::fs::write(newest.join("plan.md"), "1. Fix the bound").unwrap();
+        std::fs::write(
+            newest.join("verify_final.json"),
+            r#"[{"command":"cargo test","success":true,"stdout":"ok","stderr":""}]"#,
+        )
+        .unwrap();
+
+        let (tx, _rx) = unbounded_channel();
+        let mut overlay =
+            CouncilRunsOverlay::new(repo.path().to_path_buf(), AppEventSender::new(tx));
+
+        let area = Rect::new(0, 0, 100, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_text(&buf, area);
+        assert!(text.contains("› run-2  success"), "{text}");
+        assert!(text.contains("run-1  unknown"), "{text}");
+
+        overlay.open_selected();
+        let tabs: Vec<String> = overlay
+            .detail
+            .as_ref()
+            .map(|d| d.tabs.iter().map(|(name, _)| name.clone()).collect())
+            .unwrap_or_default();
+        assert_eq!(
+            tabs,
+            vec!["GPT critique", "Findings", "Plan", "Verification"]
+        );
+
+        overlay.switch_tab(true);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_text(&buf, area);
+        assert!(text.contains("src/lib.rs:3"), "{text}");
+        assert!(text.contains("Off by one"), "{text}");
+    }
+
+    #[test]
+    fn apply_waits_for_confirmation() {
+        let repo = TempDir::new().unwrap();
+        write_run(repo.path(), "run-7", 1);
+        let (tx, mut rx) = unbounded_channel();
+        let mut overlay =
+            CouncilRunsOverlay::new(repo.path().to_path_buf(), AppEventSender::new(tx));
+
+        overlay.request_apply();
+        assert!(rx.try_recv().is_err());
+        overlay.resolve_apply(false);
+        assert!(rx.try_recv().is_err());
+        assert!(!overlay.is_done());
+        assert_eq!(overlay.status.as_deref(), Some("Apply cancelled."));
+
+        overlay.request_apply();
+        overlay.resolve_apply(true);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::ApplyCouncilJob(run_id)) if run_id == "run-7"
+        ));
+        assert!(overlay.is_done());
+    }
+
+    #[test]
+    fn export_writes_markdown_report() {
+        let repo = TempDir::new().unwrap();
+        write_run(repo.path(), "run-7", 1);
+        let (tx, _rx) = unbounded_channel();
+        let mut overlay =
+            CouncilRunsOverlay::new(repo.path().to_path_buf(), AppEventSender::new(tx));
+
+        overlay.export();
+
+        let report =
+            std::fs::read_to_string(repo.path().join(".council/exports/run-7.md")).unwrap();
+        assert!(report.starts_with("# Council run run-7"));
+        assert!(
+            overlay
+                .status
+                .unwrap_or_default()
+                .starts_with("Exported to")
+        );
+    }
+}
@@ -2,15 +2,20 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
+use codex_core::SessionSearchHit;
+use codex_core::SessionSearchQuery;
 use codex_core::ThreadItem;
 use codex_core::ThreadsPage;
 use codex_core::path_utils;
+use codex_core::search_indexed_sessions;
+use codex_core::search_sessions;
 use codex_protocol::items::TurnItem;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
@@ -23,6 +28,7 @@ use ratatui::style::Stylize as _;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use tokio::sync::mpsc;
+use tokio::sync::watch;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use unicode_width::UnicodeWidthStr;
@@ -38,6 +44,9 @@ use codex_protocol::protocol::SessionMetaLine;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const INDEX_SEARCH_LIMIT: usize = 100;
+/// How long typing has to pause before the index is searched.
+const INDEX_SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
 
 #[derive(Debug, Clone)]
 pub enum ResumeSelection {
@@ -57,12 +66,28 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct IndexSearchRequest {
+    codex_home: PathBuf,
+    query: String,
+    cwd: Option<PathBuf>,
+    default_provider: String,
+}
+
+/// Searches the session index, which also covers sessions that have not been
+/// paged in yet and text beyond the first user message.
+type IndexSearcher = Arc<dyn Fn(IndexSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    IndexSearchLoaded {
+        query: String,
+        hits: std::io::Result<Vec<SessionSearchHit>>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -106,10 +131,13 @@ pub async fn run_resume_picker(
         });
     });
 
+    let index_searcher = spawn_index_searcher(bg_tx.clone());
+
     let mut state = PickerState::new(
         codex_home.to_path_buf(),
         alt.tui.frame_requester(),
         page_loader,
+        index_searcher,
         default_provider.clone(),
         show_all,
         filter_cwd,
@@ -154,6 +182,51 @@ pub async fn run_resume_picker(
     Ok(ResumeSelection::StartFresh)
 }
 
+/// Runs index searches one at a time. A request waits for typing to pause and
+/// is dropped once a newer one replaces it; only the first search indexes
+/// rollouts that have no index yet.
+fn spawn_index_searcher(tx: mpsc::UnboundedSender<BackgroundEvent>) -> IndexSearcher {
+    let (request_tx, mut request_rx) = watch::channel(None::<IndexSearchRequest>);
+    tokio::spawn(async move {
+        let mut backfilled = false;
+        while request_rx.changed().await.is_ok() {
+            tokio::time::sleep(INDEX_SEARCH_DEBOUNCE).await;
+            if request_rx.has_changed().unwrap_or(true) {
+                continue;
+            }
+            let Some(request) = request_rx.borrow_and_update().clone() else {
+                continue;
+            };
+            // Same filters as the pages the picker lists.
+            let query = SessionSearchQuery {
+                text: request.query.clone(),
+                cwd: request.cwd,
+                sources: INTERACTIVE_SESSION_SOURCES.to_vec(),
+                model_providers: Some(vec![request.default_provider.clone()]),
+                default_provider: request.default_provider,
+                limit: INDEX_SEARCH_LIMIT,
+                ..Default::default()
+            };
+            let hits = if backfilled {
+                search_indexed_sessions(&request.codex_home, query).await
+            } else {
+                backfilled = true;
+                search_sessions(&request.codex_home, query).await
+            };
+            if request_rx.has_changed().unwrap_or(true) {
+                continue;
+            }
+            let _ = tx.send(BackgroundEvent::IndexSearchLoaded {
+                query: request.query,
+                hits,
+            });
+        }
+    });
+    Arc::new(move |request: IndexSearchRequest| {
+        request_tx.send_replace(Some(request));
+    })
+}
+
 /// RAII guard that ensures we leave the alt-screen on scope exit.
 struct AltScreenGuard<'a> {
     tui: &'a mut Tui,
@@ -178,6 +251,8 @@ struct PickerState {
     pagination: PaginationState,
     all_rows: Vec<Row>,
     filtered_rows: Vec<Row>,
+    /// Sessions the index found for the current query.
+    index_rows: Vec<Row>,
     seen_paths: HashSet<PathBuf>,
     selected: usize,
     scroll_top: usize,
@@ -186,6 +261,7 @@ struct PickerState {
     next_request_token: usize,
     next_search_token: usize,
     page_loader: PageLoader,
+    index_searcher: IndexSearcher,
     view_rows: Option<usize>,
     default_provider: String,
     show_all: bool,
@@ -256,6 +332,7 @@ impl PickerState {
         codex_home: PathBuf,
         requester: FrameRequester,
         page_loader: PageLoader,
+        index_searcher: IndexSearcher,
         default_provider: String,
         show_all: bool,
         filter_cwd: Option<PathBuf>,
@@ -271,6 +348,7 @@ impl PickerState {
             },
             all_rows: Vec::new(),
             filtered_rows: Vec::new(),
+            index_rows: Vec::new(),
             seen_paths: HashSet::new(),
             selected: 0,
             scroll_top: 0,
@@ -279,6 +357,7 @@ impl PickerState {
             next_request_token: 0,
             next_search_token: 0,
             page_loader,
+            index_searcher,
             view_rows: None,
             default_provider,
             show_all,
@@ -404,6 +483,20 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::IndexSearchLoaded { query, hits } => {
+                if query != self.query {
+                    return Ok(());
+                }
+                // The index only adds to the preview matches, so a failed
+                // search leaves the picker usable.
+                match hits {
+                    Ok(hits) => {
+                        self.index_rows = hits.into_iter().map(hit_to_row).collect();
+                        self.apply_filter();
+                    }
+                    Err(err) => tracing::warn!("session search failed: {err}"),
+                }
+            }
         }
         Ok(())
     }
@@ -448,10 +541,17 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter
+            let mut rows: Vec<Row> = base_iter
                 .filter(|r| r.preview.to_lowercase().contains(&q))
                 .cloned()
                 .collect();
+            let mut shown: HashSet<PathBuf> = rows.iter().map(|row| row.path.clone()).collect();
+            for row in &self.index_rows {
+                if self.row_matches_filter(row) && shown.insert(row.path.clone()) {
+                    rows.push(row.clone());
+                }
+            }
+            self.filtered_rows = rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -482,11 +582,13 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.index_rows.clear();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
             return;
         }
+        self.search_index();
         if !self.filtered_rows.is_empty() {
             self.search_state = SearchState::Idle;
             return;
@@ -500,6 +602,20 @@ impl PickerState {
         self.load_more_if_needed(LoadTrigger::Search { token });
     }
 
+    fn search_index(&self) {
+        let cwd = if self.show_all {
+            None
+        } else {
+            self.filter_cwd.clone()
+        };
+        (self.index_searcher)(IndexSearchRequest {
+            codex_home: self.codex_home.clone(),
+            query: self.query.clone(),
+            cwd,
+            default_provider: self.default_provider.clone(),
+        });
+    }
+
     fn continue_search_if_needed(&mut self) {
         let Some(token) = self.search_state.active_token() else {
             return;
@@ -659,6 +775,29 @@ fn head_to_row(item: &ThreadItem) -> Row {
     }
 }
 
+fn hit_to_row(hit: SessionSearchHit) -> Row {
+    let created_at = parse_timestamp_str(&hit.created_at);
+    let updated_at = hit
+        .updated_at
+        .as_deref()
+        .and_then(parse_timestamp_str)
+        .or(created_at);
+    let preview = hit
+        .matches
+        .into_iter()
+        .next()
+        .map(|found| found.snippet)
+        .unwrap_or_else(|| String::from("(no message yet)"));
+    Row {
+        path: hit.rollout_path,
+        preview,
+        created_at,
+        updated_at,
+        cwd: Some(hit.cwd),
+        git_branch: hit.git_branch,
+    }
+}
+
 fn extract_session_meta_from_head(head: &[serde_json::Value]) -> (Option<PathBuf>, Option<String>) {
     for value in head {
         if let Ok(meta_line) = serde_json::from_value::<SessionMetaLine>(value.clone()) {
@@ -1197,6 +1336,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1346,6 +1486,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1426,6 +1567,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1494,6 +1636,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1525,6 +1668,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1569,6 +1713,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1613,6 +1758,7 @@ mod tests {
             PathBuf::from("/tmp"),
             FrameRequester::test_dummy(),
             loader,
+            Arc::new(|_| {}),
             String::from("openai"),
             true,
             None,
@@ -1713,4 +1859,130 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    fn search_hit(path: &str, cwd: &str, snippet: &str) -> SessionSearchHit {
+        SessionSearchHit {
+            thread_id: codex_protocol::ThreadId::new(),
+            rollout_path: PathBuf::from(path),
+            cwd: PathBuf::from(cwd),
+            created_at: "2025-01-05T00:00:00Z".to_string(),
+            updated_at: None,
+            git_branch: None,
+            models: Vec::new(),
+            matches: vec![codex_core::SearchMatch {
+                kind: codex_core::SearchEntryKind::AgentMessage,
+                snippet: snippet.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn set_query_merges_index_hits() {
+        let recorded_searches: Arc<Mutex<Vec<IndexSearchRequest>>> =
+            Arc::new(Mutex::new(Vec::new()));
+        let search_sink = recorded_searches.clone();
+        let searcher: IndexSearcher = Arc::new(move |req: IndexSearchRequest| {
+            search_sink.lock().unwrap().push(req);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            Arc::new(|_| {}),
+            searcher,
+            String::from("openai"),
+            false,
+            Some(PathBuf::from("/repo")),
+        );
+        state.reset_pagination();
+        let mut item = make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "fix oauth refresh");
+        item.head.insert(
+            0,
+            json!({
+                "id": "0194b6a0-0000-7000-8000-000000000001",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+            }),
+        );
+        state.ingest_page(page(vec![item], None, 1, false));
+
+        state.set_query("oauth".to_string());
+        {
+            let guard = recorded_searches.lock().unwrap();
+            assert_eq!(guard.len(), 1);
+            assert_eq!(guard[0].query, "oauth");
+            assert_eq!(guard[0].cwd, Some(PathBuf::from("/repo")));
+        }
+
+        // Results for an older query are dropped.
+        state
+            .handle_background_event(BackgroundEvent::IndexSearchLoaded {
+                query: "oa".to_string(),
+                hits: Ok(vec![search_hit("/tmp/old.jsonl", "/repo", "oa")]),
+            })
+            .unwrap();
+        assert_eq!(state.filtered_rows.len(), 1);
+
+        state
+            .handle_background_event(BackgroundEvent::IndexSearchLoaded {
+                query: "oauth".to_string(),
+                hits: Ok(vec![
+                    search_hit("/tmp/a.jsonl", "/repo", "fix oauth refresh"),
+                    search_hit("/tmp/b.jsonl", "/repo", "the oauth token expired"),
+                    search_hit("/tmp/c.jsonl", "/repo/sub", "oauth elsewhere"),
+                ]),
+            })
+            .unwrap();
+        let rows: Vec<(PathBuf, String)> = state
+            .filtered_rows
+            .iter()
+            .map(|row| (row.path.clone(), row.preview.clone()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    PathBuf::from("/tmp/a.jsonl"),
+                    "fix oauth refresh".to_string()
+                ),
+                (
+                    PathBuf::from("/tmp/b.jsonl"),
+                    "the oauth token expired".to_string()
+                ),
+            ]
+        );
+
+        state.set_query(String::new());
+        assert!(state.index_rows.is_empty());
+        assert_eq!(state.filtered_rows.len(), 1);
+        assert_eq!(recorded_searches.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn index_searcher_runs_only_the_latest_request() {
+        let codex_home = tempfile::TempDir::new().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let searcher = spawn_index_searcher(tx);
+        for query in ["o", "oa", "oauth"] {
+            searcher(IndexSearchRequest {
+                codex_home: codex_home.path().to_path_buf(),
+                query: query.to_string(),
+                cwd: None,
+                default_provider: String::from("openai"),
+            });
+        }
+
+        let Some(BackgroundEvent::IndexSearchLoaded { query, hits }) = rx.recv().await else {
+            panic!("expected index search results");
+        };
+        assert_eq!(query, "oauth");
+        assert!(hits.unwrap().is_empty());
+
+        // The worker stops with the picker.
+        drop(searcher);
+        assert!(rx.recv().await.is_none());
+    }
 }
@@ -20,13 +20,14 @@ pub enum SlashCommand {
     Resume,
     Init,
     Compact,
-    // Undo,
+    Undo,
     Diff,
     Mention,
     Status,
     #[strum(serialize = "thinthread")]
     ThinThread,
     Mcp,
+    Ps,
     Logout,
     Quit,
     Exit,
@@ -46,7 +47,7 @@ impl SlashCommand {
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::ThinThread => "run Council review/fix jobs (Council v2)",
             SlashCommand::Resume => "resume a saved chat",
-            // SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::Undo => "restore files from an earlier turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
             SlashCommand::Mention => "mention a file",
@@ -55,6 +56,7 @@ impl SlashCommand {
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Ps => "list background processes",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
             SlashCommand::TestApproval => "test approval request",
@@ -74,7 +76,7 @@ impl SlashCommand {
             | SlashCommand::Resume
             | SlashCommand::Init
             | SlashCommand::Compact
-            // | SlashCommand::Undo
+            | SlashCommand::Undo
             | SlashCommand::Model
             | SlashCommand::Approvals
             | SlashCommand::Review
@@ -85,6 +87,7 @@ impl SlashCommand {
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::Mcp
+            | SlashCommand::Ps
             | SlashCommand::Feedback
             | SlashCommand::Quit
             | SlashCommand::Exit => true,
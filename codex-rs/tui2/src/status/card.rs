@@ -26,6 +26,7 @@ use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_sandbox_carve_outs;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
 use super::rate_limits::RateLimitSnapshotDisplay;
@@ -63,6 +64,7 @@ struct StatusHistoryCell {
     directory: PathBuf,
     approval: String,
     sandbox: String,
+    sandbox_carve_outs: Vec<(&'static str, String)>,
     agents_summary: String,
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
@@ -134,6 +136,7 @@ impl StatusHistoryCell {
                 }
             }
         };
+        let sandbox_carve_outs = compose_sandbox_carve_outs(config);
         let agents_summary = compose_agents_summary(config);
         let account = compose_account_display(auth_manager, plan_type);
         let session_id = session_id.as_ref().map(std::string::ToString::to_string);
@@ -162,6 +165,7 @@ impl StatusHistoryCell {
             directory: config.cwd.clone(),
             approval,
             sandbox,
+            sandbox_carve_outs,
             agents_summary,
             account,
             session_id,
@@ -403,6 +407,11 @@ impl HistoryCell for StatusHistoryCell {
         lines.push(formatter.line("Directory", vec![Span::from(directory_value)]));
         lines.push(formatter.line("Approval", vec![Span::from(self.approval.clone())]));
         lines.push(formatter.line("Sandbox", vec![Span::from(self.sandbox.clone())]));
+        for (label, paths) in &self.sandbox_carve_outs {
+            lines.push(
+                formatter.continuation(vec![Span::from(*label).dim(), Span::from(paths.clone())]),
+            );
+        }
         lines.push(formatter.line("Agents.md", vec![Span::from(self.agents_summary.clone())]));
 
         // Council Status
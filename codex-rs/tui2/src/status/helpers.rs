@@ -7,6 +7,7 @@ use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
 use codex_protocol::account::PlanType;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
@@ -166,6 +167,31 @@ pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize
     formatted
 }
 
+/// Lists the workspace-write carve-outs as `(label, paths)` pairs, showing
+/// paths relative to the session cwd when they live under it.
+pub(crate) fn compose_sandbox_carve_outs(config: &Config) -> Vec<(&'static str, String)> {
+    let policy = config.sandbox_policy.get();
+    let display = |paths: &[AbsolutePathBuf]| {
+        paths
+            .iter()
+            .map(|path| match path.as_path().strip_prefix(&config.cwd) {
+                Ok(rel) if !rel.as_os_str().is_empty() => rel.display().to_string(),
+                _ => format_directory_display(path.as_path(), None),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut carve_outs = Vec::new();
+    if !policy.get_read_only_subpaths().is_empty() {
+        carve_outs.push(("Read-only: ", display(policy.get_read_only_subpaths())));
+    }
+    if !policy.get_deny_read_paths().is_empty() {
+        carve_outs.push(("No access: ", display(policy.get_deny_read_paths())));
+    }
+    carve_outs
+}
+
 pub(crate) fn format_reset_timestamp(dt: DateTime<Local>, captured_at: DateTime<Local>) -> String {
     let time = dt.format("%H:%M").to_string();
     if dt.date_naive() == captured_at.date_naive() {
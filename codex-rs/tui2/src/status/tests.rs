@@ -103,6 +103,8 @@ async fn status_snapshot_includes_reasoning_details() {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            read_only_subpaths: vec![],
+            deny_read_paths: vec![],
         })
         .expect("set sandbox policy");
 
@@ -425,6 +427,64 @@ async fn status_card_token_usage_excludes_cached_tokens() {
     );
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn status_card_lists_sandbox_carve_outs() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+    config
+        .sandbox_policy
+        .set(SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            read_only_subpaths: vec![
+                "/workspace/tests/.git/hooks"
+                    .try_into()
+                    .expect("absolute path"),
+            ],
+            deny_read_paths: vec!["/etc/codex-secrets".try_into().expect("absolute path")],
+        })
+        .expect("set sandbox policy");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        None,
+        &usage,
+        &None,
+        None,
+        None,
+        now,
+        &model_slug,
+    );
+    let rendered = render_lines(&composite.display_lines(120));
+
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("Read-only: .git/hooks")),
+        "read-only carve-out should be listed, got: {rendered:?}"
+    );
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("No access: /etc/codex-secrets")),
+        "denied carve-out should be listed, got: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn status_snapshot_truncates_in_narrow_terminal() {
     let temp_home = TempDir::new().expect("temp home");
@@ -493,6 +493,144 @@ fn restore_to_commit_inner(
     Ok(())
 }
 
+/// Restores only `paths`, given relative to `options.repo_path`, to their state
+/// in the ghost commit. Paths missing from the snapshot are deleted unless they
+/// were left out of it as pre-existing untracked files.
+pub fn restore_ghost_commit_paths(
+    options: &RestoreGhostCommitOptions<'_>,
+    commit: &GhostCommit,
+    paths: &[PathBuf],
+) -> Result<(), GitToolingError> {
+    ensure_git_repository(options.repo_path)?;
+
+    let repo_root = resolve_repository_root(options.repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), options.repo_path);
+    let preserved_files: HashSet<PathBuf> = commit
+        .preexisting_untracked_files()
+        .iter()
+        .cloned()
+        .collect();
+
+    let mut restore_paths = Vec::new();
+    for path in paths {
+        let path = normalize_relative_path(path)?;
+        let path = match repo_prefix.as_deref() {
+            Some(prefix) => prefix.join(path),
+            None => path,
+        };
+        if path_in_commit(repo_root.as_path(), commit.id(), &path)? {
+            restore_paths.push(path.into_os_string());
+        } else if !should_preserve(&path, &preserved_files, commit.preexisting_untracked_dirs()) {
+            remove_path(&repo_root.join(&path))?;
+        }
+    }
+    if restore_paths.is_empty() {
+        return Ok(());
+    }
+
+    let mut restore_args = vec![
+        OsString::from("restore"),
+        OsString::from("--source"),
+        OsString::from(commit.id()),
+        OsString::from("--worktree"),
+        OsString::from("--"),
+    ];
+    restore_args.extend(restore_paths);
+    run_git_for_status(repo_root.as_path(), restore_args, None)
+}
+
+/// Whether `path`, relative to the repository root, exists in the commit.
+fn path_in_commit(repo_root: &Path, commit_id: &str, path: &Path) -> Result<bool, GitToolingError> {
+    let listed = run_git_for_stdout(
+        repo_root,
+        vec![
+            OsString::from("ls-tree"),
+            OsString::from("--name-only"),
+            OsString::from(commit_id),
+            OsString::from("--"),
+            path.as_os_str().to_os_string(),
+        ],
+        None,
+    )?;
+    Ok(!listed.is_empty())
+}
+
+/// Lines added and removed in one file between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotFileChange {
+    /// Path relative to the repository path the diff was taken in.
+    pub path: PathBuf,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+/// Unified diff between two commits, limited to `repo_path`.
+pub fn diff_ghost_commits(
+    repo_path: &Path,
+    from_commit: &str,
+    to_commit: &str,
+) -> Result<String, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    // Example:
+    //   git diff --no-color --no-renames --relative <from> <to>
+    run_git_for_stdout_all(
+        repo_path,
+        vec![
+            OsString::from("diff"),
+            OsString::from("--no-color"),
+            OsString::from("--no-renames"),
+            OsString::from("--relative"),
+            OsString::from(from_commit),
+            OsString::from(to_commit),
+        ],
+        None,
+    )
+}
+
+/// Files changed between two commits, limited to `repo_path`.
+pub fn ghost_commit_changes(
+    repo_path: &Path,
+    from_commit: &str,
+    to_commit: &str,
+) -> Result<Vec<SnapshotFileChange>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    // Example:
+    //   git diff --numstat -z --no-renames --relative <from> <to>
+    let output = run_git_for_stdout_all(
+        repo_path,
+        vec![
+            OsString::from("diff"),
+            OsString::from("--numstat"),
+            OsString::from("-z"),
+            OsString::from("--no-renames"),
+            OsString::from("--relative"),
+            OsString::from(from_commit),
+            OsString::from(to_commit),
+        ],
+        None,
+    )?;
+    Ok(parse_numstat(&output))
+}
+
+/// Parses NUL-terminated `insertions<TAB>deletions<TAB>path` records.
+fn parse_numstat(output: &str) -> Vec<SnapshotFileChange> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.splitn(3, '\t');
+            let insertions = fields.next()?;
+            let deletions = fields.next()?;
+            let path = fields.next().filter(|path| !path.is_empty())?;
+            // Binary files report `-` for both counts.
+            Some(SnapshotFileChange {
+                path: PathBuf::from(path),
+                insertions: insertions.parse().unwrap_or(0),
+                deletions: deletions.parse().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
 #[derive(Default)]
 struct UntrackedSnapshot {
     files: Vec<PathBuf>,
@@ -1776,4 +1914,145 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    /// Changes between two snapshots are reported per file, relative to the session path.
+    fn changes_between_snapshots() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+
+        let workspace = repo.join("workspace");
+        std::fs::create_dir_all(&workspace)?;
+        std::fs::write(repo.join("root.txt"), "root\n")?;
+        std::fs::write(workspace.join("edited.txt"), "one\ntwo\n")?;
+        std::fs::write(workspace.join("deleted.txt"), "gone soon\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "initial",
+            ],
+        );
+
+        let before = create_ghost_commit(&CreateGhostCommitOptions::new(&workspace))?;
+        std::fs::write(repo.join("root.txt"), "root changed\n")?;
+        std::fs::write(workspace.join("edited.txt"), "one\n2\nthree\n")?;
+        std::fs::remove_file(workspace.join("deleted.txt"))?;
+        std::fs::write(workspace.join("added.txt"), "new\n")?;
+        let after = create_ghost_commit(&CreateGhostCommitOptions::new(&workspace))?;
+
+        let changes = ghost_commit_changes(&workspace, before.id(), after.id())?;
+        assert_eq!(
+            changes,
+            vec![
+                SnapshotFileChange {
+                    path: PathBuf::from("added.txt"),
+                    insertions: 1,
+                    deletions: 0,
+                },
+                SnapshotFileChange {
+                    path: PathBuf::from("deleted.txt"),
+                    insertions: 0,
+                    deletions: 1,
+                },
+                SnapshotFileChange {
+                    path: PathBuf::from("edited.txt"),
+                    insertions: 2,
+                    deletions: 1,
+                },
+            ]
+        );
+
+        let diff = diff_ghost_commits(&workspace, before.id(), after.id())?;
+        assert!(diff.contains("+++ b/added.txt"));
+        assert!(diff.contains("-two\n+2\n+three"));
+        assert!(!diff.contains("root.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    /// Binary files are counted without line totals.
+    fn parse_numstat_handles_binary_files() {
+        assert_eq!(
+            parse_numstat("3\t1\tsrc/lib.rs\0-\t-\tlogo.png\0"),
+            vec![
+                SnapshotFileChange {
+                    path: PathBuf::from("src/lib.rs"),
+                    insertions: 3,
+                    deletions: 1,
+                },
+                SnapshotFileChange {
+                    path: PathBuf::from("logo.png"),
+                    insertions: 0,
+                    deletions: 0,
+                },
+            ]
+        );
+        assert_eq!(parse_numstat(""), Vec::new());
+    }
+
+    #[test]
+    /// Restoring selected paths leaves every other file alone.
+    fn restore_selected_paths_only() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+
+        std::fs::write(repo.join("restored.txt"), "snapshot\n")?;
+        std::fs::write(repo.join("kept.txt"), "snapshot\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "initial",
+            ],
+        );
+        std::fs::write(repo.join("notes.txt"), "untracked before\n")?;
+
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("restored.txt"), "edited\n")?;
+        std::fs::write(repo.join("kept.txt"), "edited\n")?;
+        std::fs::write(repo.join("notes.txt"), "edited\n")?;
+        std::fs::write(repo.join("created.txt"), "new\n")?;
+        std::fs::write(repo.join("also-created.txt"), "new\n")?;
+
+        restore_ghost_commit_paths(
+            &RestoreGhostCommitOptions::new(repo),
+            &ghost,
+            &[
+                PathBuf::from("restored.txt"),
+                PathBuf::from("notes.txt"),
+                PathBuf::from("created.txt"),
+            ],
+        )?;
+
+        assert_eq!(
+            std::fs::read_to_string(repo.join("restored.txt"))?,
+            "snapshot\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.join("notes.txt"))?,
+            "untracked before\n"
+        );
+        assert!(!repo.join("created.txt").exists());
+        assert_eq!(std::fs::read_to_string(repo.join("kept.txt"))?, "edited\n");
+        assert!(repo.join("also-created.txt").exists());
+
+        Ok(())
+    }
 }
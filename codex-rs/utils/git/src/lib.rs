@@ -22,10 +22,14 @@ pub use ghost_commits::GhostSnapshotReport;
 pub use ghost_commits::IgnoredUntrackedFile;
 pub use ghost_commits::LargeUntrackedDir;
 pub use ghost_commits::RestoreGhostCommitOptions;
+pub use ghost_commits::SnapshotFileChange;
 pub use ghost_commits::capture_ghost_snapshot_report;
 pub use ghost_commits::create_ghost_commit;
 pub use ghost_commits::create_ghost_commit_with_report;
+pub use ghost_commits::diff_ghost_commits;
+pub use ghost_commits::ghost_commit_changes;
 pub use ghost_commits::restore_ghost_commit;
+pub use ghost_commits::restore_ghost_commit_paths;
 pub use ghost_commits::restore_ghost_commit_with_options;
 pub use ghost_commits::restore_to_commit;
 pub use platform::create_symlink;